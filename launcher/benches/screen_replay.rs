@@ -0,0 +1,137 @@
+// 記録したセッションを再生して画面バッファと状態検出のスループットを計測
+//
+// 実行: cargo bench -p climonitor-launcher --bench screen_replay
+//
+// CLIMONITOR_REPLAY_FILE に PTY 出力の生データ（`script -q` などで記録）を指定すると
+// そのセッションを再生する。未指定の場合は Claude 風の長いセッションを生成して使う。
+
+use climonitor_launcher::screen_buffer::ScreenBuffer;
+use climonitor_launcher::screen_claude_detector::ScreenClaudeStateDetector;
+use climonitor_launcher::screen_gemini_detector::ScreenGeminiStateDetector;
+use climonitor_launcher::state_detector::StateDetector;
+use std::time::{Duration, Instant};
+
+const ROWS: usize = 50;
+const COLS: usize = 120;
+/// PTY からの1回の読み込みサイズ（launcher の読み込みバッファと同じ）
+const CHUNK_SIZE: usize = 8192;
+
+fn main() {
+    let session = match std::env::var("CLIMONITOR_REPLAY_FILE") {
+        Ok(path) => std::fs::read(&path).unwrap_or_else(|e| panic!("{path}: {e}")),
+        Err(_) => synthetic_session(1000),
+    };
+    let iterations = std::env::var("CLIMONITOR_REPLAY_ITERATIONS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(5);
+
+    // PTY の読み込み単位に合わせて分割（UTF-8 の途中で切れた文字は次のチャンクで続きから解釈される）
+    let chunks: Vec<&[u8]> = session.chunks(CHUNK_SIZE).collect();
+
+    println!(
+        "replaying {:.1} MiB in {} chunks ({ROWS}x{COLS}, best of {iterations})",
+        session.len() as f64 / (1024.0 * 1024.0),
+        chunks.len()
+    );
+
+    report(
+        "screen buffer",
+        session.len(),
+        chunks.len(),
+        || {
+            let mut buffer = ScreenBuffer::new(ROWS, COLS, false);
+            for chunk in &chunks {
+                buffer.process_data(chunk);
+            }
+        },
+        iterations,
+    );
+
+    report(
+        "claude detector",
+        session.len(),
+        chunks.len(),
+        || {
+            let mut detector = ScreenClaudeStateDetector::new(false);
+            detector.resize_screen_buffer(ROWS, COLS);
+            replay(&mut detector, &chunks);
+        },
+        iterations,
+    );
+
+    report(
+        "gemini detector",
+        session.len(),
+        chunks.len(),
+        || {
+            let mut detector = ScreenGeminiStateDetector::new(false);
+            detector.resize_screen_buffer(ROWS, COLS);
+            replay(&mut detector, &chunks);
+        },
+        iterations,
+    );
+}
+
+fn replay(detector: &mut dyn StateDetector, chunks: &[&[u8]]) {
+    for chunk in chunks {
+        detector.process_bytes(chunk);
+        detector.take_notifications();
+    }
+}
+
+fn report(name: &str, bytes: usize, chunks: usize, mut run: impl FnMut(), iterations: usize) {
+    let best = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::ZERO);
+
+    let seconds = best.as_secs_f64().max(f64::EPSILON);
+    println!(
+        "{name:>16}: {:>8.2?}  {:>8.1} MiB/s  {:>9.0} chunks/s  {:>7.1} µs/chunk",
+        best,
+        bytes as f64 / (1024.0 * 1024.0) / seconds,
+        chunks as f64 / seconds,
+        best.as_secs_f64() * 1_000_000.0 / chunks as f64
+    );
+}
+
+/// Claude 風のセッション: 応答のストリーミング、スピナー行の更新、入力欄の再描画
+fn synthetic_session(turns: usize) -> Vec<u8> {
+    let border = "─".repeat(COLS - 2);
+    let prompt_box = format!(
+        "\x1b[38;2;153;153;153m╭{border}╮\r\n│ > {:<width$}│\r\n╰{border}╯\x1b[39m\r\n  ? for shortcuts\r\n",
+        "",
+        width = COLS - 5
+    );
+    // Ink は前回描画した行数だけ消去してから描き直す
+    let erase = format!("{}\x1b[2K\x1b[G", "\x1b[2K\x1b[1A".repeat(5));
+    let spinner = ['·', '✢', '✳', '✶', '✻', '✽'];
+
+    let mut session = String::from("\x1b[?25l\x1b[H\x1b[2J");
+    for turn in 0..turns {
+        session.push_str(&format!(
+            "\x1b[1m●\x1b[22m Update(src/module_{turn}.rs) を編集しています\r\n"
+        ));
+        for line in 0..8 {
+            session.push_str(&format!(
+                "  ⎿  \x1b[32m+\x1b[39m    let value_{line} = compute(\"{turn}\", {line}); // 日本語のコメント {line}\r\n"
+            ));
+        }
+        for tick in 0..6 {
+            session.push_str(&format!(
+                "\x1b[38;5;174m{} Working… \x1b[39m({}s · ↑ {} tokens · esc to interrupt)\r\n{prompt_box}{erase}",
+                spinner[tick % spinner.len()],
+                tick,
+                turn * 10 + tick
+            ));
+        }
+        session.push_str(&prompt_box);
+        session.push_str("\x1b]0;✳ Claude Code\x07");
+    }
+    session.into_bytes()
+}
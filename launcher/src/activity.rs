@@ -0,0 +1,162 @@
+// activity.rs - PTY入出力の活動状況
+//
+// 画面のパターンとは別に「出力が流れ続けているか」は実行中の強い手がかりになる。
+// 出力タスク・入力タスクで記録し、状態チェッカーが検出器とmonitorへ渡す。
+
+use crate::transition_filter::{Clock, SystemClock};
+use chrono::Utc;
+use climonitor_shared::SessionActivity;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 出力レートを計算する期間
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// ある時点の活動状況
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActivitySnapshot {
+    /// 直近の出力レート（バイト/秒）
+    pub output_bytes_per_sec: u64,
+    /// 最後の出力からの経過時間（出力がまだなければ None）
+    pub since_output: Option<Duration>,
+    /// 最後のユーザー入力からの経過時間（入力がまだなければ None）
+    pub since_input: Option<Duration>,
+}
+
+impl ActivitySnapshot {
+    /// monitorへ送る形式に変換（経過時間を時刻に直す）
+    pub fn to_session_activity(&self, stalled: bool) -> SessionActivity {
+        let now = Utc::now();
+        let at = |since: Option<Duration>| {
+            since
+                .and_then(|since| chrono::Duration::from_std(since).ok())
+                .map(|since| now - since)
+        };
+        SessionActivity {
+            output_bytes_per_sec: self.output_bytes_per_sec,
+            last_output_at: at(self.since_output),
+            last_input_at: at(self.since_input),
+            stalled,
+        }
+    }
+}
+
+/// PTY入出力の記録
+pub struct ActivityTracker {
+    clock: Arc<dyn Clock>,
+    /// 期間内の出力（時刻とバイト数）
+    recent_output: VecDeque<(Instant, usize)>,
+    last_output: Option<Instant>,
+    last_input: Option<Instant>,
+}
+
+/// 出力タスク・入力タスク・状態チェッカーで共有するトラッカー
+pub type SharedActivityTracker = Arc<Mutex<ActivityTracker>>;
+
+impl ActivityTracker {
+    pub fn new() -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            recent_output: VecDeque::new(),
+            last_output: None,
+            last_input: None,
+        }
+    }
+
+    /// 時計を差し替え
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// PTYからの出力を記録
+    pub fn record_output(&mut self, bytes: usize) {
+        let now = self.clock.now();
+        self.recent_output.push_back((now, bytes));
+        self.last_output = Some(now);
+        self.prune(now);
+    }
+
+    /// ユーザーの入力を記録
+    pub fn record_input(&mut self) {
+        self.last_input = Some(self.clock.now());
+    }
+
+    /// 現在の活動状況
+    pub fn snapshot(&mut self) -> ActivitySnapshot {
+        let now = self.clock.now();
+        self.prune(now);
+        let bytes: usize = self.recent_output.iter().map(|(_, bytes)| bytes).sum();
+
+        ActivitySnapshot {
+            output_bytes_per_sec: (bytes as f64 / RATE_WINDOW.as_secs_f64()).round() as u64,
+            since_output: self.last_output.map(|at| now.duration_since(at)),
+            since_input: self.last_input.map(|at| now.duration_since(at)),
+        }
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while self
+            .recent_output
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) >= RATE_WINDOW)
+        {
+            self.recent_output.pop_front();
+        }
+    }
+}
+
+impl Default for ActivityTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transition_filter::ManualClock;
+
+    #[test]
+    fn test_output_rate_uses_recent_window() {
+        let clock = ManualClock::new();
+        let mut tracker = ActivityTracker::new().with_clock(clock.clone());
+        assert_eq!(
+            tracker.snapshot(),
+            ActivitySnapshot {
+                output_bytes_per_sec: 0,
+                since_output: None,
+                since_input: None,
+            }
+        );
+
+        tracker.record_output(4000);
+        clock.advance(2000);
+        tracker.record_output(6000);
+        assert_eq!(tracker.snapshot().output_bytes_per_sec, 2000);
+
+        // 最初の出力が期間外になる
+        clock.advance(3000);
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.output_bytes_per_sec, 1200);
+        assert_eq!(snapshot.since_output, Some(Duration::from_secs(3)));
+
+        clock.advance(2000);
+        assert_eq!(tracker.snapshot().output_bytes_per_sec, 0);
+    }
+
+    #[test]
+    fn test_input_is_tracked_separately() {
+        let clock = ManualClock::new();
+        let mut tracker = ActivityTracker::new().with_clock(clock.clone());
+        tracker.record_output(10);
+        clock.advance(1000);
+        tracker.record_input();
+        clock.advance(500);
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.since_output, Some(Duration::from_millis(1500)));
+        assert_eq!(snapshot.since_input, Some(Duration::from_millis(500)));
+    }
+}
@@ -0,0 +1,184 @@
+// auto_approve.rs - 承認プロンプトへの自動応答ルールエンジン
+
+use climonitor_shared::{AutoApproveAction, AutoApproveRule, AutoApproveSettings};
+
+/// 自動応答の判定結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoApproveDecision {
+    /// マッチしたルールのパターン
+    pub rule: String,
+    pub action: AutoApproveAction,
+    /// 承認時に送信するキー入力
+    pub keys: String,
+    /// 判定対象となったUI boxの内容
+    pub prompt: String,
+}
+
+/// プロジェクト単位の自動応答ルールエンジン
+#[derive(Debug, Clone)]
+pub struct AutoApprover {
+    rules: Vec<AutoApproveRule>,
+    dry_run: bool,
+}
+
+impl AutoApprover {
+    /// 設定から対象プロジェクトのルールを抽出（無効またはルールなしの場合は None）
+    pub fn from_settings(settings: &AutoApproveSettings, project: Option<&str>) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+
+        let rules: Vec<AutoApproveRule> = settings
+            .rules
+            .iter()
+            .filter(|rule| match (&rule.project, project) {
+                (None, _) => true,
+                (Some(rule_project), Some(project)) => rule_project == project,
+                (Some(_), None) => false,
+            })
+            .cloned()
+            .collect();
+
+        if rules.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            rules,
+            dry_run: settings.dry_run,
+        })
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// UI boxの内容行を評価（denyルールが優先、approveルールは先勝ち）
+    pub fn evaluate(&self, content_lines: &[String]) -> Option<AutoApproveDecision> {
+        let prompt = content_lines
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if prompt.is_empty() {
+            return None;
+        }
+
+        let matched = |action: AutoApproveAction| {
+            self.rules
+                .iter()
+                .filter(move |rule| rule.action == action)
+                .find(|rule| pattern_matches(&rule.pattern, &prompt))
+        };
+
+        let rule =
+            matched(AutoApproveAction::Deny).or_else(|| matched(AutoApproveAction::Approve))?;
+
+        Some(AutoApproveDecision {
+            rule: rule.pattern.clone(),
+            action: rule.action.clone(),
+            keys: rule.keys.clone(),
+            prompt,
+        })
+    }
+}
+
+/// `*` を任意の文字列として扱う部分一致（`*` のみのパターンはマッチしない）
+fn pattern_matches(pattern: &str, text: &str) -> bool {
+    if pattern.trim_matches('*').is_empty() {
+        return false;
+    }
+
+    let mut rest = text;
+    for part in pattern.split('*').filter(|part| !part.is_empty()) {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(project: Option<&str>, pattern: &str, action: AutoApproveAction) -> AutoApproveRule {
+        AutoApproveRule {
+            project: project.map(|p| p.to_string()),
+            pattern: pattern.to_string(),
+            action,
+            keys: "\r".to_string(),
+        }
+    }
+
+    fn settings(rules: Vec<AutoApproveRule>) -> AutoApproveSettings {
+        AutoApproveSettings {
+            enabled: true,
+            dry_run: false,
+            rules,
+        }
+    }
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("Read(", "Tool: Read(src/main.rs)"));
+        assert!(pattern_matches("Bash*rm ", "Bash command\nrm -rf target"));
+        assert!(!pattern_matches("Bash*rm ", "rm -rf target\nBash command"));
+        assert!(!pattern_matches("*", "anything"));
+    }
+
+    #[test]
+    fn test_deny_rule_takes_precedence() {
+        let approver = AutoApprover::from_settings(
+            &settings(vec![
+                rule(None, "Bash", AutoApproveAction::Approve),
+                rule(None, "Bash*rm ", AutoApproveAction::Deny),
+            ]),
+            Some("climonitor"),
+        )
+        .unwrap();
+
+        let decision = approver
+            .evaluate(&lines(&[
+                "Bash command",
+                "  rm -rf build  ",
+                "Do you want to proceed?",
+            ]))
+            .unwrap();
+        assert_eq!(decision.action, AutoApproveAction::Deny);
+        assert_eq!(decision.rule, "Bash*rm ");
+        assert_eq!(
+            decision.prompt,
+            "Bash command\nrm -rf build\nDo you want to proceed?"
+        );
+
+        let decision = approver
+            .evaluate(&lines(&["Bash command", "cargo test"]))
+            .unwrap();
+        assert_eq!(decision.action, AutoApproveAction::Approve);
+        assert!(approver.evaluate(&lines(&["Edit file"])).is_none());
+    }
+
+    #[test]
+    fn test_project_filter_and_disabled() {
+        let mut config = settings(vec![rule(
+            Some("trusted"),
+            "Read(",
+            AutoApproveAction::Approve,
+        )]);
+
+        assert!(AutoApprover::from_settings(&config, Some("other")).is_none());
+        assert!(AutoApprover::from_settings(&config, None).is_none());
+        assert!(AutoApprover::from_settings(&config, Some("trusted")).is_some());
+
+        config.enabled = false;
+        assert!(AutoApprover::from_settings(&config, Some("trusted")).is_none());
+    }
+}
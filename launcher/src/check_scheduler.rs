@@ -0,0 +1,156 @@
+// check_scheduler.rs - 状態チェッカーの起床タイミング
+//
+// 出力のたびに届く通知は最短間隔でまとめ、出力がなくても保留中の状態遷移の
+// 確定時刻とkeepalive間隔では確認し直す（待機処理を状態チェッカーから分離してテストする）。
+
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// 状態を確認する理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckReason {
+    /// 初回の確認
+    Initial,
+    /// 出力による変化の通知
+    Output,
+    /// 保留中の状態遷移を確定できる時刻になった
+    Deadline,
+    /// 通知のないままkeepalive間隔が経過した
+    Keepalive,
+}
+
+/// 状態確認の間隔の管理
+pub struct CheckScheduler {
+    min_interval: Duration,
+    keepalive: Duration,
+    last_check: Option<Instant>,
+}
+
+impl CheckScheduler {
+    pub fn new(min_interval: Duration, keepalive: Duration) -> Self {
+        Self {
+            min_interval,
+            keepalive,
+            last_check: None,
+        }
+    }
+
+    /// 次に状態を確認するまで待機（初回は即座に返る）
+    ///
+    /// 前回の確認から最短間隔の間に届いた通知はまとめて次の確認で処理する。
+    /// `deadline` は保留中の状態遷移を確定できる時刻。
+    pub async fn wait(&mut self, events: &Notify, deadline: Option<Instant>) -> CheckReason {
+        let reason = match self.last_check {
+            None => CheckReason::Initial,
+            Some(last_check) => {
+                tokio::time::sleep_until(last_check + self.min_interval).await;
+                tokio::select! {
+                    _ = events.notified() => CheckReason::Output,
+                    _ = tokio::time::sleep(self.keepalive) => CheckReason::Keepalive,
+                    _ = async {
+                        match deadline {
+                            Some(deadline) => tokio::time::sleep_until(deadline).await,
+                            None => std::future::pending().await,
+                        }
+                    } => CheckReason::Deadline,
+                }
+            }
+        };
+        self.last_check = Some(Instant::now());
+        reason
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transition_filter::{Clock, FilterOutcome, TransitionFilter};
+    use climonitor_shared::{SessionStatus, StateFilterSettings};
+    use std::sync::Arc;
+
+    const MIN_INTERVAL: Duration = Duration::from_millis(100);
+    const KEEPALIVE: Duration = Duration::from_secs(10);
+
+    /// tokioの（テストでは停止した）時計
+    struct TokioClock;
+
+    impl Clock for TokioClock {
+        fn now(&self) -> std::time::Instant {
+            Instant::now().into_std()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_output_bursts_are_coalesced() {
+        let events = Arc::new(Notify::new());
+        let mut scheduler = CheckScheduler::new(MIN_INTERVAL, KEEPALIVE);
+        let start = Instant::now();
+        assert_eq!(scheduler.wait(&events, None).await, CheckReason::Initial);
+
+        // 10msごとに300ms間出力が続く
+        let notifier = tokio::spawn({
+            let events = events.clone();
+            async move {
+                for _ in 0..30 {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    events.notify_one();
+                }
+            }
+        });
+
+        // 30回の通知は最短間隔ごとの3回の確認にまとめられる
+        for expected_ms in [100, 200, 300] {
+            assert_eq!(scheduler.wait(&events, None).await, CheckReason::Output);
+            assert_eq!(start.elapsed(), Duration::from_millis(expected_ms));
+        }
+        notifier.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_pending_idle_fires_at_deadline() {
+        let events = Notify::new();
+        let mut scheduler = CheckScheduler::new(MIN_INTERVAL, KEEPALIVE);
+        let mut settings = StateFilterSettings::default();
+        settings.confirm_ms.insert("idle".to_string(), 1500);
+        let mut filter =
+            TransitionFilter::from_settings(&settings).with_clock(Arc::new(TokioClock));
+
+        assert_eq!(scheduler.wait(&events, None).await, CheckReason::Initial);
+        filter.observe(&SessionStatus::Busy);
+        let start = Instant::now();
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            FilterOutcome::Pending(SessionStatus::Idle)
+        );
+
+        // 新しい出力がなくても確定時刻に確認し直して遷移を報告する
+        let deadline = filter.next_deadline().map(Instant::from_std);
+        assert_eq!(
+            scheduler.wait(&events, deadline).await,
+            CheckReason::Deadline
+        );
+        assert_eq!(start.elapsed(), Duration::from_millis(1500));
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            FilterOutcome::Changed {
+                from: Some(SessionStatus::Busy),
+                to: SessionStatus::Idle,
+            }
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_rechecks_when_idle() {
+        let events = Notify::new();
+        let mut scheduler = CheckScheduler::new(MIN_INTERVAL, KEEPALIVE);
+        assert_eq!(scheduler.wait(&events, None).await, CheckReason::Initial);
+
+        // 通知も保留中の遷移もなければ、最短間隔の後keepalive間隔で確認する
+        for _ in 0..2 {
+            let start = Instant::now();
+            assert_eq!(scheduler.wait(&events, None).await, CheckReason::Keepalive);
+            assert_eq!(start.elapsed(), MIN_INTERVAL + KEEPALIVE);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 // cli_tool.rs - CLI ツール共通インターフェース
 
+use crate::project_detection::DetectedProject;
 use portable_pty::{CommandBuilder, PtySize};
 use std::path::Path;
 use terminal_size::{terminal_size, Height, Width};
@@ -12,8 +13,10 @@ pub trait CliTool: Send + Sync {
     /// ツール固有の環境変数を設定
     fn setup_environment(&self, cmd: &mut CommandBuilder);
 
-    /// プロジェクト名を推測
-    fn guess_project_name(&self, args: &[String], working_dir: &Path) -> Option<String>;
+    /// プロジェクト名を推測（検出ロジックは全ツール共通）
+    fn guess_project_name(&self, args: &[String], working_dir: &Path) -> Option<DetectedProject> {
+        crate::project_detection::detect_project(args, working_dir)
+    }
 
     /// ツール固有のコマンド文字列生成
     fn to_command_string(&self, args: &[String]) -> String {
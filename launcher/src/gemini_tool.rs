@@ -2,7 +2,6 @@
 
 use crate::cli_tool::CliTool;
 use portable_pty::CommandBuilder;
-use std::path::Path;
 
 /// Gemini固有のツール実装
 pub struct GeminiTool;
@@ -32,36 +31,6 @@ impl CliTool for GeminiTool {
         // Gemini固有の環境変数があれば追加
         // 例: cmd.env("GEMINI_LOG", "debug");
     }
-
-    fn guess_project_name(&self, args: &[String], working_dir: &Path) -> Option<String> {
-        // Gemini固有のプロジェクト名推測ロジック
-        // 現在はClaude同様の実装を使用
-
-        // --project 引数から取得を試行（Geminiが対応している場合）
-        if let Some(project_idx) = args.iter().position(|arg| arg == "--project") {
-            if let Some(project_name) = args.get(project_idx + 1) {
-                return Some(project_name.clone());
-            }
-        }
-
-        // 作業ディレクトリ名から推測
-        if let Some(dir_name) = working_dir.file_name() {
-            if let Some(name_str) = dir_name.to_str() {
-                return Some(name_str.to_string());
-            }
-        }
-
-        // 現在のディレクトリ名から推測
-        if let Ok(current_dir) = std::env::current_dir() {
-            if let Some(dir_name) = current_dir.file_name() {
-                if let Some(name_str) = dir_name.to_str() {
-                    return Some(name_str.to_string());
-                }
-            }
-        }
-
-        None
-    }
 }
 
 impl Default for GeminiTool {
@@ -88,7 +57,7 @@ mod tests {
         let working_dir = PathBuf::from("/tmp");
 
         let result = tool.guess_project_name(&args, &working_dir);
-        assert_eq!(result, Some("test-project".to_string()));
+        assert_eq!(result.map(|p| p.name), Some("test-project".to_string()));
     }
 
     #[test]
@@ -98,6 +67,6 @@ mod tests {
         let working_dir = PathBuf::from("/home/user/my-project");
 
         let result = tool.guess_project_name(&args, &working_dir);
-        assert_eq!(result, Some("my-project".to_string()));
+        assert_eq!(result.map(|p| p.name), Some("my-project".to_string()));
     }
 }
@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+
+use climonitor_shared::LauncherMetadata;
+
+/// launcherの実行環境を収集（取得できない項目は None）
+pub fn collect(working_dir: &Path, label: Option<String>, pid: Option<u32>) -> LauncherMetadata {
+    let git = read_git_head(working_dir);
+    LauncherMetadata {
+        git_branch: git.as_ref().map(|head| head.branch.clone()),
+        git_worktree: git.and_then(|head| head.worktree),
+        hostname: hostname(),
+        pid,
+        tty: tty(),
+        user: std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok()
+            .filter(|user| !user.is_empty()),
+        label: label.filter(|label| !label.is_empty()),
+    }
+}
+
+/// gitのHEAD情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHead {
+    /// ブランチ名（detached HEADの場合は短縮コミットハッシュ）
+    pub branch: String,
+    /// `git worktree add` で作成した作業ツリーの名前
+    pub worktree: Option<String>,
+}
+
+/// 作業ディレクトリから上位へ `.git` を探してHEADを読む（gitコマンドは使わない）
+pub fn read_git_head(working_dir: &Path) -> Option<GitHead> {
+    let (git_dir, linked) = find_git_dir(working_dir)?;
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let branch = match head.strip_prefix("ref:") {
+        Some(reference) => {
+            let reference = reference.trim();
+            reference
+                .strip_prefix("refs/heads/")
+                .unwrap_or(reference)
+                .to_string()
+        }
+        None => head.chars().take(7).collect(),
+    };
+    if branch.is_empty() {
+        return None;
+    }
+
+    // 作業ツリーのgitディレクトリは `.git/worktrees/<名前>`
+    let worktree = linked
+        .then(|| git_dir.file_name())
+        .flatten()
+        .map(|name| name.to_string_lossy().to_string());
+    Some(GitHead { branch, worktree })
+}
+
+/// gitディレクトリと、それが追加の作業ツリーかどうか
+fn find_git_dir(working_dir: &Path) -> Option<(PathBuf, bool)> {
+    for dir in working_dir.ancestors() {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some((dot_git, false));
+        }
+        if dot_git.is_file() {
+            // 作業ツリーやサブモジュールでは `.git` ファイルに `gitdir: <パス>` が書かれている
+            let content = std::fs::read_to_string(&dot_git).ok()?;
+            let git_dir = dir.join(content.trim().strip_prefix("gitdir:")?.trim());
+            // サブモジュールには commondir がない
+            let linked = git_dir.join("commondir").is_file();
+            return Some((git_dir, linked));
+        }
+    }
+    None
+}
+
+#[cfg(unix)]
+fn hostname() -> Option<String> {
+    nix::unistd::gethostname()
+        .ok()
+        .map(|name| name.to_string_lossy().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(windows)]
+fn hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
+
+#[cfg(unix)]
+fn tty() -> Option<String> {
+    nix::unistd::ttyname(std::io::stdin())
+        .ok()
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+#[cfg(windows)]
+fn tty() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_branch_from_repository() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        std::fs::write(repo.path().join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        let subdir = repo.path().join("src");
+        std::fs::create_dir(&subdir).unwrap();
+
+        assert_eq!(
+            read_git_head(&subdir),
+            Some(GitHead {
+                branch: "main".to_string(),
+                worktree: None,
+            })
+        );
+
+        // detached HEADは短縮コミットハッシュ
+        std::fs::write(
+            repo.path().join(".git/HEAD"),
+            "0123456789abcdef0123456789abcdef01234567\n",
+        )
+        .unwrap();
+        assert_eq!(read_git_head(&subdir).unwrap().branch, "0123456");
+    }
+
+    #[test]
+    fn test_reads_branch_from_linked_worktree() {
+        let root = tempfile::tempdir().unwrap();
+        let git_dir = root.path().join("repo/.git/worktrees/feature-x");
+        std::fs::create_dir_all(&git_dir).unwrap();
+        std::fs::write(git_dir.join("HEAD"), "ref: refs/heads/feature/x\n").unwrap();
+        std::fs::write(git_dir.join("commondir"), "../..\n").unwrap();
+
+        let worktree = root.path().join("feature-x");
+        std::fs::create_dir(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", git_dir.display()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_git_head(&worktree),
+            Some(GitHead {
+                branch: "feature/x".to_string(),
+                worktree: Some("feature-x".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_collect_without_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        let metadata = collect(dir.path(), Some("reviewer".to_string()), Some(42));
+        assert_eq!(metadata.git_branch, None);
+        assert_eq!(metadata.pid, Some(42));
+        assert_eq!(metadata.label.as_deref(), Some("reviewer"));
+        assert_eq!(collect(dir.path(), Some(String::new()), None).label, None);
+    }
+}
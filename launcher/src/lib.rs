@@ -1,11 +1,19 @@
+pub mod activity;
+pub mod auto_approve;
+pub mod check_scheduler;
 pub mod claude_tool;
 pub mod cli_tool;
 pub mod gemini_tool;
-pub mod grpc_client;
+pub mod launcher_metadata;
+pub mod process_metrics;
+pub mod project_detection;
+pub mod prompt_queue;
 pub mod screen_buffer;
 pub mod screen_claude_detector;
 pub mod screen_gemini_detector;
 pub mod state_detector;
+pub mod terminal_output;
 pub mod tool_wrapper;
+pub mod transition_filter;
 pub mod transport_client;
 pub mod transports;
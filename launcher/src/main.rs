@@ -3,10 +3,9 @@ use clap::{Arg, Command};
 
 // lib crate から import
 use climonitor_launcher::cli_tool::{CliToolFactory, CliToolType};
-use climonitor_launcher::grpc_client::GrpcLauncherClient;
 use climonitor_launcher::tool_wrapper::ToolWrapper;
 use climonitor_launcher::transport_client::LauncherClient;
-use climonitor_shared::Config;
+use climonitor_shared::{GrpcSettings, LoadedConfig, TcpSettings};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -29,15 +28,29 @@ async fn main() -> Result<()> {
         .arg(
             Arg::new("grpc")
                 .long("grpc")
-                .help("Use gRPC protocol instead of raw TCP/Unix socket")
+                .help("Use gRPC protocol instead of Unix socket")
                 .action(clap::ArgAction::SetTrue),
         )
         .arg(
             Arg::new("connect")
                 .long("connect")
-                .help("Connection address (Unix: socket path)")
+                .help(
+                    "Connection address (Unix: socket path, TCP: tcp://host:port, gRPC: host:port)",
+                )
                 .value_name("ADDR"),
         )
+        .arg(
+            Arg::new("auto_approve_dry_run")
+                .long("auto-approve-dry-run")
+                .help("Only log what auto-approve rules would answer, without sending keys")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("label")
+                .long("label")
+                .help("Name shown in the monitor to tell this session apart")
+                .value_name("NAME"),
+        )
         .arg(
             Arg::new("config")
                 .short('c')
@@ -89,35 +102,56 @@ async fn main() -> Result<()> {
     }
 
     // 設定を読み込み（優先順位: CLI > 環境変数 > 設定ファイル > デフォルト）
-    let mut config = if let Some(config_path) = &config_path {
-        // --config で指定された設定ファイルを読み込み
-        Config::from_file(config_path)?
-    } else if let Some((config, _path)) = Config::load_auto()? {
-        // 自動検出で設定ファイルを読み込み
-        config
-    } else {
-        // デフォルト設定を使用
-        Config::default()
-    };
-
-    // 環境変数で上書き
-    config.apply_env_overrides();
+    let mut loaded = LoadedConfig::load(config_path.as_deref())?;
 
     // CLI引数で上書き
-    if let Some(addr) = connect_addr {
-        if !addr.starts_with("tcp://") {
-            config.connection.unix_socket_path = Some(addr.into());
+    if use_grpc {
+        // CLIで指定したtransportを優先
+        loaded.config.connection.tcp = None;
+        let grpc = loaded
+            .config
+            .connection
+            .grpc
+            .get_or_insert_with(GrpcSettings::default);
+        if let Some(addr) = connect_addr {
+            grpc.bind_addr = addr.trim_start_matches("http://").to_string();
+            loaded.record_cli("connection.grpc.bind_addr", "--connect");
+        } else {
+            loaded.record_cli("connection.grpc", "--grpc");
+        }
+    } else if let Some(addr) = connect_addr {
+        if let Some(tcp_addr) = addr.strip_prefix("tcp://") {
+            loaded.config.connection.grpc = None;
+            loaded
+                .config
+                .connection
+                .tcp
+                .get_or_insert_with(TcpSettings::default)
+                .bind_addr = tcp_addr.to_string();
+            loaded.record_cli("connection.tcp.bind_addr", "--connect");
+        } else {
+            loaded.config.connection.unix_socket_path = Some(addr.into());
+            loaded.record_cli("connection.unix_socket_path", "--connect");
         }
     }
     if verbose {
-        config.logging.verbose = true;
+        loaded.config.logging.verbose = true;
+        loaded.record_cli("logging.verbose", "--verbose");
     }
     if let Some(log_file_path) = log_file.clone() {
-        config.logging.log_file = Some(log_file_path);
+        loaded.config.logging.log_file = Some(log_file_path);
+        loaded.record_cli("logging.log_file", "--log-file");
+    }
+    if matches.get_flag("auto_approve_dry_run") {
+        loaded.config.auto_approve.dry_run = true;
+        loaded.record_cli("auto_approve.dry_run", "--auto-approve-dry-run");
     }
 
+    loaded.config.validate()?;
+    let config = loaded.config;
+
     // ログシステムの初期化
-    config.logging.init_logging();
+    config.logging.init_logging()?;
 
     // 接続設定を生成
     let connection_config = config.to_connection_config();
@@ -140,173 +174,24 @@ async fn main() -> Result<()> {
 
     let tool_wrapper = ToolWrapper::new(cli_tool, tool_args).working_dir(working_dir);
 
-    if use_grpc {
-        // gRPC接続
-        if verbose {
-            println!("🚀 Using gRPC protocol");
-        }
-
-        let grpc_client = if let Some(addr) = connect_addr {
-            let endpoint = if addr.starts_with("http://") || addr.starts_with("https://") {
-                addr.to_string()
-            } else {
-                format!("http://{addr}")
-            };
-            if verbose {
-                println!("🔧 gRPC endpoint: {endpoint}");
-            }
-            let launcher_id = climonitor_shared::generate_connection_id();
-            let session_id = climonitor_shared::generate_connection_id();
-            GrpcLauncherClient::new_with_endpoint(launcher_id, session_id, endpoint).await?
-        } else {
-            GrpcLauncherClient::new(&connection_config).await?
-        };
-
-        // Connect message送信
-        grpc_client
-            .send_connect(
-                tool_wrapper.guess_project_name(),
-                tool_type,
-                tool_wrapper.get_args().to_vec(),
-                tool_wrapper
-                    .get_working_dir()
-                    .cloned()
-                    .unwrap_or_else(|| std::env::current_dir().unwrap()),
-            )
-            .await?;
-
-        if verbose {
-            println!("🔄 Running CLI tool with gRPC monitoring...");
-        }
-
-        // gRPC用のLauncherClient作成（PTY監視付き）
-        let mut launcher = LauncherClient::new_with_grpc(
-            tool_wrapper,
-            grpc_client,
-            config.logging.verbose,
-            config.logging.log_file,
-        )
-        .await?;
-
-        // monitor接続時のみターミナルガード作成
-        let _terminal_guard = if launcher.is_connected() {
-            use climonitor_launcher::transport_client::create_terminal_guard_global;
-            Some(create_terminal_guard_global(config.logging.verbose)?)
-        } else {
-            None
-        };
-
-        // gRPCパスでも同様のシグナルハンドリングを実装
-        #[cfg(unix)]
-        let mut sigint = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt())?;
-        #[cfg(unix)]
-        let mut sigterm =
-            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
-
-        // プラットフォーム固有のシグナルハンドリング
-        #[cfg(unix)]
-        {
-            tokio::select! {
-                result = launcher.run_claude() => {
-                    match result {
-                        Ok(_) => {
-                            if config.logging.verbose {
-                                println!("✅ CLI tool finished successfully");
-                            }
-                        }
-                        Err(e) => {
-                            climonitor_shared::log_error!(climonitor_shared::LogCategory::System, "CLI tool execution failed: {e}");
-                            if let Some(guard) = _terminal_guard {
-                                drop(guard); // ターミナル設定を明示的に復元
-                            }
-                            climonitor_launcher::transport_client::force_restore_terminal(); // 強制復元
-                            std::process::exit(1);
-                        }
-                    }
-                }
-                _ = sigint.recv() => {
-                    if config.logging.verbose {
-                        println!("\n🛑 Received SIGINT, shutting down gracefully...");
-                    }
-                    if let Some(guard) = _terminal_guard {
-                        drop(guard); // ターミナル設定を明示的に復元
-                    }
-                    climonitor_launcher::transport_client::force_restore_terminal(); // 強制復元
-                    std::process::exit(130); // 128 + 2 (SIGINT)
-                }
-                _ = sigterm.recv() => {
-                    if config.logging.verbose {
-                        println!("\n🛑 Received SIGTERM, shutting down gracefully...");
-                    }
-                    if let Some(guard) = _terminal_guard {
-                        drop(guard); // ターミナル設定を明示的に復元
-                    }
-                    climonitor_launcher::transport_client::force_restore_terminal(); // 強制復元
-                    std::process::exit(143); // 128 + 15 (SIGTERM)
-                }
-                _ = tokio::signal::ctrl_c() => {
-                    if config.logging.verbose {
-                        println!("\n🛑 Received Ctrl+C, shutting down gracefully...");
-                    }
-                    if let Some(guard) = _terminal_guard {
-                        drop(guard); // ターミナル設定を明示的に復元
-                    }
-                    climonitor_launcher::transport_client::force_restore_terminal(); // 強制復元
-                    std::process::exit(130); // 128 + 2 (SIGINT)
-                }
-            }
-        }
-
-        #[cfg(not(unix))]
-        {
-            tokio::select! {
-                result = launcher.run_claude() => {
-                    match result {
-                        Ok(_) => {
-                            if config.logging.verbose {
-                                println!("✅ CLI tool finished successfully");
-                            }
-                        }
-                        Err(e) => {
-                            climonitor_shared::log_error!(climonitor_shared::LogCategory::System, "❌ CLI tool execution failed: {e}");
-                            // Windows版では正常終了（TerminalGuardのDropが自動的に実行される）
-                            std::process::exit(1);
-                        }
-                    }
-                }
-                _ = tokio::signal::ctrl_c() => {
-                    if config.logging.verbose {
-                        println!("\n🛑 Received Ctrl+C, shutting down gracefully...");
-                    }
-                    // Windows版では正常終了（TerminalGuardのDropが自動的に実行される）
-                    return Ok(());
-                }
-            }
-        }
-
-        if config.logging.verbose {
-            println!("👋 climonitor-launcher finished");
-        }
-
-        return Ok(());
-    }
-
-    // 従来のTCP/Unix socket接続
+    // transportは接続設定から選択される
     let mut launcher = LauncherClient::new(
         tool_wrapper,
         connection_config,
         config.logging.verbose,
         config.logging.log_file,
     )
-    .await?;
-
-    // monitor接続時のみターミナルガード作成
-    let _terminal_guard = if launcher.is_connected() {
-        use climonitor_launcher::transport_client::create_terminal_guard_global;
-        Some(create_terminal_guard_global(config.logging.verbose)?)
-    } else {
-        None
-    };
+    .await?
+    .with_auto_approve(&config.auto_approve)
+    .with_state_filter(&config.state_filter)
+    .with_state_report(&config.state_report)
+    .with_process_metrics(&config.process_metrics)
+    .with_label(matches.get_one::<String>("label").cloned());
+
+    // ターミナルガード作成（monitorが起動していなくてもPTY経由で実行する）
+    let _terminal_guard = climonitor_launcher::transport_client::create_terminal_guard_global(
+        config.logging.verbose,
+    )?;
 
     // クロスプラットフォーム対応のシグナルハンドリング
     #[cfg(unix)]
@@ -327,9 +212,7 @@ async fn main() -> Result<()> {
                     }
                     Err(e) => {
                         climonitor_shared::log_error!(climonitor_shared::LogCategory::System, "❌ CLI tool execution failed: {e}");
-                        if let Some(guard) = _terminal_guard {
-                            drop(guard); // ターミナル設定を明示的に復元
-                        }
+                        drop(_terminal_guard); // ターミナル設定を明示的に復元
                         climonitor_launcher::transport_client::force_restore_terminal(); // 強制復元
                         std::process::exit(1);
                     }
@@ -339,9 +222,7 @@ async fn main() -> Result<()> {
                 if config.logging.verbose {
                     println!("\n🛑 Received SIGINT, shutting down gracefully...");
                 }
-                if let Some(guard) = _terminal_guard {
-                    drop(guard); // ターミナル設定を明示的に復元
-                }
+                drop(_terminal_guard); // ターミナル設定を明示的に復元
                 climonitor_launcher::transport_client::force_restore_terminal(); // 強制復元
                 std::process::exit(130); // 128 + 2 (SIGINT)
             }
@@ -349,9 +230,7 @@ async fn main() -> Result<()> {
                 if config.logging.verbose {
                     println!("\n🛑 Received SIGTERM, shutting down gracefully...");
                 }
-                if let Some(guard) = _terminal_guard {
-                    drop(guard); // ターミナル設定を明示的に復元
-                }
+                drop(_terminal_guard); // ターミナル設定を明示的に復元
                 climonitor_launcher::transport_client::force_restore_terminal(); // 強制復元
                 std::process::exit(143); // 128 + 15 (SIGTERM)
             }
@@ -359,9 +238,7 @@ async fn main() -> Result<()> {
                 if config.logging.verbose {
                     println!("\n🛑 Received Ctrl+C, shutting down gracefully...");
                 }
-                if let Some(guard) = _terminal_guard {
-                    drop(guard); // ターミナル設定を明示的に復元
-                }
+                drop(_terminal_guard); // ターミナル設定を明示的に復元
                 climonitor_launcher::transport_client::force_restore_terminal(); // 強制復元
                 std::process::exit(130); // 128 + 2 (SIGINT)
             }
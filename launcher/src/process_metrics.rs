@@ -0,0 +1,227 @@
+// process_metrics.rs - CLIツールのプロセスツリーのリソース使用量
+//
+// Linuxでは /proc からCLIツール本体と子孫プロセスのCPU時間・RSSを集計する。
+// エージェントが起動した `npm test` などが暴走していないかをmonitorで確認するため。
+
+use climonitor_shared::ProcessMetrics;
+use std::time::Instant;
+
+/// `/proc/<pid>/stat` から読み取る値
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcStat {
+    pub pid: u32,
+    pub ppid: u32,
+    /// utime + stime（クロックティック）
+    pub cpu_ticks: u64,
+    /// 常駐ページ数
+    pub rss_pages: u64,
+}
+
+/// `/proc/<pid>/stat` を解析（コマンド名は空白や括弧を含みうるので最後の `)` 以降を読む）
+pub fn parse_stat(content: &str) -> Option<ProcStat> {
+    let (head, rest) = content.rsplit_once(')')?;
+    let pid = head.split_once('(')?.0.trim().parse().ok()?;
+    // rest の先頭はフィールド3（state）
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let field = |n: usize| fields.get(n - 3)?.parse::<u64>().ok();
+
+    Some(ProcStat {
+        pid,
+        ppid: field(4)? as u32,
+        cpu_ticks: field(14)? + field(15)?,
+        rss_pages: field(24)?,
+    })
+}
+
+/// `root` とその子孫プロセス（先頭が `root`、存在しなければ空）
+pub fn process_tree(root: u32, stats: &[ProcStat]) -> Vec<&ProcStat> {
+    let mut tree: Vec<&ProcStat> = stats.iter().filter(|s| s.pid == root).collect();
+    let mut index = 0;
+    while index < tree.len() {
+        let parent = tree[index].pid;
+        tree.extend(stats.iter().filter(|s| s.ppid == parent && s.pid != root));
+        index += 1;
+    }
+    tree
+}
+
+/// プロセスツリーのリソース使用量を計測（CPU使用率は前回の計測との差分）
+pub struct ProcessSampler {
+    root: u32,
+    clock_ticks: u64,
+    page_size: u64,
+    previous: Option<(u64, Instant)>,
+}
+
+impl ProcessSampler {
+    pub fn new(root: u32) -> Self {
+        #[cfg(target_os = "linux")]
+        let (clock_ticks, page_size) = {
+            use nix::unistd::{sysconf, SysconfVar};
+            let value = |var| sysconf(var).ok().flatten().filter(|v| *v > 0);
+            (
+                value(SysconfVar::CLK_TCK).map_or(100, |v| v as u64),
+                value(SysconfVar::PAGE_SIZE).map_or(4096, |v| v as u64),
+            )
+        };
+        #[cfg(not(target_os = "linux"))]
+        let (clock_ticks, page_size) = (100, 4096);
+
+        Self {
+            root,
+            clock_ticks,
+            page_size,
+            previous: None,
+        }
+    }
+
+    /// クロックティック・ページサイズを差し替え
+    pub fn with_units(mut self, clock_ticks: u64, page_size: u64) -> Self {
+        self.clock_ticks = clock_ticks;
+        self.page_size = page_size;
+        self
+    }
+
+    /// /proc を読んで計測（Linux以外、またはCLIツールが終了している場合は None）
+    pub fn sample(&mut self) -> Option<ProcessMetrics> {
+        #[cfg(target_os = "linux")]
+        {
+            let stats = read_proc_stats();
+            self.aggregate(&stats, Instant::now())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// 読み取った値を集計（初回のCPU使用率は0%）
+    pub fn aggregate(&mut self, stats: &[ProcStat], now: Instant) -> Option<ProcessMetrics> {
+        let tree = process_tree(self.root, stats);
+        if tree.is_empty() {
+            return None;
+        }
+
+        let cpu_ticks: u64 = tree.iter().map(|s| s.cpu_ticks).sum();
+        let rss_pages: u64 = tree.iter().map(|s| s.rss_pages).sum();
+        // 終了した子プロセスの分だけ合計が減ることがあるので飽和減算
+        let cpu_percent = match self.previous {
+            Some((previous_ticks, previous_at)) => {
+                let elapsed = now.duration_since(previous_at).as_secs_f64();
+                if elapsed > 0.0 {
+                    cpu_ticks.saturating_sub(previous_ticks) as f64
+                        / self.clock_ticks as f64
+                        / elapsed
+                        * 100.0
+                } else {
+                    0.0
+                }
+            }
+            None => 0.0,
+        };
+        self.previous = Some((cpu_ticks, now));
+
+        Some(ProcessMetrics {
+            cpu_percent: cpu_percent as f32,
+            rss_bytes: rss_pages * self.page_size,
+            child_count: (tree.len() - 1) as u32,
+        })
+    }
+}
+
+/// /proc 配下の全プロセスの stat を読み取る（読めないプロセスは無視）
+#[cfg(target_os = "linux")]
+fn read_proc_stats() -> Vec<ProcStat> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .filter_map(|entry| std::fs::read_to_string(entry.path().join("stat")).ok())
+        .filter_map(|content| parse_stat(&content))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn stat(pid: u32, ppid: u32, cpu_ticks: u64, rss_pages: u64) -> ProcStat {
+        ProcStat {
+            pid,
+            ppid,
+            cpu_ticks,
+            rss_pages,
+        }
+    }
+
+    #[test]
+    fn test_parse_stat() {
+        // コマンド名に空白と括弧を含む
+        let content = "4242 (node (npm test)) S 4100 4242 4100 34816 4242 4194304 1000 0 0 0 150 50 0 0 20 0 11 0 123456 987654321 2560 18446744073709551615";
+        assert_eq!(parse_stat(content), Some(stat(4242, 4100, 200, 2560)));
+        assert_eq!(parse_stat("4242 (node) S 1"), None);
+        assert_eq!(parse_stat("garbage"), None);
+    }
+
+    #[test]
+    fn test_process_tree_includes_descendants_only() {
+        let stats = vec![
+            stat(1, 0, 0, 0),
+            stat(100, 1, 0, 0),
+            stat(101, 100, 0, 0),
+            stat(102, 101, 0, 0),
+            stat(200, 1, 0, 0),
+        ];
+        let pids: Vec<u32> = process_tree(100, &stats).iter().map(|s| s.pid).collect();
+        assert_eq!(pids, vec![100, 101, 102]);
+        assert!(process_tree(300, &stats).is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_cpu_and_rss() {
+        let mut sampler = ProcessSampler::new(100).with_units(100, 4096);
+        let start = Instant::now();
+
+        let first = sampler
+            .aggregate(&[stat(100, 1, 1000, 100), stat(101, 100, 500, 50)], start)
+            .unwrap();
+        assert_eq!(first.cpu_percent, 0.0);
+        assert_eq!(first.rss_bytes, 150 * 4096);
+        assert_eq!(first.child_count, 1);
+
+        // 2秒で300ティック = 1.5コア分
+        let second = sampler
+            .aggregate(
+                &[
+                    stat(100, 1, 1100, 100),
+                    stat(101, 100, 700, 50),
+                    stat(102, 101, 0, 10),
+                ],
+                start + Duration::from_secs(2),
+            )
+            .unwrap();
+        assert_eq!(second.cpu_percent, 150.0);
+        assert_eq!(second.child_count, 2);
+
+        // 本体が終了していれば計測しない
+        assert_eq!(
+            sampler.aggregate(&[stat(101, 1, 0, 0)], start + Duration::from_secs(4)),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_sample_current_process() {
+        let metrics = ProcessSampler::new(std::process::id()).sample().unwrap();
+        assert!(metrics.rss_bytes > 0);
+    }
+}
@@ -0,0 +1,295 @@
+// project_detection.rs - プロジェクト名の検出（全CLIツール共通）
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use climonitor_shared::ProjectSettings;
+
+/// プロジェクト名の決定方法
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectSource {
+    /// CLIツールの `--project` 引数
+    Argument,
+    /// `.climonitor/config.toml` の `[project] name`
+    Config(PathBuf),
+    /// リポジトリ直下の `Cargo.toml` / `package.json` / `pyproject.toml` のパッケージ名
+    Manifest(PathBuf),
+    /// リポジトリのディレクトリ名（追加の作業ツリーではメインリポジトリの名前）
+    Repository(PathBuf),
+    /// 作業ディレクトリ名
+    WorkingDir,
+}
+
+impl fmt::Display for ProjectSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Argument => write!(f, "--project argument"),
+            Self::Config(path) => write!(f, "config {}", path.display()),
+            Self::Manifest(path) => write!(f, "manifest {}", path.display()),
+            Self::Repository(path) => write!(f, "repository {}", path.display()),
+            Self::WorkingDir => write!(f, "working directory name"),
+        }
+    }
+}
+
+/// 検出したプロジェクト名
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedProject {
+    pub name: String,
+    pub source: ProjectSource,
+}
+
+impl DetectedProject {
+    fn new(name: impl Into<String>, source: ProjectSource) -> Self {
+        Self {
+            name: name.into(),
+            source,
+        }
+    }
+}
+
+/// プロジェクト名を検出
+///
+/// 優先順位: `--project` 引数 > `.climonitor/config.toml` > マニフェスト > リポジトリ名 > 作業ディレクトリ名。
+/// 設定ファイルは作業ディレクトリからリポジトリのルートまで、マニフェストはルートのみを探す。
+pub fn detect_project(args: &[String], working_dir: &Path) -> Option<DetectedProject> {
+    if let Some(project_idx) = args.iter().position(|arg| arg == "--project") {
+        if let Some(project_name) = args.get(project_idx + 1) {
+            return Some(DetectedProject::new(
+                project_name.clone(),
+                ProjectSource::Argument,
+            ));
+        }
+    }
+
+    let working_dir = if working_dir.is_absolute() {
+        working_dir.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(working_dir)
+    };
+    let repository_root = find_repository_root(&working_dir);
+    let project_root = repository_root.as_deref().unwrap_or(&working_dir);
+
+    for dir in working_dir.ancestors() {
+        if let Some(name) = ProjectSettings::load_from_dir(dir).and_then(|p| p.name) {
+            let path = dir.join(".climonitor").join("config.toml");
+            return Some(DetectedProject::new(name, ProjectSource::Config(path)));
+        }
+        if dir == project_root {
+            break;
+        }
+    }
+
+    if let Some(detected) = read_manifest_name(project_root) {
+        return Some(detected);
+    }
+
+    if let Some(root) = &repository_root {
+        let name = main_repository_dir(root)
+            .as_deref()
+            .and_then(dir_name)
+            .or_else(|| dir_name(root));
+        if let Some(name) = name {
+            return Some(DetectedProject::new(
+                name,
+                ProjectSource::Repository(root.clone()),
+            ));
+        }
+    }
+
+    // ルートディレクトリなど名前がない場合はカレントディレクトリ名
+    dir_name(&working_dir)
+        .or_else(|| dir_name(&std::env::current_dir().ok()?))
+        .map(|name| DetectedProject::new(name, ProjectSource::WorkingDir))
+}
+
+fn dir_name(dir: &Path) -> Option<String> {
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+}
+
+/// VCSのルート（`.git` / `.hg` / `.jj` のあるディレクトリ）
+fn find_repository_root(working_dir: &Path) -> Option<PathBuf> {
+    working_dir
+        .ancestors()
+        .find(|dir| {
+            [".git", ".hg", ".jj"]
+                .iter()
+                .any(|vcs| dir.join(vcs).exists())
+        })
+        .map(Path::to_path_buf)
+}
+
+/// `git worktree add` で作成した作業ツリーの場合はメインリポジトリのディレクトリ
+fn main_repository_dir(root: &Path) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(root.join(".git")).ok()?;
+    let git_dir = root.join(content.trim().strip_prefix("gitdir:")?.trim());
+    let common_dir = std::fs::read_to_string(git_dir.join("commondir")).ok()?;
+    let common_dir = git_dir.join(common_dir.trim()).canonicalize().ok()?;
+    // 共通のgitディレクトリは `<メインリポジトリ>/.git`
+    common_dir.parent().map(Path::to_path_buf)
+}
+
+/// マニフェストからパッケージ名を読む（Cargo.toml > package.json > pyproject.toml）
+fn read_manifest_name(dir: &Path) -> Option<DetectedProject> {
+    let detected = |path: PathBuf, name: Option<String>| {
+        name.filter(|name| !name.is_empty())
+            .map(|name| DetectedProject::new(name, ProjectSource::Manifest(path)))
+    };
+
+    let cargo = dir.join("Cargo.toml");
+    if let Some(manifest) = read_toml(&cargo) {
+        // ワークスペースのみのCargo.tomlには [package] がない
+        let name = toml_str(&manifest, &["package", "name"]);
+        if let Some(detected) = detected(cargo, name) {
+            return Some(detected);
+        }
+    }
+
+    let package_json = dir.join("package.json");
+    if let Ok(content) = std::fs::read_to_string(&package_json) {
+        let name = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|json| json.get("name")?.as_str().map(str::to_string));
+        if let Some(detected) = detected(package_json, name) {
+            return Some(detected);
+        }
+    }
+
+    let pyproject = dir.join("pyproject.toml");
+    if let Some(manifest) = read_toml(&pyproject) {
+        let name = toml_str(&manifest, &["project", "name"])
+            .or_else(|| toml_str(&manifest, &["tool", "poetry", "name"]));
+        if let Some(detected) = detected(pyproject, name) {
+            return Some(detected);
+        }
+    }
+
+    None
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    toml::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn toml_str(value: &toml::Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .try_fold(value, |value, key| value.get(key))?
+        .as_str()
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_project_argument_wins() {
+        let args = vec!["--project".to_string(), "test-project".to_string()];
+        let detected = detect_project(&args, Path::new("/tmp")).unwrap();
+        assert_eq!(
+            detected,
+            DetectedProject::new("test-project", ProjectSource::Argument)
+        );
+    }
+
+    #[test]
+    fn test_subdirectory_uses_repository_manifest() {
+        let root = tempfile::tempdir().unwrap();
+        let repo = root.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+
+        // マニフェストがなければリポジトリ名
+        let detected = detect_project(&[], &repo.join("src")).unwrap();
+        assert_eq!(detected.name, "repo");
+        assert_eq!(detected.source, ProjectSource::Repository(repo.clone()));
+
+        // ワークスペースのみのCargo.tomlは飛ばして package.json を使う
+        write(&repo.join("Cargo.toml"), "[workspace]\nmembers = []\n");
+        write(&repo.join("package.json"), r#"{"name": "web-app"}"#);
+        let detected = detect_project(&[], &repo.join("src")).unwrap();
+        assert_eq!(detected.name, "web-app");
+        assert_eq!(
+            detected.source,
+            ProjectSource::Manifest(repo.join("package.json"))
+        );
+
+        write(&repo.join("Cargo.toml"), "[package]\nname = \"my-crate\"\n");
+        assert_eq!(
+            detect_project(&[], &repo.join("src")).unwrap().name,
+            "my-crate"
+        );
+    }
+
+    #[test]
+    fn test_pyproject_name() {
+        let root = tempfile::tempdir().unwrap();
+        let repo = root.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        write(
+            &repo.join("pyproject.toml"),
+            "[tool.poetry]\nname = \"py-tool\"\n",
+        );
+        assert_eq!(detect_project(&[], &repo).unwrap().name, "py-tool");
+    }
+
+    #[test]
+    fn test_config_override() {
+        let root = tempfile::tempdir().unwrap();
+        let repo = root.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        write(&repo.join("Cargo.toml"), "[package]\nname = \"my-crate\"\n");
+        let config = repo.join(".climonitor/config.toml");
+        write(
+            &config,
+            "[logging]\nverbose = true\n\n[project]\nname = \"Override\"\n",
+        );
+
+        let detected = detect_project(&[], &repo.join("src/deep")).unwrap();
+        assert_eq!(
+            detected,
+            DetectedProject::new("Override", ProjectSource::Config(config))
+        );
+
+        // リポジトリの外の設定ファイルは使わない
+        write(
+            &root.path().join(".climonitor/config.toml"),
+            "[project]\nname = \"outside\"\n",
+        );
+        std::fs::remove_file(repo.join(".climonitor/config.toml")).unwrap();
+        assert_eq!(detect_project(&[], &repo).unwrap().name, "my-crate");
+    }
+
+    #[test]
+    fn test_linked_worktree_uses_main_repository_name() {
+        let root = tempfile::tempdir().unwrap();
+        let git_dir = root.path().join("climonitor/.git/worktrees/feat-x");
+        std::fs::create_dir_all(&git_dir).unwrap();
+        write(&git_dir.join("commondir"), "../..\n");
+        let worktree = root.path().join("feat-x");
+        write(
+            &worktree.join(".git"),
+            &format!("gitdir: {}\n", git_dir.display()),
+        );
+
+        let detected = detect_project(&[], &worktree.join("src")).unwrap();
+        assert_eq!(detected.name, "climonitor");
+        assert_eq!(detected.source, ProjectSource::Repository(worktree));
+    }
+
+    #[test]
+    fn test_working_dir_fallback() {
+        let detected = detect_project(&[], Path::new("/nonexistent/my-project")).unwrap();
+        assert_eq!(
+            detected,
+            DetectedProject::new("my-project", ProjectSource::WorkingDir)
+        );
+    }
+}
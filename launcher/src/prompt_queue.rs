@@ -0,0 +1,326 @@
+// prompt_queue.rs - Busy中に次の指示を予約し、Idle遷移時にPTYへ送信する
+
+use std::sync::{Arc, Mutex};
+use unicode_width::UnicodeWidthChar;
+
+/// 予約入力モードに入るホットキー（Ctrl+]）
+pub const QUEUE_HOTKEY: u8 = 0x1d;
+
+const ENTER: u8 = b'\r';
+const ESCAPE: u8 = 0x1b;
+const CTRL_C: u8 = 0x03;
+const BACKSPACE: u8 = 0x7f;
+const CTRL_H: u8 = 0x08;
+
+/// タスク間で共有する予約プロンプト
+pub type SharedPromptQueue = Arc<Mutex<PromptQueue>>;
+
+/// 予約プロンプト
+#[derive(Debug, Default)]
+pub struct PromptQueue {
+    staged: Option<String>,
+}
+
+impl PromptQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// プロンプトを予約（既存の予約は置き換え、空文字列は取り消し）
+    pub fn stage(&mut self, text: String) {
+        if text.trim().is_empty() {
+            self.staged = None;
+        } else {
+            self.staged = Some(text);
+        }
+    }
+
+    /// 予約を取り消し
+    pub fn clear(&mut self) {
+        self.staged = None;
+    }
+
+    /// 予約済みプロンプトを取り出し
+    pub fn take(&mut self) -> Option<String> {
+        self.staged.take()
+    }
+
+    /// 予約済みプロンプトを参照
+    pub fn staged(&self) -> Option<&str> {
+        self.staged.as_deref()
+    }
+
+    pub fn is_queued(&self) -> bool {
+        self.staged.is_some()
+    }
+}
+
+/// ホットキー処理の結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueEvent {
+    /// 予約入力モード開始
+    CaptureStarted,
+    /// 入力テキストを予約（空の場合は取り消し）
+    Staged(String),
+    /// 予約入力モードをキャンセル
+    CaptureCancelled,
+}
+
+/// 予約入力中のエスケープシーケンスの読み取り状態
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum EscapeState {
+    #[default]
+    Ground,
+    /// ESCを受信（後続がなければ単独のEsc）
+    Escape,
+    /// CSI（ESC [ ...）の終端文字待ち
+    Csi,
+    /// SS3（ESC O x）の1文字待ち
+    Ss3,
+}
+
+/// stdin入力からホットキーを検出し、予約入力をキャプチャする
+///
+/// Ctrl+] で予約入力モードに入り、Enterで確定、Esc/Ctrl+Cでキャンセル。
+/// Ctrl+] を2回続けて押すとCtrl+]そのものをPTYへ送信する。
+/// 矢印キーなどのエスケープシーケンスは読み捨て、同じ読み取りの末尾にある単独のESCだけをEscとみなす。
+#[derive(Debug, Default)]
+pub struct HotkeyInterceptor {
+    capturing: bool,
+    buffer: Vec<u8>,
+    escape: EscapeState,
+}
+
+impl HotkeyInterceptor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    /// 予約入力中のテキスト（予約入力モードでなければ None）
+    pub fn captured_text(&self) -> Option<String> {
+        self.capturing
+            .then(|| String::from_utf8_lossy(&self.buffer).into_owned())
+    }
+
+    /// 入力を処理し、PTYへ転送するバイト列と発生したイベントを返す
+    pub fn process(&mut self, data: &[u8]) -> (Vec<u8>, Vec<QueueEvent>) {
+        let mut forward = Vec::with_capacity(data.len());
+        let mut events = Vec::new();
+
+        for &byte in data {
+            if !self.capturing {
+                if byte == QUEUE_HOTKEY {
+                    self.capturing = true;
+                    self.buffer.clear();
+                    events.push(QueueEvent::CaptureStarted);
+                } else {
+                    forward.push(byte);
+                }
+                continue;
+            }
+
+            match self.escape {
+                EscapeState::Escape => {
+                    // ESC [ / ESC O 以外（Alt+キー）は1文字で終わる
+                    self.escape = match byte {
+                        b'[' => EscapeState::Csi,
+                        b'O' => EscapeState::Ss3,
+                        _ => EscapeState::Ground,
+                    };
+                    continue;
+                }
+                EscapeState::Csi => {
+                    if (0x40..=0x7e).contains(&byte) {
+                        self.escape = EscapeState::Ground;
+                    }
+                    continue;
+                }
+                EscapeState::Ss3 => {
+                    self.escape = EscapeState::Ground;
+                    continue;
+                }
+                EscapeState::Ground => {}
+            }
+
+            match byte {
+                QUEUE_HOTKEY if self.buffer.is_empty() => {
+                    // ホットキー2回押しはリテラルとして送信
+                    self.capturing = false;
+                    forward.push(QUEUE_HOTKEY);
+                    events.push(QueueEvent::CaptureCancelled);
+                }
+                ENTER | b'\n' => {
+                    self.capturing = false;
+                    let text = String::from_utf8_lossy(&self.buffer).to_string();
+                    self.buffer.clear();
+                    events.push(QueueEvent::Staged(text));
+                }
+                ESCAPE => self.escape = EscapeState::Escape,
+                CTRL_C => {
+                    self.capturing = false;
+                    self.buffer.clear();
+                    events.push(QueueEvent::CaptureCancelled);
+                }
+                BACKSPACE | CTRL_H => {
+                    // UTF-8の文字境界まで削除
+                    while let Some(last) = self.buffer.pop() {
+                        if last & 0xC0 != 0x80 {
+                            break;
+                        }
+                    }
+                }
+                // その他の制御文字は入力しない
+                _ if byte < 0x20 => {}
+                _ => self.buffer.push(byte),
+            }
+        }
+
+        // 読み取りの末尾で終わったESCは単独のEscキー
+        if self.escape == EscapeState::Escape {
+            self.escape = EscapeState::Ground;
+            self.capturing = false;
+            self.buffer.clear();
+            events.push(QueueEvent::CaptureCancelled);
+        }
+
+        (forward, events)
+    }
+}
+
+/// 予約入力中のテキストを端末の最下行に表示するシーケンス
+///
+/// カーソル位置を保存して反転表示で描画し、元に戻す。折り返しでスクロールしないよう
+/// 自動折り返しを止め、幅に収まらない場合は入力中の末尾側を表示する。
+pub fn render_capture_line(text: &str, rows: u16, cols: u16) -> String {
+    const LABEL: &str = "⏳ 予約: ";
+    const CURSOR: char = '▏';
+    let label_width: usize = LABEL.chars().filter_map(|c| c.width()).sum();
+    let available = (cols as usize).saturating_sub(label_width + 1);
+
+    let text_width: usize = text.chars().filter_map(|c| c.width()).sum();
+    let shown = if text_width <= available {
+        text.to_string()
+    } else {
+        // 省略記号（幅1）の分を除いて末尾から詰める
+        let mut width = 0;
+        let mut tail: Vec<char> = text
+            .chars()
+            .rev()
+            .take_while(|c| {
+                width += c.width().unwrap_or(0);
+                width < available
+            })
+            .collect();
+        tail.reverse();
+        format!("…{}", tail.into_iter().collect::<String>())
+    };
+
+    format!("\x1b7\x1b[?7l\x1b[{rows};1H\x1b[2K\x1b[7m{LABEL}{shown}{CURSOR}\x1b[0m\x1b[?7h\x1b8")
+}
+
+/// 予約入力の表示を消し、最下行を元の内容（`ScreenBuffer::render_row` の出力）に戻すシーケンス
+pub fn restore_capture_line(rows: u16, row_content: &str) -> String {
+    format!("\x1b7\x1b[?7l\x1b[{rows};1H\x1b[0m\x1b[2K{row_content}\x1b[0m\x1b[?7h\x1b8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passthrough_without_hotkey() {
+        let mut interceptor = HotkeyInterceptor::new();
+        let (forward, events) = interceptor.process(b"hello\r");
+        assert_eq!(forward, b"hello\r");
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_capture_and_stage() {
+        let mut interceptor = HotkeyInterceptor::new();
+        let mut input = vec![b'a', QUEUE_HOTKEY];
+        input.extend_from_slice("次はテストx".as_bytes());
+        input.push(BACKSPACE);
+        input.push(ENTER);
+        input.push(b'b');
+
+        let (forward, events) = interceptor.process(&input);
+        assert_eq!(forward, b"ab");
+        assert_eq!(
+            events,
+            vec![
+                QueueEvent::CaptureStarted,
+                QueueEvent::Staged("次はテスト".to_string())
+            ]
+        );
+        assert!(!interceptor.is_capturing());
+    }
+
+    #[test]
+    fn test_capture_cancel_and_literal_hotkey() {
+        let mut interceptor = HotkeyInterceptor::new();
+        let (forward, events) = interceptor.process(&[QUEUE_HOTKEY, b'x', ESCAPE]);
+        assert!(forward.is_empty());
+        assert_eq!(events.last(), Some(&QueueEvent::CaptureCancelled));
+
+        let (forward, _) = interceptor.process(&[QUEUE_HOTKEY, QUEUE_HOTKEY]);
+        assert_eq!(forward, vec![QUEUE_HOTKEY]);
+    }
+
+    #[test]
+    fn test_escape_sequences_do_not_cancel_capture() {
+        let mut interceptor = HotkeyInterceptor::new();
+        // 上矢印（CSI）・F1（SS3）・Alt+b は読み捨てる
+        let mut input = vec![QUEUE_HOTKEY, b'a'];
+        input.extend_from_slice(b"\x1b[A\x1bOP\x1bb");
+        input.push(b'c');
+        let (forward, events) = interceptor.process(&input);
+        assert!(forward.is_empty());
+        assert_eq!(events, vec![QueueEvent::CaptureStarted]);
+        assert_eq!(interceptor.captured_text(), Some("ac".to_string()));
+
+        // 読み取りをまたいだCSIも最後まで読み捨てる
+        interceptor.process(b"\x1b[1;");
+        interceptor.process(b"5D");
+        assert!(interceptor.is_capturing());
+
+        let (forward, events) = interceptor.process(b"d\r");
+        assert!(forward.is_empty());
+        assert_eq!(events, vec![QueueEvent::Staged("acd".to_string())]);
+        assert_eq!(interceptor.captured_text(), None);
+    }
+
+    #[test]
+    fn test_render_capture_line() {
+        let line = render_capture_line("run tests", 24, 80);
+        assert!(line.starts_with("\x1b7\x1b[?7l\x1b[24;1H\x1b[2K"));
+        assert!(line.contains("⏳ 予約: run tests▏"));
+        assert!(line.ends_with("\x1b8"));
+
+        // 幅に収まらない場合は末尾側を表示（ラベル9桁 + カーソル1桁 + 本文10桁）
+        let line = render_capture_line("次はテストを実行して", 24, 20);
+        assert!(line.contains("⏳ 予約: …実行して▏"));
+
+        assert_eq!(
+            restore_capture_line(24, "\x1b[0;1m> \x1b[0m"),
+            "\x1b7\x1b[?7l\x1b[24;1H\x1b[0m\x1b[2K\x1b[0;1m> \x1b[0m\x1b[0m\x1b[?7h\x1b8"
+        );
+    }
+
+    #[test]
+    fn test_prompt_queue_stage_and_take() {
+        let mut queue = PromptQueue::new();
+        queue.stage("run tests".to_string());
+        assert!(queue.is_queued());
+        assert_eq!(queue.staged(), Some("run tests"));
+        assert_eq!(queue.take(), Some("run tests".to_string()));
+        assert!(!queue.is_queued());
+
+        queue.stage("  ".to_string());
+        assert!(!queue.is_queued());
+    }
+}
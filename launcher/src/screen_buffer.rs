@@ -1,11 +1,40 @@
 // screen_buffer.rs - VTE based screen buffer for accurate state detection
 
+use std::collections::{BTreeMap, VecDeque};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use unicode_width::UnicodeWidthChar;
 use vte::{Params, Parser, Perform};
 
+/// 保持するハイパーリンクの最大数（古いものから破棄）
+const MAX_HYPERLINKS: usize = 32;
+/// 取り出されていない通知の最大数（古いものから破棄）
+const MAX_PENDING_NOTIFICATIONS: usize = 16;
+/// 直前の文字と結合させる ZERO WIDTH JOINER（絵文字の連結）
+const ZWJ: char = '\u{200D}';
+/// 既定のタブ間隔
+const TAB_WIDTH: usize = 8;
+
+/// セルの表示幅
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CellWidth {
+    /// 半角（1列）
+    #[default]
+    Single,
+    /// 全角文字の左半分（2列）
+    Wide,
+    /// 全角文字の右半分（文字は左隣のセルにある）
+    Continuation,
+}
+
 /// 端末の一文字を表す構造体
 #[derive(Debug, Clone, PartialEq)]
 pub struct Cell {
     pub char: char,
+    /// 結合文字（濁点・異体字セレクタ・ZWJで連結した絵文字など）
+    pub combining: String,
+    pub width: CellWidth,
     pub fg_color: Option<u8>,
     pub bg_color: Option<u8>,
     pub bold: bool,
@@ -17,6 +46,8 @@ impl Default for Cell {
     fn default() -> Self {
         Self {
             char: ' ', // 明示的に空白文字を設定（Unicode box文字対応）
+            combining: String::new(),
+            width: CellWidth::Single,
             fg_color: None,
             bg_color: None,
             bold: false,
@@ -31,6 +62,8 @@ impl Cell {
     pub fn empty() -> Self {
         Self {
             char: ' ', // 空白文字を明示的に設定
+            combining: String::new(),
+            width: CellWidth::Single,
             fg_color: None,
             bg_color: None,
             bold: false,
@@ -40,6 +73,89 @@ impl Cell {
     }
 }
 
+/// 行のセルを表示どおりの文字列に変換（全角文字の右半分は出力しない）
+fn row_text(cells: &[Cell]) -> String {
+    let mut text = String::with_capacity(cells.len());
+    for (col, cell) in cells.iter().enumerate() {
+        if cell.width == CellWidth::Continuation
+            && col > 0
+            && cells[col - 1].width == CellWidth::Wide
+        {
+            continue;
+        }
+        // 左半分を失った右半分は空白として扱う
+        if cell.width == CellWidth::Continuation {
+            text.push(' ');
+            continue;
+        }
+        text.push(cell.char);
+        text.push_str(&cell.combining);
+    }
+    text
+}
+
+/// セルの文字属性を設定するSGR（リセットから始める）
+fn cell_sgr(cell: &Cell) -> String {
+    let mut params = vec!["0".to_string()];
+    if cell.bold {
+        params.push("1".to_string());
+    }
+    if cell.italic {
+        params.push("3".to_string());
+    }
+    if cell.underline {
+        params.push("4".to_string());
+    }
+    // 保持している色は基本16色のみ（0〜7は通常色、8〜15は明るい色）
+    if let Some(fg) = cell.fg_color {
+        let base = if fg < 8 { 30 } else { 90 - 8 };
+        params.push((base + fg as u16).to_string());
+    }
+    if let Some(bg) = cell.bg_color {
+        let base = if bg < 8 { 40 } else { 100 - 8 };
+        params.push((base + bg as u16).to_string());
+    }
+    format!("\x1b[{}m", params.join(";"))
+}
+
+/// OSC 8 のハイパーリンク
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hyperlink {
+    pub uri: String,
+    /// リンクとして表示された文字列
+    pub text: String,
+}
+
+/// OSC 9 / OSC 777 のデスクトップ通知
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalNotification {
+    /// タイトル（OSC 777のみ）
+    pub title: Option<String>,
+    pub body: String,
+}
+
+/// OSC 9;4 の進捗表示（値は0〜100）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TerminalProgress {
+    Normal(u8),
+    Error(u8),
+    Indeterminate,
+    Paused(u8),
+}
+
+/// DECSC/DECRC で保存するカーソル状態
+#[derive(Debug, Clone)]
+struct SavedCursor {
+    row: usize,
+    col: usize,
+    fg: Option<u8>,
+    bg: Option<u8>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    origin_mode: bool,
+}
+
 /// スクリーンバッファ - 通常の端末画面表現（PTYサイズに動的対応）
 pub struct ScreenBuffer {
     /// グリッド（行×列）- PTYサイズに合わせて動的に設定
@@ -59,8 +175,40 @@ pub struct ScreenBuffer {
     /// スクロール範囲（DECSTBM）
     scroll_top: usize,
     scroll_bottom: usize,
+    /// 原点モード（DECOM）: カーソル位置をスクロール範囲基準にする
+    origin_mode: bool,
+    /// 自動折り返し（DECAWM）
+    autowrap: bool,
+    /// タブストップ（列ごと）
+    tab_stops: Vec<bool>,
+    /// 保存したカーソル（DECSC / CSI s）
+    saved_cursor: Option<SavedCursor>,
+    /// 直前に表示した文字（REP用）
+    last_graphic: Option<char>,
+    /// 未対応のエスケープシーケンスの出現回数
+    unhandled_sequences: BTreeMap<String, u64>,
+    /// 前回のキャッシュ更新以降に書き換えた行
+    dirty_rows: Vec<bool>,
+    /// 行ごとの表示文字列（PTY列数ぶん、`process_data` の最後に更新）
+    line_cache: Vec<String>,
+    /// 直前の `process_data` で文字内容が変わった行
+    changed_rows: Vec<usize>,
+    /// 画面の文字内容が変わるたびに増える世代番号
+    generation: u64,
+    /// UI box解析結果（世代が変わると破棄）
+    ui_boxes_cache: OnceLock<Vec<UIBox>>,
     /// VTE Parser
     parser: Parser,
+    /// ウィンドウタイトル（OSC 0/2）
+    title: Option<String>,
+    /// 表示中のハイパーリンク（OSC 8の開始から終了まで）
+    active_hyperlink: Option<Hyperlink>,
+    /// 表示済みのハイパーリンク（新しいものが末尾）
+    hyperlinks: VecDeque<Hyperlink>,
+    /// 取り出されていない通知（OSC 9/777）
+    notifications: VecDeque<TerminalNotification>,
+    /// 進捗表示（OSC 9;4）
+    progress: Option<TerminalProgress>,
     /// デバッグモード
     verbose: bool,
 }
@@ -93,11 +241,64 @@ impl ScreenBuffer {
             current_underline: false,
             scroll_top: 0,
             scroll_bottom: rows.saturating_sub(1),
+            origin_mode: false,
+            autowrap: true,
+            tab_stops: default_tab_stops(buffer_cols),
+            saved_cursor: None,
+            last_graphic: None,
+            unhandled_sequences: BTreeMap::new(),
+            dirty_rows: vec![false; rows],
+            line_cache: vec![row_text(&vec![Cell::empty(); cols]); rows],
+            changed_rows: Vec::new(),
+            generation: 0,
+            ui_boxes_cache: OnceLock::new(),
             parser: Parser::new(),
+            title: None,
+            active_hyperlink: None,
+            hyperlinks: VecDeque::new(),
+            notifications: VecDeque::new(),
+            progress: None,
             verbose,
         }
     }
 
+    /// ウィンドウタイトル（OSC 0/2）
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// 進捗表示（OSC 9;4）
+    pub fn progress(&self) -> Option<TerminalProgress> {
+        self.progress
+    }
+
+    /// 表示済みのハイパーリンク（古い順）
+    pub fn hyperlinks(&self) -> impl Iterator<Item = &Hyperlink> {
+        self.hyperlinks.iter()
+    }
+
+    /// 受信した通知を取り出す（取り出した通知はバッファから消える）
+    pub fn take_notifications(&mut self) -> Vec<TerminalNotification> {
+        self.notifications.drain(..).collect()
+    }
+
+    /// 未対応のエスケープシーケンスと出現回数
+    pub fn unhandled_sequences(&self) -> &BTreeMap<String, u64> {
+        &self.unhandled_sequences
+    }
+
+    /// 画面以外の端末状態（タイトル・進捗・リンク・未取得の通知・未対応シーケンスの集計）を引き継ぐ
+    /// （リサイズ時の再作成用）
+    pub fn inherit_terminal_state(&mut self, previous: ScreenBuffer) {
+        self.title = previous.title;
+        self.progress = previous.progress;
+        self.hyperlinks = previous.hyperlinks;
+        self.notifications = previous.notifications;
+        self.unhandled_sequences = previous.unhandled_sequences;
+        // 画面は作り直したので、キャッシュの利用側には変化として伝える
+        self.generation = previous.generation + 1;
+    }
+
     /// PTY出力を処理してスクリーンバッファを更新
     pub fn process_data(&mut self, data: &[u8]) {
         // VTE advanceを呼ぶためにScreenBufferを一時的に借用できるよう分離
@@ -106,6 +307,110 @@ impl ScreenBuffer {
             parser.advance(self, byte);
         }
         self.parser = parser;
+        self.refresh_line_cache();
+    }
+
+    /// 画面の文字内容の世代番号（内容が変わらなければ同じ値）
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// 直前の `process_data` で文字内容が変わった行
+    pub fn changed_rows(&self) -> &[usize] {
+        &self.changed_rows
+    }
+
+    /// 書き換えた行の表示文字列だけを作り直し、内容が変わっていれば世代を進める
+    fn refresh_line_cache(&mut self) {
+        self.changed_rows.clear();
+        let pty_cols = self.pty_cols();
+        for row in 0..self.grid.len() {
+            if !std::mem::take(&mut self.dirty_rows[row]) {
+                continue;
+            }
+            let cells = &self.grid[row];
+            let text = row_text(&cells[..pty_cols.min(cells.len())]);
+            if text != self.line_cache[row] {
+                self.line_cache[row] = text;
+                self.changed_rows.push(row);
+            }
+        }
+
+        if !self.changed_rows.is_empty() {
+            self.generation += 1;
+            self.ui_boxes_cache = OnceLock::new();
+        }
+    }
+
+    /// 行を書き換えたことを記録
+    fn mark_dirty(&mut self, row: usize) {
+        if let Some(dirty) = self.dirty_rows.get_mut(row) {
+            *dirty = true;
+        }
+    }
+
+    /// 範囲内の行を書き換えたことを記録
+    fn mark_rows_dirty(&mut self, rows: Range<usize>) {
+        let end = rows.end.min(self.dirty_rows.len());
+        for dirty in &mut self.dirty_rows[rows.start.min(end)..end] {
+            *dirty = true;
+        }
+    }
+
+    /// 現在の画面内容（キャッシュ済みの表示文字列を借用）
+    pub fn screen_lines(&self) -> &[String] {
+        let start_row = self.line_cache.len().saturating_sub(self.rows);
+        &self.line_cache[start_row..]
+    }
+
+    /// 表示中の行を文字属性付きで再現するシーケンス（launcherの表示で上書きした行の復元用）
+    ///
+    /// カーソル位置は変えないため、呼び出し側で行頭に移動してから出力する。
+    /// 拡張色（256色・RGB）は保持していないため既定色で再現する。
+    pub fn render_row(&self, row: usize) -> String {
+        let start_row = self.grid.len().saturating_sub(self.rows);
+        let Some(cells) = self.grid.get(start_row + row) else {
+            return String::new();
+        };
+
+        let mut rendered = String::new();
+        let mut previous: Option<&Cell> = None;
+        for (col, cell) in cells.iter().take(self.pty_cols()).enumerate() {
+            if cell.width == CellWidth::Continuation
+                && col > 0
+                && cells[col - 1].width == CellWidth::Wide
+            {
+                continue;
+            }
+            let same_attributes = previous.is_some_and(|previous| {
+                (
+                    previous.fg_color,
+                    previous.bg_color,
+                    previous.bold,
+                    previous.italic,
+                    previous.underline,
+                ) == (
+                    cell.fg_color,
+                    cell.bg_color,
+                    cell.bold,
+                    cell.italic,
+                    cell.underline,
+                )
+            });
+            if !same_attributes {
+                rendered.push_str(&cell_sgr(cell));
+            }
+            previous = Some(cell);
+            // 左半分を失った右半分は空白として扱う
+            if cell.width == CellWidth::Continuation {
+                rendered.push(' ');
+                continue;
+            }
+            rendered.push(cell.char);
+            rendered.push_str(&cell.combining);
+        }
+        rendered.push_str("\x1b[0m");
+        rendered
     }
 
     /// 現在の画面内容を文字列の配列として取得（実際の端末表示に準拠）
@@ -138,18 +443,22 @@ impl ScreenBuffer {
         }
 
         // PTY表示範囲のみを返す（バッファは+1列だが表示は元のPTYサイズ）
-        let pty_cols = self.cols.saturating_sub(1); // 元のPTY列数
-        self.grid
-            .iter()
-            .skip(start_row)
-            .map(|row| row.iter().take(pty_cols).map(|cell| cell.char).collect())
-            .collect()
+        self.screen_lines().to_vec()
     }
 
     /// UI boxを検出（改善版）
     pub fn find_ui_boxes(&self) -> Vec<UIBox> {
+        self.ui_boxes().to_vec()
+    }
+
+    /// UI boxの検出結果（画面内容が変わるまでキャッシュ）
+    pub fn ui_boxes(&self) -> &[UIBox] {
+        self.ui_boxes_cache.get_or_init(|| self.parse_ui_boxes())
+    }
+
+    fn parse_ui_boxes(&self) -> Vec<UIBox> {
         let mut boxes = Vec::new();
-        let lines = self.get_screen_lines();
+        let lines = self.screen_lines();
         let mut processed_rows = std::collections::HashSet::new();
 
         let start_row = if self.grid.len() > self.rows {
@@ -183,7 +492,7 @@ impl ScreenBuffer {
             }
 
             if line.trim_start().starts_with('╭') && !line.contains('�') {
-                if let Some(mut ui_box) = self.parse_ui_box_at(&lines, row_idx) {
+                if let Some(mut ui_box) = self.parse_ui_box_at(lines, row_idx) {
                     // 行番号をグリッド座標に変換
                     ui_box.start_row += start_row;
                     ui_box.end_row += start_row;
@@ -206,7 +515,7 @@ impl ScreenBuffer {
 
         // 2. 部分的なUI box（│の連続領域）を検索
         if boxes.is_empty() {
-            if let Some(mut partial_box) = self.find_partial_ui_box(&lines) {
+            if let Some(mut partial_box) = self.find_partial_ui_box(lines) {
                 // 行番号をグリッド座標に変換
                 partial_box.start_row += start_row;
                 partial_box.end_row += start_row;
@@ -224,7 +533,7 @@ impl ScreenBuffer {
             }
         }
 
-        boxes.sort_by(|a, b| a.start_row.cmp(&b.start_row));
+        boxes.sort_by_key(|b| b.start_row);
         boxes
     }
 
@@ -335,50 +644,286 @@ impl ScreenBuffer {
     /// カーソル位置を安全に設定
     fn set_cursor(&mut self, row: usize, col: usize) {
         self.cursor_row = row.min(self.rows.saturating_sub(1));
-        self.cursor_col = col.min(self.cols.saturating_sub(1));
+        self.cursor_col = col.min(self.last_col());
+    }
+
+    /// PTYの列数（バッファは+1列）
+    fn pty_cols(&self) -> usize {
+        self.cols.saturating_sub(1)
+    }
+
+    /// カーソルが移動できる右端の列
+    fn last_col(&self) -> usize {
+        self.pty_cols().saturating_sub(1)
+    }
+
+    /// 実際の端末でのカーソル列（行末の折り返し待ちでは右端の列）
+    fn effective_col(&self) -> usize {
+        self.cursor_col.min(self.last_col())
+    }
+
+    /// 原点モードを考慮したカーソル位置設定（CUP / VPA、引数は0始まり）
+    fn set_cursor_in_origin(&mut self, row: usize, col: usize) {
+        let row = if self.origin_mode {
+            (self.scroll_top + row).min(self.scroll_bottom)
+        } else {
+            row
+        };
+        self.set_cursor(row, col);
     }
 
-    /// 文字を現在のカーソル位置に挿入
+    /// IND: 1行下へ（スクロール範囲の最下行ではスクロール）
+    fn index(&mut self) {
+        if self.cursor_row == self.scroll_bottom {
+            self.scroll_up();
+        } else if self.cursor_row + 1 < self.rows {
+            self.cursor_row += 1;
+        }
+    }
+
+    /// RI: 1行上へ（スクロール範囲の最上行では逆スクロール）
+    fn reverse_index(&mut self) {
+        if self.cursor_row == self.scroll_top {
+            self.scroll_down_n(1);
+        } else if self.cursor_row > 0 {
+            self.cursor_row -= 1;
+        }
+    }
+
+    /// DECSC: カーソル位置・文字属性・原点モードを保存
+    fn save_cursor(&mut self) {
+        self.saved_cursor = Some(SavedCursor {
+            row: self.cursor_row,
+            col: self.cursor_col,
+            fg: self.current_fg,
+            bg: self.current_bg,
+            bold: self.current_bold,
+            italic: self.current_italic,
+            underline: self.current_underline,
+            origin_mode: self.origin_mode,
+        });
+    }
+
+    /// DECRC: 保存したカーソルを復元（未保存の場合はホーム位置と既定の属性）
+    fn restore_cursor(&mut self) {
+        let saved = self.saved_cursor.clone().unwrap_or(SavedCursor {
+            row: 0,
+            col: 0,
+            fg: None,
+            bg: None,
+            bold: false,
+            italic: false,
+            underline: false,
+            origin_mode: false,
+        });
+        self.current_fg = saved.fg;
+        self.current_bg = saved.bg;
+        self.current_bold = saved.bold;
+        self.current_italic = saved.italic;
+        self.current_underline = saved.underline;
+        self.origin_mode = saved.origin_mode;
+        self.cursor_row = saved.row.min(self.rows.saturating_sub(1));
+        // 折り返し待ちの位置も復元する
+        self.cursor_col = saved.col.min(self.pty_cols());
+    }
+
+    /// ECH: カーソル位置からn文字を消去（カーソルは移動しない）
+    fn erase_characters(&mut self, n: usize) {
+        let row = self.cursor_row;
+        let start = self.effective_col();
+        let end = (start + n).min(self.pty_cols());
+        if row >= self.grid.len() || start >= end {
+            return;
+        }
+        self.split_wide_char_at(row, start);
+        self.split_wide_char_at(row, end - 1);
+        self.mark_dirty(row);
+        for cell in &mut self.grid[row][start..end] {
+            *cell = Cell::empty();
+        }
+    }
+
+    /// CHT / HT: n個先のタブストップへ（なければ右端）
+    fn tab_forward(&mut self, n: usize) {
+        let last_col = self.last_col();
+        let mut col = self.effective_col();
+        for _ in 0..n {
+            col = (col + 1..=last_col)
+                .find(|&c| self.tab_stops.get(c).copied().unwrap_or(false))
+                .unwrap_or(last_col);
+        }
+        self.cursor_col = col;
+    }
+
+    /// CBT: n個前のタブストップへ（なければ行頭）
+    fn tab_backward(&mut self, n: usize) {
+        let mut col = self.effective_col();
+        for _ in 0..n {
+            col = (0..col)
+                .rev()
+                .find(|&c| self.tab_stops.get(c).copied().unwrap_or(false))
+                .unwrap_or(0);
+        }
+        self.cursor_col = col;
+    }
+
+    /// RIS: 端末を初期状態に戻す（タイトルなどの画面外の状態は保持）
+    fn reset(&mut self) {
+        let fresh = ScreenBuffer::new(self.rows, self.pty_cols(), self.verbose);
+        let previous = std::mem::replace(self, fresh);
+        self.inherit_terminal_state(previous);
+    }
+
+    /// DECALN: 画面全体を 'E' で埋める（画面調整用のテストパターン）
+    fn screen_alignment_test(&mut self) {
+        let pty_cols = self.pty_cols();
+        self.mark_rows_dirty(0..self.rows);
+        for row in &mut self.grid {
+            for cell in row.iter_mut().take(pty_cols) {
+                *cell = Cell {
+                    char: 'E',
+                    ..Cell::empty()
+                };
+            }
+        }
+        self.scroll_top = 0;
+        self.scroll_bottom = self.rows.saturating_sub(1);
+        self.origin_mode = false;
+        self.set_cursor(0, 0);
+    }
+
+    /// 未対応のシーケンスを記録
+    fn record_unhandled(&mut self, sequence: String) {
+        let count = self
+            .unhandled_sequences
+            .entry(sequence.clone())
+            .or_insert(0);
+        *count += 1;
+        if self.verbose {
+            climonitor_shared::log_debug!(
+                climonitor_shared::LogCategory::Screen,
+                "❓ [UNHANDLED] {sequence} (x{count})"
+            );
+        }
+    }
+
+    /// 文字を現在のカーソル位置に挿入（全角は2セル、結合文字は直前のセルに追加）
     fn insert_char(&mut self, ch: char) {
+        let width = match ch.width() {
+            Some(0) | None => return self.append_combining(ch),
+            Some(width) => width,
+        };
+        // ZWJの次の文字は同じ絵文字の一部
+        if let Some((row, col)) = self.previous_cell() {
+            if self.grid[row][col].combining.ends_with(ZWJ) {
+                return self.append_combining(ch);
+            }
+        }
+
+        // 行末の折り返し待ち、または全角文字が行末に収まらない場合は次の行へ
+        // （DECAWMが無効の場合は右端の列を上書き）
+        let pty_cols = self.pty_cols();
+        if self.cursor_col + width > pty_cols {
+            if !self.autowrap {
+                self.cursor_col = pty_cols.saturating_sub(width);
+            } else if self.cursor_col > 0 {
+                self.wrap_line(ch);
+            }
+        }
+        self.last_graphic = Some(ch);
+
         if self.cursor_row < self.grid.len() && self.cursor_col < self.cols {
-            self.grid[self.cursor_row][self.cursor_col] = Cell {
+            let row = self.cursor_row;
+            let col = self.cursor_col;
+            self.mark_dirty(row);
+            let wide = width == 2 && col + 1 < self.cols;
+            self.split_wide_char_at(row, col);
+            if wide {
+                self.split_wide_char_at(row, col + 1);
+            }
+
+            let cell = Cell {
                 char: ch,
+                combining: String::new(),
+                width: if wide {
+                    CellWidth::Wide
+                } else {
+                    CellWidth::Single
+                },
                 fg_color: self.current_fg,
                 bg_color: self.current_bg,
                 bold: self.current_bold,
                 italic: self.current_italic,
                 underline: self.current_underline,
             };
+            if wide {
+                self.grid[row][col + 1] = Cell {
+                    char: ' ',
+                    width: CellWidth::Continuation,
+                    ..cell.clone()
+                };
+            }
+            self.grid[row][col] = cell;
 
-            // カーソルを右に移動
-            self.cursor_col += 1;
-            if self.cursor_col >= self.cols {
-                self.cursor_col = 0;
-                self.cursor_row += 1;
-                // 範囲外に移動した場合はスクロール
-                if self.cursor_row >= self.grid.len() {
-                    if self.verbose
-                        && (ch == '╭'
-                            || ch == '╰'
-                            || ch == '│'
-                            || ch == '─'
-                            || ch == '╮'
-                            || ch == '╯')
-                    {
-                        climonitor_shared::log_debug!(
-                            climonitor_shared::LogCategory::Screen,
-                            "🔄 [INSERT_SCROLL] '{}' triggered scroll at ({}, {}) grid_len={}",
-                            ch,
-                            self.cursor_row,
-                            self.cursor_col,
-                            self.grid.len()
-                        );
-                    }
-                    self.cursor_row = self.grid.len().saturating_sub(1);
-                    self.scroll_up();
+            // カーソルを右に移動（右端を超えた位置は次の文字での折り返し待ち）
+            self.cursor_col += if wide { 2 } else { 1 };
+        }
+    }
+
+    /// 結合文字を直前のセルに追加（直前のセルがなければ捨てる）
+    fn append_combining(&mut self, ch: char) {
+        if let Some((row, col)) = self.previous_cell() {
+            self.mark_dirty(row);
+            self.grid[row][col].combining.push(ch);
+        }
+    }
+
+    /// カーソル直前の文字のセル位置（全角文字は左半分、行頭では None）
+    fn previous_cell(&self) -> Option<(usize, usize)> {
+        let row = self.cursor_row;
+        let col = self.cursor_col.checked_sub(1)?;
+        match self.grid.get(row)?.get(col)?.width {
+            CellWidth::Continuation if col > 0 => Some((row, col - 1)),
+            _ => Some((row, col)),
+        }
+    }
+
+    /// 上書きされる全角文字のもう半分を空白にする
+    fn split_wide_char_at(&mut self, row: usize, col: usize) {
+        let Some(cells) = self.grid.get_mut(row) else {
+            return;
+        };
+        match cells.get(col).map(|cell| cell.width) {
+            Some(CellWidth::Wide) => {
+                if let Some(right) = cells.get_mut(col + 1) {
+                    *right = Cell::empty();
                 }
             }
+            Some(CellWidth::Continuation) if col > 0 => {
+                cells[col - 1] = Cell::empty();
+            }
+            _ => return,
+        }
+        self.mark_dirty(row);
+    }
+
+    /// 次の行の先頭へ折り返し（スクロール範囲の最下行ではスクロール）
+    fn wrap_line(&mut self, ch: char) {
+        if self.verbose
+            && self.cursor_row == self.scroll_bottom
+            && matches!(ch, '╭' | '╰' | '│' | '─' | '╮' | '╯')
+        {
+            climonitor_shared::log_debug!(
+                climonitor_shared::LogCategory::Screen,
+                "🔄 [INSERT_SCROLL] '{}' triggered scroll at ({}, {}) grid_len={}",
+                ch,
+                self.cursor_row,
+                self.cursor_col,
+                self.grid.len()
+            );
         }
+        self.cursor_col = 0;
+        self.index();
     }
 
     /// 画面をクリア
@@ -394,6 +939,7 @@ impl ScreenBuffer {
 
         // バッファを完全にリセット（Unicode box文字残骸を確実に除去）
         self.grid = vec![vec![Cell::empty(); self.cols]; self.rows];
+        self.mark_rows_dirty(0..self.rows);
 
         self.cursor_row = 0;
         self.cursor_col = 0;
@@ -424,33 +970,21 @@ impl ScreenBuffer {
             );
         }
 
-        // 実際の端末に準拠したスクロール処理：全体的な上シフト
-        for _ in 0..actual_scroll {
-            // スクロール範囲内の内容を1行ずつ上にシフト
-            for row in self.scroll_top..self.scroll_bottom {
-                if row + 1 < self.grid.len() {
-                    // 下の行の内容を上の行にコピー
-                    self.grid[row] = self.grid[row + 1].clone();
-                }
-            }
-
-            // 最下行（scroll_bottom）をクリア
-            if self.scroll_bottom < self.grid.len() {
-                let old_content: String = self.grid[self.scroll_bottom]
-                    .iter()
-                    .map(|c| c.char)
-                    .collect();
-                self.grid[self.scroll_bottom] = vec![Cell::empty(); self.cols];
-
-                if self.verbose && !old_content.trim().is_empty() {
+        if self.verbose {
+            for cells in self.grid.iter().skip(self.scroll_top).take(actual_scroll) {
+                let old_content = row_text(cells);
+                if !old_content.trim().is_empty() {
                     climonitor_shared::log_debug!(
                         climonitor_shared::LogCategory::Screen,
-                        "🗑️  [SCROLL_CLEAR] Bottom line cleared: '{}'",
+                        "🗑️  [SCROLL_CLEAR] Line scrolled out: '{}'",
                         old_content.trim()
                     );
                 }
             }
         }
+
+        // 実際の端末に準拠したスクロール処理：範囲内を上にシフトし、最下行を空ける
+        self.shift_rows_up(self.scroll_top..self.scroll_bottom + 1, actual_scroll);
     }
 
     /// n行下にスクロール（スクロール範囲考慮）
@@ -472,23 +1006,45 @@ impl ScreenBuffer {
             );
         }
 
-        // スクロール範囲内の行を下にシフト（グリッドサイズ固定）
-        for _ in 0..actual_scroll {
-            // self.scroll_bottom から self.scroll_top + 1 までを逆順に処理
-            for row in (self.scroll_top..self.scroll_bottom).rev() {
-                if row + 1 < self.grid.len() {
-                    self.grid[row + 1] = self.grid[row].clone();
-                }
-            }
-            // 先頭行（scroll_top）をクリア
-            if self.scroll_top < self.grid.len() {
-                self.grid[self.scroll_top] = vec![Cell::empty(); self.cols];
-            }
+        // スクロール範囲内の行を下にシフトし、先頭行を空ける（グリッドサイズ固定）
+        self.shift_rows_down(self.scroll_top..self.scroll_bottom + 1, actual_scroll);
+    }
+
+    /// 範囲内の行をn行上にずらし、空いた下側の行を消去（行の複製はしない）
+    fn shift_rows_up(&mut self, rows: Range<usize>, n: usize) {
+        let end = rows.end.min(self.grid.len());
+        if rows.start >= end {
+            return;
         }
+        let region = &mut self.grid[rows.start..end];
+        let n = n.min(region.len());
+        region.rotate_left(n);
+        let len = region.len();
+        for row in &mut region[len - n..] {
+            row.fill(Cell::empty());
+        }
+        self.mark_rows_dirty(rows.start..end);
+    }
+
+    /// 範囲内の行をn行下にずらし、空いた上側の行を消去（行の複製はしない）
+    fn shift_rows_down(&mut self, rows: Range<usize>, n: usize) {
+        let end = rows.end.min(self.grid.len());
+        if rows.start >= end {
+            return;
+        }
+        let region = &mut self.grid[rows.start..end];
+        let n = n.min(region.len());
+        region.rotate_right(n);
+        for row in &mut region[..n] {
+            row.fill(Cell::empty());
+        }
+        self.mark_rows_dirty(rows.start..end);
     }
 
     /// カーソルから画面末尾まで消去
     fn clear_from_cursor_to_end(&mut self) {
+        self.split_wide_char_at(self.cursor_row, self.cursor_col);
+        self.mark_rows_dirty(self.cursor_row..self.rows);
         // 現在の行のカーソル位置から行末まで消去
         if let Some(row) = self.grid.get_mut(self.cursor_row) {
             for cell in row.iter_mut().skip(self.cursor_col) {
@@ -506,6 +1062,8 @@ impl ScreenBuffer {
 
     /// 画面先頭からカーソルまで消去
     fn clear_from_start_to_cursor(&mut self) {
+        self.split_wide_char_at(self.cursor_row, self.cursor_col);
+        self.mark_rows_dirty(0..self.cursor_row + 1);
         // 最初の行から現在の行の前まで全て消去
         for row in self.grid.iter_mut().take(self.cursor_row) {
             for cell in row.iter_mut() {
@@ -538,18 +1096,7 @@ impl ScreenBuffer {
         }
 
         // 挿入位置から下の行を下にシフト（グリッドサイズ固定）
-        for _ in 0..actual_insert {
-            // self.scroll_bottom から insert_row + 1 までを逆順に処理
-            for row in (insert_row..self.scroll_bottom).rev() {
-                if row + 1 < self.grid.len() {
-                    self.grid[row + 1] = self.grid[row].clone();
-                }
-            }
-            // 挿入行をクリア
-            if insert_row < self.grid.len() {
-                self.grid[insert_row] = vec![Cell::empty(); self.cols];
-            }
-        }
+        self.shift_rows_down(insert_row..self.scroll_bottom + 1, actual_insert);
     }
 
     /// n行を削除（DL - Delete Line）
@@ -568,18 +1115,8 @@ impl ScreenBuffer {
             );
         }
 
-        // 削除位置から下の行を上にシフト（グリッドサイズ固定）
-        for _ in 0..actual_delete {
-            for row in delete_row..self.scroll_bottom {
-                if row + 1 < self.grid.len() {
-                    self.grid[row] = self.grid[row + 1].clone();
-                }
-            }
-            // スクロール範囲の最下部をクリア
-            if self.scroll_bottom < self.grid.len() {
-                self.grid[self.scroll_bottom] = vec![Cell::empty(); self.cols];
-            }
-        }
+        // 削除位置から下の行を上にシフトし、スクロール範囲の最下部を空ける
+        self.shift_rows_up(delete_row..self.scroll_bottom + 1, actual_delete);
     }
 
     /// n文字を挿入（ICH - Insert Character）
@@ -588,6 +1125,7 @@ impl ScreenBuffer {
             return;
         }
 
+        self.mark_dirty(self.cursor_row);
         let row = &mut self.grid[self.cursor_row];
         let insert_col = self.cursor_col;
         let actual_insert = n.min(self.cols - insert_col);
@@ -619,6 +1157,7 @@ impl ScreenBuffer {
             return;
         }
 
+        self.mark_dirty(self.cursor_row);
         let row = &mut self.grid[self.cursor_row];
         let delete_col = self.cursor_col;
         let actual_delete = n.min(row.len() - delete_col);
@@ -643,6 +1182,200 @@ impl ScreenBuffer {
             }
         }
     }
+
+    /// DECSET / DECRST（CSI ? n h / CSI ? n l）
+    fn set_private_mode(&mut self, mode: u16, enable: bool) {
+        match mode {
+            6 => {
+                // DECOM - 原点モード（切替時はホームポジションへ）
+                self.origin_mode = enable;
+                self.set_cursor_in_origin(0, 0);
+            }
+            7 => {
+                // DECAWM - 自動折り返し
+                self.autowrap = enable;
+            }
+            25 => {
+                if self.verbose {
+                    climonitor_shared::log_debug!(
+                        climonitor_shared::LogCategory::Screen,
+                        "👁️  [CURSOR_VISIBILITY] Cursor visibility: {}",
+                        if enable { "ON" } else { "OFF" }
+                    );
+                }
+            }
+            47 | 1047 | 1049 => {
+                // 代替画面バッファ（切替時に画面をクリアしてエミュレート）
+                if self.verbose {
+                    climonitor_shared::log_debug!(
+                        climonitor_shared::LogCategory::Screen,
+                        "🔄 [ALT_SCREEN] Switch to {} screen buffer ({mode})",
+                        if enable { "alternate" } else { "main" }
+                    );
+                }
+                match (mode, enable) {
+                    (1049, true) => {
+                        self.save_cursor();
+                        self.clear_screen();
+                    }
+                    (1049, false) => self.restore_cursor(),
+                    (_, true) => self.clear_screen(),
+                    _ => {}
+                }
+            }
+            1048 => {
+                if enable {
+                    self.save_cursor();
+                } else {
+                    self.restore_cursor();
+                }
+            }
+            2004 => {
+                if self.verbose {
+                    climonitor_shared::log_debug!(
+                        climonitor_shared::LogCategory::Screen,
+                        "📋 [BRACKETED_PASTE] {} bracketed paste mode",
+                        if enable { "Enable" } else { "Disable" }
+                    );
+                }
+            }
+            1004 => {
+                if self.verbose {
+                    climonitor_shared::log_debug!(
+                        climonitor_shared::LogCategory::Screen,
+                        "👀 [FOCUS_TRACKING] {} focus tracking mode",
+                        if enable { "Enable" } else { "Disable" }
+                    );
+                }
+            }
+            // カーソル点滅・同期出力・マウス報告は画面内容に影響しない
+            12 | 2026 | 1000 | 1002 | 1003 | 1005 | 1006 | 1015 => {}
+            _ => self.record_unhandled(format!("CSI ?{mode}{}", if enable { 'h' } else { 'l' })),
+        }
+    }
+
+    /// SGR（Select Graphic Rendition）- 文字属性設定
+    fn select_graphic_rendition(&mut self, params: &Params) {
+        let mut params = params.iter();
+        while let Some(param) = params.next() {
+            let Some(&value) = param.first() else {
+                continue;
+            };
+            match value {
+                0 => {
+                    // リセット
+                    self.current_fg = None;
+                    self.current_bg = None;
+                    self.current_bold = false;
+                    self.current_italic = false;
+                    self.current_underline = false;
+                }
+                1 => self.current_bold = true,
+                3 => self.current_italic = true,
+                4 => self.current_underline = true,
+                22 => self.current_bold = false,
+                23 => self.current_italic = false,
+                24 => self.current_underline = false,
+                30..=37 => self.current_fg = Some(value as u8 - 30),
+                38 | 48 | 58 if param.len() == 1 => {
+                    // 拡張色（256色・RGB）は保持しない。';'区切りの後続パラメータを読み飛ばす
+                    match params.next().and_then(|p| p.first()) {
+                        Some(5) => {
+                            params.next();
+                        }
+                        Some(2) => {
+                            params.nth(2);
+                        }
+                        _ => {}
+                    }
+                }
+                39 => self.current_fg = None, // デフォルト前景色
+                40..=47 => self.current_bg = Some(value as u8 - 40),
+                49 => self.current_bg = None, // デフォルト背景色
+                90..=97 => self.current_fg = Some(value as u8 - 90 + 8), // 明るい前景色
+                100..=107 => self.current_bg = Some(value as u8 - 100 + 8), // 明るい背景色
+                _ => {
+                    // 他のSGRパラメータ（dim・blink・反転など）は無視
+                }
+            }
+        }
+    }
+
+    fn set_title(&mut self, title: String) {
+        let title = title.trim();
+        self.title = (!title.is_empty()).then(|| title.to_string());
+    }
+
+    /// OSC 8;params;uri（URIが空ならリンク終了）
+    fn set_hyperlink(&mut self, uri: String) {
+        if let Some(link) = self.active_hyperlink.take() {
+            self.hyperlinks.push_back(link);
+            while self.hyperlinks.len() > MAX_HYPERLINKS {
+                self.hyperlinks.pop_front();
+            }
+        }
+        if !uri.is_empty() {
+            self.active_hyperlink = Some(Hyperlink {
+                uri,
+                text: String::new(),
+            });
+        }
+    }
+
+    /// OSC 9;4;state;value（0: 解除, 1: 通常, 2: エラー, 3: 不定, 4: 一時停止）
+    fn set_progress(&mut self, state: Option<&&[u8]>, value: Option<&&[u8]>) {
+        let value = value
+            .and_then(|value| std::str::from_utf8(value).ok())
+            .and_then(|value| value.parse::<u8>().ok())
+            .unwrap_or(0)
+            .min(100);
+        self.progress = match state.copied() {
+            Some(b"1") => Some(TerminalProgress::Normal(value)),
+            Some(b"2") => Some(TerminalProgress::Error(value)),
+            Some(b"3") => Some(TerminalProgress::Indeterminate),
+            Some(b"4") => Some(TerminalProgress::Paused(value)),
+            _ => None,
+        };
+    }
+
+    fn push_notification(&mut self, title: Option<String>, body: String) {
+        let title = title.filter(|title| !title.trim().is_empty());
+        if title.is_none() && body.trim().is_empty() {
+            return;
+        }
+        if self.verbose {
+            climonitor_shared::log_debug!(
+                climonitor_shared::LogCategory::Screen,
+                "🔔 [OSC_NOTIFY] title={title:?} body={body:?}"
+            );
+        }
+        self.notifications
+            .push_back(TerminalNotification { title, body });
+        while self.notifications.len() > MAX_PENDING_NOTIFICATIONS {
+            self.notifications.pop_front();
+        }
+    }
+}
+
+/// 8列ごとのタブストップ
+fn default_tab_stops(cols: usize) -> Vec<bool> {
+    (0..cols)
+        .map(|col| col > 0 && col % TAB_WIDTH == 0)
+        .collect()
+}
+
+/// CSIのn番目の数値パラメータ（省略時と0は既定値）
+fn csi_param(params: &Params, index: usize, default: usize) -> usize {
+    params
+        .iter()
+        .nth(index)
+        .and_then(|param| param.first().copied())
+        .filter(|&value| value != 0)
+        .map_or(default, usize::from)
+}
+
+fn is_numeric(param: &[u8]) -> bool {
+    !param.is_empty() && param.iter().all(u8::is_ascii_digit)
 }
 
 /// UI boxの情報
@@ -683,6 +1416,9 @@ impl Perform for ScreenBuffer {
                 );
             }
         }
+        if let Some(link) = self.active_hyperlink.as_mut() {
+            link.text.push(c);
+        }
         self.insert_char(c);
     }
 
@@ -699,98 +1435,127 @@ impl Perform for ScreenBuffer {
             );
         }
         match byte {
-            b'\n' => {
-                // 改行：カーソルを次の行の先頭に移動
-                self.cursor_col = 0; // 列を0にリセット
-                self.cursor_row += 1;
-                if self.cursor_row >= self.grid.len() {
-                    self.cursor_row = self.grid.len().saturating_sub(1);
-                    // スクロール処理：1行上にスクロール
-                    self.scroll_up();
-                }
+            b'\n' | b'\x0b' | b'\x0c' => {
+                // 改行：カーソルを次の行の先頭に移動（PTYのONLCRと同じく行頭へ）
+                self.cursor_col = 0;
+                self.index();
             }
             b'\r' => {
                 // キャリッジリターン：カーソルを行の先頭に移動
                 self.cursor_col = 0;
             }
             b'\t' => {
-                // タブ：8の倍数位置に移動
-                self.cursor_col = ((self.cursor_col / 8) + 1) * 8;
-                if self.cursor_col >= self.cols {
-                    self.cursor_col = self.cols - 1;
-                }
+                // タブ：次のタブストップへ移動
+                self.tab_forward(1);
             }
             b'\x08' => {
-                // バックスペース
-                if self.cursor_col > 0 {
-                    self.cursor_col -= 1;
-                }
-            }
-            _ => {
-                if self.verbose {
-                    climonitor_shared::log_debug!(
-                        climonitor_shared::LogCategory::Screen,
-                        "Unhandled execute: 0x{byte:02x}"
-                    );
-                }
+                // バックスペース（折り返し待ちの場合は右端の列から戻る）
+                self.cursor_col = self.effective_col().saturating_sub(1);
             }
+            // NUL・ベル・文字セット切り替え（SO/SI）は画面に影響しない
+            b'\0' | b'\x07' | b'\x0e' | b'\x0f' => {}
+            _ => self.record_unhandled(format!("C0 0x{byte:02X}")),
         }
     }
 
-    /// フック開始
-    fn hook(&mut self, _params: &Params, _intermediates: &[u8], _ignore: bool, _c: char) {
-        // 今回は使用しない
+    /// フック開始（DCS）
+    fn hook(&mut self, _params: &Params, intermediates: &[u8], _ignore: bool, c: char) {
+        self.record_unhandled(format!("DCS {}{c}", String::from_utf8_lossy(intermediates)));
     }
 
     /// 文字列挿入
     fn put(&mut self, _byte: u8) {
-        // 今回は使用しない
+        // DCSの内容は使用しない
     }
 
     /// フック終了
     fn unhook(&mut self) {
-        // 今回は使用しない
+        // DCSの内容は使用しない
     }
 
-    /// OSCコマンド開始
-    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {
-        // 今回は使用しない
+    /// OSCコマンド（タイトル・ハイパーリンク・通知・進捗）
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        // 本文中の ';' でも分割されているため、必要に応じて結合し直す
+        let join = |from: usize| -> String {
+            params
+                .get(from..)
+                .unwrap_or_default()
+                .iter()
+                .map(|param| String::from_utf8_lossy(param))
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+
+        match params.first().copied() {
+            Some(b"0") | Some(b"2") => self.set_title(join(1)),
+            Some(b"8") => self.set_hyperlink(join(2)),
+            // ConEmu形式のサブコマンド（進捗表示の OSC 9;4 以外は無視）
+            Some(b"9") if params.len() > 2 && params[1] == b"4" => {
+                self.set_progress(params.get(2), params.get(3));
+            }
+            Some(b"9") if params.len() > 2 && is_numeric(params[1]) => {}
+            Some(b"9") => self.push_notification(None, join(1)),
+            Some(b"777") if params.get(1) == Some(&&b"notify"[..]) => {
+                let title = params
+                    .get(2)
+                    .map(|title| String::from_utf8_lossy(title).to_string());
+                self.push_notification(title, join(3));
+            }
+            // アイコン名は表示に影響しない
+            Some(b"1") => {}
+            Some(command) => {
+                let command = String::from_utf8_lossy(command).to_string();
+                self.record_unhandled(format!("OSC {command}"));
+            }
+            None => {}
+        }
     }
 
     /// CSI（Control Sequence Introducer）ディスパッチ
-    fn csi_dispatch(&mut self, params: &Params, _intermediates: &[u8], _ignore: bool, c: char) {
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], ignore: bool, c: char) {
         if self.verbose {
             let param_str: Vec<String> = params.iter().map(|p| format!("{p:?}")).collect();
             climonitor_shared::log_debug!(
                 climonitor_shared::LogCategory::Screen,
-                "🎛️  [CSI] Dispatching '{}' with params: [{}]",
+                "🎛️  [CSI] Dispatching '{}{}' with params: [{}]",
+                String::from_utf8_lossy(intermediates),
                 c,
                 param_str.join(", ")
             );
         }
 
-        match c {
-            'H' | 'f' => {
-                // カーソル位置設定（VTE標準準拠）
-                let row = params.iter().next().unwrap_or(&[1])[0] as usize;
-                let col = params.iter().nth(1).unwrap_or(&[1])[0] as usize;
-                let new_row = row.saturating_sub(1);
-                let new_col = col.saturating_sub(1);
+        let sequence = || format!("CSI {}{c}", String::from_utf8_lossy(intermediates));
+        if ignore {
+            // パラメータが多すぎるシーケンス
+            self.record_unhandled(sequence());
+            return;
+        }
+
+        match (intermediates, c) {
+            ([], 'H') | ([], 'f') => {
+                // CUP - カーソル位置設定（原点モードではスクロール範囲基準）
+                let row = csi_param(params, 0, 1) - 1;
+                let col = csi_param(params, 1, 1) - 1;
 
                 if self.verbose {
-                    climonitor_shared::log_debug!(climonitor_shared::LogCategory::Screen,
-                        "📍 [CURSOR_POS] Moving cursor to ({new_row}, {new_col}) [params: row={row}, col={col}]"
+                    climonitor_shared::log_debug!(
+                        climonitor_shared::LogCategory::Screen,
+                        "📍 [CURSOR_POS] Moving cursor to ({row}, {col})"
                     );
                 }
 
-                self.set_cursor(new_row, new_col);
+                self.set_cursor_in_origin(row, col);
             }
-            'A' => {
-                // カーソル上移動（列位置は保持）
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
+            ([], 'A') => {
+                // CUU - カーソル上移動（スクロール範囲内では上端で停止、列位置は保持）
+                let count = csi_param(params, 0, 1);
                 let old_row = self.cursor_row;
-                self.cursor_row = self.cursor_row.saturating_sub(count);
-                // 列位置は変更しない（VTE仕様準拠）
+                let top = if self.cursor_row >= self.scroll_top {
+                    self.scroll_top
+                } else {
+                    0
+                };
+                self.cursor_row = self.cursor_row.saturating_sub(count).max(top);
 
                 if self.verbose {
                     climonitor_shared::log_debug!(
@@ -803,25 +1568,83 @@ impl Perform for ScreenBuffer {
                     );
                 }
             }
-            'B' => {
-                // カーソル下移動
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.cursor_row = (self.cursor_row + count).min(self.rows - 1);
-            }
-            'C' => {
-                // カーソル右移動
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.cursor_col = (self.cursor_col + count).min(self.cols - 1);
-            }
-            'D' => {
-                // カーソル左移動
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.cursor_col = self.cursor_col.saturating_sub(count);
-            }
-            'J' => {
-                // 画面消去
-                let mode = params.iter().next().unwrap_or(&[0])[0];
-                match mode {
+            ([], 'B') | ([], 'e') => {
+                // CUD / VPR - カーソル下移動（スクロール範囲内では下端で停止）
+                let count = csi_param(params, 0, 1);
+                let bottom = if self.cursor_row <= self.scroll_bottom {
+                    self.scroll_bottom
+                } else {
+                    self.rows.saturating_sub(1)
+                };
+                self.cursor_row = (self.cursor_row + count).min(bottom);
+            }
+            ([], 'C') | ([], 'a') => {
+                // CUF / HPR - カーソル右移動
+                let count = csi_param(params, 0, 1);
+                self.cursor_col = (self.effective_col() + count).min(self.last_col());
+            }
+            ([], 'D') => {
+                // CUB - カーソル左移動
+                let count = csi_param(params, 0, 1);
+                self.cursor_col = self.effective_col().saturating_sub(count);
+            }
+            ([], 'E') => {
+                // CNL - n行下の行頭
+                let count = csi_param(params, 0, 1);
+                let bottom = if self.cursor_row <= self.scroll_bottom {
+                    self.scroll_bottom
+                } else {
+                    self.rows.saturating_sub(1)
+                };
+                self.cursor_row = (self.cursor_row + count).min(bottom);
+                self.cursor_col = 0;
+            }
+            ([], 'F') => {
+                // CPL - n行上の行頭
+                let count = csi_param(params, 0, 1);
+                let top = if self.cursor_row >= self.scroll_top {
+                    self.scroll_top
+                } else {
+                    0
+                };
+                self.cursor_row = self.cursor_row.saturating_sub(count).max(top);
+                self.cursor_col = 0;
+            }
+            ([], 'G') | ([], '`') => {
+                // CHA / HPA - カーソル列位置設定
+                let col = csi_param(params, 0, 1) - 1;
+                self.cursor_col = col.min(self.last_col());
+            }
+            ([], 'd') => {
+                // VPA - カーソル行位置設定（列は保持）
+                let row = csi_param(params, 0, 1) - 1;
+                let col = self.effective_col();
+                self.set_cursor_in_origin(row, col);
+            }
+            ([], 'I') => {
+                // CHT - n個先のタブストップへ
+                self.tab_forward(csi_param(params, 0, 1));
+            }
+            ([], 'Z') => {
+                // CBT - n個前のタブストップへ
+                self.tab_backward(csi_param(params, 0, 1));
+            }
+            ([], 'g') => {
+                // TBC - タブストップ解除（0: カーソル位置、3: すべて）
+                match csi_param(params, 0, 0) {
+                    0 => {
+                        let col = self.effective_col();
+                        if let Some(stop) = self.tab_stops.get_mut(col) {
+                            *stop = false;
+                        }
+                    }
+                    3 => self.tab_stops.iter_mut().for_each(|stop| *stop = false),
+                    _ => {}
+                }
+            }
+            ([], 'J') => {
+                // ED - 画面消去
+                match csi_param(params, 0, 0) {
                     0 => {
                         // カーソルから画面末尾まで消去
                         if self.verbose {
@@ -843,116 +1666,100 @@ impl Perform for ScreenBuffer {
                         }
                         self.clear_from_start_to_cursor();
                     }
-                    2 => {
-                        // 画面全体消去
+                    2 | 3 => {
+                        // 画面全体消去（3はスクロールバックも含むがバッファにはない）
                         if self.verbose {
                             climonitor_shared::log_debug!(
                                 climonitor_shared::LogCategory::Screen,
                                 "🧹 [CLEAR_SCREEN] Clearing entire screen"
                             );
                         }
+                        // カーソル位置は変わらない
+                        let (row, col) = (self.cursor_row, self.cursor_col);
                         self.clear_screen();
+                        self.cursor_row = row;
+                        self.cursor_col = col;
                     }
                     _ => {}
                 }
             }
-            'K' => {
-                // 行消去
-                let mode = params.iter().next().unwrap_or(&[0])[0];
+            ([], 'K') => {
+                // EL - 行消去
+                let mode = csi_param(params, 0, 0);
                 if self.cursor_row < self.grid.len() {
-                    match mode {
-                        0 => {
-                            // カーソル位置から行末まで消去
-                            if let Some(row) = self.grid.get_mut(self.cursor_row) {
-                                for cell in row.iter_mut().skip(self.cursor_col) {
-                                    *cell = Cell::empty();
-                                }
-                            }
-                        }
-                        1 => {
-                            // 行頭からカーソル位置まで消去
-                            if let Some(row) = self.grid.get_mut(self.cursor_row) {
-                                for cell in row.iter_mut().take(self.cursor_col + 1) {
-                                    *cell = Cell::empty();
-                                }
-                            }
-                        }
-                        2 => {
-                            // 行全体を消去
-                            if self.verbose {
-                                let old_content: String =
-                                    if let Some(row) = self.grid.get(self.cursor_row) {
-                                        row.iter()
-                                            .map(|c| c.char)
-                                            .collect::<String>()
-                                            .trim()
-                                            .to_string()
-                                    } else {
-                                        "N/A".to_string()
-                                    };
-                                let cursor_row = self.cursor_row;
-                                let grid_height = self.grid.len();
-                                let cols = self.cols;
-                                climonitor_shared::log_debug!(climonitor_shared::LogCategory::Screen, "🧹 [CLEAR_LINE] Mode=2 clearing entire line {cursor_row} (grid size: {grid_height}x{cols}) old_content: '{old_content}'");
-                            }
-                            if let Some(row) = self.grid.get_mut(self.cursor_row) {
-                                for cell in row.iter_mut() {
-                                    *cell = Cell::empty();
-                                }
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "✅ [CLEAR_LINE] Line {} successfully cleared",
-                                        self.cursor_row
-                                    );
-                                }
-                            } else {
-                                let cursor_row = self.cursor_row;
-                                let grid_height = self.grid.len();
-                                climonitor_shared::log_debug!(climonitor_shared::LogCategory::Screen, "❌ [CLEAR_LINE_ERROR] Cursor row {cursor_row} is out of bounds (grid height: {grid_height})");
-                            }
-                        }
-                        _ => {}
+                    let col = self.effective_col();
+                    // 消去範囲の境界にかかる全角文字は丸ごと消す
+                    self.split_wide_char_at(self.cursor_row, col);
+                    self.mark_dirty(self.cursor_row);
+                    let row = &mut self.grid[self.cursor_row];
+                    let range = match mode {
+                        // カーソル位置から行末まで消去
+                        0 => col..row.len(),
+                        // 行頭からカーソル位置まで消去
+                        1 => 0..col + 1,
+                        // 行全体を消去
+                        2 => 0..row.len(),
+                        _ => 0..0,
+                    };
+                    for cell in &mut row[range] {
+                        *cell = Cell::empty();
+                    }
+                    if self.verbose && mode == 2 {
+                        climonitor_shared::log_debug!(
+                            climonitor_shared::LogCategory::Screen,
+                            "✅ [CLEAR_LINE] Line {} successfully cleared",
+                            self.cursor_row
+                        );
                     }
                 }
             }
-            'L' => {
+            ([], 'X') => {
+                // ECH - Erase Character
+                self.erase_characters(csi_param(params, 0, 1));
+            }
+            ([], 'b') => {
+                // REP - 直前の文字をn回繰り返す
+                if let Some(ch) = self.last_graphic {
+                    for _ in 0..csi_param(params, 0, 1).min(self.pty_cols() * self.rows) {
+                        self.insert_char(ch);
+                    }
+                }
+            }
+            ([], 'L') => {
                 // IL - Insert Line
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.insert_lines(count);
+                self.insert_lines(csi_param(params, 0, 1));
             }
-            'M' => {
+            ([], 'M') => {
                 // DL - Delete Line
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.delete_lines(count);
+                self.delete_lines(csi_param(params, 0, 1));
             }
-            '@' => {
+            ([], '@') => {
                 // ICH - Insert Character
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.insert_characters(count);
+                self.insert_characters(csi_param(params, 0, 1));
             }
-            'P' => {
+            ([], 'P') => {
                 // DCH - Delete Character
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.delete_characters(count);
+                self.delete_characters(csi_param(params, 0, 1));
             }
-            'S' => {
+            ([], 'S') => {
                 // SU - Scroll Up
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.scroll_up_n(count);
+                self.scroll_up_n(csi_param(params, 0, 1));
             }
-            'T' => {
+            ([], 'T') => {
                 // SD - Scroll Down
-                let count = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.scroll_down_n(count);
+                self.scroll_down_n(csi_param(params, 0, 1));
             }
-            'r' => {
+            ([], 'r') => {
                 // DECSTBM - Set Top and Bottom Margins (スクロール範囲設定)
-                let top = params.iter().next().unwrap_or(&[1])[0] as usize;
-                let bottom = params.iter().nth(1).unwrap_or(&[self.rows as u16])[0] as usize;
+                let top = csi_param(params, 0, 1) - 1;
+                let bottom = csi_param(params, 1, self.rows).min(self.rows) - 1;
+                if top >= bottom {
+                    // 2行未満の範囲は無効
+                    return;
+                }
 
-                self.scroll_top = top.saturating_sub(1);
-                self.scroll_bottom = bottom.saturating_sub(1).min(self.rows.saturating_sub(1));
+                self.scroll_top = top;
+                self.scroll_bottom = bottom;
 
                 if self.verbose {
                     climonitor_shared::log_debug!(
@@ -963,216 +1770,73 @@ impl Perform for ScreenBuffer {
                     );
                 }
 
-                // カーソルをホームポジション（スクロール範囲の左上）に移動
-                self.set_cursor(self.scroll_top, 0);
-            }
-            'm' => {
-                // SGR（Select Graphic Rendition）- 文字属性設定
-                for param in params.iter() {
-                    if let Some(&value) = param.first() {
-                        match value {
-                            0 => {
-                                // リセット
-                                self.current_fg = None;
-                                self.current_bg = None;
-                                self.current_bold = false;
-                                self.current_italic = false;
-                                self.current_underline = false;
-                            }
-                            1 => self.current_bold = true,
-                            3 => self.current_italic = true,
-                            4 => self.current_underline = true,
-                            22 => self.current_bold = false,
-                            23 => self.current_italic = false,
-                            24 => self.current_underline = false,
-                            2 => {} // Dim/faint - 無視
-                            30..=37 => self.current_fg = Some(value as u8 - 30),
-                            38 => {
-                                // 拡張前景色 - 複雑なので簡略化
-                                // 次のパラメータは無視
-                            }
-                            39 => self.current_fg = None, // デフォルト前景色
-                            40..=47 => self.current_bg = Some(value as u8 - 40),
-                            49 => self.current_bg = None, // デフォルト背景色
-                            90..=97 => self.current_fg = Some(value as u8 - 90 + 8), // 明るい前景色
-                            100..=107 => self.current_bg = Some(value as u8 - 100 + 8), // 明るい背景色
-                            _ => {
-                                // 他のSGRパラメータは無視（verboseログも削除）
-                            }
-                        }
-                    }
-                }
+                // カーソルをホームポジションに移動
+                self.set_cursor_in_origin(0, 0);
             }
-            'G' => {
-                // カーソル列位置設定
-                let col = params.iter().next().unwrap_or(&[1])[0] as usize;
-                self.cursor_col = col.saturating_sub(1).min(self.cols - 1);
+            ([], 's') => {
+                // SCOSC - カーソル位置保存
+                self.save_cursor();
             }
-            'h' => {
-                // Set Mode - 重要なモードを実装
-                for param in params.iter() {
-                    if let Some(&value) = param.first() {
-                        match value {
-                            25 => {
-                                // Show cursor
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "👁️  [CURSOR_SHOW] Cursor visibility: ON"
-                                    );
-                                }
-                            }
-                            1049 => {
-                                // Save cursor and switch to alternate screen buffer
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "🔄 [ALT_SCREEN] Switch to alternate screen buffer"
-                                    );
-                                }
-                                // 現在の画面をクリア（alternate screenの効果をエミュレート）
-                                self.clear_screen();
-                            }
-                            1047 => {
-                                // Switch to alternate screen buffer
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "🔄 [ALT_SCREEN] Switch to alternate screen buffer (1047)"
-                                    );
-                                }
-                                self.clear_screen();
-                            }
-                            47 => {
-                                // Switch to alternate screen buffer (older variant)
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "🔄 [ALT_SCREEN] Switch to alternate screen buffer (47)"
-                                    );
-                                }
-                                self.clear_screen();
-                            }
-                            2004 => {
-                                // Bracketed Paste Mode - Enable
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "📋 [BRACKETED_PASTE] Enable bracketed paste mode"
-                                    );
-                                }
-                                // TODO: Set internal flag for bracketed paste mode
-                            }
-                            1004 => {
-                                // Focus Tracking Mode - Enable
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "👀 [FOCUS_TRACKING] Enable focus tracking mode"
-                                    );
-                                }
-                                // TODO: Set internal flag and implement focus event notification
-                            }
-                            _ => {
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "❓ [MODE_SET] Unhandled mode: {value}"
-                                    );
-                                }
-                            }
-                        }
-                    }
-                }
+            ([], 'u') => {
+                // SCORC - カーソル位置復元
+                self.restore_cursor();
             }
-            'l' => {
-                // Reset Mode - 重要なモードを実装
+            ([], 'm') => self.select_graphic_rendition(params),
+            ([b'?'], 'h') | ([b'?'], 'l') => {
                 for param in params.iter() {
-                    if let Some(&value) = param.first() {
-                        match value {
-                            25 => {
-                                // Hide cursor
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "🙈 [CURSOR_HIDE] Cursor visibility: OFF"
-                                    );
-                                }
-                            }
-                            1049 => {
-                                // Restore cursor and switch to main screen buffer
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "🔄 [MAIN_SCREEN] Switch to main screen buffer"
-                                    );
-                                }
-                                // メイン画面に戻る（現在の実装では何もしない）
-                            }
-                            1047 => {
-                                // Switch to main screen buffer
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "🔄 [MAIN_SCREEN] Switch to main screen buffer (1047)"
-                                    );
-                                }
-                            }
-                            47 => {
-                                // Switch to main screen buffer (older variant)
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "🔄 [MAIN_SCREEN] Switch to main screen buffer (47)"
-                                    );
-                                }
-                            }
-                            2004 => {
-                                // Bracketed Paste Mode - Disable
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "📋 [BRACKETED_PASTE] Disable bracketed paste mode"
-                                    );
-                                }
-                                // TODO: Clear internal flag for bracketed paste mode
-                            }
-                            1004 => {
-                                // Focus Tracking Mode - Disable
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "👀 [FOCUS_TRACKING] Disable focus tracking mode"
-                                    );
-                                }
-                                // TODO: Clear internal flag and stop focus event notification
-                            }
-                            _ => {
-                                if self.verbose {
-                                    climonitor_shared::log_debug!(
-                                        climonitor_shared::LogCategory::Screen,
-                                        "❓ [MODE_RESET] Unhandled mode: {value}"
-                                    );
-                                }
-                            }
-                        }
+                    if let Some(&mode) = param.first() {
+                        self.set_private_mode(mode, c == 'h');
                     }
                 }
             }
-            's' => {
-                // カーソル位置保存 - 簡略化のため無視
-            }
-            'u' => {
-                // カーソル位置復元 - 簡略化のため無視
-            }
-            _ => {
-                // 他のCSIコマンドは無視（verboseログも削除）
-            }
+            // 端末への問い合わせ（DA / DSR / XTVERSION）は実際の端末が応答する
+            ([], 'c')
+            | ([b'>'], 'c')
+            | ([b'='], 'c')
+            | ([], 'n')
+            | ([b'?'], 'n')
+            | ([b'>'], 'q') => {}
+            // キーボード拡張（kitty keyboard protocol / modifyOtherKeys）は入力側のみ
+            ([b'>'], 'u') | ([b'<'], 'u') | ([b'='], 'u') | ([b'?'], 'u') | ([b'>'], 'm') => {}
+            // カーソル形状（DECSCUSR）・ウィンドウ操作は画面内容に影響しない
+            ([b' '], 'q') | ([], 't') => {}
+            _ => self.record_unhandled(sequence()),
         }
     }
 
     /// ESCシーケンスディスパッチ
-    fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, _byte: u8) {
-        // 今回は使用しない
+    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
+        match (intermediates, byte) {
+            // DECSC / DECRC
+            ([], b'7') => self.save_cursor(),
+            ([], b'8') => self.restore_cursor(),
+            // IND / RI / NEL
+            ([], b'D') => self.index(),
+            ([], b'M') => self.reverse_index(),
+            ([], b'E') => {
+                self.cursor_col = 0;
+                self.index();
+            }
+            // HTS - カーソル位置にタブストップを設定
+            ([], b'H') => {
+                let col = self.effective_col();
+                if let Some(stop) = self.tab_stops.get_mut(col) {
+                    *stop = true;
+                }
+            }
+            // RIS - 端末リセット
+            ([], b'c') => self.reset(),
+            // DECALN
+            ([b'#'], b'8') => self.screen_alignment_test(),
+            // キーパッドモード（DECKPAM / DECKPNM）・文字列終端（ST）
+            ([], b'=') | ([], b'>') | ([], b'\\') => {}
+            // 文字セット指定（G0〜G3）は表示する文字に影響しない
+            ([b'('], _) | ([b')'], _) | ([b'*'], _) | ([b'+'], _) => {}
+            _ => self.record_unhandled(format!(
+                "ESC {}{}",
+                String::from_utf8_lossy(intermediates),
+                byte as char
+            )),
+        }
     }
 }
@@ -1,17 +1,27 @@
 // screen_claude_detector.rs - Screen buffer based Claude state detector
 
-use crate::screen_buffer::ScreenBuffer;
-use crate::state_detector::StateDetector;
-use climonitor_shared::SessionStatus;
+use crate::screen_buffer::{ScreenBuffer, TerminalNotification, TerminalProgress};
+use crate::state_detector::{decision_from_notification, StateDecision, StateDetector};
+use climonitor_shared::{SessionStatus, StateEvidence};
+use std::collections::BTreeMap;
 use std::time::Instant;
 
 /// スクリーンバッファベースのClaude状態検出器
 pub struct ScreenClaudeStateDetector {
     screen_buffer: ScreenBuffer,
     current_state: SessionStatus,
+    /// 現在の状態を判定した根拠
+    current_evidence: Option<StateEvidence>,
     previous_had_esc_interrupt: bool,
+    /// 直近に"esc to interrupt)"があった行（完了判定の根拠）
+    last_esc_interrupt_line: Option<(usize, String)>,
     last_state_change: Option<Instant>,
     last_ui_context: Option<String>,
+    pending_notifications: Vec<TerminalNotification>,
+    /// 前回解析した画面の世代番号
+    screen_generation: Option<u64>,
+    /// 前回の画面でのUI boxからの判定結果
+    ui_box_state: Option<StateDecision>,
     verbose: bool,
 }
 
@@ -34,9 +44,14 @@ impl ScreenClaudeStateDetector {
         Self {
             screen_buffer,
             current_state: SessionStatus::Connected,
+            current_evidence: None,
             previous_had_esc_interrupt: false,
+            last_esc_interrupt_line: None,
             last_state_change: None,
             last_ui_context: None,
+            pending_notifications: Vec::new(),
+            screen_generation: None,
+            ui_box_state: None,
             verbose,
         }
     }
@@ -45,21 +60,26 @@ impl ScreenClaudeStateDetector {
     ///
     /// Claudeは連続処理時に"esc to interrupt"が一瞬消えることがあるため、
     /// 真の状態変化（開始/完了）のみを検出してちらつきを防ぐ
-    fn detect_claude_completion_state(&mut self) -> Option<SessionStatus> {
+    fn detect_claude_completion_state(&mut self) -> Option<StateDecision> {
         // UIボックス近辺での"esc to interrupt)"検出のみ
-        let ui_boxes = self.screen_buffer.find_ui_boxes();
-        let has_esc_interrupt = if let Some(latest_box) = ui_boxes.last() {
+        let lines = self.screen_buffer.screen_lines();
+        let esc_interrupt_line = self.screen_buffer.ui_boxes().last().and_then(|latest_box| {
             // UIボックス上の2行以内に"esc to interrupt)"があるかチェック
-            latest_box
+            let text = latest_box
                 .above_lines
                 .iter()
                 .rev() // 下から上へ検索
                 .take(2) // 最大2行
-                .any(|line| line.contains("esc to interrupt)"))
-        } else {
-            // UIボックスがない場合は実行中ではないと判断
-            false
-        };
+                .find(|line| line.contains("esc to interrupt)"))?;
+            let above = &lines[..latest_box.start_row.min(lines.len())];
+            let row = above.iter().rposition(|line| line == text)?;
+            Some((row, text.clone()))
+        });
+        // UIボックスがない場合は実行中ではないと判断
+        let has_esc_interrupt = esc_interrupt_line.is_some();
+
+        // 画面が変わらない間は次回以降もこの判定結果を使う
+        self.ui_box_state = self.detect_ui_box_state();
 
         let now = Instant::now();
 
@@ -84,7 +104,16 @@ impl ScreenClaudeStateDetector {
             }
             self.last_state_change = Some(now);
             self.previous_had_esc_interrupt = false;
-            return Some(SessionStatus::Idle);
+            let decision = StateDecision::new(
+                SessionStatus::Idle,
+                "claude.esc_to_interrupt_disappeared",
+                0.8,
+            );
+            // 消える直前に"esc to interrupt)"があった行
+            return Some(match self.last_esc_interrupt_line.take() {
+                Some((row, text)) => decision.with_line(row, &text),
+                None => decision,
+            });
         } else if !self.previous_had_esc_interrupt && has_esc_interrupt {
             // "esc to interrupt"が現れた = 実行開始
             if self.verbose {
@@ -95,41 +124,79 @@ impl ScreenClaudeStateDetector {
             }
             self.last_state_change = Some(now);
             self.previous_had_esc_interrupt = true;
-            return Some(SessionStatus::Busy);
+            let decision =
+                StateDecision::new(SessionStatus::Busy, "claude.esc_to_interrupt_appeared", 0.9);
+            self.last_esc_interrupt_line = esc_interrupt_line.clone();
+            return Some(match esc_interrupt_line {
+                Some((row, text)) => decision.with_line(row, &text),
+                None => decision,
+            });
         }
 
-        // 状態変化なし、基本的なUI box検出を実行
+        // 状態変化なし、基本的なUI box検出の結果を使う
         self.previous_had_esc_interrupt = has_esc_interrupt;
+        if esc_interrupt_line.is_some() {
+            self.last_esc_interrupt_line = esc_interrupt_line;
+        }
+        self.ui_box_state.clone()
+    }
 
-        // UI boxからの基本的な状態検出
-        let ui_boxes = self.screen_buffer.find_ui_boxes();
-        if let Some(latest_box) = ui_boxes.last() {
-            // 承認プロンプト検出
-            for content_line in &latest_box.content_lines {
-                if content_line.contains("Do you want")
-                    || content_line.contains("Would you like")
-                    || content_line.contains("May I")
-                    || content_line.contains("proceed?")
-                    || content_line.contains("y/n")
-                {
-                    return Some(SessionStatus::WaitingInput);
-                }
-            }
+    /// UI boxからの基本的な状態検出
+    fn detect_ui_box_state(&self) -> Option<StateDecision> {
+        let latest_box = self.screen_buffer.ui_boxes().last()?;
+        let lines = self.screen_buffer.screen_lines();
+        // パターンを含む画面の行（行番号付き）
+        let find_line = |rows: std::ops::Range<usize>, pattern: &str| {
+            let end = rows.end.min(lines.len());
+            (rows.start.min(end)..end).find(|&row| lines[row].contains(pattern))
+        };
 
-            // IDE接続確認
-            for below_line in &latest_box.below_lines {
-                if below_line.contains("◯ IDE connected") {
-                    return Some(SessionStatus::Idle);
-                }
+        // 承認プロンプト検出
+        let box_rows = latest_box.start_row..latest_box.end_row + 1;
+        for pattern in ["Do you want", "Would you like", "May I", "proceed?", "y/n"] {
+            if latest_box
+                .content_lines
+                .iter()
+                .any(|content_line| content_line.contains(pattern))
+            {
+                let decision = StateDecision::new(
+                    SessionStatus::WaitingInput,
+                    format!("claude.approval_prompt({pattern:?})"),
+                    0.85,
+                );
+                return Some(match find_line(box_rows, pattern) {
+                    Some(row) => decision.with_line(row, &lines[row]),
+                    None => decision,
+                });
             }
         }
 
-        None
+        // IDE接続確認
+        let below_rows = latest_box.end_row + 1..latest_box.end_row + 4;
+        let row = find_line(below_rows, "◯ IDE connected")?;
+        Some(
+            StateDecision::new(SessionStatus::Idle, "claude.ide_connected", 0.6)
+                .with_line(row, &lines[row]),
+        )
+    }
+
+    /// ウィンドウタイトルのタスク概要（先頭のスピナー記号を除く）
+    fn get_title_context(&self) -> Option<String> {
+        let title = self
+            .screen_buffer
+            .title()?
+            .trim_start_matches(|c: char| !c.is_alphanumeric())
+            .trim();
+        // 既定のタイトルはコンテキストにならない
+        if title.is_empty() || title == "Claude Code" {
+            return None;
+        }
+        Some(title.to_string())
     }
 
     /// 現在のバッファからUIコンテキストを直接取得（キャッシュなし）
     fn get_current_ui_context(&self) -> Option<String> {
-        let screen_lines = self.screen_buffer.get_screen_lines();
+        let screen_lines = self.screen_buffer.screen_lines();
 
         // 画面全体から行頭●マーカーを探す（逆順で最新のものを取得）
         for line in screen_lines.iter().rev() {
@@ -146,21 +213,47 @@ impl ScreenClaudeStateDetector {
 }
 
 impl StateDetector for ScreenClaudeStateDetector {
-    fn process_output(&mut self, output: &str) -> Option<SessionStatus> {
+    fn process_bytes(&mut self, bytes: &[u8]) -> Option<StateDecision> {
         // 画面バッファを更新
-        let bytes = output.as_bytes();
         self.screen_buffer.process_data(bytes);
 
-        // 新しいUIコンテキストがある場合は更新
-        let current_context = self.get_current_ui_context();
-        if current_context.is_some() {
-            self.last_ui_context = current_context;
+        // 画面の文字内容が変わっていなければ前回の解析結果を使う
+        let generation = self.screen_buffer.generation();
+        let mut detected = if self.screen_generation != Some(generation) {
+            self.screen_generation = Some(generation);
+
+            // 新しいUIコンテキストがある場合は更新
+            let current_context = self.get_current_ui_context();
+            if current_context.is_some() {
+                self.last_ui_context = current_context;
+            }
+
+            // Claude固有の"esc to interrupt"ロジックを適用
+            self.detect_claude_completion_state()
+        } else {
+            self.ui_box_state.clone()
+        };
+
+        // デスクトップ通知は画面より確実なシグナルとして優先
+        let notifications = self.screen_buffer.take_notifications();
+        if let Some(notification) = notifications.last() {
+            let decision = decision_from_notification(notification);
+            if self.verbose {
+                climonitor_shared::log_debug!(
+                    climonitor_shared::LogCategory::Claude,
+                    "🔔 [CLAUDE_NOTIFY] {:?} → {:?}",
+                    notification.body,
+                    decision.status
+                );
+            }
+            detected = Some(decision);
         }
+        self.pending_notifications.extend(notifications);
 
-        // Claude固有の"esc to interrupt"ロジックを適用
-        if let Some(new_state) = self.detect_claude_completion_state() {
-            self.current_state = new_state.clone();
-            return Some(new_state);
+        if let Some(decision) = detected {
+            self.current_state = decision.status.clone();
+            self.current_evidence = Some(decision.evidence.clone());
+            return Some(decision);
         }
 
         None
@@ -170,6 +263,10 @@ impl StateDetector for ScreenClaudeStateDetector {
         &self.current_state
     }
 
+    fn current_evidence(&self) -> Option<&StateEvidence> {
+        self.current_evidence.as_ref()
+    }
+
     fn debug_buffer(&self) {
         // デバッグ用に画面内容を表示
         let lines = self.screen_buffer.get_screen_lines();
@@ -186,7 +283,7 @@ impl StateDetector for ScreenClaudeStateDetector {
 
     fn get_ui_above_text(&self) -> Option<String> {
         // Claude固有: 行頭●の右側のテキストを取得（最新=一番下のもの）
-        let screen_lines = self.screen_buffer.get_screen_lines();
+        let screen_lines = self.screen_buffer.screen_lines();
 
         // 画面全体から行頭●マーカーを探す（逆順で最新のものを取得）
         for line in screen_lines.iter().rev() {
@@ -199,11 +296,43 @@ impl StateDetector for ScreenClaudeStateDetector {
             }
         }
 
-        // バッファ内にコンテキストがない場合は前回の状態を保持
-        self.last_ui_context.clone()
+        // ●がなければウィンドウタイトル、それもなければ前回の状態を保持
+        self.get_title_context()
+            .or_else(|| self.last_ui_context.clone())
+    }
+
+    fn get_ui_box_content(&self) -> Option<Vec<String>> {
+        self.screen_buffer
+            .find_ui_boxes()
+            .pop()
+            .map(|ui_box| ui_box.content_lines)
     }
 
     fn resize_screen_buffer(&mut self, rows: usize, cols: usize) {
-        self.screen_buffer = crate::screen_buffer::ScreenBuffer::new(rows, cols, self.verbose);
+        let previous = std::mem::replace(
+            &mut self.screen_buffer,
+            ScreenBuffer::new(rows, cols, self.verbose),
+        );
+        self.screen_buffer.inherit_terminal_state(previous);
+    }
+
+    fn render_screen_row(&self, row: usize) -> String {
+        self.screen_buffer.render_row(row)
+    }
+
+    fn terminal_title(&self) -> Option<String> {
+        self.screen_buffer.title().map(str::to_string)
+    }
+
+    fn terminal_progress(&self) -> Option<TerminalProgress> {
+        self.screen_buffer.progress()
+    }
+
+    fn unhandled_sequences(&self) -> BTreeMap<String, u64> {
+        self.screen_buffer.unhandled_sequences().clone()
+    }
+
+    fn take_notifications(&mut self) -> Vec<TerminalNotification> {
+        std::mem::take(&mut self.pending_notifications)
     }
 }
@@ -1,16 +1,24 @@
 // screen_gemini_detector.rs - Screen buffer based Gemini state detector
 
-use crate::screen_buffer::ScreenBuffer;
-use crate::state_detector::StateDetector;
-use climonitor_shared::SessionStatus;
+use crate::screen_buffer::{ScreenBuffer, TerminalNotification, TerminalProgress};
+use crate::state_detector::{decision_from_notification, StateDecision, StateDetector};
+use climonitor_shared::{SessionStatus, StateEvidence};
+use std::collections::BTreeMap;
 use std::time::Instant;
 
 /// スクリーンバッファベースのGemini状態検出器
 pub struct ScreenGeminiStateDetector {
     screen_buffer: ScreenBuffer,
     current_state: SessionStatus,
+    /// 現在の状態を判定した根拠
+    current_evidence: Option<StateEvidence>,
     last_state_change: Option<Instant>,
     last_ui_context: Option<String>,
+    pending_notifications: Vec<TerminalNotification>,
+    /// 前回解析した画面の世代番号
+    screen_generation: Option<u64>,
+    /// 前回の画面からの判定結果
+    screen_state: Option<StateDecision>,
     verbose: bool,
 }
 
@@ -33,24 +41,26 @@ impl ScreenGeminiStateDetector {
         Self {
             screen_buffer,
             current_state: SessionStatus::Connected,
+            current_evidence: None,
             last_state_change: None,
             last_ui_context: None,
+            pending_notifications: Vec::new(),
+            screen_generation: None,
+            screen_state: None,
             verbose,
         }
     }
 
     /// 画面内容から状態パターンをチェック
-    fn check_screen_patterns(&self, screen_lines: &[String]) -> Option<SessionStatus> {
-        for line in screen_lines {
-            if let Some(state) = self.check_single_line_patterns(line) {
-                return Some(state);
-            }
-        }
-        None
+    fn check_screen_patterns(&self, screen_lines: &[String]) -> Option<StateDecision> {
+        screen_lines
+            .iter()
+            .enumerate()
+            .find_map(|(row, line)| self.check_single_line_patterns(row, line))
     }
 
     /// 単一行のパターンチェック
-    fn check_single_line_patterns(&self, line: &str) -> Option<SessionStatus> {
+    fn check_single_line_patterns(&self, row: usize, line: &str) -> Option<StateDecision> {
         let trimmed = line.trim();
         if trimmed.is_empty() {
             return None;
@@ -64,7 +74,14 @@ impl ScreenGeminiStateDetector {
                     "⏳ [GEMINI_CONFIRMATION] Screen-wide confirmation detected: {trimmed}"
                 );
             }
-            return Some(SessionStatus::WaitingInput);
+            return Some(
+                StateDecision::new(
+                    SessionStatus::WaitingInput,
+                    "gemini.waiting_for_confirmation",
+                    0.9,
+                )
+                .with_line(row, line),
+            );
         }
 
         // 実行中状態
@@ -75,47 +92,38 @@ impl ScreenGeminiStateDetector {
                     "⚡ [GEMINI_BUSY] Processing detected: {trimmed}"
                 );
             }
-            return Some(SessionStatus::Busy);
+            return Some(
+                StateDecision::new(SessionStatus::Busy, "gemini.esc_to_cancel", 0.9)
+                    .with_line(row, line),
+            );
         }
 
         None
     }
 
     /// Gemini固有の状態検出: シンプルなパターンマッチング
-    fn detect_gemini_state(&mut self) -> Option<SessionStatus> {
-        let screen_lines = self.screen_buffer.get_screen_lines();
-        let ui_boxes = self.screen_buffer.find_ui_boxes();
+    fn detect_gemini_state(&self) -> Option<StateDecision> {
+        let screen_lines = self.screen_buffer.screen_lines();
+        let ui_boxes = self.screen_buffer.ui_boxes();
 
-        // 全ての画面内容から状態パターンをチェック
-        if let Some(state) = self.check_screen_patterns(&screen_lines) {
-            return Some(state);
+        // 全ての画面内容から状態パターンをチェック（UI boxの上下の行も含む）
+        if let Some(decision) = self.check_screen_patterns(screen_lines) {
+            return Some(decision);
         }
 
-        // UI boxがある場合は、各UI boxとその上下の行をチェック
-        if !ui_boxes.is_empty() {
-            for ui_box in &ui_boxes {
-                // UI boxの上下の行をチェック
-                for line in &ui_box.above_lines {
-                    if let Some(state) = self.check_single_line_patterns(line) {
-                        return Some(state);
-                    }
-                }
-
-                for line in &ui_box.below_lines {
-                    if let Some(state) = self.check_single_line_patterns(line) {
-                        return Some(state);
-                    }
-                }
-            }
-
-            // 特別な状態が検出されない場合はIdle
+        // UI boxがあり、特別な状態が検出されない場合はIdle
+        if let Some(ui_box) = ui_boxes.last() {
             if self.verbose {
                 climonitor_shared::log_debug!(
                     climonitor_shared::LogCategory::Gemini,
                     "🔵 [GEMINI_IDLE] No busy or waiting patterns detected"
                 );
             }
-            return Some(SessionStatus::Idle);
+            let decision = StateDecision::new(SessionStatus::Idle, "gemini.prompt_box", 0.6);
+            return Some(match screen_lines.get(ui_box.start_row) {
+                Some(line) => decision.with_line(ui_box.start_row, line),
+                None => decision,
+            });
         }
 
         // UI boxがない場合も特別な状態が検出されない場合はIdle
@@ -125,12 +133,16 @@ impl ScreenGeminiStateDetector {
                 "🔵 [GEMINI_IDLE] No UI boxes, defaulting to Idle"
             );
         }
-        Some(SessionStatus::Idle)
+        Some(StateDecision::new(
+            SessionStatus::Idle,
+            "gemini.no_activity",
+            0.3,
+        ))
     }
 
     /// 現在のバッファからUIコンテキストを直接取得（キャッシュなし）
     fn get_current_ui_context(&self) -> Option<String> {
-        let screen_lines = self.screen_buffer.get_screen_lines();
+        let screen_lines = self.screen_buffer.screen_lines();
 
         // 画面全体から行頭✦マーカーを探す（逆順で最新のものを取得）
         for line in screen_lines.iter().rev() {
@@ -147,23 +159,34 @@ impl ScreenGeminiStateDetector {
 }
 
 impl StateDetector for ScreenGeminiStateDetector {
-    fn process_output(&mut self, output: &str) -> Option<SessionStatus> {
+    fn process_bytes(&mut self, bytes: &[u8]) -> Option<StateDecision> {
         // 基本的なスクリーンバッファ処理
-        let bytes = output.as_bytes();
         self.screen_buffer.process_data(bytes);
 
-        // 新しいUIコンテキストがある場合は更新
-        let current_context = self.get_current_ui_context();
-        if current_context.is_some() {
-            self.last_ui_context = current_context;
+        // 画面の文字内容が変わったときだけ解析し直す
+        let generation = self.screen_buffer.generation();
+        if self.screen_generation != Some(generation) {
+            self.screen_generation = Some(generation);
+
+            // 新しいUIコンテキストがある場合は更新
+            let current_context = self.get_current_ui_context();
+            if current_context.is_some() {
+                self.last_ui_context = current_context;
+            }
+
+            self.screen_state = self.detect_gemini_state();
         }
 
-        // Gemini特有の検出ロジックを適用
-        if let Some(gemini_state) = self.detect_gemini_state() {
+        // Gemini特有の検出ロジックを適用（デスクトップ通知があればそちらを優先）
+        let notifications = self.screen_buffer.take_notifications();
+        let notified = notifications.last().map(decision_from_notification);
+        self.pending_notifications.extend(notifications);
+
+        if let Some(decision) = notified.or_else(|| self.screen_state.clone()) {
             let now = Instant::now();
 
             // 状態変化の記録
-            if gemini_state != self.current_state {
+            if decision.status != self.current_state {
                 self.last_state_change = Some(now);
 
                 if self.verbose {
@@ -171,14 +194,15 @@ impl StateDetector for ScreenGeminiStateDetector {
                         climonitor_shared::LogCategory::Gemini,
                         "🎯 [GEMINI_STATE_CHANGE] {:?} → {:?}",
                         self.current_state,
-                        gemini_state
+                        decision.status
                     );
                 }
             }
 
             // 状態を更新
-            self.current_state = gemini_state.clone();
-            return Some(gemini_state);
+            self.current_state = decision.status.clone();
+            self.current_evidence = Some(decision.evidence.clone());
+            return Some(decision);
         }
 
         None
@@ -188,6 +212,10 @@ impl StateDetector for ScreenGeminiStateDetector {
         &self.current_state
     }
 
+    fn current_evidence(&self) -> Option<&StateEvidence> {
+        self.current_evidence.as_ref()
+    }
+
     fn debug_buffer(&self) {
         let lines = self.screen_buffer.get_screen_lines();
         for (i, line) in lines.iter().enumerate() {
@@ -203,7 +231,7 @@ impl StateDetector for ScreenGeminiStateDetector {
 
     fn get_ui_above_text(&self) -> Option<String> {
         // Gemini固有: 行頭✦の右側のテキストを取得（最新=一番下のもの）
-        let screen_lines = self.screen_buffer.get_screen_lines();
+        let screen_lines = self.screen_buffer.screen_lines();
 
         // 画面全体から行頭✦マーカーを探す（逆順で最新のものを取得）
         for line in screen_lines.iter().rev() {
@@ -220,7 +248,38 @@ impl StateDetector for ScreenGeminiStateDetector {
         self.last_ui_context.clone()
     }
 
+    fn get_ui_box_content(&self) -> Option<Vec<String>> {
+        self.screen_buffer
+            .find_ui_boxes()
+            .pop()
+            .map(|ui_box| ui_box.content_lines)
+    }
+
     fn resize_screen_buffer(&mut self, rows: usize, cols: usize) {
-        self.screen_buffer = crate::screen_buffer::ScreenBuffer::new(rows, cols, self.verbose);
+        let previous = std::mem::replace(
+            &mut self.screen_buffer,
+            ScreenBuffer::new(rows, cols, self.verbose),
+        );
+        self.screen_buffer.inherit_terminal_state(previous);
+    }
+
+    fn render_screen_row(&self, row: usize) -> String {
+        self.screen_buffer.render_row(row)
+    }
+
+    fn terminal_title(&self) -> Option<String> {
+        self.screen_buffer.title().map(str::to_string)
+    }
+
+    fn terminal_progress(&self) -> Option<TerminalProgress> {
+        self.screen_buffer.progress()
+    }
+
+    fn unhandled_sequences(&self) -> BTreeMap<String, u64> {
+        self.screen_buffer.unhandled_sequences().clone()
+    }
+
+    fn take_notifications(&mut self) -> Vec<TerminalNotification> {
+        std::mem::take(&mut self.pending_notifications)
     }
 }
@@ -1,23 +1,144 @@
 // state_detector.rs - 状態検出の抽象化レイヤー
 
-use climonitor_shared::SessionStatus;
+use crate::activity::ActivitySnapshot;
+use crate::screen_buffer::{TerminalNotification, TerminalProgress};
+use climonitor_shared::{EvidenceLine, SessionStatus, StateEvidence};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// 状態判定の結果（判定した状態とその根拠）
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDecision {
+    pub status: SessionStatus,
+    pub evidence: StateEvidence,
+}
+
+impl StateDecision {
+    pub fn new(status: SessionStatus, rule: impl Into<String>, confidence: f32) -> Self {
+        Self {
+            status,
+            evidence: StateEvidence {
+                rules: vec![rule.into()],
+                lines: Vec::new(),
+                confidence,
+            },
+        }
+    }
+
+    /// ルールにマッチした画面の行を追加
+    pub fn with_line(mut self, row: usize, text: &str) -> Self {
+        self.evidence.lines.push(EvidenceLine {
+            row: row as u32,
+            text: text.trim_end().to_string(),
+        });
+        self
+    }
+}
 
 /// 状態検出器の共通インターフェース
 pub trait StateDetector: Send + Sync {
-    /// 新しい出力を処理して状態を更新
-    fn process_output(&mut self, output: &str) -> Option<SessionStatus>;
+    /// 新しい出力を処理して状態を更新（判定した場合はその根拠も返す）
+    ///
+    /// PTYの読み込み単位はUTF-8の文字境界と一致しないため、バイト列のまま渡す
+    /// （文字の途中で切れた場合は次の呼び出しで続きから解釈される）。
+    fn process_bytes(&mut self, bytes: &[u8]) -> Option<StateDecision>;
+
+    /// 文字列の出力を処理して状態を更新
+    fn process_output(&mut self, output: &str) -> Option<StateDecision> {
+        self.process_bytes(output.as_bytes())
+    }
 
     /// 現在の状態を取得
     fn current_state(&self) -> &SessionStatus;
 
+    /// 現在の状態を判定した根拠
+    fn current_evidence(&self) -> Option<&StateEvidence>;
+
     /// デバッグ用：現在のバッファを表示
     fn debug_buffer(&self);
 
     /// UI box上の⏺文字以降のテキストを取得
     fn get_ui_above_text(&self) -> Option<String>;
 
+    /// 最新のUI box内の行を取得（承認プロンプトの判定用）
+    fn get_ui_box_content(&self) -> Option<Vec<String>>;
+
     /// ターミナルサイズ変更時のscreen buffer再初期化
     fn resize_screen_buffer(&mut self, rows: usize, cols: usize);
+
+    /// 表示中の行を文字属性付きで再現（予約入力の表示を消した後の復元用）
+    fn render_screen_row(&self, row: usize) -> String;
+
+    /// ウィンドウタイトル（OSC 0/2）
+    fn terminal_title(&self) -> Option<String>;
+
+    /// 進捗表示（OSC 9;4）
+    fn terminal_progress(&self) -> Option<TerminalProgress>;
+
+    /// 受信したデスクトップ通知（OSC 9/777）を取り出す
+    fn take_notifications(&mut self) -> Vec<TerminalNotification>;
+
+    /// 未対応のエスケープシーケンスとその出現回数
+    fn unhandled_sequences(&self) -> BTreeMap<String, u64>;
+
+    /// PTY入出力の活動状況から、Busyのまま出力が `stall_after` 以上止まっているかを判定
+    ///
+    /// 実行中は経過時間やスピナーの表示で出力が続くため、止まっていれば固まっているとみなす。
+    fn detect_stall(
+        &self,
+        activity: &ActivitySnapshot,
+        stall_after: Duration,
+    ) -> Option<StateEvidence> {
+        let stalled = *self.current_state() == SessionStatus::Busy
+            && activity
+                .since_output
+                .is_some_and(|since_output| since_output >= stall_after);
+        stalled.then(|| {
+            let minutes = activity.since_output.unwrap_or_default().as_secs() / 60;
+            StateDecision::new(
+                SessionStatus::Busy,
+                format!("activity.output_stalled({minutes}m)"),
+                0.6,
+            )
+            .evidence
+        })
+    }
+}
+
+/// デスクトップ通知から状態を判定
+///
+/// CLIツールは入力が必要になったときに通知を出すため、許可・確認を求める通知は確認待ち、
+/// それ以外（完了・入力待ち）はアイドルとみなす。
+pub fn status_from_notification(notification: &TerminalNotification) -> SessionStatus {
+    decision_from_notification(notification).status
+}
+
+/// デスクトップ通知からの判定（マッチしたキーワードを根拠に含める）
+pub fn decision_from_notification(notification: &TerminalNotification) -> StateDecision {
+    let text = format!(
+        "{} {}",
+        notification.title.as_deref().unwrap_or_default(),
+        notification.body
+    )
+    .to_lowercase();
+    let keyword = [
+        "permission",
+        "approve",
+        "approval",
+        "confirm",
+        "allow",
+        "y/n",
+    ]
+    .into_iter()
+    .find(|keyword| text.contains(keyword));
+    match keyword {
+        Some(keyword) => StateDecision::new(
+            SessionStatus::WaitingInput,
+            format!("notification.confirmation({keyword:?})"),
+            0.95,
+        ),
+        None => StateDecision::new(SessionStatus::Idle, "notification.attention", 0.7),
+    }
 }
 
 /// 状態検出器のファクトリー
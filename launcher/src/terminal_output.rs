@@ -0,0 +1,265 @@
+// terminal_output.rs - 端末への書き込みを一つのタスクにまとめる
+//
+// CLIツールの出力とlauncher自身の表示（予約入力の最下行・ベル）を同じタスクから書き込み、
+// launcherの表示はPTY出力がエスケープシーケンスやUTF-8文字の途中でない位置まで遅らせる
+// （PTYの読み込み単位はシーケンスの境界と一致しないため）。
+
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+use crate::prompt_queue::restore_capture_line;
+use crate::state_detector::StateDetector;
+
+/// 書き込みタスクのキュー長（PTY出力の読み込み単位ごとに1件）
+pub const OUTPUT_QUEUE_CAPACITY: usize = 64;
+
+/// タスク間で共有する状態検出器（最下行の復元に画面内容を使う）
+pub type SharedStateDetector = Arc<Mutex<Box<dyn StateDetector + Send>>>;
+
+/// 端末に書き込む内容
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerminalOutput {
+    /// CLIツールの出力
+    Pty(Vec<u8>),
+    /// launcher自身の表示（カーソル位置を保存・復元する完結したシーケンス）
+    Overlay(String),
+    /// launcherの表示で上書きした行（1始まり）を画面バッファの内容に戻す
+    RestoreRow(u16),
+}
+
+/// エスケープシーケンスの解析状態
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum SequenceState {
+    #[default]
+    Ground,
+    /// UTF-8の後続バイト待ち（残りバイト数）
+    Utf8(u8),
+    Escape,
+    EscapeIntermediate,
+    Csi,
+    /// OSC/DCS/SOS/PM/APC の文字列（BELまたはSTで終了）
+    String,
+    /// 文字列中のESC（`\` ならST）
+    StringEscape,
+}
+
+/// PTY出力がシーケンスの境界にあるかを追跡
+#[derive(Debug, Default)]
+pub struct SequenceTracker {
+    state: SequenceState,
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 書き込んだバイト列を反映
+    pub fn feed(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state = Self::next_state(self.state, byte);
+        }
+    }
+
+    /// 他の表示を挟んでも端末の解釈が変わらない位置か
+    pub fn at_boundary(&self) -> bool {
+        self.state == SequenceState::Ground
+    }
+
+    fn next_state(state: SequenceState, byte: u8) -> SequenceState {
+        use SequenceState::*;
+        const ESC: u8 = 0x1b;
+        const BEL: u8 = 0x07;
+        // CAN/SUBはどの状態でもシーケンスを中断する
+        const CAN: u8 = 0x18;
+        const SUB: u8 = 0x1a;
+
+        match state {
+            Ground => match byte {
+                ESC => Escape,
+                0xc0..=0xdf => Utf8(1),
+                0xe0..=0xef => Utf8(2),
+                0xf0..=0xf7 => Utf8(3),
+                _ => Ground,
+            },
+            Utf8(remaining) => match byte {
+                0x80..=0xbf if remaining > 1 => Utf8(remaining - 1),
+                0x80..=0xbf => Ground,
+                // 不正なUTF-8は端末が置換文字にするため、このバイトから解釈し直す
+                _ => Self::next_state(Ground, byte),
+            },
+            Escape => match byte {
+                b'[' => Csi,
+                b']' | b'P' | b'X' | b'^' | b'_' => String,
+                0x20..=0x2f => EscapeIntermediate,
+                ESC => Escape,
+                _ => Ground,
+            },
+            EscapeIntermediate => match byte {
+                0x20..=0x2f => EscapeIntermediate,
+                ESC => Escape,
+                _ => Ground,
+            },
+            Csi => match byte {
+                0x40..=0x7e | CAN | SUB => Ground,
+                ESC => Escape,
+                _ => Csi,
+            },
+            String => match byte {
+                BEL | CAN | SUB => Ground,
+                ESC => StringEscape,
+                _ => String,
+            },
+            StringEscape => match byte {
+                b'\\' => Ground,
+                _ => Self::next_state(Escape, byte),
+            },
+        }
+    }
+}
+
+/// 端末への書き込みタスク
+pub struct TerminalWriter<W> {
+    writer: W,
+    tracker: SequenceTracker,
+    /// PTY出力がシーケンスの途中のため遅らせているlauncherの表示
+    pending: Vec<TerminalOutput>,
+    /// 行の復元に使う画面内容（ない場合は空行に戻す）
+    state_detector: Option<SharedStateDetector>,
+}
+
+impl<W: AsyncWrite + Unpin> TerminalWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            tracker: SequenceTracker::new(),
+            pending: Vec::new(),
+            state_detector: None,
+        }
+    }
+
+    /// 行の復元に状態検出器の画面内容を使う
+    pub fn with_state_detector(mut self, state_detector: SharedStateDetector) -> Self {
+        self.state_detector = Some(state_detector);
+        self
+    }
+
+    /// 送信側が全て閉じるか書き込みに失敗するまで書き込む
+    pub async fn run(mut self, mut rx: mpsc::Receiver<TerminalOutput>) -> std::io::Result<()> {
+        while let Some(output) = rx.recv().await {
+            self.write(output).await?;
+        }
+        Ok(())
+    }
+
+    /// 一件書き込む（launcherの表示はシーケンスの境界まで遅らせる）
+    pub async fn write(&mut self, output: TerminalOutput) -> std::io::Result<()> {
+        match output {
+            TerminalOutput::Pty(data) => {
+                self.writer.write_all(&data).await?;
+                self.tracker.feed(&data);
+            }
+            overlay => self.pending.push(overlay),
+        }
+        if self.tracker.at_boundary() {
+            for overlay in std::mem::take(&mut self.pending) {
+                let sequence = self.render_overlay(overlay);
+                self.writer.write_all(sequence.as_bytes()).await?;
+            }
+        }
+        self.writer.flush().await
+    }
+
+    fn render_overlay(&self, overlay: TerminalOutput) -> String {
+        match overlay {
+            TerminalOutput::Overlay(sequence) => sequence,
+            TerminalOutput::RestoreRow(row) => {
+                let content = self
+                    .state_detector
+                    .as_ref()
+                    .and_then(|detector| {
+                        let detector = detector.lock().ok()?;
+                        Some(detector.render_screen_row(row.saturating_sub(1) as usize))
+                    })
+                    .unwrap_or_default();
+                restore_capture_line(row, &content)
+            }
+            TerminalOutput::Pty(_) => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn written(outputs: Vec<TerminalOutput>) -> String {
+        let mut buffer = Vec::new();
+        let mut writer = TerminalWriter::new(&mut buffer);
+        for output in outputs {
+            writer.write(output).await.unwrap();
+        }
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_tracker_boundaries() {
+        let mut tracker = SequenceTracker::new();
+        for (chunk, boundary) in [
+            (&b"plain"[..], true),
+            (b"\x1b[38;5", false),
+            (b";1m", true),
+            (b"\x1b]0;title", false),
+            (b"\x07", true),
+            (b"\x1b]8;;uri\x1b", false),
+            (b"\\", true),
+            (b"\xe6\x97", false),
+            (b"\xa5", true),
+            (b"\x1b(", false),
+            (b"B", true),
+        ] {
+            tracker.feed(chunk);
+            assert_eq!(tracker.at_boundary(), boundary, "after {chunk:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overlay_waits_for_sequence_end() {
+        // CSIの途中に届いた表示はシーケンスの終わりまで遅らせる
+        let output = written(vec![
+            TerminalOutput::Pty(b"\x1b[3".to_vec()),
+            TerminalOutput::Overlay("<overlay>".to_string()),
+            TerminalOutput::Pty(b"1mred".to_vec()),
+        ])
+        .await;
+        assert_eq!(output, "\x1b[31mred<overlay>");
+
+        // 境界にあればそのまま書き込む
+        let output = written(vec![
+            TerminalOutput::Pty(b"text".to_vec()),
+            TerminalOutput::Overlay("<overlay>".to_string()),
+        ])
+        .await;
+        assert_eq!(output, "text<overlay>");
+    }
+
+    #[tokio::test]
+    async fn test_restore_row_uses_screen_content() {
+        let detector: SharedStateDetector =
+            Arc::new(Mutex::new(crate::state_detector::create_state_detector(
+                climonitor_shared::CliToolType::Claude,
+                false,
+            )));
+        detector.lock().unwrap().resize_screen_buffer(2, 8);
+        detector.lock().unwrap().process_bytes(b"\r\n> hi");
+
+        let mut buffer = Vec::new();
+        let mut writer = TerminalWriter::new(&mut buffer).with_state_detector(detector);
+        writer.write(TerminalOutput::RestoreRow(2)).await.unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            restore_capture_line(2, "\x1b[0m> hi    \x1b[0m")
+        );
+    }
+}
@@ -0,0 +1,375 @@
+// transition_filter.rs - 状態遷移のデバウンス・ヒステリシス
+//
+// 検出器の判定はそのままだとちらつく（Claudeの"esc to interrupt"が連続処理中に
+// 一瞬消える、Geminiはパターンがなければ即Idleになる）ため、monitorへ送る前に
+// 最短継続時間・確定待ち時間・許可された遷移で絞り込む。
+
+use climonitor_shared::{SessionStatus, StateFilterSettings};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 現在時刻の取得元（テストでは手動で進める時計に差し替える）
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// 実時間の時計
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// 手動で進める時計（テスト用）
+#[cfg(test)]
+pub(crate) struct ManualClock(std::sync::Mutex<Instant>);
+
+#[cfg(test)]
+impl ManualClock {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self(std::sync::Mutex::new(Instant::now())))
+    }
+
+    pub(crate) fn advance(&self, ms: u64) {
+        *self.0.lock().unwrap() += Duration::from_millis(ms);
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// 検出結果に対するフィルタの判断
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterOutcome {
+    /// 報告済みの状態と同じ
+    Unchanged,
+    /// 遷移を報告する
+    Changed {
+        from: Option<SessionStatus>,
+        to: SessionStatus,
+    },
+    /// 許可されていない遷移
+    Blocked(SessionStatus),
+    /// 最短継続時間・確定待ち時間を満たすまで保留
+    Pending(SessionStatus),
+}
+
+/// 検出器と送信処理の間に置く状態遷移フィルタ
+#[derive(Clone)]
+pub struct TransitionFilter {
+    min_dwell: HashMap<SessionStatus, Duration>,
+    confirm: HashMap<SessionStatus, Duration>,
+    /// 遷移元ごとに許可する遷移先（ない場合はすべて許可）
+    allowed: HashMap<SessionStatus, HashSet<SessionStatus>>,
+    clock: Arc<dyn Clock>,
+    /// 報告済みの状態とその開始時刻
+    reported: Option<(SessionStatus, Instant)>,
+    /// 遷移待ちの状態と最初に検出した時刻
+    pending: Option<(SessionStatus, Instant)>,
+}
+
+impl TransitionFilter {
+    /// 設定から作成（状態名は `Config::validate` で検証済み）
+    pub fn from_settings(settings: &StateFilterSettings) -> Self {
+        let durations = |map: &std::collections::BTreeMap<String, u64>| {
+            map.iter()
+                .filter_map(|(name, ms)| Some((name.parse().ok()?, Duration::from_millis(*ms))))
+                .collect()
+        };
+        let allowed = settings
+            .allowed_transitions
+            .iter()
+            .filter_map(|(from, targets)| {
+                let targets = targets.iter().filter_map(|to| to.parse().ok()).collect();
+                Some((from.parse().ok()?, targets))
+            })
+            .collect();
+
+        Self {
+            min_dwell: durations(&settings.min_dwell_ms),
+            confirm: durations(&settings.confirm_ms),
+            allowed,
+            clock: Arc::new(SystemClock),
+            reported: None,
+            pending: None,
+        }
+    }
+
+    /// 時計を差し替え
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 報告済みの状態
+    pub fn reported(&self) -> Option<&SessionStatus> {
+        self.reported.as_ref().map(|(status, _)| status)
+    }
+
+    /// 検出器の現在の状態を入力し、報告すべきかを判断（最初の状態は常に報告）
+    pub fn observe(&mut self, status: &SessionStatus) -> FilterOutcome {
+        let now = self.clock.now();
+        let Some((reported, reported_since)) = &self.reported else {
+            self.reported = Some((status.clone(), now));
+            return FilterOutcome::Changed {
+                from: None,
+                to: status.clone(),
+            };
+        };
+
+        if status == reported {
+            self.pending = None;
+            return FilterOutcome::Unchanged;
+        }
+
+        if !self.is_allowed(reported, status) {
+            self.pending = None;
+            return FilterOutcome::Blocked(status.clone());
+        }
+
+        // 別の状態が検出された時点から確定待ちをやり直す
+        let first_seen = match &self.pending {
+            Some((pending, since)) if pending == status => *since,
+            _ => {
+                self.pending = Some((status.clone(), now));
+                now
+            }
+        };
+
+        let confirmed = now.duration_since(first_seen) >= self.duration(&self.confirm, status);
+        let dwelled =
+            now.duration_since(*reported_since) >= self.duration(&self.min_dwell, reported);
+        if !confirmed || !dwelled {
+            return FilterOutcome::Pending(status.clone());
+        }
+
+        let from = reported.clone();
+        self.reported = Some((status.clone(), now));
+        self.pending = None;
+        FilterOutcome::Changed {
+            from: Some(from),
+            to: status.clone(),
+        }
+    }
+
+    /// 保留中の遷移を確定できる時刻（保留がなければ None）
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let (pending, first_seen) = self.pending.as_ref()?;
+        let (reported, reported_since) = self.reported.as_ref()?;
+        let confirmed_at = *first_seen + self.duration(&self.confirm, pending);
+        let dwelled_at = *reported_since + self.duration(&self.min_dwell, reported);
+        Some(confirmed_at.max(dwelled_at))
+    }
+
+    fn is_allowed(&self, from: &SessionStatus, to: &SessionStatus) -> bool {
+        self.allowed
+            .get(from)
+            .is_none_or(|targets| targets.contains(to))
+    }
+
+    fn duration(&self, map: &HashMap<SessionStatus, Duration>, status: &SessionStatus) -> Duration {
+        map.get(status).copied().unwrap_or_default()
+    }
+}
+
+impl Default for TransitionFilter {
+    fn default() -> Self {
+        Self::from_settings(&StateFilterSettings::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(
+        min_dwell_ms: &[(&str, u64)],
+        confirm_ms: &[(&str, u64)],
+        allowed: &[(&str, &[&str])],
+    ) -> StateFilterSettings {
+        StateFilterSettings {
+            min_dwell_ms: min_dwell_ms
+                .iter()
+                .map(|(name, ms)| (name.to_string(), *ms))
+                .collect(),
+            confirm_ms: confirm_ms
+                .iter()
+                .map(|(name, ms)| (name.to_string(), *ms))
+                .collect(),
+            allowed_transitions: allowed
+                .iter()
+                .map(|(from, targets)| {
+                    (
+                        from.to_string(),
+                        targets.iter().map(|to| to.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn changed(from: Option<SessionStatus>, to: SessionStatus) -> FilterOutcome {
+        FilterOutcome::Changed { from, to }
+    }
+
+    #[test]
+    fn test_first_state_is_reported_immediately() {
+        let clock = ManualClock::new();
+        let mut filter = TransitionFilter::default().with_clock(clock.clone());
+        assert_eq!(filter.reported(), None);
+        assert_eq!(
+            filter.observe(&SessionStatus::Connected),
+            changed(None, SessionStatus::Connected)
+        );
+        assert_eq!(
+            filter.observe(&SessionStatus::Connected),
+            FilterOutcome::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_idle_flicker_is_suppressed() {
+        let clock = ManualClock::new();
+        let mut filter = TransitionFilter::from_settings(&settings(&[], &[("idle", 1500)], &[]))
+            .with_clock(clock.clone());
+        filter.observe(&SessionStatus::Busy);
+
+        // 1秒だけIdleになってBusyに戻る
+        clock.advance(1000);
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            FilterOutcome::Pending(SessionStatus::Idle)
+        );
+        clock.advance(1000);
+        assert_eq!(
+            filter.observe(&SessionStatus::Busy),
+            FilterOutcome::Unchanged
+        );
+
+        // 確定待ちは最初からやり直し
+        clock.advance(1000);
+        filter.observe(&SessionStatus::Idle);
+        clock.advance(1000);
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            FilterOutcome::Pending(SessionStatus::Idle)
+        );
+        clock.advance(500);
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            changed(Some(SessionStatus::Busy), SessionStatus::Idle)
+        );
+        assert_eq!(filter.reported(), Some(&SessionStatus::Idle));
+
+        // 確定待ちのない状態へは即座に遷移
+        assert_eq!(
+            filter.observe(&SessionStatus::Busy),
+            changed(Some(SessionStatus::Idle), SessionStatus::Busy)
+        );
+    }
+
+    #[test]
+    fn test_min_dwell_delays_leaving_state() {
+        let clock = ManualClock::new();
+        let mut filter =
+            TransitionFilter::from_settings(&settings(&[("waiting_input", 3000)], &[], &[]))
+                .with_clock(clock.clone());
+        filter.observe(&SessionStatus::Busy);
+        assert_eq!(
+            filter.observe(&SessionStatus::WaitingInput),
+            changed(Some(SessionStatus::Busy), SessionStatus::WaitingInput)
+        );
+
+        clock.advance(1000);
+        assert_eq!(
+            filter.observe(&SessionStatus::Busy),
+            FilterOutcome::Pending(SessionStatus::Busy)
+        );
+        assert_eq!(
+            filter.next_deadline(),
+            Some(clock.now() + Duration::from_millis(2000))
+        );
+        clock.advance(1999);
+        assert_eq!(
+            filter.observe(&SessionStatus::Busy),
+            FilterOutcome::Pending(SessionStatus::Busy)
+        );
+        clock.advance(1);
+        assert_eq!(
+            filter.observe(&SessionStatus::Busy),
+            changed(Some(SessionStatus::WaitingInput), SessionStatus::Busy)
+        );
+    }
+
+    #[test]
+    fn test_dwell_and_confirmation_are_both_required() {
+        let clock = ManualClock::new();
+        let mut filter =
+            TransitionFilter::from_settings(&settings(&[("busy", 2000)], &[("idle", 1500)], &[]))
+                .with_clock(clock.clone());
+        filter.observe(&SessionStatus::Busy);
+
+        // 継続時間は満たしたが、Idleが続いた時間が足りない
+        clock.advance(2500);
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            FilterOutcome::Pending(SessionStatus::Idle)
+        );
+        // 新しい出力がなくても、この時刻に確認すれば遷移できる
+        assert_eq!(
+            filter.next_deadline(),
+            Some(clock.now() + Duration::from_millis(1500))
+        );
+        clock.advance(1500);
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            changed(Some(SessionStatus::Busy), SessionStatus::Idle)
+        );
+        assert_eq!(filter.next_deadline(), None);
+    }
+
+    #[test]
+    fn test_transition_matrix() {
+        let clock = ManualClock::new();
+        let mut filter = TransitionFilter::default().with_clock(clock.clone());
+        filter.observe(&SessionStatus::Connected);
+
+        // 既定では起動直後のConnected→Idleは報告しない
+        clock.advance(5000);
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            FilterOutcome::Blocked(SessionStatus::Idle)
+        );
+        assert_eq!(filter.reported(), Some(&SessionStatus::Connected));
+        assert_eq!(
+            filter.observe(&SessionStatus::Busy),
+            changed(Some(SessionStatus::Connected), SessionStatus::Busy)
+        );
+
+        // 行列にない遷移元からはすべて許可
+        let mut filter =
+            TransitionFilter::from_settings(&settings(&[], &[], &[("error", &["busy"])]))
+                .with_clock(clock.clone());
+        filter.observe(&SessionStatus::Connected);
+        assert_eq!(
+            filter.observe(&SessionStatus::Error),
+            changed(Some(SessionStatus::Connected), SessionStatus::Error)
+        );
+        assert_eq!(
+            filter.observe(&SessionStatus::Idle),
+            FilterOutcome::Blocked(SessionStatus::Idle)
+        );
+        assert_eq!(
+            filter.observe(&SessionStatus::Busy),
+            changed(Some(SessionStatus::Error), SessionStatus::Busy)
+        );
+    }
+}
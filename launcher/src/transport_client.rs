@@ -2,34 +2,83 @@ use anyhow::Result;
 use chrono::Utc;
 use portable_pty::MasterPty;
 use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Notify};
 use tokio::task::JoinHandle;
 
+use crate::activity::{ActivityTracker, SharedActivityTracker};
+use crate::auto_approve::{AutoApproveDecision, AutoApprover};
+use crate::check_scheduler::CheckScheduler;
+use crate::process_metrics::ProcessSampler;
+use crate::prompt_queue::{
+    render_capture_line, HotkeyInterceptor, PromptQueue, QueueEvent, SharedPromptQueue,
+};
+use crate::screen_buffer::TerminalNotification;
+use crate::terminal_output::{
+    SharedStateDetector, TerminalOutput, TerminalWriter, OUTPUT_QUEUE_CAPACITY,
+};
 use crate::tool_wrapper::ToolWrapper;
+use crate::transition_filter::{FilterOutcome, TransitionFilter};
+use climonitor_shared::logging::tracing::Instrument;
 use climonitor_shared::{
-    generate_connection_id, transport::MessageSender, ConnectionConfig, LauncherToMonitor,
-    SessionStatus,
+    generate_launcher_id, generate_session_id, transport::MessageSender, AutoApproveAction,
+    AutoApproveSettings, ConnectionConfig, MonitorToLauncher, ProcessMetricsSettings,
+    SessionStatus, StateEvidence, StateFilterSettings, StateReportSettings,
 };
 
 /// PTY処理に必要な設定をまとめた構造体
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct PtyConfig {
     pub launcher_id: String,
     pub session_id: String,
     pub verbose: bool,
     pub log_file: Option<PathBuf>,
     pub tool_type: crate::cli_tool::CliToolType,
-    pub connection_config: ConnectionConfig,
-    pub grpc_client: Option<crate::grpc_client::GrpcLauncherClient>,
+    pub message_sender: Option<Arc<dyn MessageSender>>,
+    pub auto_approver: Option<AutoApprover>,
+    pub transition_filter: TransitionFilter,
+    pub state_report: StateReportSettings,
+}
+
+/// PTYへ注入する入力
+enum PtyInjection {
+    /// 予約プロンプト（テキスト送信後にEnter）
+    Prompt(String),
+    /// 自動応答のキー入力（そのまま送信）
+    Keys(String),
 }
 
 /// PTY監視処理用の設定構造体
+#[derive(Clone)]
 struct PtyMonitoringConfig {
-    launcher_id: String,
     session_id: String,
     verbose: bool,
-    tool_type: crate::cli_tool::CliToolType,
-    connection_config: ConnectionConfig,
-    grpc_client: Option<crate::grpc_client::GrpcLauncherClient>,
+    message_sender: Option<Arc<dyn MessageSender>>,
+    prompt_queue: SharedPromptQueue,
+    inject_tx: mpsc::UnboundedSender<PtyInjection>,
+    auto_approver: Option<AutoApprover>,
+    transition_filter: TransitionFilter,
+    state_report: StateReportSettings,
+    /// 状態・コンテキスト・予約の変化を状態チェッカーに知らせる
+    state_events: Arc<Notify>,
+    /// PTY入出力の活動状況
+    activity: SharedActivityTracker,
+}
+
+/// 状態チェッカーを起こすかの判定に使う検出器の状態（状態・コンテキスト・承認待ちのUI box）
+type DetectorSnapshot = (SessionStatus, Option<String>, Option<Vec<String>>);
+
+fn detector_snapshot(
+    detector: &(dyn crate::state_detector::StateDetector + Send),
+    with_approval: bool,
+) -> DetectorSnapshot {
+    let status = detector.current_state().clone();
+    let approval_lines = if with_approval && status == SessionStatus::WaitingInput {
+        detector.get_ui_box_content()
+    } else {
+        None
+    };
+    (status, detector.get_ui_above_text(), approval_lines)
 }
 
 /// ダミーターミナルガード（main関数で実際のガードが作成済みの場合）
@@ -41,14 +90,20 @@ pub struct DummyTerminalGuard {
 /// Transport対応 Launcher クライアント
 pub struct TransportLauncherClient {
     launcher_id: String,
-    message_sender: Option<Box<dyn MessageSender>>,
-    grpc_client: Option<crate::grpc_client::GrpcLauncherClient>,
+    /// 全タスクで共有する送信キュー
+    message_sender: Option<Arc<dyn MessageSender>>,
     connection_config: ConnectionConfig,
     tool_wrapper: ToolWrapper,
     project_name: Option<String>,
     session_id: String,
     verbose: bool,
     log_file: Option<PathBuf>,
+    auto_approver: Option<AutoApprover>,
+    transition_filter: TransitionFilter,
+    state_report: StateReportSettings,
+    process_metrics: ProcessMetricsSettings,
+    /// `--label` で指定した表示名
+    label: Option<String>,
 }
 
 impl TransportLauncherClient {
@@ -59,20 +114,36 @@ impl TransportLauncherClient {
         verbose: bool,
         log_file: Option<PathBuf>,
     ) -> Result<Self> {
-        let launcher_id = generate_connection_id();
-        let session_id = generate_connection_id();
-        let project_name = tool_wrapper.guess_project_name();
+        let launcher_id = generate_launcher_id();
+        let session_id = generate_session_id();
+        climonitor_shared::logging::set_log_launcher_id(&launcher_id);
+        let project = tool_wrapper.guess_project_name();
+        if verbose {
+            if let Some(project) = &project {
+                climonitor_shared::log_info!(
+                    climonitor_shared::LogCategory::System,
+                    "📁 Project: {} (from {})",
+                    project.name,
+                    project.source
+                );
+            }
+        }
+        let project_name = project.map(|project| project.name);
 
         let mut client = Self {
             launcher_id,
             message_sender: None,
-            grpc_client: None,
             connection_config,
             tool_wrapper,
             project_name,
             session_id,
             verbose,
             log_file,
+            auto_approver: None,
+            transition_filter: TransitionFilter::default(),
+            state_report: StateReportSettings::default(),
+            process_metrics: ProcessMetricsSettings::default(),
+            label: None,
         };
 
         // Monitor サーバーに接続を試行
@@ -81,32 +152,34 @@ impl TransportLauncherClient {
         Ok(client)
     }
 
-    /// gRPC client付きで新しいTransportLauncherClientを作成
-    pub async fn new_with_grpc(
-        tool_wrapper: ToolWrapper,
-        grpc_client: crate::grpc_client::GrpcLauncherClient,
-        verbose: bool,
-        log_file: Option<PathBuf>,
-    ) -> Result<Self> {
-        let launcher_id = grpc_client.get_launcher_id().to_string();
-        let session_id = grpc_client.get_session_id().to_string();
-        let project_name = tool_wrapper.guess_project_name();
+    /// 承認プロンプトの自動応答ルールを設定
+    pub fn with_auto_approve(mut self, settings: &AutoApproveSettings) -> Self {
+        self.auto_approver = AutoApprover::from_settings(settings, self.project_name.as_deref());
+        self
+    }
 
-        let client = Self {
-            launcher_id,
-            message_sender: None, // gRPCクライアントは別途管理
-            grpc_client: Some(grpc_client),
-            connection_config: climonitor_shared::ConnectionConfig::default_grpc(), // ダミー
-            tool_wrapper,
-            project_name,
-            session_id,
-            verbose,
-            log_file,
-        };
+    /// monitorへ送る状態遷移のフィルタを設定
+    pub fn with_state_filter(mut self, settings: &StateFilterSettings) -> Self {
+        self.transition_filter = TransitionFilter::from_settings(settings);
+        self
+    }
 
-        // Note: gRPCクライアントは既に接続済みのため、接続試行は不要
+    /// 状態通知の頻度を設定
+    pub fn with_state_report(mut self, settings: &StateReportSettings) -> Self {
+        self.state_report = settings.clone();
+        self
+    }
 
-        Ok(client)
+    /// リソース使用量の計測間隔を設定
+    pub fn with_process_metrics(mut self, settings: &ProcessMetricsSettings) -> Self {
+        self.process_metrics = settings.clone();
+        self
+    }
+
+    /// monitorに表示する名前を設定
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.label = label;
+        self
     }
 
     /// Monitor サーバーへの接続を試行
@@ -119,143 +192,153 @@ impl TransportLauncherClient {
             );
         }
 
-        match crate::transports::create_message_sender(&self.connection_config).await {
-            Ok(sender) => {
-                self.message_sender = Some(sender);
-                if self.verbose {
-                    climonitor_shared::log_info!(
-                        climonitor_shared::LogCategory::Transport,
-                        "🔗 Connected to monitor server"
-                    );
-                }
-            }
-            Err(e) => {
-                if self.verbose {
-                    climonitor_shared::log_warn!(
-                        climonitor_shared::LogCategory::Transport,
-                        "⚠️  Failed to connect to monitor server: {e}. Running without monitoring."
-                    );
-                }
-            }
+        // 接続は送信時に確立され、切断時は自動的に再接続される
+        self.message_sender = Some(crate::transports::create_message_sender(
+            &self.connection_config,
+            self.launcher_id.clone(),
+            self.verbose,
+        ));
+        if self.verbose {
+            climonitor_shared::log_info!(
+                climonitor_shared::LogCategory::Transport,
+                "🔗 Monitor message sender ready"
+            );
         }
 
         Ok(())
     }
 
-    /// Monitor サーバーに接続されているかチェック
-    pub fn is_connected(&self) -> bool {
-        self.message_sender.is_some() || self.grpc_client.is_some()
-    }
-
-    /// 接続メッセージを送信
-    async fn send_connect_message(&mut self) -> Result<()> {
-        let _connect_msg = LauncherToMonitor::Connect {
-            launcher_id: self.launcher_id.clone(),
-            project: self.project_name.clone(),
-            tool_type: self.tool_wrapper.get_tool_type(),
-            claude_args: self.tool_wrapper.get_args().to_vec(),
-            working_dir: self
-                .tool_wrapper
-                .get_working_dir()
-                .cloned()
-                .unwrap_or_else(|| std::env::current_dir().unwrap_or_default()),
-            timestamp: Utc::now(),
-        };
-
-        if let Some(ref grpc_client) = self.grpc_client {
+    /// 接続メッセージを送信（`pid` はCLIツールの子プロセス）
+    async fn send_connect_message(&mut self, pid: Option<u32>) -> Result<()> {
+        let Some(ref sender) = self.message_sender else {
             if self.verbose {
-                climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Grpc,
-                    "📤 Sending gRPC connect message: launcher_id={}, project={:?}",
-                    self.launcher_id,
-                    self.project_name
-                );
-            }
-            grpc_client
-                .send_connect(
-                    self.project_name.clone(),
-                    self.tool_wrapper.get_tool_type(),
-                    self.tool_wrapper.get_args().to_vec(),
-                    self.tool_wrapper
-                        .get_working_dir()
-                        .cloned()
-                        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default()),
-                )
-                .await?;
-            if self.verbose {
-                climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Grpc,
-                    "✅ gRPC connect message sent successfully"
-                );
-            }
-        } else if let Some(ref sender) = self.message_sender {
-            if self.verbose {
-                climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Transport,
-                    "📤 Sending connect message: launcher_id={}, project={:?}",
-                    self.launcher_id,
-                    self.project_name
-                );
-            }
-            sender
-                .send_connect(
-                    self.project_name.clone(),
-                    self.tool_wrapper.get_tool_type(),
-                    self.tool_wrapper.get_args().to_vec(),
-                    self.tool_wrapper
-                        .get_working_dir()
-                        .cloned()
-                        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default()),
-                )
-                .await?;
-            if self.verbose {
-                climonitor_shared::log_debug!(
+                climonitor_shared::log_warn!(
                     climonitor_shared::LogCategory::Transport,
-                    "✅ Connect message sent successfully"
+                    "⚠️  No connection available for sending connect message"
                 );
             }
-        } else if self.verbose {
-            climonitor_shared::log_warn!(
+            return Ok(());
+        };
+
+        if self.verbose {
+            climonitor_shared::log_debug!(
                 climonitor_shared::LogCategory::Transport,
-                "⚠️  No connection available for sending connect message"
+                "📤 Sending connect message: launcher_id={}, project={:?}",
+                self.launcher_id,
+                self.project_name
             );
         }
-        Ok(())
+        let working_dir = self
+            .tool_wrapper
+            .get_working_dir()
+            .cloned()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+        let metadata = crate::launcher_metadata::collect(&working_dir, self.label.clone(), pid);
+        sender
+            .send_connect(
+                self.project_name.clone(),
+                self.tool_wrapper.get_tool_type(),
+                self.tool_wrapper.get_args().to_vec(),
+                working_dir,
+                metadata,
+            )
+            .await
     }
 
     /// 切断メッセージを送信
-    async fn send_disconnect_message(&mut self) -> Result<()> {
-        if let Some(ref grpc_client) = self.grpc_client {
-            grpc_client.send_disconnect().await?;
-            if self.verbose {
-                climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Grpc,
-                    "📤 Sent gRPC disconnect message to monitor"
-                );
-            }
-        } else if let Some(ref sender) = self.message_sender {
-            sender.send_disconnect(self.session_id.clone()).await?;
-            if self.verbose {
-                climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Transport,
-                    "📤 Sent disconnect message to monitor"
-                );
+    ///
+    /// monitorが起動していなくてもCLIツールの実行結果には影響させないため、
+    /// 送信できなかったメッセージは警告のみとする。
+    async fn send_disconnect_message(&mut self) {
+        let Some(ref sender) = self.message_sender else {
+            return;
+        };
+        // 送信待ちのメッセージとDisconnectが届くまで待機
+        let result = match sender.send_disconnect().await {
+            Ok(()) => sender.flush().await,
+            Err(e) => Err(e),
+        };
+        if !self.verbose {
+            return;
+        }
+        match result {
+            Ok(()) => climonitor_shared::log_debug!(
+                climonitor_shared::LogCategory::Transport,
+                "📤 Sent disconnect message to monitor"
+            ),
+            Err(e) => climonitor_shared::log_warn!(
+                climonitor_shared::LogCategory::Transport,
+                "⚠️  Failed to deliver messages to monitor: {e}"
+            ),
+        }
+    }
+
+    /// CLIツールのプロセスツリーのリソース使用量を定期送信するタスクを起動
+    fn spawn_process_metrics_sampler(&self, pid: Option<u32>) -> Option<JoinHandle<()>> {
+        let interval_secs = self.process_metrics.interval_secs;
+        let (Some(pid), Some(sender)) = (pid, self.message_sender.clone()) else {
+            return None;
+        };
+        if interval_secs == 0 {
+            return None;
+        }
+        let session_id = self.session_id.clone();
+
+        Some(tokio::spawn(async move {
+            let mut sampler = ProcessSampler::new(pid);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+            let mut baseline = true;
+            loop {
+                interval.tick().await;
+                // /proc の走査はブロッキングI/O
+                let result = tokio::task::spawn_blocking(move || {
+                    let metrics = sampler.sample();
+                    (sampler, metrics)
+                })
+                .await;
+                // 計測できない環境（Linux以外）やCLIツールの終了後は停止
+                let Ok((returned, Some(metrics))) = result else {
+                    break;
+                };
+                sampler = returned;
+
+                // 初回はCPU使用率の基準を取るだけ
+                if std::mem::take(&mut baseline) {
+                    continue;
+                }
+                if sender
+                    .send_process_metrics(session_id.clone(), metrics)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
             }
+        }))
+    }
+
+    /// CLIツールの終了コード・実行時間を送信
+    async fn send_process_exit_message(
+        &self,
+        status: &portable_pty::ExitStatus,
+        runtime: std::time::Duration,
+    ) -> Result<()> {
+        if let Some(ref sender) = self.message_sender {
+            sender
+                .send_process_exit(
+                    self.session_id.clone(),
+                    status.exit_code(),
+                    status.signal().map(|signal| signal.to_string()),
+                    runtime.as_secs(),
+                )
+                .await?;
         }
         Ok(())
     }
 
     /// 状態更新メッセージを送信
     pub async fn send_state_update(&self, status: SessionStatus, message: String) -> Result<()> {
-        if let Some(ref grpc_client) = self.grpc_client {
-            if self.verbose {
-                climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Grpc,
-                    "📤 Sending gRPC state update: {status:?}"
-                );
-            }
-            grpc_client.send_state_update(status, Some(message)).await?;
-        } else if let Some(ref sender) = self.message_sender {
+        if let Some(ref sender) = self.message_sender {
             if self.verbose {
                 climonitor_shared::log_debug!(
                     climonitor_shared::LogCategory::Transport,
@@ -263,12 +346,10 @@ impl TransportLauncherClient {
                 );
             }
             sender
-                .send_status_update(
-                    self.session_id.clone(),
-                    status,
-                    Utc::now(),
-                    self.project_name.clone(),
-                )
+                .send_status_update(self.session_id.clone(), status, Utc::now(), None)
+                .await?;
+            sender
+                .send_context_update(self.session_id.clone(), message, Utc::now())
                 .await?;
         }
         Ok(())
@@ -276,15 +357,7 @@ impl TransportLauncherClient {
 
     /// コンテキスト更新メッセージを送信
     pub async fn send_context_update(&self, ui_above_text: String) -> Result<()> {
-        if let Some(ref grpc_client) = self.grpc_client {
-            if self.verbose {
-                climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Grpc,
-                    "📤 Sending gRPC context update"
-                );
-            }
-            grpc_client.send_context_update(Some(ui_above_text)).await?;
-        } else if let Some(ref sender) = self.message_sender {
+        if let Some(ref sender) = self.message_sender {
             if self.verbose {
                 climonitor_shared::log_debug!(
                     climonitor_shared::LogCategory::Transport,
@@ -308,19 +381,17 @@ impl TransportLauncherClient {
             );
         }
 
-        // Monitor に接続できていない場合は単純にClaude実行
-        if !self.is_connected() {
-            if self.verbose {
-                climonitor_shared::log_info!(
-                    climonitor_shared::LogCategory::System,
-                    "🔄 Running CLI tool without monitoring (monitor not connected)"
-                );
-            }
-            return self.tool_wrapper.run_directly().await;
-        }
+        // ターミナルガードはmain関数で作成済み（ここでは作らない）
+        let terminal_guard = DummyTerminalGuard {
+            verbose: self.verbose,
+        };
 
-        // 接続メッセージを送信
-        if let Err(e) = self.send_connect_message().await {
+        // CLI ツール プロセス起動（全プラットフォームでPTYを使用）
+        let (mut process, pty_master) = self.tool_wrapper.spawn_with_pty()?;
+        let started_at = std::time::Instant::now();
+
+        // 接続メッセージを送信（子プロセスのPIDを含めるため起動後、状態監視の開始前に送る）
+        if let Err(e) = self.send_connect_message(process.process_id()).await {
             if self.verbose {
                 climonitor_shared::log_warn!(
                     climonitor_shared::LogCategory::Transport,
@@ -334,19 +405,10 @@ impl TransportLauncherClient {
             );
         }
 
-        // ターミナルガードはmain関数で作成済み（ここでは作らない）
-        let terminal_guard = DummyTerminalGuard {
-            verbose: self.verbose,
-        };
-
-        // CLI ツール プロセス起動（全プラットフォームでPTYを使用）
-        let (mut process, io_handle) = {
-            let (process, pty_master) = self.tool_wrapper.spawn_with_pty()?;
-            let pty_handle = self
-                .start_pty_bidirectional_io(pty_master, terminal_guard)
-                .await?;
-            (process, pty_handle)
-        };
+        let io_handle = self
+            .start_pty_bidirectional_io(pty_master, terminal_guard)
+            .await?;
+        let metrics_handle = self.spawn_process_metrics_sampler(process.process_id());
 
         if self.verbose {
             climonitor_shared::log_info!(
@@ -361,8 +423,11 @@ impl TransportLauncherClient {
         // シグナルハンドリングとリサイズ処理
         let exit_status = self.wait_with_signals(&mut wait_task).await;
 
-        // I/Oタスクを終了
+        // I/Oタスクとリソース計測を終了
         io_handle.abort();
+        if let Some(handle) = metrics_handle {
+            handle.abort();
+        }
 
         // 少し待機してI/Oが完了するのを待つ
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -375,6 +440,18 @@ impl TransportLauncherClient {
                         "🏁 CLI tool process exited with status: {status:?}"
                     );
                 }
+                // 切断前に終了コードを報告（失敗してもDisconnectは送る）
+                if let Err(e) = self
+                    .send_process_exit_message(&status, started_at.elapsed())
+                    .await
+                {
+                    if self.verbose {
+                        climonitor_shared::log_warn!(
+                            climonitor_shared::LogCategory::Transport,
+                            "⚠️  Failed to send exit status: {e}"
+                        );
+                    }
+                }
             }
             Err(e) => {
                 if self.verbose {
@@ -384,14 +461,7 @@ impl TransportLauncherClient {
                     );
                 }
                 // エラー時でも切断メッセージを送信
-                if let Err(disconnect_err) = self.send_disconnect_message().await {
-                    if self.verbose {
-                        climonitor_shared::log_warn!(
-                            climonitor_shared::LogCategory::Transport,
-                            "⚠️  Failed to send disconnect message: {disconnect_err}"
-                        );
-                    }
-                }
+                self.send_disconnect_message().await;
                 // 接続を明示的に閉じる
                 if let Some(sender) = self.message_sender.take() {
                     drop(sender);
@@ -407,7 +477,7 @@ impl TransportLauncherClient {
         }
 
         // 切断メッセージ送信
-        self.send_disconnect_message().await?;
+        self.send_disconnect_message().await;
 
         // 接続を明示的に閉じる
         if let Some(sender) = self.message_sender.take() {
@@ -435,24 +505,32 @@ impl TransportLauncherClient {
         let verbose = self.verbose;
         let log_file = self.log_file.clone();
         let tool_type = self.tool_wrapper.get_tool_type();
-        let connection_config = self.connection_config.clone();
-        let grpc_client = self.grpc_client.clone();
+        let message_sender = self.message_sender.clone();
+        let auto_approver = self.auto_approver.clone();
+        let transition_filter = self.transition_filter.clone();
+        let state_report = self.state_report.clone();
 
         // PTYのリサイズ機能を有効にするため、Arc<Mutex<>>でラップ
         let pty_master_shared = std::sync::Arc::new(std::sync::Mutex::new(pty_master));
 
-        let handle = tokio::spawn(async move {
-            let config = PtyConfig {
-                launcher_id,
-                session_id,
-                verbose,
-                log_file,
-                tool_type,
-                connection_config,
-                grpc_client,
-            };
-            Self::handle_pty_bidirectional_io(pty_master_shared, config, _terminal_guard).await;
-        });
+        let span = climonitor_shared::logging::launcher_span(&launcher_id, &session_id);
+        let handle = tokio::spawn(
+            async move {
+                let config = PtyConfig {
+                    launcher_id,
+                    session_id,
+                    verbose,
+                    log_file,
+                    tool_type,
+                    message_sender,
+                    auto_approver,
+                    transition_filter,
+                    state_report,
+                };
+                Self::handle_pty_bidirectional_io(pty_master_shared, config, _terminal_guard).await;
+            }
+            .instrument(span),
+        );
 
         Ok(handle)
     }
@@ -532,29 +610,108 @@ impl TransportLauncherClient {
         // 設定値を事前にコピー（move クロージャで使用するため）
         let config_clone = config.clone();
 
+        // プロンプト予約キューとPTYへの注入チャネル
+        let prompt_queue: SharedPromptQueue =
+            std::sync::Arc::new(std::sync::Mutex::new(PromptQueue::new()));
+        let (inject_tx, inject_rx) = mpsc::unbounded_channel();
+        let state_events = Arc::new(Notify::new());
+        let activity = Arc::new(std::sync::Mutex::new(ActivityTracker::new()));
+        let state_detector: SharedStateDetector = Arc::new(std::sync::Mutex::new(
+            crate::state_detector::create_state_detector(
+                config_clone.tool_type.clone(),
+                config_clone.verbose,
+            ),
+        ));
+
+        // 端末への書き込みはPTY出力と予約入力の表示で一つのタスクにまとめる
+        let (output_tx, output_rx) = mpsc::channel(OUTPUT_QUEUE_CAPACITY);
+        let terminal_writer = {
+            let writer = TerminalWriter::new(tokio::io::stdout())
+                .with_state_detector(state_detector.clone());
+            let verbose = config.verbose;
+            tokio::spawn(
+                async move {
+                    if let Err(e) = writer.run(output_rx).await {
+                        if verbose {
+                            climonitor_shared::log_warn!(
+                                climonitor_shared::LogCategory::System,
+                                "⚠️  Failed to write to stdout: {e}"
+                            );
+                        }
+                    }
+                }
+                .in_current_span(),
+            )
+        };
+
+        // Monitorからのプロンプト予約を受信（受信可能なtransportのみ）
+        let monitor_listener = config_clone
+            .message_sender
+            .as_ref()
+            .and_then(|sender| sender.subscribe_monitor_messages())
+            .map(|receiver| {
+                let prompt_queue = prompt_queue.clone();
+                let state_events = state_events.clone();
+                let verbose = config.verbose;
+                tokio::spawn(
+                    async move {
+                        Self::handle_monitor_messages(
+                            receiver,
+                            prompt_queue,
+                            state_events,
+                            verbose,
+                        )
+                        .await;
+                    }
+                    .in_current_span(),
+                )
+            });
+
         // 双方向I/Oタスクを起動
         let pty_master_for_resize = pty_master.clone();
         let monitoring_config = PtyMonitoringConfig {
-            launcher_id: config_clone.launcher_id.clone(),
             session_id: config_clone.session_id.clone(),
             verbose: config_clone.verbose,
-            tool_type: config_clone.tool_type,
-            connection_config: config_clone.connection_config,
-            grpc_client: config_clone.grpc_client.clone(),
+            message_sender: config_clone.message_sender.clone(),
+            prompt_queue: prompt_queue.clone(),
+            inject_tx,
+            auto_approver: config_clone.auto_approver.clone(),
+            transition_filter: config_clone.transition_filter.clone(),
+            state_report: config_clone.state_report.clone(),
+            state_events: state_events.clone(),
+            activity: activity.clone(),
         };
-        let mut pty_to_stdout = tokio::spawn(async move {
-            Self::handle_pty_to_stdout_with_monitoring(
-                pty_reader,
-                log_writer,
-                monitoring_config,
-                pty_master_for_resize,
-            )
-            .await;
-        });
-
-        let mut stdin_to_pty = tokio::spawn(async move {
-            Self::handle_stdin_to_pty_simple(pty_writer, config.verbose).await;
-        });
+        let pty_output_tx = output_tx.clone();
+        let mut pty_to_stdout = tokio::spawn(
+            async move {
+                Self::handle_pty_to_stdout_with_monitoring(
+                    pty_reader,
+                    log_writer,
+                    monitoring_config,
+                    pty_master_for_resize,
+                    state_detector,
+                    pty_output_tx,
+                )
+                .await;
+            }
+            .in_current_span(),
+        );
+
+        let mut stdin_to_pty = tokio::spawn(
+            async move {
+                Self::handle_stdin_to_pty_simple(
+                    pty_writer,
+                    config.verbose,
+                    prompt_queue,
+                    state_events,
+                    activity,
+                    inject_rx,
+                    output_tx,
+                )
+                .await;
+            }
+            .in_current_span(),
+        );
 
         // タスクの完了を待つ
         tokio::select! {
@@ -571,6 +728,56 @@ impl TransportLauncherClient {
                 pty_to_stdout.abort();
             }
         }
+
+        if let Some(listener) = monitor_listener {
+            listener.abort();
+        }
+        // 両タスクの終了で送信側が閉じるため、残りの出力を書き終えるまで待つ
+        let _ = terminal_writer.await;
+    }
+
+    /// Monitorからのメッセージでプロンプト予約を更新
+    async fn handle_monitor_messages(
+        mut receiver: tokio::sync::broadcast::Receiver<MonitorToLauncher>,
+        prompt_queue: SharedPromptQueue,
+        state_events: Arc<Notify>,
+        verbose: bool,
+    ) {
+        loop {
+            let message = match receiver.recv().await {
+                Ok(message) => message,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            };
+
+            if let Ok(mut queue) = prompt_queue.lock() {
+                match message {
+                    MonitorToLauncher::QueuePrompt { text, .. } => {
+                        if verbose {
+                            climonitor_shared::log_debug!(
+                                climonitor_shared::LogCategory::Session,
+                                "⏳ [PROMPT_QUEUE] Prompt queued by monitor"
+                            );
+                        }
+                        queue.stage(text);
+                    }
+                    MonitorToLauncher::ClearQueuedPrompt { .. } => {
+                        if verbose {
+                            climonitor_shared::log_debug!(
+                                climonitor_shared::LogCategory::Session,
+                                "⏳ [PROMPT_QUEUE] Queued prompt cleared by monitor"
+                            );
+                        }
+                        queue.clear();
+                    }
+                    // 接続応答は送信キュー側で適用済み
+                    MonitorToLauncher::ConnectResponse { .. }
+                    | MonitorToLauncher::ConnectRejected { .. } => continue,
+                }
+            }
+            // 予約状態の変化をmonitorへ送り返す
+            state_events.notify_one();
+        }
     }
 
     /// プロセス終了とシグナルを待機
@@ -642,49 +849,30 @@ impl TransportLauncherClient {
         mut log_writer: Option<tokio::fs::File>,
         config: PtyMonitoringConfig,
         pty_master: std::sync::Arc<std::sync::Mutex<Box<dyn MasterPty + Send>>>,
+        state_detector: SharedStateDetector,
+        output_tx: mpsc::Sender<TerminalOutput>,
     ) {
-        use crate::state_detector::create_state_detector;
-        use climonitor_shared::SessionStatus;
-
-        let state_detector: std::sync::Arc<
-            std::sync::Mutex<Box<dyn crate::state_detector::StateDetector + Send>>,
-        > = std::sync::Arc::new(std::sync::Mutex::new(create_state_detector(
-            config.tool_type,
-            config.verbose,
-        )));
-        let last_notified_status = std::sync::Arc::new(std::sync::Mutex::new(SessionStatus::Idle));
-
         // ターミナルサイズ監視用
         let mut last_terminal_size = crate::cli_tool::get_pty_size();
         use std::io::Read;
         use tokio::io::AsyncWriteExt;
 
         let mut buffer = [0u8; 8192];
-        let mut stdout = tokio::io::stdout();
 
-        // 定期的な状態チェックタスクを起動
+        // 状態チェックタスクを起動（出力による変化はstate_eventsで通知）
         let state_checker_task = {
             let state_detector_clone = state_detector.clone();
-            let last_notified_status_clone = last_notified_status.clone();
-            let launcher_id_clone = config.launcher_id.clone();
-            let session_id_clone = config.session_id.clone();
-            let config_clone = config.connection_config.clone();
-            let grpc_client_clone = config.grpc_client.clone();
-            let verbose = config.verbose;
+            let config_clone = config.clone();
 
-            tokio::spawn(async move {
-                Self::periodic_state_checker(
-                    state_detector_clone,
-                    last_notified_status_clone,
-                    launcher_id_clone,
-                    session_id_clone,
-                    config_clone,
-                    grpc_client_clone,
-                    verbose,
-                )
-                .await;
-            })
+            tokio::spawn(
+                async move {
+                    Self::state_checker(state_detector_clone, config_clone).await;
+                }
+                .in_current_span(),
+            )
         };
+        let with_approval = config.auto_approver.is_some();
+        let mut last_snapshot = None;
 
         loop {
             match pty_reader.read(&mut buffer) {
@@ -694,22 +882,32 @@ impl TransportLauncherClient {
                             climonitor_shared::LogCategory::System,
                             "📡 PTY reader EOF"
                         );
+                        // セッション中に未対応だったエスケープシーケンスの集計
+                        if let Ok(detector) = state_detector.lock() {
+                            for (sequence, count) in detector.unhandled_sequences() {
+                                climonitor_shared::log_debug!(
+                                    climonitor_shared::LogCategory::Screen,
+                                    "❓ [UNHANDLED_SUMMARY] {sequence}: {count}"
+                                );
+                            }
+                        }
                     }
                     state_checker_task.abort();
                     break;
                 }
                 Ok(n) => {
                     let data = &buffer[..n];
-                    let output_str = String::from_utf8_lossy(data);
+                    if let Ok(mut activity) = config.activity.lock() {
+                        activity.record_output(n);
+                    }
 
-                    // 標準出力に書き込み
-                    if let Err(e) = stdout.write_all(data).await {
-                        if config.verbose {
-                            climonitor_shared::log_warn!(
-                                climonitor_shared::LogCategory::System,
-                                "⚠️  Failed to write to stdout: {e}"
-                            );
-                        }
+                    // 標準出力に書き込み（書き込みタスクが終了していれば端末に書けなくなっている）
+                    if output_tx
+                        .send(TerminalOutput::Pty(data.to_vec()))
+                        .await
+                        .is_err()
+                    {
+                        state_checker_task.abort();
                         break;
                     }
 
@@ -768,13 +966,38 @@ impl TransportLauncherClient {
                         last_terminal_size = current_terminal_size;
                     }
 
-                    // 状態検出器に出力を送信（内部状態更新のみ）
-                    if let Ok(mut detector) = state_detector.lock() {
-                        detector.process_output(&output_str);
+                    // 状態検出器に出力を送信し、状態・コンテキストが変われば状態チェッカーを起こす
+                    let notifications = if let Ok(mut detector) = state_detector.lock() {
+                        detector.process_bytes(data);
+                        let snapshot = detector_snapshot(detector.as_ref(), with_approval);
+                        if last_snapshot.as_ref() != Some(&snapshot) {
+                            last_snapshot = Some(snapshot);
+                            config.state_events.notify_one();
+                        }
+                        detector.take_notifications()
+                    } else {
+                        Vec::new()
+                    };
+
+                    // CLIツールのデスクトップ通知をmonitorへ転送
+                    for notification in notifications {
+                        if let Err(e) = Self::send_terminal_notification(
+                            notification,
+                            config.message_sender.as_deref(),
+                            &config.session_id,
+                        )
+                        .await
+                        {
+                            if config.verbose {
+                                climonitor_shared::log_warn!(
+                                    climonitor_shared::LogCategory::Transport,
+                                    "⚠️  Failed to send terminal notification: {e}"
+                                );
+                            }
+                        }
                     }
 
                     // 出力をフラッシュ
-                    let _ = stdout.flush().await;
                     if let Some(ref mut log_file) = log_writer {
                         let _ = log_file.flush().await;
                     }
@@ -793,10 +1016,15 @@ impl TransportLauncherClient {
         }
     }
 
-    /// Stdin入力をPTYに転送
+    /// Stdin入力をPTYに転送（予約ホットキーの検出と予約プロンプトの注入を含む）
     async fn handle_stdin_to_pty_simple(
         mut pty_writer: Box<dyn std::io::Write + Send>,
         verbose: bool,
+        prompt_queue: SharedPromptQueue,
+        state_events: Arc<Notify>,
+        activity: SharedActivityTracker,
+        mut inject_rx: mpsc::UnboundedReceiver<PtyInjection>,
+        output_tx: mpsc::Sender<TerminalOutput>,
     ) {
         use std::io::Write;
         use tokio::io::AsyncReadExt;
@@ -810,9 +1038,38 @@ impl TransportLauncherClient {
 
         let mut stdin = tokio::io::stdin();
         let mut buffer = [0u8; 1024];
+        let mut interceptor = HotkeyInterceptor::new();
 
         loop {
-            match stdin.read(&mut buffer).await {
+            let read_result = tokio::select! {
+                result = stdin.read(&mut buffer) => result,
+                Some(injection) = inject_rx.recv() => {
+                    let (text, submit) = match injection {
+                        PtyInjection::Prompt(prompt) => (prompt, true),
+                        PtyInjection::Keys(keys) => (keys, false),
+                    };
+                    if verbose {
+                        climonitor_shared::log_debug!(
+                            climonitor_shared::LogCategory::Session,
+                            "⌨️  [PTY_INJECT] Injecting {} bytes into PTY",
+                            text.len()
+                        );
+                    }
+                    if pty_writer.write_all(text.as_bytes()).is_err() || pty_writer.flush().is_err() {
+                        break;
+                    }
+                    if submit {
+                        // テキストとEnterを分けて送信（ペースト扱いでの改行挿入を避ける）
+                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                        if pty_writer.write_all(b"\r").is_err() || pty_writer.flush().is_err() {
+                            break;
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            match read_result {
                 Ok(0) => {
                     if verbose {
                         climonitor_shared::log_debug!(
@@ -823,7 +1080,26 @@ impl TransportLauncherClient {
                     break;
                 }
                 Ok(n) => {
-                    let data = &buffer[..n];
+                    if let Ok(mut activity) = activity.lock() {
+                        activity.record_input();
+                    }
+                    let was_capturing = interceptor.is_capturing();
+                    let (forward, events) = interceptor.process(&buffer[..n]);
+                    for event in events {
+                        // 予約確定をベルで知らせる（画面描画には影響しない）
+                        if matches!(event, QueueEvent::Staged(_)) {
+                            let _ = output_tx
+                                .send(TerminalOutput::Overlay("\x07".to_string()))
+                                .await;
+                        }
+                        Self::apply_queue_event(&prompt_queue, event, verbose);
+                        state_events.notify_one();
+                    }
+                    Self::render_capture(&interceptor, was_capturing, &output_tx).await;
+                    if forward.is_empty() {
+                        continue;
+                    }
+                    let data = forward.as_slice();
 
                     if let Err(e) = pty_writer.write_all(data) {
                         if verbose {
@@ -865,57 +1141,167 @@ impl TransportLauncherClient {
         }
     }
 
-    /// 定期的な状態チェッカー（1秒ごと）
-    async fn periodic_state_checker(
-        state_detector: std::sync::Arc<
-            std::sync::Mutex<Box<dyn crate::state_detector::StateDetector + Send>>,
-        >,
-        last_notified_status: std::sync::Arc<std::sync::Mutex<SessionStatus>>,
-        launcher_id: String,
-        session_id: String,
-        connection_config: ConnectionConfig,
-        grpc_client: Option<crate::grpc_client::GrpcLauncherClient>,
-        verbose: bool,
+    /// 予約入力中のテキストを端末の最下行に表示（終了時はツールの表示に戻す）
+    async fn render_capture(
+        interceptor: &HotkeyInterceptor,
+        was_capturing: bool,
+        output_tx: &mpsc::Sender<TerminalOutput>,
     ) {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        let size = crate::cli_tool::get_pty_size();
+        let output = match interceptor.captured_text() {
+            Some(text) => TerminalOutput::Overlay(render_capture_line(&text, size.rows, size.cols)),
+            None if was_capturing => TerminalOutput::RestoreRow(size.rows),
+            None => return,
+        };
+        let _ = output_tx.send(output).await;
+    }
+
+    /// ホットキーイベントをプロンプト予約に反映
+    fn apply_queue_event(prompt_queue: &SharedPromptQueue, event: QueueEvent, verbose: bool) {
+        if verbose {
+            climonitor_shared::log_debug!(
+                climonitor_shared::LogCategory::Session,
+                "⏳ [PROMPT_QUEUE] Hotkey event: {event:?}"
+            );
+        }
+
+        if let QueueEvent::Staged(text) = event {
+            if let Ok(mut queue) = prompt_queue.lock() {
+                queue.stage(text);
+            }
+        }
+    }
+
+    /// 状態チェッカー（変化の通知・保留中の遷移の確定時刻・keepaliveで起動）
+    async fn state_checker(state_detector: SharedStateDetector, config: PtyMonitoringConfig) {
+        let PtyMonitoringConfig {
+            ref session_id,
+            verbose,
+            ref message_sender,
+            ref prompt_queue,
+            ref inject_tx,
+            ref auto_approver,
+            ref transition_filter,
+            ref state_report,
+            ref state_events,
+            ref activity,
+            ..
+        } = config;
+        let mut transition_filter = transition_filter.clone();
+        let min_interval = std::time::Duration::from_millis(state_report.min_interval_ms);
+        let keepalive = std::time::Duration::from_secs(state_report.keepalive_secs);
+        let mut scheduler = CheckScheduler::new(min_interval, keepalive);
         let mut last_ui_context: Option<String> = None;
+        let mut last_queued_prompt: Option<String> = None;
+        let mut last_approval_prompt: Option<String> = None;
+        let stall_after = (state_report.stall_after_secs > 0)
+            .then(|| std::time::Duration::from_secs(state_report.stall_after_secs));
+        // 最後に送った活動状況（送信時刻・出力中か・停止中か）
+        let mut last_activity_report: Option<(tokio::time::Instant, bool, bool)> = None;
 
         loop {
-            interval.tick().await;
+            let deadline = transition_filter
+                .next_deadline()
+                .map(tokio::time::Instant::from_std);
+            scheduler.wait(state_events, deadline).await;
 
-            let (current_status, current_ui_context) = {
+            let Some(activity_snapshot) = activity.lock().ok().map(|mut a| a.snapshot()) else {
+                continue;
+            };
+            let (current_status, current_evidence, current_ui_context, approval_lines, stall) = {
                 if let Ok(detector) = state_detector.lock() {
+                    let current_status = detector.current_state().clone();
+                    // 承認待ちの場合のみUI boxを取得（自動応答の判定用）
+                    let approval_lines = if auto_approver.is_some()
+                        && current_status == SessionStatus::WaitingInput
+                    {
+                        detector.get_ui_box_content()
+                    } else {
+                        None
+                    };
+                    let stall = stall_after
+                        .and_then(|after| detector.detect_stall(&activity_snapshot, after));
                     (
-                        detector.current_state().clone(),
+                        current_status,
+                        detector.current_evidence().cloned(),
                         detector.get_ui_above_text(),
+                        approval_lines,
+                        stall,
                     )
                 } else {
                     continue;
                 }
             };
 
-            let (_status_changed, should_notify_status) = {
-                if let Ok(mut last_status) = last_notified_status.lock() {
-                    if current_status != *last_status {
-                        // Connected→Idle の直接遷移を防ぐ
-                        if *last_status == SessionStatus::Connected
-                            && current_status == SessionStatus::Idle
-                        {
-                            if verbose {
-                                climonitor_shared::log_debug!(climonitor_shared::LogCategory::Session, "🔒 [STATE_TRANSITION] Blocked Connected→Idle transition, keeping Connected");
-                            }
-                            (false, false) // 状態変化を通知しない（Connected状態を維持）
-                        } else {
-                            *last_status = current_status.clone();
-                            (true, true)
+            // 検出結果のちらつきを抑えてから状態変化を通知
+            let (became_idle_from_busy, should_notify_status) =
+                match transition_filter.observe(&current_status) {
+                    FilterOutcome::Changed { from, to } => (
+                        from == Some(SessionStatus::Busy) && to == SessionStatus::Idle,
+                        true,
+                    ),
+                    FilterOutcome::Unchanged => (false, false),
+                    outcome => {
+                        if verbose {
+                            climonitor_shared::log_debug!(
+                                climonitor_shared::LogCategory::Session,
+                                "🔒 [STATE_TRANSITION] {:?} → {outcome:?}",
+                                transition_filter.reported()
+                            );
                         }
-                    } else {
                         (false, false)
                     }
-                } else {
-                    (false, false)
+                };
+
+            // Busy→Idle 遷移時に予約プロンプトを送信
+            if became_idle_from_busy {
+                let queued = prompt_queue.lock().ok().and_then(|mut queue| queue.take());
+                if let Some(prompt) = queued {
+                    if verbose {
+                        climonitor_shared::log_debug!(
+                            climonitor_shared::LogCategory::Session,
+                            "⏳ [PROMPT_QUEUE] Busy→Idle detected, sending queued prompt"
+                        );
+                    }
+                    let _ = inject_tx.send(PtyInjection::Prompt(prompt));
                 }
-            };
+            }
+
+            // 承認プロンプトの自動応答（同じプロンプトには一度だけ応答）
+            if let (Some(approver), Some(lines)) = (auto_approver.as_ref(), approval_lines) {
+                if let Some(decision) = approver.evaluate(&lines) {
+                    if last_approval_prompt.as_ref() != Some(&decision.prompt) {
+                        last_approval_prompt = Some(decision.prompt.clone());
+                        Self::apply_auto_approve_decision(decision, approver.is_dry_run(), &config)
+                            .await;
+                    }
+                }
+            } else if current_status != SessionStatus::WaitingInput {
+                last_approval_prompt = None;
+            }
+
+            // 予約状態の変化をmonitorに通知
+            let current_queued_prompt = prompt_queue
+                .lock()
+                .ok()
+                .and_then(|queue| queue.staged().map(|s| s.to_string()));
+            if current_queued_prompt != last_queued_prompt {
+                if let Err(e) = Self::send_prompt_queue_update(
+                    current_queued_prompt.clone(),
+                    message_sender.as_deref(),
+                    session_id,
+                )
+                .await
+                {
+                    if verbose {
+                        climonitor_shared::log_warn!(
+                            climonitor_shared::LogCategory::Transport,
+                            "⚠️  Failed to send prompt queue update: {e}"
+                        );
+                    }
+                }
+                last_queued_prompt = current_queued_prompt;
+            }
 
             let context_changed = current_ui_context != last_ui_context;
 
@@ -924,17 +1310,16 @@ impl TransportLauncherClient {
                 if verbose {
                     climonitor_shared::log_debug!(
                         climonitor_shared::LogCategory::Session,
-                        "🔄 Periodic status update: {current_status:?}"
+                        "🔄 Status update: {current_status:?}"
                     );
                 }
 
-                if let Err(e) = Self::send_periodic_status_update(
+                if let Err(e) = Self::send_detected_status_update(
                     current_status,
+                    current_evidence,
                     current_ui_context.clone(),
-                    &connection_config,
-                    grpc_client.as_ref(),
-                    &launcher_id,
-                    &session_id,
+                    message_sender.as_deref(),
+                    session_id,
                     verbose,
                 )
                 .await
@@ -942,7 +1327,7 @@ impl TransportLauncherClient {
                     if verbose {
                         climonitor_shared::log_warn!(
                             climonitor_shared::LogCategory::Transport,
-                            "⚠️  Failed to send periodic status update: {e}"
+                            "⚠️  Failed to send status update: {e}"
                         );
                     }
                 }
@@ -956,12 +1341,10 @@ impl TransportLauncherClient {
                     );
                 }
 
-                if let Err(e) = Self::send_periodic_context_update(
+                if let Err(e) = Self::send_detected_context_update(
                     current_ui_context.clone(),
-                    &connection_config,
-                    grpc_client.as_ref(),
-                    &launcher_id,
-                    &session_id,
+                    message_sender.as_deref(),
+                    session_id,
                     verbose,
                 )
                 .await
@@ -969,100 +1352,213 @@ impl TransportLauncherClient {
                     if verbose {
                         climonitor_shared::log_warn!(
                             climonitor_shared::LogCategory::Transport,
-                            "⚠️  Failed to send periodic context update: {e}"
+                            "⚠️  Failed to send context update: {e}"
                         );
                     }
                 }
 
                 last_ui_context = current_ui_context;
             }
+
+            // 出力中・停止中の切り替わりは即座に、出力や入力が続く間はkeepalive間隔で送信
+            let streaming = activity_snapshot.output_bytes_per_sec > 0;
+            let stalled = stall.is_some();
+            let should_report = match last_activity_report {
+                None => true,
+                Some((sent_at, sent_streaming, sent_stalled)) => {
+                    let since_sent = sent_at.elapsed();
+                    (sent_streaming, sent_stalled) != (streaming, stalled)
+                        || (since_sent >= keepalive
+                            && (streaming
+                                || activity_snapshot
+                                    .since_input
+                                    .is_some_and(|since_input| since_input < since_sent)))
+                }
+            };
+            if should_report {
+                // 出力停止はSessionActivityでmonitorに通知済みのため、ログは画面を乱さない場合のみ
+                if let Some(ref evidence) = stall {
+                    if last_activity_report.is_none_or(|(_, _, sent_stalled)| !sent_stalled)
+                        && (verbose || climonitor_shared::logging::has_diagnostic_sink())
+                    {
+                        climonitor_shared::log_info!(
+                            climonitor_shared::LogCategory::Session,
+                            "⚠️  [STALLED] Busy without output: {}",
+                            evidence.describe().join(", ")
+                        );
+                    }
+                }
+                if let Some(sender) = message_sender {
+                    if let Err(e) = sender
+                        .send_activity(
+                            session_id.clone(),
+                            activity_snapshot.to_session_activity(stalled),
+                        )
+                        .await
+                    {
+                        if verbose {
+                            climonitor_shared::log_warn!(
+                                climonitor_shared::LogCategory::Transport,
+                                "⚠️  Failed to send activity: {e}"
+                            );
+                        }
+                    }
+                }
+                last_activity_report = Some((tokio::time::Instant::now(), streaming, stalled));
+            }
         }
     }
 
-    /// 定期的な状態更新送信
-    async fn send_periodic_status_update(
+    /// 検出した状態の送信（コンテキストも併せて送る）
+    async fn send_detected_status_update(
         status: SessionStatus,
+        evidence: Option<StateEvidence>,
         ui_above_text: Option<String>,
-        connection_config: &ConnectionConfig,
-        grpc_client: Option<&crate::grpc_client::GrpcLauncherClient>,
-        _launcher_id: &str,
+        message_sender: Option<&dyn MessageSender>,
         session_id: &str,
         verbose: bool,
     ) -> Result<()> {
-        if let Some(grpc_client) = grpc_client {
+        if let Some(sender) = message_sender {
             if verbose {
                 climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Grpc,
-                    "📤 Sent gRPC periodic status update: {status:?}"
+                    climonitor_shared::LogCategory::Transport,
+                    "📤 Sending status update: {status:?}"
                 );
             }
-            grpc_client.send_state_update(status, ui_above_text).await?;
-        } else {
-            // Create a temporary sender with the same launcher_id
-            if let Ok(sender) = crate::transports::create_message_sender_with_id(
-                connection_config,
-                _launcher_id.to_string(),
-            )
-            .await
-            {
+            sender
+                .send_status_update(session_id.to_string(), status, Utc::now(), evidence)
+                .await?;
+
+            // UIコンテキストは別メッセージで送信
+            if let Some(ui_text) = ui_above_text {
                 sender
-                    .send_status_update(session_id.to_string(), status, Utc::now(), None)
+                    .send_context_update(session_id.to_string(), ui_text, Utc::now())
                     .await?;
-
-                // Send context update separately if ui_above_text is provided
-                if let Some(ui_text) = ui_above_text {
-                    sender
-                        .send_context_update(session_id.to_string(), ui_text, Utc::now())
-                        .await?;
-                }
             }
         }
         Ok(())
     }
 
-    /// 定期的なコンテキスト更新送信
-    async fn send_periodic_context_update(
+    /// 検出したコンテキストの送信
+    async fn send_detected_context_update(
         ui_above_text: Option<String>,
-        connection_config: &ConnectionConfig,
-        grpc_client: Option<&crate::grpc_client::GrpcLauncherClient>,
-        _launcher_id: &str,
+        message_sender: Option<&dyn MessageSender>,
         session_id: &str,
         verbose: bool,
     ) -> Result<()> {
-        if let Some(grpc_client) = grpc_client {
-            grpc_client.send_context_update(ui_above_text).await?;
+        if let Some(sender) = message_sender {
+            sender
+                .send_context_update(
+                    session_id.to_string(),
+                    ui_above_text.unwrap_or_default(),
+                    Utc::now(),
+                )
+                .await?;
+            if verbose {
+                climonitor_shared::log_debug!(
+                    climonitor_shared::LogCategory::Transport,
+                    "📤 Sent context update"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// 自動応答の判定を適用し、監査イベントとしてmonitorに送信
+    async fn apply_auto_approve_decision(
+        decision: AutoApproveDecision,
+        dry_run: bool,
+        config: &PtyMonitoringConfig,
+    ) {
+        let verbose = config.verbose;
+        let AutoApproveDecision {
+            rule,
+            action,
+            keys,
+            prompt,
+        } = decision;
+
+        if dry_run {
+            // dry-runではキー入力を送信しない。判定結果はmonitorの監査イベントに残り、
+            // ログはラップしたツールの画面を乱さない出力先（--verbose・診断ログファイル）にのみ出す
+            if verbose || climonitor_shared::logging::has_diagnostic_sink() {
+                climonitor_shared::log_info!(
+                    climonitor_shared::LogCategory::Session,
+                    "🧪 [AUTO_APPROVE] (dry-run) rule '{rule}' would {action:?}: {keys:?}"
+                );
+            }
+        } else if action == AutoApproveAction::Approve {
             if verbose {
                 climonitor_shared::log_debug!(
-                    climonitor_shared::LogCategory::Grpc,
-                    "📤 Sent gRPC context update"
+                    climonitor_shared::LogCategory::Session,
+                    "✅ [AUTO_APPROVE] rule '{rule}' approved prompt"
                 );
             }
+            let _ = config.inject_tx.send(PtyInjection::Keys(keys));
+        } else if verbose {
+            climonitor_shared::log_debug!(
+                climonitor_shared::LogCategory::Session,
+                "🚫 [AUTO_APPROVE] rule '{rule}' denied auto-approval, waiting for user"
+            );
+        }
+
+        let result = if let Some(sender) = &config.message_sender {
+            sender
+                .send_auto_approve_decision(
+                    config.session_id.clone(),
+                    rule,
+                    action,
+                    prompt,
+                    dry_run,
+                )
+                .await
         } else {
-            // Create a temporary sender with the same launcher_id
-            if let Ok(sender) = crate::transports::create_message_sender_with_id(
-                connection_config,
-                _launcher_id.to_string(),
-            )
-            .await
-            {
-                sender
-                    .send_context_update(
-                        session_id.to_string(),
-                        ui_above_text.unwrap_or_default(),
-                        Utc::now(),
-                    )
-                    .await?;
+            Ok(())
+        };
+
+        if let Err(e) = result {
+            if verbose {
+                climonitor_shared::log_warn!(
+                    climonitor_shared::LogCategory::Transport,
+                    "⚠️  Failed to send auto-approve decision: {e}"
+                );
             }
         }
+    }
+
+    /// 予約プロンプト状態の送信
+    async fn send_prompt_queue_update(
+        queued_prompt: Option<String>,
+        message_sender: Option<&dyn MessageSender>,
+        session_id: &str,
+    ) -> Result<()> {
+        if let Some(sender) = message_sender {
+            sender
+                .send_prompt_queue_update(session_id.to_string(), queued_prompt)
+                .await?;
+        }
         Ok(())
     }
 
-    // This method is no longer needed as we use the trait-based MessageSender API
+    /// デスクトップ通知の送信
+    async fn send_terminal_notification(
+        notification: TerminalNotification,
+        message_sender: Option<&dyn MessageSender>,
+        session_id: &str,
+    ) -> Result<()> {
+        if let Some(sender) = message_sender {
+            sender
+                .send_terminal_notification(
+                    session_id.to_string(),
+                    notification.title,
+                    notification.body,
+                )
+                .await?;
+        }
+        Ok(())
+    }
 }
 
-// Drop実装を削除し、明示的な切断処理に依存
-// （run_claude関数内で既に適切に切断メッセージが送信されている）
-
 /// ターミナル状態の自動復元ガード（クロスプラットフォーム対応）
 pub struct TerminalGuard {
     verbose: bool,
@@ -1360,16 +1856,61 @@ impl Drop for TransportLauncherClient {
     fn drop(&mut self) {
         // Drop時に同期的に切断メッセージを送信することは困難なため、
         // 主にログ出力とクリーンアップに集中
-        if self.verbose && (self.message_sender.is_some() || self.grpc_client.is_some()) {
+        if self.verbose && self.message_sender.is_some() {
             climonitor_shared::log_debug!(
                 climonitor_shared::LogCategory::System,
                 "📤 TransportLauncherClient dropping - connection cleanup"
             );
         }
 
-        // 注意: 実際の切断メッセージ送信は run_claude() の終了時に行われる
-        // または main関数のシグナルハンドリングで明示的に呼び出される
+        // 注意: 切断メッセージは run_claude() の終了時に送信される
+        // （シグナルで終了した場合は送信されず、monitorが接続の切断を検出する）
     }
 }
 
 pub type LauncherClient = TransportLauncherClient;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompt_queue::PromptQueue;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_monitor_messages_update_prompt_queue() {
+        let (monitor_tx, receiver) = tokio::sync::broadcast::channel(16);
+        let prompt_queue: SharedPromptQueue = Arc::new(std::sync::Mutex::new(PromptQueue::new()));
+        let state_events = Arc::new(Notify::new());
+        let listener = tokio::spawn(TransportLauncherClient::handle_monitor_messages(
+            receiver,
+            prompt_queue.clone(),
+            state_events.clone(),
+            false,
+        ));
+
+        // 予約・取り消しのたびに状態チェッカーを起こしてmonitorへ予約状態を送り返す
+        monitor_tx
+            .send(MonitorToLauncher::QueuePrompt {
+                launcher_id: "launcher".to_string(),
+                text: "run the tests".to_string(),
+            })
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(1), state_events.notified())
+            .await
+            .unwrap();
+        assert_eq!(prompt_queue.lock().unwrap().staged(), Some("run the tests"));
+
+        monitor_tx
+            .send(MonitorToLauncher::ClearQueuedPrompt {
+                launcher_id: "launcher".to_string(),
+            })
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(1), state_events.notified())
+            .await
+            .unwrap();
+        assert!(!prompt_queue.lock().unwrap().is_queued());
+
+        drop(monitor_tx);
+        listener.await.unwrap();
+    }
+}
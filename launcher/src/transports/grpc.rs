@@ -1,26 +1,30 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use std::path::PathBuf;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
 use tokio_stream::wrappers::ReceiverStream;
-use tonic::transport::Channel;
 
 use climonitor_shared::{
+    grpc::monitor_message::Message as GrpcMonitorMessageKind,
     grpc::monitor_service_client::MonitorServiceClient,
-    grpc::LauncherMessage as GrpcLauncherMessage, message_conversion as grpc_conversion,
-    transport::MessageSender, CliToolType, ConnectionConfig, LauncherToMonitor, SessionStatus,
+    grpc::{LauncherMessage as GrpcLauncherMessage, MonitorMessage as GrpcMonitorMessage},
+    message_conversion::grpc_conversion,
+    ConnectionConfig, LauncherToMonitor, MonitorToLauncher,
 };
 
-/// gRPC クライアント実装
-pub struct GrpcMessageSender {
-    _client: MonitorServiceClient<Channel>,
-    launcher_id: String,
-    tx: mpsc::Sender<GrpcLauncherMessage>,
+use super::MessageTransport;
+
+/// gRPC 送信路（双方向ストリーム）
+pub struct GrpcTransport {
+    tx: Option<mpsc::Sender<GrpcLauncherMessage>>,
+    stream_handle: JoinHandle<()>,
 }
 
-impl GrpcMessageSender {
-    pub async fn new(config: &ConnectionConfig) -> Result<Self> {
+impl GrpcTransport {
+    pub async fn connect(
+        config: &ConnectionConfig,
+        monitor_tx: broadcast::Sender<MonitorToLauncher>,
+    ) -> Result<Self> {
         match config {
             ConnectionConfig::Grpc { bind_addr, .. } => {
                 let endpoint =
@@ -30,107 +34,113 @@ impl GrpcMessageSender {
                         format!("http://{bind_addr}")
                     };
 
-                let client = MonitorServiceClient::connect(endpoint).await?;
-                let launcher_id = climonitor_shared::generate_connection_id();
+                let mut client = MonitorServiceClient::connect(endpoint).await?;
 
                 let (tx, rx) = mpsc::channel(100);
                 let input_stream = ReceiverStream::new(rx);
 
-                let mut client_clone = client.clone();
-                tokio::spawn(async move {
-                    match client_clone.stream_session(input_stream).await {
+                let stream_handle = tokio::spawn(async move {
+                    match client.stream_session(input_stream).await {
                         Ok(response) => {
                             let mut stream = response.into_inner();
-                            while let Ok(Some(_message)) = stream.message().await {
-                                // Monitor → Launcherメッセージの処理（現在は未実装）
+                            loop {
+                                match stream.message().await {
+                                    Ok(Some(message)) => {
+                                        Self::handle_monitor_message(message, &monitor_tx)
+                                    }
+                                    Ok(None) => break,
+                                    Err(e) => {
+                                        climonitor_shared::log_warn!(
+                                            climonitor_shared::LogCategory::Grpc,
+                                            "⚠️  Error receiving monitor message: {e}"
+                                        );
+                                        break;
+                                    }
+                                }
                             }
                         }
                         Err(e) => {
                             climonitor_shared::log_warn!(
                                 climonitor_shared::LogCategory::Grpc,
-                                "gRPC stream error: {e}"
+                                "⚠️  gRPC stream error: {e}"
                             );
                         }
                     }
                 });
 
                 Ok(Self {
-                    _client: client,
-                    launcher_id,
-                    tx,
+                    tx: Some(tx),
+                    stream_handle,
                 })
             }
             _ => anyhow::bail!("gRPC transport requires gRPC configuration"),
         }
     }
 
-    async fn send_grpc_message(&self, message: LauncherToMonitor) -> Result<()> {
-        let grpc_message = grpc_conversion::grpc_conversion::to_grpc_launcher_message(message)?;
-        self.tx
-            .send(grpc_message)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to send gRPC message: {}", e))?;
-        Ok(())
+    fn handle_monitor_message(
+        message: GrpcMonitorMessage,
+        monitor_tx: &broadcast::Sender<MonitorToLauncher>,
+    ) {
+        // 接続応答やプロンプト予約などlauncher側で処理するメッセージは購読者に転送
+        if let Some(message) = grpc_conversion::from_grpc_monitor_message(message.clone()) {
+            let _ = monitor_tx.send(message);
+            return;
+        }
+
+        match message.message {
+            Some(GrpcMonitorMessageKind::RequestReconnect(req)) => {
+                // TODO: 再接続要求への対応（現状は次回送信失敗時に再接続）
+                climonitor_shared::log_debug!(
+                    climonitor_shared::LogCategory::Grpc,
+                    "🔄 Monitor requests reconnection: {}",
+                    req.reason
+                );
+            }
+            Some(GrpcMonitorMessageKind::Ping(ping)) => {
+                // TODO: Pong応答を実装
+                climonitor_shared::log_debug!(
+                    climonitor_shared::LogCategory::Grpc,
+                    "🏓 Ping received: sequence={}",
+                    ping.sequence
+                );
+            }
+            // from_grpc_monitor_message で処理済み（接続拒否も送信キュー側で表示）
+            Some(GrpcMonitorMessageKind::ConnectResponse(_))
+            | Some(GrpcMonitorMessageKind::QueuePrompt(_))
+            | Some(GrpcMonitorMessageKind::ClearQueuedPrompt(_))
+            | None => {}
+        }
     }
 }
 
 #[async_trait]
-impl MessageSender for GrpcMessageSender {
-    async fn send_connect(
-        &self,
-        project: Option<String>,
-        tool_type: CliToolType,
-        args: Vec<String>,
-        working_dir: PathBuf,
-    ) -> Result<()> {
-        let message = LauncherToMonitor::Connect {
-            launcher_id: self.launcher_id.clone(),
-            project,
-            tool_type,
-            claude_args: args,
-            working_dir,
-            timestamp: Utc::now(),
+impl MessageTransport for GrpcTransport {
+    async fn send(&mut self, message: &LauncherToMonitor) -> Result<()> {
+        let Some(ref tx) = self.tx else {
+            anyhow::bail!("gRPC stream already closed");
         };
-        self.send_grpc_message(message).await
+        let grpc_message = grpc_conversion::to_grpc_launcher_message(message.clone())?;
+        // ストリームが切断されていれば送信に失敗し、呼び出し側で再接続される
+        tx.send(grpc_message)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to send gRPC message: {}", e))?;
+        Ok(())
     }
 
-    async fn send_status_update(
-        &self,
-        session_id: String,
-        status: SessionStatus,
-        timestamp: DateTime<Utc>,
-        _project_name: Option<String>,
-    ) -> Result<()> {
-        let message = LauncherToMonitor::StateUpdate {
-            launcher_id: self.launcher_id.clone(),
-            session_id,
-            status,
-            ui_above_text: None,
-            timestamp,
-        };
-        self.send_grpc_message(message).await
+    fn is_closed(&self) -> bool {
+        self.stream_handle.is_finished()
     }
 
-    async fn send_context_update(
-        &self,
-        session_id: String,
-        ui_text: String,
-        timestamp: DateTime<Utc>,
-    ) -> Result<()> {
-        let message = LauncherToMonitor::ContextUpdate {
-            launcher_id: self.launcher_id.clone(),
-            session_id,
-            ui_above_text: Some(ui_text),
-            timestamp,
-        };
-        self.send_grpc_message(message).await
+    async fn close(&mut self) -> Result<()> {
+        // 送信側を閉じてストリームの終了を待つ
+        self.tx = None;
+        (&mut self.stream_handle).await?;
+        Ok(())
     }
+}
 
-    async fn send_disconnect(&self, _session_id: String) -> Result<()> {
-        let message = LauncherToMonitor::Disconnect {
-            launcher_id: self.launcher_id.clone(),
-            timestamp: Utc::now(),
-        };
-        self.send_grpc_message(message).await
+impl Drop for GrpcTransport {
+    fn drop(&mut self) {
+        self.stream_handle.abort();
     }
 }
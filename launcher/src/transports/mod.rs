@@ -1,40 +1,110 @@
 use anyhow::Result;
-use climonitor_shared::{transport::MessageSender, ConnectionConfig};
+use async_trait::async_trait;
+use climonitor_shared::{
+    transport::MessageSender, ConnectionConfig, LauncherToMonitor, MonitorToLauncher,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
 
 pub mod grpc;
+pub mod queued;
+pub mod tcp;
 #[cfg(unix)]
 pub mod unix;
 
-/// クライアント用ファクトリー
-pub async fn create_message_sender(config: &ConnectionConfig) -> Result<Box<dyn MessageSender>> {
-    match config {
-        #[cfg(unix)]
-        ConnectionConfig::Unix { .. } => {
-            let sender = unix::UnixMessageSender::new(config).await?;
-            Ok(Box::new(sender))
-        }
-        ConnectionConfig::Grpc { .. } => {
-            let sender = grpc::GrpcMessageSender::new(config).await?;
-            Ok(Box::new(sender))
-        }
+pub use queued::QueuedMessageSender;
+
+/// 確立済みの送信路（QueuedMessageSenderの下位層）
+#[async_trait]
+pub trait MessageTransport: Send {
+    async fn send(&mut self, message: &LauncherToMonitor) -> Result<()>;
+
+    /// 接続先のプロトコルバージョンを設定（以降の送信形式に反映）
+    fn set_peer_protocol_version(&mut self, _version: u32) {}
+
+    /// 接続先が接続を閉じたか（受信タスクの終了で検出できる送信路のみ）
+    fn is_closed(&self) -> bool {
+        false
+    }
+
+    /// 送信済みメッセージを届けてから接続を閉じる
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
     }
 }
 
-/// 指定されたlauncher_idでクライアント用MessageSenderを作成
-pub async fn create_message_sender_with_id(
+/// 接続の待機上限（パケットを破棄するネットワークでも送信キューを止めない）
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 設定に応じた送信路を接続（`CONNECT_TIMEOUT` で打ち切る）
+///
+/// Monitor → Launcher メッセージを受信できる送信路は `monitor_tx` に転送する。
+pub async fn connect_transport(
     config: &ConnectionConfig,
-    launcher_id: String,
-) -> Result<Box<dyn MessageSender>> {
+    monitor_tx: broadcast::Sender<MonitorToLauncher>,
+) -> Result<Box<dyn MessageTransport>> {
+    tokio::time::timeout(CONNECT_TIMEOUT, connect_transport_inner(config, monitor_tx))
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out connecting to monitor"))?
+}
+
+async fn connect_transport_inner(
+    config: &ConnectionConfig,
+    monitor_tx: broadcast::Sender<MonitorToLauncher>,
+) -> Result<Box<dyn MessageTransport>> {
     match config {
         #[cfg(unix)]
-        ConnectionConfig::Unix { .. } => {
-            let sender = unix::UnixMessageSender::new_with_launcher_id(config, launcher_id).await?;
-            Ok(Box::new(sender))
-        }
-        ConnectionConfig::Grpc { .. } => {
-            // gRPCの場合は既存のIDを使用する仕組みが必要だが、今回はUnix socket問題の修正のみ
-            let sender = grpc::GrpcMessageSender::new(config).await?;
-            Ok(Box::new(sender))
-        }
+        ConnectionConfig::Unix { .. } => Ok(Box::new(
+            unix::UnixTransport::connect(config, monitor_tx).await?,
+        )),
+        ConnectionConfig::Grpc { .. } => Ok(Box::new(
+            grpc::GrpcTransport::connect(config, monitor_tx).await?,
+        )),
+        ConnectionConfig::Tcp { .. } => Ok(Box::new(
+            tcp::TcpTransport::connect(config, monitor_tx).await?,
+        )),
     }
 }
+
+/// 改行区切りJSONのMonitor → Launcher メッセージを受信して転送（Unix socket/TCP共通）
+pub(crate) fn spawn_json_lines_reader<R>(
+    reader: R,
+    monitor_tx: broadcast::Sender<MonitorToLauncher>,
+) -> JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            match serde_json::from_str::<MonitorToLauncher>(line.trim()) {
+                Ok(message) => {
+                    let _ = monitor_tx.send(message);
+                }
+                Err(e) => {
+                    climonitor_shared::log_warn!(
+                        climonitor_shared::LogCategory::Transport,
+                        "⚠️  Failed to parse monitor message '{}': {e}",
+                        line.trim()
+                    );
+                }
+            }
+        }
+    })
+}
+
+/// クライアント用ファクトリー（接続は送信時に確立・再接続される）
+pub fn create_message_sender(
+    config: &ConnectionConfig,
+    launcher_id: String,
+    verbose: bool,
+) -> Arc<dyn MessageSender> {
+    Arc::new(QueuedMessageSender::new(
+        config.clone(),
+        launcher_id,
+        verbose,
+    ))
+}
@@ -0,0 +1,958 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+use climonitor_shared::logging::tracing::Instrument;
+use climonitor_shared::{
+    negotiate_protocol_version, supported_capabilities, transport::MessageSender,
+    AutoApproveAction, CliToolType, ConnectionConfig, LauncherMetadata, LauncherToMonitor,
+    MonitorToLauncher, ProcessMetrics, SessionActivity, SessionStatus, StateEvidence,
+    LEGACY_PROTOCOL_VERSION, PROTOCOL_VERSION,
+};
+
+use super::MessageTransport;
+
+/// 送信待ちキューの上限（超えると送信側が待機する）
+const QUEUE_CAPACITY: usize = 256;
+/// 1メッセージあたりの送信試行回数
+const MAX_SEND_ATTEMPTS: u32 = 3;
+/// 再接続の初回待機時間（試行ごとに倍増）
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+/// flush と、キューが満杯のときの送信要求の待機上限
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(3);
+/// 1メッセージの書き込みの待機上限（受信しないmonitorでソケットバッファが埋まった場合）
+const SEND_TIMEOUT: Duration = Duration::from_secs(2);
+/// 送信に失敗した後、再接続を試みずにメッセージを破棄する期間（キューを滞留させない）
+const OFFLINE_COOLDOWN: Duration = Duration::from_secs(5);
+/// Connect送信後に接続応答を待つ上限（応答のないまま過ぎたmonitorはプロトコルv1として扱う）
+///
+/// 負荷の高いv2のmonitorの応答を取り違えないよう、数秒間は待ち続ける。
+const LEGACY_DETECT_TIMEOUT: Duration = Duration::from_secs(3);
+/// 接続応答を待つ間に、monitorが接続を閉じたかを確認する間隔
+const CLOSE_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 接続応答の待機結果
+enum ConnectReply {
+    /// 接続応答・接続拒否を受信した
+    Answered,
+    /// 応答しないまま接続を閉じた（新しい形式を解釈できない古いmonitor）
+    Closed,
+    /// 待機上限まで応答がない（接続応答に対応していないv1のmonitor）
+    Silent,
+}
+
+enum Outbound {
+    Message(Box<LauncherToMonitor>),
+    Flush(oneshot::Sender<Result<()>>),
+}
+
+/// launcher全体で共有する永続的なMessageSender
+///
+/// 送信は単一の書き込みタスクが順番に行い、切断時は再接続して再送する。
+/// monitorが接続応答で別のlauncher IDを割り当てた場合、以降の送信はそのIDで行い、
+/// monitorが対応していない機能のメッセージは送信しない。接続応答を返さないまま数秒経つか、
+/// Connectを受けて接続を閉じたmonitorはプロトコルv1とみなし、v1の形式でConnectを送り直して
+/// 拡張メッセージを送信しない。
+pub struct QueuedMessageSender {
+    launcher_id: String,
+    tx: mpsc::Sender<Outbound>,
+    monitor_tx: broadcast::Sender<MonitorToLauncher>,
+}
+
+impl QueuedMessageSender {
+    pub fn new(config: ConnectionConfig, launcher_id: String, verbose: bool) -> Self {
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        let (monitor_tx, _) = broadcast::channel(16);
+        let writer = QueueWriter {
+            config,
+            launcher_id: launcher_id.clone(),
+            monitor_rx: monitor_tx.subscribe(),
+            monitor_tx: monitor_tx.clone(),
+            verbose,
+            transport: None,
+            connected_before: false,
+            connect_message: None,
+            monitor_capabilities: None,
+            disconnected: false,
+            undelivered: 0,
+            offline_until: None,
+        };
+        tokio::spawn(writer.run(rx).in_current_span());
+
+        Self {
+            launcher_id,
+            tx,
+            monitor_tx,
+        }
+    }
+
+    /// 送信キューに追加（monitorに届かない間も終了処理を止めないよう待機に上限を設ける）
+    async fn enqueue(&self, message: LauncherToMonitor) -> Result<()> {
+        match tokio::time::timeout(
+            FLUSH_TIMEOUT,
+            self.tx.send(Outbound::Message(Box::new(message))),
+        )
+        .await
+        {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(_)) => anyhow::bail!("Message queue closed"),
+            Err(_) => anyhow::bail!("Timed out queueing message"),
+        }
+    }
+
+    /// 送信キューに追加（満杯なら破棄し、PTY出力などの呼び出し元を待たせない）
+    fn try_enqueue(&self, message: LauncherToMonitor) -> Result<()> {
+        self.tx
+            .try_send(Outbound::Message(Box::new(message)))
+            .map_err(|e| match e {
+                TrySendError::Full(_) => anyhow::anyhow!("Message queue full; message dropped"),
+                TrySendError::Closed(_) => anyhow::anyhow!("Message queue closed"),
+            })
+    }
+}
+
+#[async_trait]
+impl MessageSender for QueuedMessageSender {
+    async fn send_connect(
+        &self,
+        project: Option<String>,
+        tool_type: CliToolType,
+        args: Vec<String>,
+        working_dir: PathBuf,
+        metadata: LauncherMetadata,
+    ) -> Result<()> {
+        self.enqueue(LauncherToMonitor::Connect {
+            launcher_id: self.launcher_id.clone(),
+            project,
+            tool_type,
+            tool_args: args,
+            working_dir,
+            timestamp: Utc::now(),
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: supported_capabilities(),
+            metadata,
+        })
+        .await
+    }
+
+    async fn send_status_update(
+        &self,
+        session_id: String,
+        status: SessionStatus,
+        timestamp: DateTime<Utc>,
+        evidence: Option<StateEvidence>,
+    ) -> Result<()> {
+        self.enqueue(LauncherToMonitor::StateUpdate {
+            launcher_id: self.launcher_id.clone(),
+            session_id,
+            status,
+            ui_above_text: None,
+            timestamp,
+            evidence,
+        })
+        .await
+    }
+
+    async fn send_context_update(
+        &self,
+        session_id: String,
+        ui_text: String,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        self.try_enqueue(LauncherToMonitor::ContextUpdate {
+            launcher_id: self.launcher_id.clone(),
+            session_id,
+            ui_above_text: Some(ui_text),
+            timestamp,
+        })
+    }
+
+    async fn send_prompt_queue_update(
+        &self,
+        session_id: String,
+        queued_prompt: Option<String>,
+    ) -> Result<()> {
+        self.try_enqueue(LauncherToMonitor::PromptQueueUpdate {
+            launcher_id: self.launcher_id.clone(),
+            session_id,
+            queued_prompt,
+            timestamp: Utc::now(),
+        })
+    }
+
+    async fn send_auto_approve_decision(
+        &self,
+        session_id: String,
+        rule: String,
+        action: AutoApproveAction,
+        prompt: String,
+        dry_run: bool,
+    ) -> Result<()> {
+        self.enqueue(LauncherToMonitor::AutoApproveDecision {
+            launcher_id: self.launcher_id.clone(),
+            session_id,
+            rule,
+            action,
+            prompt,
+            dry_run,
+            timestamp: Utc::now(),
+        })
+        .await
+    }
+
+    async fn send_terminal_notification(
+        &self,
+        session_id: String,
+        title: Option<String>,
+        body: String,
+    ) -> Result<()> {
+        self.try_enqueue(LauncherToMonitor::TerminalNotification {
+            launcher_id: self.launcher_id.clone(),
+            session_id,
+            title,
+            body,
+            timestamp: Utc::now(),
+        })
+    }
+
+    async fn send_process_metrics(
+        &self,
+        session_id: String,
+        metrics: ProcessMetrics,
+    ) -> Result<()> {
+        self.try_enqueue(LauncherToMonitor::ProcessMetrics {
+            launcher_id: self.launcher_id.clone(),
+            session_id,
+            metrics,
+            timestamp: Utc::now(),
+        })
+    }
+
+    async fn send_activity(&self, session_id: String, activity: SessionActivity) -> Result<()> {
+        self.try_enqueue(LauncherToMonitor::Activity {
+            launcher_id: self.launcher_id.clone(),
+            session_id,
+            activity,
+            timestamp: Utc::now(),
+        })
+    }
+
+    async fn send_process_exit(
+        &self,
+        session_id: String,
+        exit_code: u32,
+        signal: Option<String>,
+        runtime_secs: u64,
+    ) -> Result<()> {
+        self.enqueue(LauncherToMonitor::Exited {
+            launcher_id: self.launcher_id.clone(),
+            session_id,
+            exit_code,
+            signal,
+            runtime_secs,
+            timestamp: Utc::now(),
+        })
+        .await
+    }
+
+    async fn send_disconnect(&self) -> Result<()> {
+        self.enqueue(LauncherToMonitor::Disconnect {
+            launcher_id: self.launcher_id.clone(),
+            timestamp: Utc::now(),
+        })
+        .await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let flush = async {
+            let (done_tx, done_rx) = oneshot::channel();
+            self.tx
+                .send(Outbound::Flush(done_tx))
+                .await
+                .map_err(|_| anyhow::anyhow!("Message queue closed"))?;
+            done_rx
+                .await
+                .map_err(|_| anyhow::anyhow!("Message queue closed"))?
+        };
+
+        match tokio::time::timeout(FLUSH_TIMEOUT, flush).await {
+            Ok(result) => result,
+            Err(_) => anyhow::bail!("Timed out flushing message queue"),
+        }
+    }
+
+    fn subscribe_monitor_messages(&self) -> Option<broadcast::Receiver<MonitorToLauncher>> {
+        Some(self.monitor_tx.subscribe())
+    }
+}
+
+/// キューからメッセージを取り出して送信する書き込みタスク
+struct QueueWriter {
+    config: ConnectionConfig,
+    /// 送信に使用するlauncher ID（monitorの接続応答で置き換えられる）
+    launcher_id: String,
+    /// 接続応答の受信用
+    monitor_rx: broadcast::Receiver<MonitorToLauncher>,
+    /// 送信路が受信したMonitor → Launcherメッセージの転送先
+    monitor_tx: broadcast::Sender<MonitorToLauncher>,
+    verbose: bool,
+    transport: Option<Box<dyn MessageTransport>>,
+    /// 一度でも接続できたか（再接続時のConnect再送判定用）
+    connected_before: bool,
+    /// 再接続時に再送するConnectメッセージ
+    connect_message: Option<LauncherToMonitor>,
+    /// 接続応答で通知されたmonitorの対応機能（応答がなければ空 = v1の機能のみ、未接続なら None）
+    monitor_capabilities: Option<Vec<String>>,
+    /// Disconnect送信済み、または接続を拒否された（以降のメッセージは破棄）
+    disconnected: bool,
+    /// 前回のflush以降に送信できなかったメッセージ数
+    undelivered: usize,
+    /// 送信に失敗した後、この時刻までは再接続せずに破棄する
+    offline_until: Option<tokio::time::Instant>,
+}
+
+impl QueueWriter {
+    async fn run(mut self, mut rx: mpsc::Receiver<Outbound>) {
+        while let Some(outbound) = rx.recv().await {
+            match outbound {
+                Outbound::Message(message) => self.deliver(*message).await,
+                Outbound::Flush(done) => {
+                    let result = match std::mem::take(&mut self.undelivered) {
+                        0 => Ok(()),
+                        count => Err(anyhow::anyhow!("{count} message(s) could not be delivered")),
+                    };
+                    let _ = done.send(result);
+                }
+            }
+        }
+    }
+
+    async fn deliver(&mut self, message: LauncherToMonitor) {
+        if self.disconnected {
+            return;
+        }
+        // タイムアウト後に届いた接続応答を反映
+        self.apply_pending_connect_replies();
+        if self.disconnected {
+            self.close().await;
+            return;
+        }
+        // monitorが対応していないメッセージは送信しない
+        if let (Some(required), Some(capabilities)) =
+            (message.required_capability(), &self.monitor_capabilities)
+        {
+            if !capabilities.iter().any(|name| name == required) {
+                if self.verbose {
+                    climonitor_shared::log_debug!(
+                        climonitor_shared::LogCategory::Transport,
+                        "Skipping message: monitor does not support {required}"
+                    );
+                }
+                return;
+            }
+        }
+
+        let is_connect = matches!(message, LauncherToMonitor::Connect { .. });
+        let is_disconnect = matches!(message, LauncherToMonitor::Disconnect { .. });
+        if is_connect {
+            self.connect_message = Some(message.clone());
+        }
+
+        // 直前に送信できなかったmonitorにはしばらく接続を試みない
+        if self
+            .offline_until
+            .is_some_and(|until| tokio::time::Instant::now() < until)
+        {
+            self.undelivered += 1;
+            if is_disconnect {
+                self.close().await;
+            }
+            return;
+        }
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_SEND_ATTEMPTS {
+            match self.try_send(&message, is_connect).await {
+                Ok(()) => {
+                    self.offline_until = None;
+                    if is_disconnect || self.disconnected {
+                        self.close().await;
+                    }
+                    return;
+                }
+                Err(e) => {
+                    // 接続を破棄し、次の試行で再接続する
+                    self.transport = None;
+                    if self.verbose {
+                        climonitor_shared::log_debug!(
+                            climonitor_shared::LogCategory::Transport,
+                            "🔄 Send attempt {attempt}/{MAX_SEND_ATTEMPTS} failed: {e}"
+                        );
+                    }
+                    if attempt < MAX_SEND_ATTEMPTS {
+                        tokio::time::sleep(backoff).await;
+                        backoff *= 2;
+                    }
+                }
+            }
+        }
+
+        self.undelivered += 1;
+        self.offline_until = Some(tokio::time::Instant::now() + OFFLINE_COOLDOWN);
+        if self.verbose {
+            climonitor_shared::log_warn!(
+                climonitor_shared::LogCategory::Transport,
+                "⚠️  Dropped message after {MAX_SEND_ATTEMPTS} attempts"
+            );
+        }
+        if is_disconnect {
+            self.close().await;
+        }
+    }
+
+    async fn try_send(&mut self, message: &LauncherToMonitor, is_connect: bool) -> Result<()> {
+        if self.transport.is_none() {
+            let mut transport =
+                super::connect_transport(&self.config, self.monitor_tx.clone()).await?;
+
+            // monitor再起動などで再接続した場合はlauncherを登録し直す
+            if self.connected_before && !is_connect {
+                if let Some(mut connect_message) = self.connect_message.clone() {
+                    connect_message.set_launcher_id(&self.launcher_id);
+                    self.register(&mut transport, &connect_message).await?;
+                    if self.disconnected {
+                        return Ok(());
+                    }
+                    if self.verbose {
+                        climonitor_shared::log_info!(
+                            climonitor_shared::LogCategory::Transport,
+                            "🔗 Reconnected to monitor"
+                        );
+                    }
+                }
+            }
+            self.connected_before = true;
+            self.transport = Some(transport);
+        }
+
+        let mut message = message.clone();
+        message.set_launcher_id(&self.launcher_id);
+        let Some(mut transport) = self.transport.take() else {
+            anyhow::bail!("No transport available");
+        };
+        let result = if is_connect {
+            self.register(&mut transport, &message).await
+        } else {
+            send_with_timeout(transport.as_mut(), &message).await
+        };
+        self.transport = Some(transport);
+        result
+    }
+
+    /// Connectを送信して接続応答を待つ
+    ///
+    /// 応答しないまま `LEGACY_DETECT_TIMEOUT` が過ぎるか接続を閉じたmonitorはプロトコルv1とみなし、
+    /// v1の形式（`claude_args`）でConnectを送り直す（閉じられた場合は接続し直す）。
+    async fn register(
+        &mut self,
+        transport: &mut Box<dyn MessageTransport>,
+        connect_message: &LauncherToMonitor,
+    ) -> Result<()> {
+        transport.set_peer_protocol_version(PROTOCOL_VERSION);
+        send_with_timeout(transport.as_mut(), connect_message).await?;
+        let reason = match self.await_connect_response(transport.as_mut()).await {
+            ConnectReply::Answered => return Ok(()),
+            ConnectReply::Silent => "did not answer the connect request",
+            ConnectReply::Closed => {
+                *transport =
+                    super::connect_transport(&self.config, self.monitor_tx.clone()).await?;
+                "closed the connection on the connect request"
+            }
+        };
+
+        if self.verbose {
+            climonitor_shared::log_warn!(
+                climonitor_shared::LogCategory::Transport,
+                "⚠️  Monitor {reason}; assuming protocol version {LEGACY_PROTOCOL_VERSION}"
+            );
+        }
+        self.monitor_capabilities = Some(Vec::new());
+        transport.set_peer_protocol_version(LEGACY_PROTOCOL_VERSION);
+        send_with_timeout(transport.as_mut(), connect_message).await
+    }
+
+    /// 接続応答を待って反映
+    async fn await_connect_response(
+        &mut self,
+        transport: &mut dyn MessageTransport,
+    ) -> ConnectReply {
+        let deadline = tokio::time::sleep(LEGACY_DETECT_TIMEOUT);
+        tokio::pin!(deadline);
+        let mut close_check = tokio::time::interval(CLOSE_CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                biased;
+                received = self.monitor_rx.recv() => match received {
+                    Ok(reply) => {
+                        if self.apply_connect_reply(reply) {
+                            return ConnectReply::Answered;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => return ConnectReply::Closed,
+                },
+                // 閉じる直前に届いた応答は上の分岐で先に受け取る
+                _ = close_check.tick() => {
+                    if transport.is_closed() && self.monitor_rx.is_empty() {
+                        return ConnectReply::Closed;
+                    }
+                }
+                _ = &mut deadline => return ConnectReply::Silent,
+            }
+        }
+    }
+
+    fn apply_pending_connect_replies(&mut self) {
+        loop {
+            match self.monitor_rx.try_recv() {
+                Ok(reply) => {
+                    self.apply_connect_reply(reply);
+                }
+                Err(broadcast::error::TryRecvError::Lagged(_)) => {}
+                Err(_) => return,
+            }
+        }
+    }
+
+    /// 自分宛ての接続応答・接続拒否を反映（該当した場合は true）
+    fn apply_connect_reply(&mut self, reply: MonitorToLauncher) -> bool {
+        match reply {
+            MonitorToLauncher::ConnectResponse {
+                requested_id,
+                launcher_id,
+                protocol_version,
+                capabilities,
+            } if requested_id == self.launcher_id => {
+                // monitorが古すぎる場合はlauncher側で接続を打ち切る
+                match negotiate_protocol_version(protocol_version) {
+                    Ok(_) => {
+                        self.adopt_launcher_id(launcher_id);
+                        self.monitor_capabilities = Some(capabilities);
+                    }
+                    Err(reason) => self.reject(&reason),
+                }
+                true
+            }
+            MonitorToLauncher::ConnectRejected {
+                requested_id,
+                reason,
+            } if requested_id == self.launcher_id => {
+                self.reject(&reason);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn reject(&mut self, reason: &str) {
+        climonitor_shared::log_error!(
+            climonitor_shared::LogCategory::Transport,
+            "🚫 Monitor connection rejected: {reason}"
+        );
+        self.disconnected = true;
+    }
+
+    fn adopt_launcher_id(&mut self, launcher_id: String) {
+        if launcher_id == self.launcher_id {
+            return;
+        }
+        climonitor_shared::log_info!(
+            climonitor_shared::LogCategory::Transport,
+            "🆔 Monitor assigned launcher id {launcher_id} (requested {})",
+            self.launcher_id
+        );
+        climonitor_shared::logging::set_log_launcher_id(&launcher_id);
+        self.launcher_id = launcher_id;
+    }
+
+    async fn close(&mut self) {
+        self.disconnected = true;
+        if let Some(mut transport) = self.transport.take() {
+            if let Err(e) = transport.close().await {
+                if self.verbose {
+                    climonitor_shared::log_debug!(
+                        climonitor_shared::LogCategory::Transport,
+                        "⚠️  Failed to close transport: {e}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// 書き込みが進まない送信路で送信キューを止めないよう待機に上限を設けて送信
+async fn send_with_timeout(
+    transport: &mut dyn MessageTransport,
+    message: &LauncherToMonitor,
+) -> Result<()> {
+    tokio::time::timeout(SEND_TIMEOUT, transport.send(message))
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out sending message to monitor"))?
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+    use tokio::net::{UnixListener, UnixStream};
+
+    type Connection = Lines<BufReader<UnixStream>>;
+
+    async fn accept(listener: &UnixListener) -> Connection {
+        let (stream, _) = listener.accept().await.unwrap();
+        BufReader::new(stream).lines()
+    }
+
+    async fn read_messages(connection: &mut Connection, count: usize) -> Vec<LauncherToMonitor> {
+        let mut messages = Vec::new();
+        while messages.len() < count {
+            let line = connection.next_line().await.unwrap().unwrap();
+            messages.push(serde_json::from_str(&line).unwrap());
+        }
+        messages
+    }
+
+    /// monitorとしてlauncherへメッセージを送る
+    async fn write_reply(connection: &mut Connection, reply: &MonitorToLauncher) {
+        let mut line = serde_json::to_string(reply).unwrap();
+        line.push('\n');
+        let stream = connection.get_mut().get_mut();
+        stream.write_all(line.as_bytes()).await.unwrap();
+    }
+
+    /// monitorとして接続応答を返す
+    async fn answer_connect(connection: &mut Connection, protocol_version: u32) {
+        let reply = MonitorToLauncher::ConnectResponse {
+            requested_id: "launcher".to_string(),
+            launcher_id: "launcher".to_string(),
+            protocol_version,
+            capabilities: supported_capabilities(),
+        };
+        write_reply(connection, &reply).await;
+    }
+
+    async fn send_connect(sender: &QueuedMessageSender) {
+        sender
+            .send_connect(
+                None,
+                CliToolType::Claude,
+                vec!["--resume".to_string()],
+                PathBuf::from("/tmp"),
+                LauncherMetadata::default(),
+            )
+            .await
+            .unwrap();
+    }
+
+    fn unix_sender(socket_path: PathBuf) -> QueuedMessageSender {
+        QueuedMessageSender::new(
+            ConnectionConfig::Unix { socket_path },
+            "launcher".to_string(),
+            false,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_messages_share_one_connection_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("monitor.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let sender = unix_sender(socket_path);
+
+        let statuses = [
+            SessionStatus::Busy,
+            SessionStatus::Idle,
+            SessionStatus::WaitingInput,
+        ];
+        for status in statuses.iter().cloned() {
+            sender
+                .send_status_update("session".to_string(), status, Utc::now(), None)
+                .await
+                .unwrap();
+        }
+        sender.send_disconnect().await.unwrap();
+
+        let mut connection = accept(&listener).await;
+        let messages = read_messages(&mut connection, 4).await;
+        sender.flush().await.unwrap();
+
+        let received: Vec<_> = messages
+            .iter()
+            .filter_map(|message| match message {
+                LauncherToMonitor::StateUpdate { status, .. } => Some(status.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(received, statuses);
+        assert!(matches!(
+            messages.last(),
+            Some(LauncherToMonitor::Disconnect { launcher_id, .. }) if launcher_id == "launcher"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replays_connect_and_stops_after_disconnect() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("monitor.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let sender = unix_sender(socket_path.clone());
+
+        send_connect(&sender).await;
+        let mut connection = accept(&listener).await;
+        let first = read_messages(&mut connection, 1).await;
+        assert!(matches!(first[0], LauncherToMonitor::Connect { .. }));
+        answer_connect(&mut connection, PROTOCOL_VERSION).await;
+        sender.flush().await.unwrap();
+
+        // monitor再起動: 旧接続を閉じて新しいソケットで待ち受け
+        drop(connection);
+        drop(listener);
+        std::fs::remove_file(&socket_path).unwrap();
+        let listener = UnixListener::bind(&socket_path).unwrap();
+
+        sender
+            .send_status_update("session".to_string(), SessionStatus::Busy, Utc::now(), None)
+            .await
+            .unwrap();
+        let mut connection = accept(&listener).await;
+        let replayed = read_messages(&mut connection, 1).await;
+        assert!(matches!(replayed[0], LauncherToMonitor::Connect { .. }));
+        answer_connect(&mut connection, PROTOCOL_VERSION).await;
+        let replayed = read_messages(&mut connection, 1).await;
+        assert!(matches!(
+            replayed[0],
+            LauncherToMonitor::StateUpdate {
+                status: SessionStatus::Busy,
+                ..
+            }
+        ));
+
+        // Disconnect後のメッセージは送信されず、接続が閉じられる
+        sender.send_disconnect().await.unwrap();
+        sender
+            .send_status_update("session".to_string(), SessionStatus::Idle, Utc::now(), None)
+            .await
+            .unwrap();
+        sender.flush().await.unwrap();
+        let last = read_messages(&mut connection, 1).await;
+        assert!(matches!(last[0], LauncherToMonitor::Disconnect { .. }));
+        assert!(connection.next_line().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_monitor_messages_reach_subscribers() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("monitor.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let sender = unix_sender(socket_path);
+        let mut receiver = sender.subscribe_monitor_messages().unwrap();
+
+        send_connect(&sender).await;
+        let mut connection = accept(&listener).await;
+        read_messages(&mut connection, 1).await;
+        answer_connect(&mut connection, PROTOCOL_VERSION).await;
+
+        // 接続応答の後にmonitorから届いたプロンプト予約も購読側に渡す
+        let queue_prompt = MonitorToLauncher::QueuePrompt {
+            launcher_id: "launcher".to_string(),
+            text: "run the tests".to_string(),
+        };
+        write_reply(&mut connection, &queue_prompt).await;
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            let message = tokio::time::timeout(Duration::from_secs(1), receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            received.push(message);
+        }
+        assert!(matches!(
+            received[0],
+            MonitorToLauncher::ConnectResponse { .. }
+        ));
+        assert_eq!(received[1], queue_prompt);
+    }
+
+    #[tokio::test]
+    async fn test_silent_monitor_is_treated_as_legacy() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("monitor.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let sender = unix_sender(socket_path);
+
+        // 接続応答を返さない（v1の）monitor
+        send_connect(&sender).await;
+        sender
+            .send_process_exit("session".to_string(), 0, None, 5)
+            .await
+            .unwrap();
+        sender
+            .send_status_update("session".to_string(), SessionStatus::Idle, Utc::now(), None)
+            .await
+            .unwrap();
+        sender.send_disconnect().await.unwrap();
+
+        let mut connection = accept(&listener).await;
+        let mut lines = Vec::new();
+        while let Some(line) = connection.next_line().await.unwrap() {
+            lines.push(line);
+        }
+        sender.flush().await.unwrap();
+
+        // 新しい形式のConnectの後、v1の形式で送り直す
+        assert_eq!(lines.len(), 4, "{lines:?}");
+        assert!(lines[0].contains(r#""tool_args":["--resume"]"#));
+        assert!(lines[1].contains(r#""claude_args":["--resume"]"#));
+        assert!(!lines[1].contains("tool_args"));
+        // v1が解釈できない Exited は送信しない
+        let messages: Vec<LauncherToMonitor> = lines[2..]
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(matches!(
+            messages[0],
+            LauncherToMonitor::StateUpdate {
+                status: SessionStatus::Idle,
+                ..
+            }
+        ));
+        assert!(matches!(messages[1], LauncherToMonitor::Disconnect { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_slow_v2_monitor_is_not_downgraded() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("monitor.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let sender = unix_sender(socket_path);
+
+        send_connect(&sender).await;
+        sender
+            .send_process_exit("session".to_string(), 0, None, 5)
+            .await
+            .unwrap();
+
+        // 負荷が高く接続応答が遅れるv2のmonitor
+        let mut connection = accept(&listener).await;
+        let connect = connection.next_line().await.unwrap().unwrap();
+        assert!(connect.contains(r#""tool_args":["--resume"]"#));
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        answer_connect(&mut connection, PROTOCOL_VERSION).await;
+
+        // Connectを送り直さず、拡張メッセージもそのまま届く
+        let messages = read_messages(&mut connection, 1).await;
+        assert!(matches!(messages[0], LauncherToMonitor::Exited { .. }));
+        sender.send_disconnect().await.unwrap();
+        sender.flush().await.unwrap();
+        assert!(matches!(
+            read_messages(&mut connection, 1).await[0],
+            LauncherToMonitor::Disconnect { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_monitor_closing_on_connect_is_treated_as_legacy() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("monitor.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let sender = unix_sender(socket_path);
+        let started = std::time::Instant::now();
+
+        send_connect(&sender).await;
+        sender.send_disconnect().await.unwrap();
+
+        // 新しい形式のConnectを解釈できず接続を閉じる古いmonitor
+        let mut connection = accept(&listener).await;
+        let connect = connection.next_line().await.unwrap().unwrap();
+        assert!(connect.contains("tool_args"));
+        drop(connection);
+
+        // 待機上限を待たずに接続し直し、v1の形式でConnectを送る
+        let mut connection = accept(&listener).await;
+        let connect = connection.next_line().await.unwrap().unwrap();
+        assert!(connect.contains(r#""claude_args":["--resume"]"#));
+        assert!(started.elapsed() < LEGACY_DETECT_TIMEOUT);
+        sender.flush().await.unwrap();
+        assert!(matches!(
+            read_messages(&mut connection, 1).await[0],
+            LauncherToMonitor::Disconnect { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_monitor_below_min_protocol_version_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("monitor.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let sender = unix_sender(socket_path);
+
+        send_connect(&sender).await;
+        let mut connection = accept(&listener).await;
+        read_messages(&mut connection, 1).await;
+        answer_connect(&mut connection, climonitor_shared::MIN_PROTOCOL_VERSION - 1).await;
+
+        // 接続を打ち切り、以降のメッセージは送信しない
+        sender
+            .send_status_update("session".to_string(), SessionStatus::Busy, Utc::now(), None)
+            .await
+            .unwrap();
+        sender.flush().await.unwrap();
+        assert!(connection.next_line().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_monitor_does_not_block_senders() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("monitor.sock");
+        // 接続は受け付けるが読み出さないmonitor（ソケットバッファが埋まると書き込みが進まない）
+        let _listener = UnixListener::bind(&socket_path).unwrap();
+        let sender = unix_sender(socket_path);
+        let started = std::time::Instant::now();
+
+        // 送信キューが満杯になっても、PTY出力から送る通知は待たずに破棄される
+        let body = "x".repeat(64 * 1024);
+        let mut dropped = 0;
+        for _ in 0..QUEUE_CAPACITY * 2 {
+            if sender
+                .send_terminal_notification("session".to_string(), None, body.clone())
+                .await
+                .is_err()
+            {
+                dropped += 1;
+            }
+        }
+        assert!(dropped > 0);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        // 終了時のDisconnectとflushもそれぞれ上限までしか待たない
+        let _ = sender.send_disconnect().await;
+        assert!(sender.flush().await.is_err());
+        assert!(started.elapsed() < FLUSH_TIMEOUT * 2 + Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_flush_reports_undelivered_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let sender = unix_sender(dir.path().join("missing.sock"));
+
+        sender.send_disconnect().await.unwrap();
+        let error = sender.flush().await.unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("1 message(s) could not be delivered"));
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use climonitor_shared::{ConnectionConfig, LauncherToMonitor, MonitorToLauncher, PROTOCOL_VERSION};
+
+use super::MessageTransport;
+
+/// TCP 送信路（JSON Lines、Unix Socketと同じ形式）
+pub struct TcpTransport {
+    writer: OwnedWriteHalf,
+    reader_handle: JoinHandle<()>,
+    /// 接続先monitorのプロトコルバージョン（シリアライズ形式の選択用）
+    peer_protocol_version: u32,
+}
+
+impl TcpTransport {
+    pub async fn connect(
+        config: &ConnectionConfig,
+        monitor_tx: broadcast::Sender<MonitorToLauncher>,
+    ) -> Result<Self> {
+        match config {
+            ConnectionConfig::Tcp { bind_addr, .. } => {
+                let stream = TcpStream::connect(bind_addr)
+                    .await
+                    .with_context(|| format!("Failed to connect to TCP monitor: {bind_addr}"))?;
+                // 状態更新は小さなメッセージのため即時送信する
+                stream.set_nodelay(true)?;
+                let (reader, writer) = stream.into_split();
+                Ok(Self {
+                    writer,
+                    reader_handle: super::spawn_json_lines_reader(reader, monitor_tx),
+                    peer_protocol_version: PROTOCOL_VERSION,
+                })
+            }
+            _ => anyhow::bail!("TCP transport requires TCP configuration"),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageTransport for TcpTransport {
+    async fn send(&mut self, message: &LauncherToMonitor) -> Result<()> {
+        // メッセージをJSONにシリアライズして1行で送信
+        let mut line = message.to_json(self.peer_protocol_version)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+
+    fn set_peer_protocol_version(&mut self, version: u32) {
+        self.peer_protocol_version = version;
+    }
+
+    fn is_closed(&self) -> bool {
+        self.reader_handle.is_finished()
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.writer.shutdown().await?;
+        Ok(())
+    }
+}
+
+impl Drop for TcpTransport {
+    fn drop(&mut self) {
+        self.reader_handle.abort();
+    }
+}
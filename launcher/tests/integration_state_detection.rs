@@ -70,7 +70,10 @@ fn test_claude_idle_detection_with_real_ui() {
     let mut state_changes = Vec::new();
 
     for line in &idle_sequence {
-        if let Some(status) = detector.process_output(line) {
+        if let Some(status) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             state_changes.push(status);
         }
     }
@@ -103,7 +106,10 @@ fn test_claude_waiting_input_detection() {
     let mut detected_waiting = false;
 
     for line in &waiting_sequence {
-        if let Some(status) = detector.process_output(line) {
+        if let Some(status) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             println!("Waiting input detected: {status:?}");
             if status == SessionStatus::WaitingInput {
                 detected_waiting = true;
@@ -187,7 +193,10 @@ fn test_claude_error_state_detection() {
     let mut error_detected = false;
 
     for line in &error_sequence {
-        if let Some(status) = detector.process_output(line) {
+        if let Some(status) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             println!("Error state detected: {status:?}");
             if status == SessionStatus::Error {
                 error_detected = true;
@@ -256,7 +265,10 @@ fn test_claude_large_output_performance() {
     let mut large_output_handled = false;
 
     for line in &large_sequence {
-        if let Some(status) = detector.process_output(line) {
+        if let Some(status) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             if status == SessionStatus::Busy {
                 large_output_handled = true;
             }
@@ -309,7 +321,10 @@ fn test_gemini_busy_detection_with_real_data() {
     let mut detected_busy = false;
 
     for (i, line) in gemini_busy_sequence.iter().enumerate() {
-        if let Some(status) = detector.process_output(line) {
+        if let Some(status) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             println!("Gemini Line {}: State detected: {:?}", i + 1, status);
             if status == SessionStatus::Busy {
                 detected_busy = true;
@@ -336,7 +351,10 @@ fn test_gemini_spinner_animation_detection() {
     for (i, frame) in spinner_frames.iter().enumerate() {
         let line = format!("│ {frame} Waiting for auth... (Press ESC to cancel)                                                                                                                                │");
 
-        if let Some(status) = detector.process_output(&line) {
+        if let Some(status) = detector
+            .process_output(&line)
+            .map(|decision| decision.status)
+        {
             println!("Spinner frame {}: {} -> State: {:?}", i + 1, frame, status);
         }
     }
@@ -366,7 +384,10 @@ fn test_gemini_idle_prompt_detection() {
     let mut state_changes = Vec::new();
 
     for line in &gemini_idle_sequence {
-        if let Some(status) = detector.process_output(line) {
+        if let Some(status) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             state_changes.push(status);
         }
     }
@@ -398,7 +419,10 @@ fn test_gemini_welcome_screen_processing() {
     ];
 
     for line in &welcome_sequence {
-        if let Some(status) = detector.process_output(line) {
+        if let Some(status) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             println!("Welcome screen state change: {status:?}");
         }
     }
@@ -422,12 +446,20 @@ fn test_gemini_vs_claude_pattern_differentiation() {
     let claude_pattern = "│ Processing... esc to interrupt) │";
 
     // Geminiパターンの処理
-    let gemini_result = gemini_detector.process_output(gemini_pattern);
-    let claude_on_gemini = claude_detector.process_output(gemini_pattern);
+    let gemini_result = gemini_detector
+        .process_output(gemini_pattern)
+        .map(|decision| decision.status);
+    let claude_on_gemini = claude_detector
+        .process_output(gemini_pattern)
+        .map(|decision| decision.status);
 
     // Claudeパターンの処理
-    let claude_result = claude_detector.process_output(claude_pattern);
-    let gemini_on_claude = gemini_detector.process_output(claude_pattern);
+    let claude_result = claude_detector
+        .process_output(claude_pattern)
+        .map(|decision| decision.status);
+    let gemini_on_claude = gemini_detector
+        .process_output(claude_pattern)
+        .map(|decision| decision.status);
 
     println!("Gemini pattern on Gemini detector: {gemini_result:?}");
     println!("Gemini pattern on Claude detector: {claude_on_gemini:?}");
@@ -21,12 +21,16 @@ fn test_claude_tool_command_generation() {
     // プロジェクト名の抽出テスト（引数から）
     let args = vec!["--project".to_string(), "test-project".to_string()];
     let test_dir = create_test_working_dir();
-    let project_name = claude_tool.guess_project_name(&args, &test_dir);
+    let project_name = claude_tool
+        .guess_project_name(&args, &test_dir)
+        .map(|project| project.name);
     assert_eq!(project_name, Some("test-project".to_string()));
 
     // ディレクトリからのプロジェクト名抽出
     let no_args: Vec<String> = vec![];
-    let dir_project_name = claude_tool.guess_project_name(&no_args, &test_dir);
+    let dir_project_name = claude_tool
+        .guess_project_name(&no_args, &test_dir)
+        .map(|project| project.name);
     assert_eq!(dir_project_name, Some("test".to_string()));
 }
 
@@ -42,7 +46,9 @@ fn test_gemini_tool_command_generation() {
     // プロジェクト名の抽出テスト（Geminiは通常--projectを使わない）
     let args = vec!["--model".to_string(), "gemini-pro".to_string()];
     let test_dir = create_test_working_dir();
-    let project_name = gemini_tool.guess_project_name(&args, &test_dir);
+    let project_name = gemini_tool
+        .guess_project_name(&args, &test_dir)
+        .map(|project| project.name);
     // 引数からは取得できないので、ディレクトリ名が使われる
     assert_eq!(project_name, Some("test".to_string()));
 }
@@ -69,7 +75,9 @@ fn test_unicode_project_names() {
     let japanese_args = vec!["--project".to_string(), "日本語プロジェクト".to_string()];
     let test_dir = create_test_working_dir();
 
-    let project_name = claude_tool.guess_project_name(&japanese_args, &test_dir);
+    let project_name = claude_tool
+        .guess_project_name(&japanese_args, &test_dir)
+        .map(|project| project.name);
     assert_eq!(project_name, Some("日本語プロジェクト".to_string()));
 }
 
@@ -81,12 +89,16 @@ fn test_edge_case_project_names() {
 
     // 空のプロジェクト名
     let empty_args = vec!["--project".to_string(), "".to_string()];
-    let empty_project = claude_tool.guess_project_name(&empty_args, &test_dir);
+    let empty_project = claude_tool
+        .guess_project_name(&empty_args, &test_dir)
+        .map(|project| project.name);
     assert_eq!(empty_project, Some("".to_string()));
 
     // スペースを含むプロジェクト名
     let space_args = vec!["--project".to_string(), "project with spaces".to_string()];
-    let space_project = claude_tool.guess_project_name(&space_args, &test_dir);
+    let space_project = claude_tool
+        .guess_project_name(&space_args, &test_dir)
+        .map(|project| project.name);
     assert_eq!(space_project, Some("project with spaces".to_string()));
 
     // 特殊文字を含むプロジェクト名
@@ -94,7 +106,9 @@ fn test_edge_case_project_names() {
         "--project".to_string(),
         "project-with_special.chars".to_string(),
     ];
-    let special_project = claude_tool.guess_project_name(&special_args, &test_dir);
+    let special_project = claude_tool
+        .guess_project_name(&special_args, &test_dir)
+        .map(|project| project.name);
     assert_eq!(
         special_project,
         Some("project-with_special.chars".to_string())
@@ -109,12 +123,16 @@ fn test_missing_project_args() {
 
     // --project 引数がない場合（ディレクトリ名が使われる）
     let no_project_args = vec!["--help".to_string()];
-    let no_project = claude_tool.guess_project_name(&no_project_args, &test_dir);
+    let no_project = claude_tool
+        .guess_project_name(&no_project_args, &test_dir)
+        .map(|project| project.name);
     assert_eq!(no_project, Some("test".to_string()));
 
     // --project はあるが値がない場合
     let incomplete_args = vec!["--project".to_string()];
-    let incomplete_project = claude_tool.guess_project_name(&incomplete_args, &test_dir);
+    let incomplete_project = claude_tool
+        .guess_project_name(&incomplete_args, &test_dir)
+        .map(|project| project.name);
     // 値がない場合はディレクトリ名が使われる
     assert_eq!(incomplete_project, Some("test".to_string()));
 }
@@ -132,7 +150,9 @@ fn test_multiple_project_args() {
         "second-project".to_string(),
     ];
 
-    let project_name = claude_tool.guess_project_name(&multiple_args, &test_dir);
+    let project_name = claude_tool
+        .guess_project_name(&multiple_args, &test_dir)
+        .map(|project| project.name);
     // 実装では最初の--projectが優先される
     assert_eq!(project_name, Some("first-project".to_string()));
 }
@@ -145,18 +165,24 @@ fn test_directory_based_project_names() {
 
     // 現在のディレクトリ
     let current_dir = std::env::current_dir().unwrap();
-    let current_project = claude_tool.guess_project_name(&no_args, &current_dir);
+    let current_project = claude_tool
+        .guess_project_name(&no_args, &current_dir)
+        .map(|project| project.name);
     assert!(current_project.is_some());
 
     // ルートディレクトリ
     let root_dir = std::path::PathBuf::from("/");
-    let root_project = claude_tool.guess_project_name(&no_args, &root_dir);
+    let root_project = claude_tool
+        .guess_project_name(&no_args, &root_dir)
+        .map(|project| project.name);
     // ルートディレクトリの場合、現在のディレクトリ名が使われる
     assert!(root_project.is_some());
 
     // 存在しないディレクトリ
     let nonexistent_dir = std::path::PathBuf::from("/nonexistent/directory");
-    let nonexistent_project = claude_tool.guess_project_name(&no_args, &nonexistent_dir);
+    let nonexistent_project = claude_tool
+        .guess_project_name(&no_args, &nonexistent_dir)
+        .map(|project| project.name);
     assert_eq!(nonexistent_project, Some("directory".to_string()));
 }
 
@@ -205,7 +231,9 @@ fn test_gemini_tool_specifics() {
 
     // プロジェクト名推測（ディレクトリベース）
     let test_dir = create_test_working_dir();
-    let project = gemini_tool.guess_project_name(&args, &test_dir);
+    let project = gemini_tool
+        .guess_project_name(&args, &test_dir)
+        .map(|project| project.name);
     assert_eq!(project, Some("test".to_string()));
 }
 
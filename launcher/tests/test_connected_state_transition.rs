@@ -20,7 +20,9 @@ fn test_connected_state_maintained_without_execution() {
     ];
 
     for line in idle_ui_sequence.iter() {
-        let result = detector.process_output(line);
+        let result = detector
+            .process_output(line)
+            .map(|decision| decision.status);
         // 状態変化が発生しないことを確認
         assert!(
             result.is_none(),
@@ -55,7 +57,10 @@ fn test_connected_to_busy_transition() {
 
     let mut state_changed = false;
     for line in busy_sequence.iter() {
-        if let Some(new_state) = detector.process_output(line) {
+        if let Some(new_state) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             assert_eq!(
                 new_state,
                 SessionStatus::Busy,
@@ -106,7 +111,10 @@ fn test_busy_to_idle_transition() {
 
     let mut state_changed = false;
     for line in idle_sequence.iter() {
-        if let Some(new_state) = detector.process_output(line) {
+        if let Some(new_state) = detector
+            .process_output(line)
+            .map(|decision| decision.status)
+        {
             assert_eq!(
                 new_state,
                 SessionStatus::Idle,
@@ -135,7 +143,9 @@ fn test_no_direct_connected_to_idle_transition() {
     ];
 
     for line in sequence.iter() {
-        let result = detector.process_output(line);
+        let result = detector
+            .process_output(line)
+            .map(|decision| decision.status);
         // Connected→Idleの直接遷移は発生しない
         assert!(
             result.is_none() || result != Some(SessionStatus::Idle),
@@ -0,0 +1,138 @@
+// 状態判定の根拠（ルール・該当行・確信度）と出力停止の判定のテスト
+
+use climonitor_launcher::activity::ActivitySnapshot;
+use climonitor_launcher::screen_buffer::TerminalNotification;
+use climonitor_launcher::screen_claude_detector::ScreenClaudeStateDetector;
+use climonitor_launcher::screen_gemini_detector::ScreenGeminiStateDetector;
+use climonitor_launcher::state_detector::{decision_from_notification, StateDetector};
+use climonitor_shared::{EvidenceLine, SessionStatus};
+use std::time::Duration;
+
+const PROMPT_BOX: &str = "╭──────────────────╮\r\n│ >                │\r\n╰──────────────────╯\r\n";
+
+#[test]
+fn test_claude_approval_prompt_evidence() {
+    let mut detector = ScreenClaudeStateDetector::new(false);
+    let dialog = "● Write(hello.txt)\r\n╭──────────────────────╮\r\n│ Do you want to edit? │\r\n╰──────────────────────╯\r\n";
+
+    let decision = detector.process_output(dialog).expect("state change");
+    assert_eq!(decision.status, SessionStatus::WaitingInput);
+    assert_eq!(
+        decision.evidence.rules,
+        vec!["claude.approval_prompt(\"Do you want\")"]
+    );
+    assert_eq!(decision.evidence.confidence, 0.85);
+    assert_eq!(
+        decision.evidence.lines,
+        vec![EvidenceLine {
+            row: 2,
+            text: "│ Do you want to edit? │".to_string(),
+        }]
+    );
+    assert_eq!(detector.current_evidence(), Some(&decision.evidence));
+}
+
+#[test]
+fn test_claude_busy_and_idle_evidence() {
+    let mut detector = ScreenClaudeStateDetector::new(false);
+    assert_eq!(detector.current_evidence(), None);
+
+    let busy = detector
+        .process_output(&format!(
+            "✻ Thinking… (3s · esc to interrupt)\r\n{PROMPT_BOX}"
+        ))
+        .expect("busy");
+    assert_eq!(busy.status, SessionStatus::Busy);
+    assert_eq!(
+        busy.evidence.rules,
+        vec!["claude.esc_to_interrupt_appeared"]
+    );
+    assert_eq!(busy.evidence.lines[0].row, 0);
+    assert!(busy.evidence.lines[0].text.contains("esc to interrupt"));
+
+    // 消えた場合は最後に見えていた行を根拠にする
+    let idle = detector
+        .process_output(&format!("\x1b[2J\x1b[H● Done\r\n{PROMPT_BOX}"))
+        .expect("idle");
+    assert_eq!(idle.status, SessionStatus::Idle);
+    assert_eq!(
+        idle.evidence.rules,
+        vec!["claude.esc_to_interrupt_disappeared"]
+    );
+    assert_eq!(idle.evidence.lines, busy.evidence.lines);
+    assert_eq!(
+        idle.evidence.describe(),
+        vec![
+            "claude.esc_to_interrupt_disappeared (80%)".to_string(),
+            "L1: ✻ Thinking… (3s · esc to interrupt)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_gemini_evidence() {
+    let mut detector = ScreenGeminiStateDetector::new(false);
+
+    let busy = detector
+        .process_output("⠏ Generating (esc to cancel, 2s)\r\n")
+        .expect("busy");
+    assert_eq!(busy.status, SessionStatus::Busy);
+    assert_eq!(busy.evidence.rules, vec!["gemini.esc_to_cancel"]);
+    assert_eq!(busy.evidence.lines[0].row, 0);
+
+    let waiting = detector
+        .process_output("\x1b[2J\x1b[H\r\n\r\n⠏ Waiting for user confirmation...\r\n")
+        .expect("waiting");
+    assert_eq!(waiting.status, SessionStatus::WaitingInput);
+    assert_eq!(
+        waiting.evidence.rules,
+        vec!["gemini.waiting_for_confirmation"]
+    );
+    assert_eq!(waiting.evidence.lines[0].row, 2);
+    assert_eq!(detector.current_evidence(), Some(&waiting.evidence));
+}
+
+#[test]
+fn test_notification_decision() {
+    let notification = |body: &str| TerminalNotification {
+        title: None,
+        body: body.to_string(),
+    };
+
+    let decision =
+        decision_from_notification(&notification("Claude needs your permission to use Bash"));
+    assert_eq!(decision.status, SessionStatus::WaitingInput);
+    assert_eq!(decision.evidence.confidence, 0.95);
+    assert!(decision.evidence.rules[0].starts_with("notification.confirmation("));
+
+    let decision = decision_from_notification(&notification("Claude is waiting for your input"));
+    assert_eq!(decision.status, SessionStatus::Idle);
+    assert_eq!(decision.evidence.rules, vec!["notification.attention"]);
+}
+
+#[test]
+fn test_stalled_busy_detection() {
+    let activity = |since_output_secs| ActivitySnapshot {
+        output_bytes_per_sec: 0,
+        since_output: Some(Duration::from_secs(since_output_secs)),
+        since_input: None,
+    };
+    let stall_after = Duration::from_secs(300);
+
+    let mut detector = ScreenGeminiStateDetector::new(false);
+    detector
+        .process_output("⠏ Generating (esc to cancel, 2s)\r\n")
+        .expect("busy");
+    assert_eq!(detector.detect_stall(&activity(299), stall_after), None);
+
+    let evidence = detector
+        .detect_stall(&activity(360), stall_after)
+        .expect("stalled");
+    assert_eq!(evidence.rules, vec!["activity.output_stalled(6m)"]);
+
+    // Busy以外は出力が止まっていても停止とはみなさない
+    detector
+        .process_output("\x1b[2J\x1b[H\r\n⠏ Waiting for user confirmation...\r\n")
+        .expect("waiting");
+    assert_eq!(detector.detect_stall(&activity(360), stall_after), None);
+}
@@ -0,0 +1,42 @@
+// monitorが起動していなくてもCLIツールの実行結果が変わらないことをテストする
+
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt;
+
+use climonitor_launcher::claude_tool::ClaudeTool;
+use climonitor_launcher::tool_wrapper::ToolWrapper;
+use climonitor_launcher::transport_client::TransportLauncherClient;
+use climonitor_shared::ConnectionConfig;
+
+#[test]
+fn test_tool_succeeds_without_monitor() {
+    // 正常終了するだけの偽の claude を PATH の先頭に置く
+    let dir = tempfile::tempdir().unwrap();
+    let fake_claude = dir.path().join("claude");
+    std::fs::write(&fake_claude, "#!/bin/sh\nexit 0\n").unwrap();
+    std::fs::set_permissions(&fake_claude, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = std::env::var("PATH").unwrap_or_default();
+    std::env::set_var("PATH", format!("{}:{path}", dir.path().display()));
+
+    // 誰も待ち受けていないsocket
+    let config = ConnectionConfig::Unix {
+        socket_path: dir.path().join("no-monitor.sock"),
+    };
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let result = runtime.block_on(async {
+        let tool_wrapper = ToolWrapper::new(Box::new(ClaudeTool::new()), Vec::new());
+        let mut launcher = TransportLauncherClient::new(tool_wrapper, config, false, None)
+            .await
+            .unwrap();
+        launcher.run_claude().await
+    });
+    // stdinの読み取りはブロッキングスレッドで行われるので終了を待たない
+    runtime.shutdown_background();
+
+    assert!(
+        result.is_ok(),
+        "launcher failed without monitor: {result:?}"
+    );
+}
@@ -0,0 +1,111 @@
+// 画面内容のキャッシュと世代番号のテスト（変化がなければ再解析しない）
+
+use climonitor_launcher::screen_buffer::ScreenBuffer;
+use climonitor_launcher::screen_claude_detector::ScreenClaudeStateDetector;
+use climonitor_launcher::state_detector::StateDetector;
+use climonitor_shared::SessionStatus;
+
+const PROMPT_BOX: &str = "╭──────────────────╮\r\n│ > hello          │\r\n╰──────────────────╯\r\n";
+
+#[test]
+fn test_generation_advances_only_on_text_changes() {
+    let mut buffer = ScreenBuffer::new(10, 20, false);
+    let initial = buffer.generation();
+
+    buffer.process_data(b"hello\r\nworld");
+    let after_write = buffer.generation();
+    assert!(after_write > initial);
+    assert_eq!(buffer.changed_rows(), &[0, 1]);
+
+    // カーソル移動・文字属性・タイトル・同じ内容の再描画では変わらない
+    buffer.process_data(b"\x1b[H\x1b[1;31m\x1b]0;title\x07hello\x1b[0m\x1b[2;1H\x1b[Kworld");
+    assert_eq!(buffer.generation(), after_write);
+    assert!(buffer.changed_rows().is_empty());
+
+    buffer.process_data(b"\x1b[1;1Hj");
+    assert_eq!(buffer.generation(), after_write + 1);
+    assert_eq!(buffer.changed_rows(), &[0]);
+    assert_eq!(buffer.screen_lines()[0].trim_end(), "jello");
+}
+
+#[test]
+fn test_changed_rows_after_scroll() {
+    let mut buffer = ScreenBuffer::new(3, 10, false);
+    buffer.process_data(b"1\r\n2\r\n3");
+    buffer.process_data(b"\r\n4");
+    assert_eq!(buffer.changed_rows(), &[0, 1, 2]);
+    let lines: Vec<&str> = buffer.screen_lines().iter().map(|l| l.trim_end()).collect();
+    assert_eq!(lines, vec!["2", "3", "4"]);
+}
+
+#[test]
+fn test_cached_lines_match_bytewise_processing() {
+    let data = format!(
+        "● 日本語の出力\r\n{PROMPT_BOX}\x1b[3A\x1b[2K\x1b[G│ > typed          │\x1b[2B\r\n\x1b[5;3Hxyz\x1b[1P"
+    );
+
+    let mut whole = ScreenBuffer::new(8, 20, false);
+    whole.process_data(data.as_bytes());
+
+    let mut bytewise = ScreenBuffer::new(8, 20, false);
+    for byte in data.as_bytes() {
+        bytewise.process_data(std::slice::from_ref(byte));
+    }
+
+    assert_eq!(whole.screen_lines(), bytewise.screen_lines());
+    assert_eq!(whole.get_screen_lines(), whole.screen_lines());
+}
+
+#[test]
+fn test_ui_box_cache_is_invalidated() {
+    let mut buffer = ScreenBuffer::new(10, 20, false);
+    buffer.process_data(PROMPT_BOX.as_bytes());
+    assert_eq!(buffer.ui_boxes()[0].content_lines, vec!["> hello"]);
+
+    buffer.process_data("\x1b[2;3H> bye  ".as_bytes());
+    assert_eq!(buffer.ui_boxes()[0].content_lines, vec!["> bye"]);
+
+    buffer.process_data(b"\x1b[2J");
+    assert!(buffer.ui_boxes().is_empty());
+}
+
+#[test]
+fn test_detector_keeps_state_when_screen_is_unchanged() {
+    let mut detector = ScreenClaudeStateDetector::new(false);
+    let dialog =
+        "╭──────────────────────╮\r\n│ Do you want to edit? │\r\n╰──────────────────────╯\r\n";
+    assert_eq!(
+        detector
+            .process_output(dialog)
+            .map(|decision| decision.status),
+        Some(SessionStatus::WaitingInput)
+    );
+
+    // 画面が変わらない出力でも同じ判定を返す
+    assert_eq!(
+        detector
+            .process_output("\x1b[?25l\x1b[H\x1b[?25h")
+            .map(|decision| decision.status),
+        Some(SessionStatus::WaitingInput)
+    );
+    assert_eq!(*detector.current_state(), SessionStatus::WaitingInput);
+
+    // リサイズ後の空の画面は解析し直す
+    detector.resize_screen_buffer(10, 40);
+    assert!(detector.process_output("\x1b[H").is_none());
+    assert_eq!(detector.get_ui_box_content(), None);
+}
+
+#[test]
+fn test_render_row_reproduces_attributes() {
+    let mut buffer = ScreenBuffer::new(3, 12, false);
+    buffer.process_data(b"top\r\n\r\n\x1b[1;31m> \x1b[0;94m\xe6\x97\xa5\xe6\x9c\xac\x1b[0m ok");
+
+    // 属性が変わる位置でだけSGRを出力し、全角文字の右半分は出力しない
+    assert_eq!(
+        buffer.render_row(2),
+        "\x1b[0;1;31m> \x1b[0;94m日本\x1b[0m ok   \x1b[0m"
+    );
+    assert_eq!(buffer.render_row(0), "\x1b[0mtop         \x1b[0m");
+    assert_eq!(buffer.render_row(5), "");
+}
@@ -0,0 +1,165 @@
+// OSCシーケンス（タイトル・ハイパーリンク・通知・進捗）の取り込みテスト
+
+use climonitor_launcher::screen_buffer::{ScreenBuffer, TerminalNotification, TerminalProgress};
+use climonitor_launcher::screen_claude_detector::ScreenClaudeStateDetector;
+use climonitor_launcher::screen_gemini_detector::ScreenGeminiStateDetector;
+use climonitor_launcher::state_detector::{status_from_notification, StateDetector};
+use climonitor_shared::SessionStatus;
+
+#[test]
+fn test_window_title() {
+    let mut buffer = ScreenBuffer::new(24, 80, false);
+    assert_eq!(buffer.title(), None);
+
+    // BEL終端
+    buffer.process_data(b"\x1b]0;\xe2\x9c\xb3 Fix login bug\x07");
+    assert_eq!(buffer.title(), Some("✳ Fix login bug"));
+
+    // ST終端、チャンク分割、本文中の ';'
+    buffer.process_data(b"\x1b]2;build; test");
+    buffer.process_data(b"\x1b\\");
+    assert_eq!(buffer.title(), Some("build; test"));
+
+    // 空のタイトルで解除
+    buffer.process_data(b"\x1b]2;\x07");
+    assert_eq!(buffer.title(), None);
+
+    // タイトルは画面に描画されない
+    assert!(buffer
+        .get_screen_lines()
+        .iter()
+        .all(|l| l.trim().is_empty()));
+}
+
+#[test]
+fn test_hyperlinks() {
+    let mut buffer = ScreenBuffer::new(24, 80, false);
+    buffer.process_data(b"see \x1b]8;;https://example.com/a;b\x1b\\docs\x1b]8;;\x1b\\ here");
+    buffer.process_data(b"\x1b]8;id=1;file:///tmp/x.rs\x07x.rs\x1b]8;;\x07");
+
+    let links: Vec<_> = buffer.hyperlinks().cloned().collect();
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].uri, "https://example.com/a;b");
+    assert_eq!(links[0].text, "docs");
+    assert_eq!(links[1].uri, "file:///tmp/x.rs");
+    assert_eq!(links[1].text, "x.rs");
+    assert!(buffer.get_screen_lines()[0].starts_with("see docs herex.rs"));
+}
+
+#[test]
+fn test_notifications() {
+    let mut buffer = ScreenBuffer::new(24, 80, false);
+    buffer.process_data(b"\x1b]9;Claude is waiting for your input\x07");
+    buffer.process_data(b"\x1b]777;notify;Claude Code;Task done; all tests pass\x1b\\");
+
+    assert_eq!(
+        buffer.take_notifications(),
+        vec![
+            TerminalNotification {
+                title: None,
+                body: "Claude is waiting for your input".to_string(),
+            },
+            TerminalNotification {
+                title: Some("Claude Code".to_string()),
+                body: "Task done; all tests pass".to_string(),
+            },
+        ]
+    );
+    assert!(buffer.take_notifications().is_empty());
+}
+
+#[test]
+fn test_progress() {
+    let mut buffer = ScreenBuffer::new(24, 80, false);
+    buffer.process_data(b"\x1b]9;4;1;42\x07");
+    assert_eq!(buffer.progress(), Some(TerminalProgress::Normal(42)));
+    buffer.process_data(b"\x1b]9;4;2;250\x07");
+    assert_eq!(buffer.progress(), Some(TerminalProgress::Error(100)));
+    buffer.process_data(b"\x1b]9;4;3\x07");
+    assert_eq!(buffer.progress(), Some(TerminalProgress::Indeterminate));
+    buffer.process_data(b"\x1b]9;4;0;0\x07");
+    assert_eq!(buffer.progress(), None);
+
+    // 進捗以外のConEmuサブコマンドは通知として扱わない
+    buffer.process_data(b"\x1b]9;9;/home/user\x07");
+    assert!(buffer.take_notifications().is_empty());
+}
+
+#[test]
+fn test_status_from_notification() {
+    let notification = |body: &str| TerminalNotification {
+        title: None,
+        body: body.to_string(),
+    };
+    assert_eq!(
+        status_from_notification(&notification("Claude needs your permission to use Bash")),
+        SessionStatus::WaitingInput
+    );
+    assert_eq!(
+        status_from_notification(&notification("Claude is waiting for your input")),
+        SessionStatus::Idle
+    );
+}
+
+#[test]
+fn test_claude_notification_is_state_signal() {
+    let mut detector = ScreenClaudeStateDetector::new(false);
+    assert_eq!(*detector.current_state(), SessionStatus::Connected);
+
+    let status = detector.process_output("\x1b]9;Claude needs your permission to use Bash\x07");
+    assert_eq!(
+        status.map(|decision| decision.status),
+        Some(SessionStatus::WaitingInput)
+    );
+    assert_eq!(*detector.current_state(), SessionStatus::WaitingInput);
+
+    // 通知はlauncherが取り出してmonitorへ転送する
+    let notifications = detector.take_notifications();
+    assert_eq!(notifications.len(), 1);
+    assert!(detector.take_notifications().is_empty());
+
+    detector.process_output("\x1b]777;notify;Claude Code;Claude is waiting for your input\x07");
+    assert_eq!(*detector.current_state(), SessionStatus::Idle);
+}
+
+#[test]
+fn test_claude_title_as_context() {
+    let mut detector = ScreenClaudeStateDetector::new(false);
+
+    // 既定のタイトルはコンテキストにならない
+    detector.process_output("\x1b]0;Claude Code\x07");
+    assert_eq!(detector.get_ui_above_text(), None);
+
+    detector.process_output("\x1b]0;\u{2733} Refactor parser\x07");
+    assert_eq!(
+        detector.get_ui_above_text(),
+        Some("Refactor parser".to_string())
+    );
+    assert_eq!(
+        detector.terminal_title(),
+        Some("\u{2733} Refactor parser".to_string())
+    );
+
+    // ●の行があればそちらを優先
+    detector.process_output("● Reading src/main.rs\r\n");
+    assert_eq!(
+        detector.get_ui_above_text(),
+        Some("Reading src/main.rs".to_string())
+    );
+}
+
+#[test]
+fn test_terminal_state_survives_resize() {
+    let mut detector = ScreenGeminiStateDetector::new(false);
+    detector.process_output("\x1b]2;Gemini - project\x07\x1b]9;4;1;10\x07");
+    detector.resize_screen_buffer(40, 120);
+
+    assert_eq!(
+        detector.terminal_title(),
+        Some("Gemini - project".to_string())
+    );
+    assert_eq!(
+        detector.terminal_progress(),
+        Some(TerminalProgress::Normal(10))
+    );
+}
@@ -0,0 +1,185 @@
+// transportによらず同じMessageSender経由でmonitorに届くことをテストする
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use climonitor_launcher::transports::create_message_sender;
+use climonitor_monitor::config_reload::RuntimeSettings;
+use climonitor_monitor::transports::create_message_receiver;
+use climonitor_shared::transport::MessageHandler;
+use climonitor_shared::{
+    supported_capabilities, CliToolType, ConnectionConfig, LauncherMetadata, LauncherToMonitor,
+    MonitorToLauncher, SessionStatus, PROTOCOL_VERSION,
+};
+
+/// monitor側でIDの衝突を検出したものとして割り当てるID
+const ASSIGNED_ID: &str = "launcher-assigned";
+
+#[derive(Clone, Default)]
+struct RecordingHandler {
+    messages: Arc<Mutex<Vec<LauncherToMonitor>>>,
+    /// 接続を拒否する
+    reject: bool,
+}
+
+#[async_trait]
+impl MessageHandler for RecordingHandler {
+    async fn handle_message(
+        &self,
+        message: LauncherToMonitor,
+    ) -> anyhow::Result<Option<MonitorToLauncher>> {
+        let reply = match &message {
+            LauncherToMonitor::Connect { launcher_id, .. } if self.reject => {
+                Some(MonitorToLauncher::ConnectRejected {
+                    requested_id: launcher_id.clone(),
+                    reason: "protocol version 2 is not supported".to_string(),
+                })
+            }
+            LauncherToMonitor::Connect { launcher_id, .. } => {
+                Some(MonitorToLauncher::ConnectResponse {
+                    requested_id: launcher_id.clone(),
+                    launcher_id: ASSIGNED_ID.to_string(),
+                    protocol_version: PROTOCOL_VERSION,
+                    capabilities: supported_capabilities(),
+                })
+            }
+            _ => None,
+        };
+        self.messages.lock().unwrap().push(message);
+        Ok(reply)
+    }
+}
+
+fn metadata() -> LauncherMetadata {
+    LauncherMetadata {
+        git_branch: Some("feature-x".to_string()),
+        pid: Some(1234),
+        label: Some("reviewer".to_string()),
+        ..Default::default()
+    }
+}
+
+/// monitorを起動し、launcherから Connect → StateUpdate → Disconnect を送信
+///
+/// monitorは接続応答で別のIDを割り当てるか、接続を拒否する
+async fn send_session(config: ConnectionConfig, reject: bool) -> Vec<LauncherToMonitor> {
+    let handler = RecordingHandler {
+        reject,
+        ..Default::default()
+    };
+    let runtime_settings = Arc::new(tokio::sync::RwLock::new(RuntimeSettings::default()));
+    let mut receiver =
+        create_message_receiver(&config, Box::new(handler.clone()), runtime_settings)
+            .await
+            .unwrap();
+    tokio::spawn(async move {
+        let _ = receiver.start_server().await;
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let sender = create_message_sender(&config, "launcher-1".to_string(), false);
+    sender
+        .send_connect(
+            Some("project".to_string()),
+            CliToolType::Claude,
+            Vec::new(),
+            std::env::temp_dir(),
+            metadata(),
+        )
+        .await
+        .unwrap();
+    sender
+        .send_status_update(
+            "session-1".to_string(),
+            SessionStatus::Busy,
+            chrono::Utc::now(),
+            None,
+        )
+        .await
+        .unwrap();
+    sender.send_disconnect().await.unwrap();
+    sender.flush().await.unwrap();
+
+    // monitor側の処理完了を待つ（拒否された場合は届かないメッセージを待ち切る）
+    for _ in 0..50 {
+        if handler.messages.lock().unwrap().len() >= 3 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let messages = handler.messages.lock().unwrap().clone();
+    messages
+}
+
+/// Connect以降はmonitorが割り当てたIDで送信される
+fn assert_session_messages(messages: &[LauncherToMonitor]) {
+    assert_eq!(messages.len(), 3, "unexpected messages: {messages:?}");
+    assert!(matches!(
+        &messages[0],
+        LauncherToMonitor::Connect { launcher_id, metadata: sent, .. }
+            if launcher_id == "launcher-1" && *sent == metadata()
+    ));
+    assert!(matches!(
+        &messages[1],
+        LauncherToMonitor::StateUpdate { launcher_id, status: SessionStatus::Busy, .. }
+            if launcher_id == ASSIGNED_ID
+    ));
+    assert!(matches!(
+        &messages[2],
+        LauncherToMonitor::Disconnect { launcher_id, .. } if launcher_id == ASSIGNED_ID
+    ));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unix_transport_delivers_session_messages() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = ConnectionConfig::Unix {
+        socket_path: dir.path().join("climonitor.sock"),
+    };
+
+    assert_session_messages(&send_session(config, false).await);
+}
+
+/// 空いているポートを確保
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+#[tokio::test]
+async fn test_tcp_transport_delivers_session_messages() {
+    let config = ConnectionConfig::Tcp {
+        bind_addr: format!("127.0.0.1:{}", free_port()),
+        allowed_ips: vec!["127.0.0.1".to_string()],
+    };
+
+    assert_session_messages(&send_session(config, false).await);
+}
+
+#[tokio::test]
+async fn test_grpc_transport_delivers_session_messages() {
+    let port = free_port();
+    let config = ConnectionConfig::Grpc {
+        bind_addr: format!("127.0.0.1:{port}"),
+        allowed_ips: vec!["127.0.0.1".to_string()],
+    };
+
+    assert_session_messages(&send_session(config, false).await);
+}
+
+#[tokio::test]
+async fn test_rejected_connection_stops_sending() {
+    let config = ConnectionConfig::Tcp {
+        bind_addr: format!("127.0.0.1:{}", free_port()),
+        allowed_ips: vec!["127.0.0.1".to_string()],
+    };
+
+    let messages = send_session(config, true).await;
+    assert_eq!(messages.len(), 1, "unexpected messages: {messages:?}");
+    assert!(matches!(&messages[0], LauncherToMonitor::Connect { .. }));
+}
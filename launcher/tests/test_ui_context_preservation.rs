@@ -158,3 +158,20 @@ fn test_context_preservation_during_state_transitions() {
         Some("ファイル処理中...".to_string())
     );
 }
+
+#[test]
+fn test_wide_character_split_across_reads() {
+    let mut detector = create_state_detector(CliToolType::Claude, false);
+
+    // PTYの読み込み境界が全角文字（3バイト）の途中にかかった場合
+    let output = "● ファイルを読み込んでいます...\n".as_bytes();
+    let split = "● フ".len() - 1;
+    assert!(std::str::from_utf8(&output[..split]).is_err());
+    detector.process_bytes(&output[..split]);
+    detector.process_bytes(&output[split..]);
+
+    assert_eq!(
+        detector.get_ui_above_text(),
+        Some("ファイルを読み込んでいます...".to_string())
+    );
+}
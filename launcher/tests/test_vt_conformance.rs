@@ -0,0 +1,310 @@
+// VTシーケンスの適合性テスト（実際の端末と同じ画面になること）
+//
+// 各ケースの末尾で '@' を出力し、カーソル位置も画面上で検証する。
+
+use climonitor_launcher::screen_buffer::ScreenBuffer;
+
+struct Case {
+    name: &'static str,
+    rows: usize,
+    cols: usize,
+    input: &'static str,
+    expected: &'static [&'static str],
+}
+
+const CASES: &[Case] = &[
+    Case {
+        name: "DECSC/DECRC (ESC 7 / ESC 8)",
+        rows: 3,
+        cols: 10,
+        input: "ab\x1b7\x1b[3;5Hxy\x1b8@",
+        expected: &["ab@", "", "    xy"],
+    },
+    Case {
+        name: "SCOSC/SCORC (CSI s / CSI u)",
+        rows: 3,
+        cols: 10,
+        input: "\x1b[2;3H\x1b[s\x1b[Hzz\x1b[u@",
+        expected: &["zz", "  @", ""],
+    },
+    Case {
+        name: "DECRC without DECSC goes home",
+        rows: 2,
+        cols: 10,
+        input: "abc\x1b8@",
+        expected: &["@bc", ""],
+    },
+    Case {
+        name: "ECH (CSI X) erases without moving",
+        rows: 1,
+        cols: 10,
+        input: "abcdefgh\x1b[3G\x1b[3X@",
+        expected: &["ab@  fgh"],
+    },
+    Case {
+        name: "ECH splits wide characters",
+        rows: 1,
+        cols: 10,
+        input: "日本語\x1b[2G\x1b[2X@",
+        expected: &[" @  語"],
+    },
+    Case {
+        name: "REP (CSI b) repeats the last character",
+        rows: 1,
+        cols: 10,
+        input: "─\x1b[4b@",
+        expected: &["─────@"],
+    },
+    Case {
+        name: "VPA (CSI d) keeps the column",
+        rows: 4,
+        cols: 10,
+        input: "abc\x1b[3d@",
+        expected: &["abc", "", "   @", ""],
+    },
+    Case {
+        name: "CHA / HPA / HPR",
+        rows: 1,
+        cols: 10,
+        input: "\x1b[5G1\x1b[2`2\x1b[3a3",
+        expected: &[" 2  13"],
+    },
+    Case {
+        name: "CNL / CPL",
+        rows: 4,
+        cols: 10,
+        input: "abc\x1b[2Ex\x1b[1Fy@",
+        expected: &["abc", "y@", "x", ""],
+    },
+    Case {
+        name: "HT uses default tab stops",
+        rows: 1,
+        cols: 20,
+        input: "a\tb\tc",
+        expected: &["a       b       c"],
+    },
+    Case {
+        name: "HT stops at the right margin",
+        rows: 1,
+        cols: 12,
+        input: "\t\t\t@",
+        expected: &["           @"],
+    },
+    Case {
+        name: "CHT / CBT",
+        rows: 1,
+        cols: 30,
+        input: "\x1b[2Ia\x1b[2Zb",
+        expected: &["        b       a"],
+    },
+    Case {
+        name: "HTS / TBC",
+        rows: 2,
+        cols: 20,
+        input: "\x1b[3g\x1b[4G\x1bH\x1b[G\tx\r\n\x1b[3G\x1bH\x1b[0g\r\tz",
+        expected: &["   x", "   z"],
+    },
+    Case {
+        name: "IND (ESC D) scrolls at the bottom",
+        rows: 2,
+        cols: 10,
+        input: "one\r\ntwo\x1bD@",
+        expected: &["two", "   @"],
+    },
+    Case {
+        name: "RI (ESC M) scrolls down at the top",
+        rows: 3,
+        cols: 10,
+        input: "one\r\ntwo\x1b[H\x1bM@",
+        expected: &["@", "one", "two"],
+    },
+    Case {
+        name: "NEL (ESC E)",
+        rows: 2,
+        cols: 10,
+        input: "abc\x1bE@",
+        expected: &["abc", "@"],
+    },
+    Case {
+        name: "DECSTBM limits scrolling",
+        rows: 4,
+        cols: 10,
+        input: "head\r\n1\r\n2\r\nfoot\x1b[2;3r\x1b[3H\n@",
+        expected: &["head", "2", "@", "foot"],
+    },
+    Case {
+        name: "DECSTBM with invalid range is ignored",
+        rows: 3,
+        cols: 10,
+        input: "abc\x1b[2;2r@",
+        expected: &["abc@", "", ""],
+    },
+    Case {
+        name: "CUU / CUD stop at the scroll region",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[2;4r\x1b[3H\x1b[9Aa\x1b[9Bb",
+        expected: &["", "a", "", " b", ""],
+    },
+    Case {
+        name: "DECOM makes CUP relative to the region",
+        rows: 5,
+        cols: 10,
+        input: "\x1b[2;4r\x1b[?6h@\x1b[2;2Hx\x1b[9;1Hy\x1b[?6l\x1b[1;1Hz",
+        expected: &["z", "@", " x", "y", ""],
+    },
+    Case {
+        name: "deferred wrap at the right margin",
+        rows: 2,
+        cols: 5,
+        input: "abcde\rX",
+        expected: &["Xbcde", ""],
+    },
+    Case {
+        name: "deferred wrap continues on the next line",
+        rows: 2,
+        cols: 5,
+        input: "abcdef",
+        expected: &["abcde", "f"],
+    },
+    Case {
+        name: "CUB from the pending wrap position",
+        rows: 1,
+        cols: 5,
+        input: "abcde\x1b[DX",
+        expected: &["abcXe"],
+    },
+    Case {
+        name: "DECAWM off overwrites the last column",
+        rows: 2,
+        cols: 5,
+        input: "\x1b[?7labcdefg\x1b[?7h",
+        expected: &["abcdg", ""],
+    },
+    Case {
+        name: "EL at the pending wrap position",
+        rows: 1,
+        cols: 5,
+        input: "abcde\x1b[K",
+        expected: &["abcd"],
+    },
+    Case {
+        name: "ICH / DCH",
+        rows: 1,
+        cols: 10,
+        input: "abcdef\x1b[2G\x1b[2@\x1b[5G\x1b[P",
+        expected: &["a  bdef"],
+    },
+    Case {
+        name: "IL / DL",
+        rows: 4,
+        cols: 10,
+        input: "1\r\n2\r\n3\r\n4\x1b[2H\x1b[L\x1b[4H\x1b[M",
+        expected: &["1", "", "2", ""],
+    },
+    Case {
+        name: "SU / SD",
+        rows: 3,
+        cols: 10,
+        input: "1\r\n2\r\n3\x1b[2S\x1b[T",
+        expected: &["", "3", ""],
+    },
+    Case {
+        name: "ED 1 / ED 2 keep the cursor",
+        rows: 3,
+        cols: 10,
+        input: "abc\r\ndef\r\nghi\x1b[2;2H\x1b[1J@\x1b[3;3H\x1b[2J#",
+        expected: &["", "", "  #"],
+    },
+    Case {
+        name: "SGR with extended colors does not leak parameters",
+        rows: 1,
+        cols: 10,
+        input: "\x1b[38;5;4;48;2;1;2;3mA\x1b[38:2::1:2:3mB\x1b[0m@",
+        expected: &["AB@"],
+    },
+    Case {
+        name: "alternate screen (1049) restores the cursor (no separate buffer)",
+        rows: 2,
+        cols: 10,
+        input: "main\x1b[?1049hALT\x1b[?1049l@",
+        expected: &["ALT @", ""],
+    },
+    Case {
+        name: "DECALN",
+        rows: 2,
+        cols: 4,
+        input: "ab\x1b#8@",
+        expected: &["@EEE", "EEEE"],
+    },
+    Case {
+        name: "RIS",
+        rows: 2,
+        cols: 10,
+        input: "abc\r\ndef\x1b[2;3r\x1bc\x1b[9B@",
+        expected: &["", "@"],
+    },
+    Case {
+        name: "ignored sequences do not print",
+        rows: 1,
+        cols: 20,
+        input: "\x1b(B\x1b)0\x1b=\x1b>\x1b[>1u\x1b[<u\x1b[>4;2m\x1b[2 q\x1b[c\x1b[6n\x1b[?2026h\x1b[?1006hok",
+        expected: &["ok"],
+    },
+];
+
+fn render(case: &Case) -> Vec<String> {
+    let mut buffer = ScreenBuffer::new(case.rows, case.cols, false);
+    buffer.process_data(case.input.as_bytes());
+    buffer
+        .get_screen_lines()
+        .iter()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+#[test]
+fn test_vt_conformance_table() {
+    let failures: Vec<String> = CASES
+        .iter()
+        .filter_map(|case| {
+            let actual = render(case);
+            (actual != case.expected).then(|| {
+                format!(
+                    "{}: expected {:?}, got {:?}",
+                    case.name, case.expected, actual
+                )
+            })
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_unhandled_sequences_are_counted() {
+    let mut buffer = ScreenBuffer::new(5, 20, false);
+    buffer.process_data(b"\x1b[5y\x1b[5y\x1b[?9999h\x1b%G\x1bP1$tx\x1b\\\x1b]1337;File=x\x07ok");
+
+    let unhandled: Vec<(&str, u64)> = buffer
+        .unhandled_sequences()
+        .iter()
+        .map(|(sequence, count)| (sequence.as_str(), *count))
+        .collect();
+    assert_eq!(
+        unhandled,
+        vec![
+            ("CSI ?9999h", 1),
+            ("CSI y", 2),
+            ("DCS $t", 1),
+            ("ESC %G", 1),
+            ("OSC 1337", 1),
+        ]
+    );
+    assert_eq!(buffer.get_screen_lines()[0].trim_end(), "ok");
+
+    // 対応済み・意図的に無視するシーケンスは記録しない
+    let mut buffer = ScreenBuffer::new(5, 20, false);
+    buffer.process_data(b"\x1b7\x1b8\x1b[?25l\x1b[?2004h\x1b]0;title\x07\x1b[3X\x1b(B");
+    assert!(buffer.unhandled_sequences().is_empty());
+}
@@ -0,0 +1,113 @@
+// 全角文字・結合文字のセル配置テスト（実際の端末の表示幅に一致すること）
+
+use climonitor_launcher::screen_buffer::ScreenBuffer;
+
+fn screen(rows: usize, cols: usize, data: &str) -> ScreenBuffer {
+    let mut buffer = ScreenBuffer::new(rows, cols, false);
+    buffer.process_data(data.as_bytes());
+    buffer
+}
+
+fn line(buffer: &ScreenBuffer, row: usize) -> String {
+    buffer.get_screen_lines()[row].trim_end().to_string()
+}
+
+#[test]
+fn test_wide_chars_occupy_two_columns() {
+    // 7列目（1始まり）は全角3文字の直後
+    let buffer = screen(5, 20, "日本語abc\x1b[1;7HX");
+    assert_eq!(line(&buffer, 0), "日本語Xbc");
+}
+
+#[test]
+fn test_status_update_after_japanese_text() {
+    // 経過時間だけを表示幅で計算した列に上書きする再描画
+    let buffer = screen(5, 60, "· 日本語を翻訳中… (3s · esc to interrupt)\x1b[20G4");
+    assert_eq!(
+        line(&buffer, 0),
+        "· 日本語を翻訳中… (4s · esc to interrupt)"
+    );
+}
+
+#[test]
+fn test_line_wraps_at_display_width() {
+    let buffer = screen(5, 10, "あいうえおかき");
+    assert_eq!(line(&buffer, 0), "あいうえお");
+    assert_eq!(line(&buffer, 1), "かき");
+
+    // 行末に1列しか残っていない場合、全角文字は次の行へ
+    let buffer = screen(5, 10, "abcdefghiあ");
+    assert_eq!(line(&buffer, 0), "abcdefghi");
+    assert_eq!(line(&buffer, 1), "あ");
+}
+
+#[test]
+fn test_combining_characters_and_emoji_sequences() {
+    // 結合文字は直前のセルに付く
+    let buffer = screen(5, 20, "e\u{301}x\x1b[2GY");
+    assert_eq!(line(&buffer, 0), "e\u{301}Y");
+
+    let buffer = screen(5, 20, "か\u{3099}き\x1b[3GX");
+    assert_eq!(line(&buffer, 0), "か\u{3099}X");
+
+    // ZWJで連結した絵文字は1つの全角セル
+    let family = "👨\u{200D}👩\u{200D}👧";
+    let buffer = screen(5, 20, &format!("{family}!\x1b[3G?"));
+    assert_eq!(line(&buffer, 0), format!("{family}?"));
+}
+
+#[test]
+fn test_overwriting_half_of_wide_char() {
+    // 右半分を上書きすると左半分も消える
+    let buffer = screen(5, 20, "日本\x1b[2Gx");
+    assert_eq!(line(&buffer, 0), " x本");
+
+    // 左半分を上書きすると右半分も消える
+    let buffer = screen(5, 20, "日本\x1b[3Gy");
+    assert_eq!(line(&buffer, 0), "日y");
+
+    // 行消去の境界にかかる全角文字は丸ごと消える
+    let buffer = screen(5, 20, "日本語\x1b[4G\x1b[K");
+    assert_eq!(line(&buffer, 0), "日");
+}
+
+#[test]
+fn test_ui_box_after_wrapped_japanese_line() {
+    // 折り返す日本語の行とUI boxを、表示行数ぶん消去して再描画する（Inkの描画方式）
+    let frame = |text: &str| {
+        format!(
+            "● {text}\r\n╭{border}╮\r\n│ > 入力{padding}│\r\n╰{border}╯\r\n",
+            border = "─".repeat(18),
+            padding = " ".repeat(11),
+        )
+    };
+    let erase = format!("{}\x1b[2K\x1b[G", "\x1b[2K\x1b[1A".repeat(5));
+
+    let buffer = screen(
+        10,
+        20,
+        &format!(
+            "header\r\n{}{erase}{}",
+            frame("日本語のテキストを長く表示する"),
+            frame("翻訳したテキストを長く表示する")
+        ),
+    );
+
+    let lines: Vec<String> = (0..6).map(|row| line(&buffer, row)).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "header",
+            "● 翻訳したテキストを",
+            "長く表示する",
+            format!("╭{}╮", "─".repeat(18)).as_str(),
+            format!("│ > 入力{}│", " ".repeat(11)).as_str(),
+            format!("╰{}╯", "─".repeat(18)).as_str(),
+        ]
+    );
+
+    let ui_box = buffer.find_ui_boxes().pop().expect("UI box");
+    assert_eq!(ui_box.start_row, 3);
+    assert_eq!(ui_box.content_lines, vec!["> 入力"]);
+    assert_eq!(ui_box.above_lines.first().map(|l| l.trim()), Some("header"));
+}
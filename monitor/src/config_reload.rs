@@ -0,0 +1,518 @@
+// config_reload.rs - 設定ファイルの変更を検知し、再起動なしで反映する
+
+use chrono::{DateTime, Local, Utc};
+use climonitor_shared::{Config, NotificationSettings, UiSettings};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{broadcast, RwLock};
+
+/// 設定ファイルの更新確認間隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 再起動なしで反映できる設定
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeSettings {
+    pub notification: NotificationSettings,
+    pub ui: UiSettings,
+    /// gRPC/TCP接続のIP許可リスト
+    pub allowed_ips: Vec<String>,
+    /// WebダッシュボードのIP許可リスト
+    pub web_allowed_ips: Vec<String>,
+    /// WebダッシュボードのWebSocketで許可する追加のOrigin
+    pub web_allowed_origins: Vec<String>,
+    /// ダッシュボードからのプロンプト予約を受け付けるか
+    pub web_allow_prompt_queue: bool,
+    /// 直近の再読み込み結果（UI表示用）
+    pub last_reload: Option<ReloadReport>,
+}
+
+/// タスク間で共有する実行時設定
+pub type SharedRuntimeSettings = Arc<RwLock<RuntimeSettings>>;
+
+impl RuntimeSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            notification: config.notification.clone(),
+            ui: config.ui.clone(),
+            allowed_ips: listener_allowed_ips(config).cloned().unwrap_or_default(),
+            web_allowed_ips: config
+                .web
+                .as_ref()
+                .map(|web| web.allowed_ips.clone())
+                .unwrap_or_default(),
+            web_allowed_origins: config
+                .web
+                .as_ref()
+                .map(|web| web.allowed_origins.clone())
+                .unwrap_or_default(),
+            web_allow_prompt_queue: config
+                .web
+                .as_ref()
+                .is_some_and(|web| web.allow_prompt_queue),
+            last_reload: None,
+        }
+    }
+}
+
+/// 設定再読み込みの結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReloadReport {
+    pub timestamp: DateTime<Utc>,
+    /// 反映した設定キー
+    pub applied: Vec<&'static str>,
+    /// 反映に再起動が必要な設定キー
+    pub restart_required: Vec<&'static str>,
+    /// 読み込みエラー（以前の設定を維持）
+    pub error: Option<String>,
+}
+
+impl ReloadReport {
+    /// 実行中の設定と新しい設定の差分を分類
+    pub fn classify(running: &Config, new: &Config) -> Self {
+        let mut applied = Vec::new();
+        let mut restart_required = Vec::new();
+
+        // tracing出力中はsubscriberのフィルタが固定されるため再起動が必要
+        let tracing = running.logging.tracing || new.logging.tracing;
+        let mut log_filter = |key: &'static str, changed: bool| {
+            if changed {
+                if tracing {
+                    restart_required.push(key);
+                } else {
+                    applied.push(key);
+                }
+            }
+        };
+        log_filter("logging.level", running.logging.level != new.logging.level);
+        log_filter(
+            "logging.categories",
+            running.logging.categories != new.logging.categories,
+        );
+
+        if running.notification != new.notification {
+            applied.push("notification");
+        }
+        if running.ui != new.ui {
+            applied.push("ui");
+        }
+
+        let listeners = [
+            (
+                [
+                    "connection.grpc",
+                    "connection.grpc.allowed_ips",
+                    "connection.grpc.bind_addr",
+                ],
+                running
+                    .connection
+                    .grpc
+                    .as_ref()
+                    .map(|grpc| (&grpc.bind_addr, &grpc.allowed_ips)),
+                new.connection
+                    .grpc
+                    .as_ref()
+                    .map(|grpc| (&grpc.bind_addr, &grpc.allowed_ips)),
+            ),
+            (
+                [
+                    "connection.tcp",
+                    "connection.tcp.allowed_ips",
+                    "connection.tcp.bind_addr",
+                ],
+                running
+                    .connection
+                    .tcp
+                    .as_ref()
+                    .map(|tcp| (&tcp.bind_addr, &tcp.allowed_ips)),
+                new.connection
+                    .tcp
+                    .as_ref()
+                    .map(|tcp| (&tcp.bind_addr, &tcp.allowed_ips)),
+            ),
+            (
+                ["web", "web.allowed_ips", "web.bind_addr"],
+                running
+                    .web
+                    .as_ref()
+                    .map(|web| (&web.bind_addr, &web.allowed_ips)),
+                new.web
+                    .as_ref()
+                    .map(|web| (&web.bind_addr, &web.allowed_ips)),
+            ),
+        ];
+        for ([section, allowed_ips_key, bind_addr_key], running_listener, new_listener) in listeners
+        {
+            match (running_listener, new_listener) {
+                (Some(running_listener), Some(new_listener)) => {
+                    if running_listener.1 != new_listener.1 {
+                        applied.push(allowed_ips_key);
+                    }
+                    if running_listener.0 != new_listener.0 {
+                        restart_required.push(bind_addr_key);
+                    }
+                }
+                (None, None) => {}
+                _ => restart_required.push(section),
+            }
+        }
+        if let (Some(running_web), Some(new_web)) = (running.web.as_ref(), new.web.as_ref()) {
+            if running_web.allowed_origins != new_web.allowed_origins {
+                applied.push("web.allowed_origins");
+            }
+            if running_web.allow_prompt_queue != new_web.allow_prompt_queue {
+                applied.push("web.allow_prompt_queue");
+            }
+        }
+
+        let restart_checks = [
+            (
+                "connection.unix_socket_path",
+                running.connection.unix_socket_path != new.connection.unix_socket_path,
+            ),
+            (
+                "logging.verbose",
+                running.logging.verbose != new.logging.verbose,
+            ),
+            (
+                "logging.log_file",
+                running.logging.log_file != new.logging.log_file,
+            ),
+            (
+                "logging.format",
+                running.logging.format != new.logging.format,
+            ),
+            (
+                "logging.diagnostic_file",
+                running.logging.diagnostic_file != new.logging.diagnostic_file
+                    || running.logging.max_file_size_mb != new.logging.max_file_size_mb
+                    || running.logging.max_files != new.logging.max_files,
+            ),
+            (
+                "logging.tracing",
+                running.logging.tracing != new.logging.tracing,
+            ),
+            ("federation", running.federation != new.federation),
+        ];
+        restart_required.extend(
+            restart_checks
+                .into_iter()
+                .filter(|(_, changed)| *changed)
+                .map(|(key, _)| key),
+        );
+
+        Self {
+            timestamp: Utc::now(),
+            applied,
+            restart_required,
+            error: None,
+        }
+    }
+
+    fn failed(error: String) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            applied: Vec::new(),
+            restart_required: Vec::new(),
+            error: Some(error),
+        }
+    }
+
+    /// UI表示用の行
+    pub fn summary_lines(&self) -> Vec<String> {
+        let time = self.timestamp.with_timezone(&Local).format("%H:%M:%S");
+        let mut lines = Vec::new();
+
+        if let Some(ref error) = self.error {
+            let first_line = error.lines().next().unwrap_or_default();
+            lines.push(format!("❌ Config reload failed at {time}: {first_line}"));
+            return lines;
+        }
+        if !self.applied.is_empty() {
+            lines.push(format!(
+                "⚙️  Config reloaded at {time}: {}",
+                self.applied.join(", ")
+            ));
+        }
+        if !self.restart_required.is_empty() {
+            lines.push(format!(
+                "⚠️  Restart required to apply: {}",
+                self.restart_required.join(", ")
+            ));
+        }
+        lines
+    }
+}
+
+/// 設定の読み込み処理（ファイル + 環境変数 + CLI引数のマージと検証）
+pub type ConfigLoader = Box<dyn Fn() -> anyhow::Result<Config> + Send + Sync>;
+
+/// 設定ファイルの変更を監視し、実行時設定に反映する
+pub struct ConfigWatcher {
+    path: PathBuf,
+    /// 実行中の設定（再起動が必要な項目は起動時の値のまま）
+    running: Config,
+    loader: ConfigLoader,
+    settings: SharedRuntimeSettings,
+    ui_update_sender: broadcast::Sender<()>,
+    verbose: bool,
+}
+
+impl ConfigWatcher {
+    pub fn new(
+        path: PathBuf,
+        running: Config,
+        loader: ConfigLoader,
+        settings: SharedRuntimeSettings,
+        ui_update_sender: broadcast::Sender<()>,
+        verbose: bool,
+    ) -> Self {
+        Self {
+            path,
+            running,
+            loader,
+            settings,
+            ui_update_sender,
+            verbose,
+        }
+    }
+
+    /// 監視ループ（更新時刻の変化で再読み込み）
+    pub async fn run(mut self) {
+        let mut last_modified = self.modified_time();
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            // 保存途中などでファイルが一時的に存在しない場合は次回に持ち越す
+            let Some(modified) = self.modified_time() else {
+                continue;
+            };
+            if last_modified == Some(modified) {
+                continue;
+            }
+            last_modified = Some(modified);
+
+            self.reload().await;
+        }
+    }
+
+    fn modified_time(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// 設定を再読み込みして反映
+    pub async fn reload(&mut self) -> ReloadReport {
+        let report = match (self.loader)() {
+            Ok(new_config) => {
+                let report = ReloadReport::classify(&self.running, &new_config);
+                self.apply(&new_config, &report).await;
+                report
+            }
+            Err(e) => ReloadReport::failed(format!("{e:#}")),
+        };
+
+        if self.verbose {
+            for line in report.summary_lines() {
+                climonitor_shared::log_info!(climonitor_shared::LogCategory::System, "{line}");
+            }
+        }
+
+        self.settings.write().await.last_reload = Some(report.clone());
+        let _ = self.ui_update_sender.send(());
+        report
+    }
+
+    /// 再起動不要な項目のみを反映
+    async fn apply(&mut self, new_config: &Config, report: &ReloadReport) {
+        if report.applied.contains(&"logging.level")
+            || report.applied.contains(&"logging.categories")
+        {
+            if let Err(e) = new_config.logging.apply_filters() {
+                climonitor_shared::log_warn!(
+                    climonitor_shared::LogCategory::System,
+                    "⚠️  Failed to apply log filters: {e}"
+                );
+            } else {
+                self.running.logging.level = new_config.logging.level.clone();
+                self.running.logging.categories = new_config.logging.categories.clone();
+            }
+        }
+
+        let mut settings = self.settings.write().await;
+        settings.notification = new_config.notification.clone();
+        settings.ui = new_config.ui.clone();
+        self.running.notification = new_config.notification.clone();
+        self.running.ui = new_config.ui.clone();
+
+        if let (Some(running_grpc), Some(new_grpc)) = (
+            self.running.connection.grpc.as_mut(),
+            new_config.connection.grpc.as_ref(),
+        ) {
+            settings.allowed_ips = new_grpc.allowed_ips.clone();
+            running_grpc.allowed_ips = new_grpc.allowed_ips.clone();
+        }
+        if let (Some(running_tcp), Some(new_tcp)) = (
+            self.running.connection.tcp.as_mut(),
+            new_config.connection.tcp.as_ref(),
+        ) {
+            settings.allowed_ips = new_tcp.allowed_ips.clone();
+            running_tcp.allowed_ips = new_tcp.allowed_ips.clone();
+        }
+        if let (Some(running_web), Some(new_web)) =
+            (self.running.web.as_mut(), new_config.web.as_ref())
+        {
+            settings.web_allowed_ips = new_web.allowed_ips.clone();
+            running_web.allowed_ips = new_web.allowed_ips.clone();
+            settings.web_allowed_origins = new_web.allowed_origins.clone();
+            running_web.allowed_origins = new_web.allowed_origins.clone();
+            settings.web_allow_prompt_queue = new_web.allow_prompt_queue;
+            running_web.allow_prompt_queue = new_web.allow_prompt_queue;
+        }
+    }
+}
+
+/// 待ち受け中のgRPC/TCP接続のIP許可リスト
+fn listener_allowed_ips(config: &Config) -> Option<&Vec<String>> {
+    config
+        .connection
+        .grpc
+        .as_ref()
+        .map(|grpc| &grpc.allowed_ips)
+        .or_else(|| config.connection.tcp.as_ref().map(|tcp| &tcp.allowed_ips))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use climonitor_shared::{GrpcSettings, TcpSettings};
+
+    fn grpc_config(bind_addr: &str, allowed_ips: &[&str]) -> Config {
+        let mut config = Config::default();
+        config.connection.grpc = Some(GrpcSettings {
+            bind_addr: bind_addr.to_string(),
+            allowed_ips: allowed_ips.iter().map(|ip| ip.to_string()).collect(),
+        });
+        config
+    }
+
+    #[test]
+    fn test_classify_reloadable_and_restart_required() {
+        let running = grpc_config("127.0.0.1:50051", &["127.0.0.1"]);
+        let mut new = grpc_config("0.0.0.0:50051", &["127.0.0.1", "10.0.0.0/8"]);
+        new.logging.level = "debug".to_string();
+        new.ui.refresh_interval_secs = 1;
+        new.logging.format = climonitor_shared::LogFormat::Json;
+
+        let report = ReloadReport::classify(&running, &new);
+        assert_eq!(
+            report.applied,
+            vec!["logging.level", "ui", "connection.grpc.allowed_ips"]
+        );
+        assert_eq!(
+            report.restart_required,
+            vec!["connection.grpc.bind_addr", "logging.format"]
+        );
+
+        // tracing出力中はログフィルタも再起動が必要
+        let mut tracing_running = running.clone();
+        tracing_running.logging.tracing = true;
+        let mut tracing_new = tracing_running.clone();
+        tracing_new.logging.level = "trace".to_string();
+        let report = ReloadReport::classify(&tracing_running, &tracing_new);
+        assert!(report.applied.is_empty());
+        assert_eq!(report.restart_required, vec!["logging.level"]);
+
+        // TCPの許可リストも即時反映、gRPCからの切り替えは再起動が必要
+        let mut tcp_running = Config::default();
+        tcp_running.connection.tcp = Some(TcpSettings::default());
+        let mut tcp_new = tcp_running.clone();
+        if let Some(tcp) = tcp_new.connection.tcp.as_mut() {
+            tcp.allowed_ips = vec!["10.0.0.0/8".to_string()];
+        }
+        let report = ReloadReport::classify(&tcp_running, &tcp_new);
+        assert_eq!(report.applied, vec!["connection.tcp.allowed_ips"]);
+        let report = ReloadReport::classify(&running, &tcp_running);
+        assert_eq!(
+            report.restart_required,
+            vec!["connection.grpc", "connection.tcp"]
+        );
+
+        // ダッシュボードの起動・停止は再起動が必要
+        let mut web_new = running.clone();
+        web_new.web = Some(climonitor_shared::WebSettings::default());
+        let report = ReloadReport::classify(&running, &web_new);
+        assert_eq!(report.restart_required, vec!["web"]);
+
+        // 起動中のダッシュボードは許可リストのみ再起動なしで反映
+        let mut web_running = running.clone();
+        web_running.web = Some(climonitor_shared::WebSettings::default());
+        let mut web_changed = web_running.clone();
+        if let Some(web) = web_changed.web.as_mut() {
+            web.allowed_ips = vec!["192.168.1.0/24".to_string()];
+            web.allowed_origins = vec!["https://dashboard.example".to_string()];
+            web.allow_prompt_queue = true;
+            web.bind_addr = "0.0.0.0:8080".to_string();
+        }
+        let report = ReloadReport::classify(&web_running, &web_changed);
+        assert_eq!(
+            report.applied,
+            vec![
+                "web.allowed_ips",
+                "web.allowed_origins",
+                "web.allow_prompt_queue"
+            ]
+        );
+        assert_eq!(report.restart_required, vec!["web.bind_addr"]);
+    }
+
+    #[tokio::test]
+    async fn test_reload_applies_settings_and_keeps_restart_notice() {
+        let running = grpc_config("127.0.0.1:50051", &["127.0.0.1"]);
+        let settings = Arc::new(RwLock::new(RuntimeSettings::from_config(&running)));
+        let (ui_update_sender, mut ui_update_receiver) = broadcast::channel(10);
+
+        let next = Arc::new(std::sync::Mutex::new(Ok(grpc_config(
+            "0.0.0.0:6000",
+            &["192.168.1.0/24"],
+        ))));
+        let loader_next = next.clone();
+        let loader: ConfigLoader = Box::new(move || {
+            loader_next
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|config: &Config| config.clone())
+                .map_err(|e: &String| anyhow::anyhow!(e.clone()))
+        });
+
+        let mut watcher = ConfigWatcher::new(
+            PathBuf::from("/nonexistent/config.toml"),
+            running,
+            loader,
+            settings.clone(),
+            ui_update_sender,
+            false,
+        );
+
+        let report = watcher.reload().await;
+        assert_eq!(report.applied, vec!["connection.grpc.allowed_ips"]);
+        assert_eq!(report.restart_required, vec!["connection.grpc.bind_addr"]);
+        assert_eq!(settings.read().await.allowed_ips, vec!["192.168.1.0/24"]);
+        assert!(ui_update_receiver.try_recv().is_ok());
+
+        // 再起動が必要な変更は次回の再読み込みでも報告され続ける
+        let report = watcher.reload().await;
+        assert!(report.applied.is_empty());
+        assert_eq!(report.restart_required, vec!["connection.grpc.bind_addr"]);
+
+        // 読み込みエラー時は以前の設定を維持
+        *next.lock().unwrap() = Err("Invalid configuration".to_string());
+        let report = watcher.reload().await;
+        assert!(report.error.is_some());
+        assert_eq!(settings.read().await.allowed_ips, vec!["192.168.1.0/24"]);
+        assert!(report.summary_lines()[0].contains("Config reload failed"));
+    }
+}
@@ -0,0 +1,127 @@
+// federation.rs - 上流monitorのセッションを購読して自分の表示に統合する
+
+use anyhow::{Context, Result};
+use climonitor_shared::grpc::monitor_service_client::MonitorServiceClient;
+use climonitor_shared::grpc::WatchRequest;
+use climonitor_shared::message_conversion::grpc_conversion;
+use climonitor_shared::{LogCategory, UpstreamSettings};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+
+use crate::session_manager::{SessionManager, UpstreamState};
+
+/// 再接続間隔の初期値
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// 再接続間隔の上限
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// 上流monitor 1台分の購読クライアント
+pub struct FederationClient {
+    upstream: UpstreamSettings,
+    session_manager: Arc<RwLock<SessionManager>>,
+    ui_update_sender: broadcast::Sender<()>,
+}
+
+impl FederationClient {
+    pub fn new(
+        upstream: UpstreamSettings,
+        session_manager: Arc<RwLock<SessionManager>>,
+        ui_update_sender: broadcast::Sender<()>,
+    ) -> Self {
+        Self {
+            upstream,
+            session_manager,
+            ui_update_sender,
+        }
+    }
+
+    /// 購読ループ（切断時は受信済みのセッションを削除し、間隔を延ばしながら再接続）
+    pub async fn run(self) {
+        let mut retry_delay = INITIAL_RETRY_DELAY;
+
+        loop {
+            self.set_state(UpstreamState::Connecting).await;
+
+            let mut connected = false;
+            let error = match self.watch(&mut connected).await {
+                Ok(()) => "stream closed by upstream".to_string(),
+                Err(e) => format!("{e:#}"),
+            };
+            if connected {
+                retry_delay = INITIAL_RETRY_DELAY;
+            }
+
+            self.session_manager
+                .write()
+                .await
+                .remove_origin(&self.upstream.name);
+            self.set_state(UpstreamState::Disconnected {
+                error: error.clone(),
+            })
+            .await;
+            climonitor_shared::log_warn!(
+                LogCategory::Grpc,
+                "⚠️  Upstream monitor {} ({}) disconnected: {error}, retrying in {}s",
+                self.upstream.name,
+                self.upstream.addr,
+                retry_delay.as_secs()
+            );
+
+            tokio::time::sleep(retry_delay).await;
+            retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+        }
+    }
+
+    /// 上流monitorに接続し、スナップショットを受信するたびに反映
+    async fn watch(&self, connected: &mut bool) -> Result<()> {
+        let endpoint = if self.upstream.addr.starts_with("http://") {
+            self.upstream.addr.clone()
+        } else {
+            format!("http://{}", self.upstream.addr)
+        };
+        let mut client = MonitorServiceClient::connect(endpoint)
+            .await
+            .with_context(|| format!("Failed to connect to {}", self.upstream.addr))?;
+        let mut stream = client
+            .watch_sessions(WatchRequest {
+                subscriber: subscriber_name(),
+            })
+            .await?
+            .into_inner();
+
+        *connected = true;
+        self.set_state(UpstreamState::Connected).await;
+        climonitor_shared::log_info!(
+            LogCategory::Grpc,
+            "🔗 Subscribed to upstream monitor {} ({})",
+            self.upstream.name,
+            self.upstream.addr
+        );
+
+        while let Some(snapshot) = stream.message().await? {
+            let snapshot = grpc_conversion::from_grpc_session_snapshot(snapshot);
+            self.session_manager
+                .write()
+                .await
+                .replace_origin(&self.upstream.name, snapshot);
+            let _ = self.ui_update_sender.send(());
+        }
+        Ok(())
+    }
+
+    async fn set_state(&self, state: UpstreamState) {
+        self.session_manager.write().await.set_upstream_state(
+            &self.upstream.name,
+            &self.upstream.addr,
+            state,
+        );
+        let _ = self.ui_update_sender.send(());
+    }
+}
+
+/// 上流monitorのログに表示する購読元の名前
+fn subscriber_name() -> String {
+    std::env::var("HOSTNAME").unwrap_or_else(|_| "climonitor".to_string())
+}
@@ -2,7 +2,7 @@ use crate::session_manager::SessionManager;
 use anyhow::Result;
 use climonitor_shared::grpc::{
     monitor_service_server::{MonitorService, MonitorServiceServer},
-    ConnectResponse, LauncherMessage, MonitorMessage,
+    LauncherMessage, MonitorMessage, SessionSnapshot, WatchRequest,
 };
 use climonitor_shared::message_conversion::grpc_conversion;
 use std::sync::Arc;
@@ -74,6 +74,17 @@ impl MonitorService for CliMonitorService {
 
         Ok(Response::new(ReceiverStream::new(rx)))
     }
+
+    type WatchSessionsStream = ReceiverStream<Result<SessionSnapshot, Status>>;
+
+    async fn watch_sessions(
+        &self,
+        _request: Request<WatchRequest>,
+    ) -> Result<Response<Self::WatchSessionsStream>, Status> {
+        Err(Status::unimplemented(
+            "WatchSessions is served by the transport-based gRPC server",
+        ))
+    }
 }
 
 impl CliMonitorService {
@@ -83,40 +94,24 @@ impl CliMonitorService {
         launcher_msg: LauncherMessage,
         tx: &mpsc::Sender<Result<MonitorMessage, Status>>,
     ) -> Result<()> {
-        if let Some(ref message) = launcher_msg.message {
-            // 接続メッセージの場合は応答を送信（messageをcloneする前に処理）
-            let response_opt = if let climonitor_shared::grpc::launcher_message::Message::Connect(
-                ref connect_req,
-            ) = message
-            {
-                Some(MonitorMessage {
-                    message: Some(
-                        climonitor_shared::grpc::monitor_message::Message::ConnectResponse(
-                            ConnectResponse {
-                                launcher_id: connect_req.launcher_id.clone(),
-                                success: true,
-                                message: Some("Connected successfully".to_string()),
-                            },
-                        ),
-                    ),
-                })
-            } else {
-                None
-            };
-
+        if launcher_msg.message.is_some() {
             // gRPCメッセージを既存のprotocolに変換
             let protocol_msg = grpc_conversion::from_grpc_launcher_message(launcher_msg)?;
 
-            // 既存のSessionManagerで処理
-            {
+            // 既存のSessionManagerで処理（接続メッセージには割り当てたIDを応答）
+            let response_opt = {
                 let mut manager = session_manager.write().await;
-                if let Err(e) = manager.handle_message(protocol_msg) {
-                    climonitor_shared::log_warn!(
-                        climonitor_shared::LogCategory::Grpc,
-                        "⚠️  Session manager error: {e}"
-                    );
+                match manager.handle_message(protocol_msg) {
+                    Ok(reply) => reply.map(grpc_conversion::to_grpc_monitor_message),
+                    Err(e) => {
+                        climonitor_shared::log_warn!(
+                            climonitor_shared::LogCategory::Grpc,
+                            "⚠️  Session manager error: {e}"
+                        );
+                        None
+                    }
                 }
-            }
+            };
 
             // UI更新チャネルにメッセージを送信
             if let Err(e) = ui_tx.send(()) {
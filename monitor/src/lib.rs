@@ -1,3 +1,5 @@
+pub mod config_reload;
+pub mod federation;
 pub mod grpc_server;
 pub mod live_ui;
 pub mod notification;
@@ -5,3 +7,4 @@ pub mod session_manager;
 pub mod transport_server;
 pub mod transports;
 pub mod unicode_utils;
+pub mod web_server;
@@ -1,10 +1,15 @@
 use chrono::{DateTime, Local, Utc};
+use climonitor_shared::{ProcessMetrics, SessionActivity};
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 
+use crate::config_reload::{RuntimeSettings, SharedRuntimeSettings};
 use crate::session_manager::SessionManager;
 use crate::unicode_utils::truncate_str;
 
+/// ライブUIに表示する自動応答の判定数（新しい順）
+const AUTO_APPROVE_EVENTS_SHOWN: usize = 5;
+
 /// ターミナル幅を取得（デフォルト80）
 fn get_terminal_width() -> usize {
     if let Some((width, _)) = term_size::dimensions() {
@@ -21,6 +26,7 @@ pub struct LiveUI {
     verbose: bool,
     last_update: Option<DateTime<Utc>>,
     rendering: bool,
+    runtime_settings: SharedRuntimeSettings,
 }
 
 impl LiveUI {
@@ -35,9 +41,16 @@ impl LiveUI {
             verbose,
             last_update: None,
             rendering: false,
+            runtime_settings: Arc::new(RwLock::new(RuntimeSettings::default())),
         }
     }
 
+    /// 設定の再読み込みで更新される実行時設定を共有
+    pub fn with_runtime_settings(mut self, runtime_settings: SharedRuntimeSettings) -> Self {
+        self.runtime_settings = runtime_settings;
+        self
+    }
+
     /// UI表示ループ開始
     pub async fn run(&mut self) -> anyhow::Result<()> {
         println!("🔥 Claude Session Monitor - Live Mode");
@@ -49,6 +62,8 @@ impl LiveUI {
 
         // 更新ループ
         loop {
+            let refresh_interval = self.runtime_settings.read().await.ui.refresh_interval_secs;
+
             tokio::select! {
                 // 更新通知受信
                 _ = self.update_receiver.recv() => {
@@ -57,8 +72,8 @@ impl LiveUI {
                     }
                 }
 
-                // 定期更新（5秒間隔）
-                _ = tokio::time::sleep(tokio::time::Duration::from_secs(5)) => {
+                // 定期更新（ui.refresh_interval_secs 間隔）
+                _ = tokio::time::sleep(tokio::time::Duration::from_secs(refresh_interval.max(1))) => {
                     if !self.rendering {
                         self.render_ui().await;
                     }
@@ -99,6 +114,9 @@ impl LiveUI {
         // セッション詳細（unknown project は除外）
         self.render_sessions().await;
 
+        // 自動応答の監査イベント
+        self.render_auto_approve_events().await;
+
         // フッター
         self.render_footer();
 
@@ -113,6 +131,29 @@ impl LiveUI {
 
         println!("🔥 Claude Session Monitor - Live Mode");
         println!("📊 Launchers: {launcher_count}");
+
+        // 上流monitorの接続状態
+        let upstreams: Vec<String> = session_manager
+            .get_upstreams()
+            .map(|upstream| upstream.summary())
+            .collect();
+        if !upstreams.is_empty() {
+            println!(
+                "{}",
+                truncate_str(
+                    &format!("🌐 Upstreams: {}", upstreams.join("  ")),
+                    terminal_width
+                )
+            );
+        }
+
+        // 設定の再読み込み結果
+        if let Some(ref report) = self.runtime_settings.read().await.last_reload {
+            for line in report.summary_lines() {
+                println!("{}", truncate_str(&line, terminal_width));
+            }
+        }
+
         println!("{}", "═".repeat(terminal_width));
     }
 
@@ -143,14 +184,34 @@ impl LiveUI {
                 b_time.cmp(&a_time) // 新しい順（降順）
             });
 
+            // 同じグループに複数のlauncherがある場合は区別できる名前を表示
+            let group_size = launchers.len();
             for (launcher, session_opt) in launchers {
                 // Tool type display
                 let tool_type_display = match launcher.tool_type {
                     climonitor_shared::CliToolType::Claude => " 🤖",
                     climonitor_shared::CliToolType::Gemini => " ✨",
                 };
-
-                if let Some(session) = session_opt {
+                let instance_display = launcher
+                    .metadata
+                    .instance_label()
+                    .filter(|_| group_size > 1 || launcher.metadata.label.is_some())
+                    .map(|label| format!(" [{label}]"))
+                    .unwrap_or_default();
+
+                if let Some(exit) = session_opt.and_then(|s| s.exit.as_ref()) {
+                    // 終了済み：成功は緑、失敗は赤で保持期間中だけ表示
+                    let (icon, color) = if exit.success() {
+                        ("✅", "\x1b[32m")
+                    } else {
+                        ("❌", "\x1b[31m")
+                    };
+                    let elapsed = format_duration_since(exit.exited_at);
+                    println!(
+                        "    {color}{icon}{tool_type_display}{instance_display} 終了 ({}) | {elapsed}\x1b[0m",
+                        exit.summary()
+                    );
+                } else if let Some(session) = session_opt {
                     // セッションがある場合：通常表示
                     let status_icon = session.status.icon();
                     let status_label = session.status.label();
@@ -161,6 +222,11 @@ impl LiveUI {
                     } else {
                         ""
                     };
+                    let activity_indicator = session
+                        .activity
+                        .as_ref()
+                        .and_then(|activity| format_activity(activity, Utc::now()))
+                        .unwrap_or_default();
 
                     // UI box上のテキスト表示
                     let ui_above_display = if let Some(ref ui_text) = session.ui_above_text {
@@ -169,9 +235,11 @@ impl LiveUI {
                         let prefix_length = 4
                             + 1
                             + 2
+                            + instance_display.len()
                             + 1
                             + status_label.len()
                             + execution_indicator.len()
+                            + activity_indicator.len()
                             + 3
                             + elapsed.len()
                             + 1;
@@ -182,7 +250,7 @@ impl LiveUI {
                     };
 
                     println!(
-                        "    {status_icon}{tool_type_display} {status_label}{execution_indicator} | {elapsed}{ui_above_display}"
+                        "    {status_icon}{tool_type_display}{instance_display} {status_label}{execution_indicator}{activity_indicator} | {elapsed}{ui_above_display}"
                     );
 
                     // 最新メッセージ表示
@@ -197,12 +265,24 @@ impl LiveUI {
                     }
 
                     // 詳細情報（verbose モード）
-                    if self.verbose && !session.evidence.is_empty() {
-                        let evidence = session.evidence.join(", ");
-                        println!("      🔍 Evidence: {evidence}");
+                    // 1行目が判定ルールと確信度、以降がマッチした画面の行
+                    if self.verbose {
+                        if let Some((rule, lines)) = session.evidence.split_first() {
+                            println!("      🔍 Evidence: {rule}");
+                            for line in lines {
+                                println!("         {line}");
+                            }
+                        }
                     }
 
                     if self.verbose {
+                        if let Some(metrics) = format_metrics(&session.metrics) {
+                            println!("      📈 {metrics}");
+                        }
+                        let summary = launcher.metadata.summary();
+                        if !summary.is_empty() {
+                            println!("      🖥️  {summary}");
+                        }
                         if let Some(ref context) = session.launcher_context {
                             let context_display = truncate_str(context, 50);
                             println!("      📝 Context: {context_display}");
@@ -211,13 +291,35 @@ impl LiveUI {
                 } else {
                     // セッションがない場合：待機中表示
                     let elapsed = format_duration_since(launcher.last_activity);
-                    println!("    🔗{tool_type_display} 接続済み | {elapsed}");
+                    println!("    🔗{tool_type_display}{instance_display} 接続済み | {elapsed}");
                 }
             }
             println!();
         }
     }
 
+    /// 直近の自動応答の判定を描画（判定がなければ何も表示しない）
+    async fn render_auto_approve_events(&self) {
+        let session_manager = self.session_manager.read().await;
+        let events: Vec<_> = session_manager.get_auto_approve_events().collect();
+        if events.is_empty() {
+            return;
+        }
+
+        let terminal_width = get_terminal_width();
+        println!("  🤖 Auto-approve:");
+        for event in events.iter().rev().take(AUTO_APPROVE_EVENTS_SHOWN) {
+            let time = event.timestamp.with_timezone(&Local).format("%H:%M:%S");
+            let line = format!(
+                "    {time} {} | {}",
+                event.summary(),
+                event.prompt_preview()
+            );
+            println!("{}", truncate_str(&line, terminal_width));
+        }
+        println!();
+    }
+
     /// フッター描画
     fn render_footer(&self) {
         if let Some(last_update) = self.last_update {
@@ -254,6 +356,79 @@ fn format_duration_since(time: DateTime<Utc>) -> String {
     }
 }
 
+/// 値の推移を ▁▂▃▄▅▆▇█ で表示（最大値を基準に正規化）
+fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if max <= 0.0 {
+                BARS[0]
+            } else {
+                BARS[((value / max) * 7.0).round().clamp(0.0, 7.0) as usize]
+            }
+        })
+        .collect()
+}
+
+/// バイト数を読みやすい単位で表示
+fn format_bytes(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    let mb = bytes as f64 / MB;
+    if mb >= 1024.0 {
+        format!("{:.1}GB", mb / 1024.0)
+    } else {
+        format!("{mb:.0}MB")
+    }
+}
+
+/// リソース使用量の最新値と推移（例: `CPU 12% ▁▃█ | RSS 230MB ▂▂▃ | 子プロセス 4`）
+fn format_metrics(metrics: &[ProcessMetrics]) -> Option<String> {
+    let latest = metrics.last()?;
+    let cpu: Vec<f64> = metrics.iter().map(|m| m.cpu_percent as f64).collect();
+    let rss: Vec<f64> = metrics.iter().map(|m| m.rss_bytes as f64).collect();
+    Some(format!(
+        "CPU {:.0}% {} | RSS {} {} | 子プロセス {}",
+        latest.cpu_percent,
+        sparkline(&cpu),
+        format_bytes(latest.rss_bytes),
+        sparkline(&rss),
+        latest.child_count
+    ))
+}
+
+/// 出力レートを読みやすい単位で表示
+fn format_rate(bytes_per_sec: u64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes = bytes_per_sec as f64;
+    if bytes >= KB * KB {
+        format!("{:.1}MB/s", bytes / KB / KB)
+    } else if bytes >= KB {
+        format!("{:.1}KB/s", bytes / KB)
+    } else {
+        format!("{bytes_per_sec}B/s")
+    }
+}
+
+/// 状態の横に出す活動状況（出力停止中は経過分数、出力中はレート）
+fn format_activity(activity: &SessionActivity, now: DateTime<Utc>) -> Option<String> {
+    if activity.stalled {
+        let minutes = activity
+            .last_output_at
+            .map(|at| now.signed_duration_since(at).num_minutes().max(0))
+            .unwrap_or_default();
+        Some(format!(" ⚠️ 出力停止 {minutes}m"))
+    } else if activity.output_bytes_per_sec > 0 {
+        Some(format!(
+            " ⚡ {}",
+            format_rate(activity.output_bytes_per_sec)
+        ))
+    } else {
+        None
+    }
+}
+
 /// ロケールに基づいて時間単位のサフィックスを取得
 fn get_locale_suffixes() -> (&'static str, &'static str, &'static str, &'static str) {
     // 環境変数でロケールを判定
@@ -300,14 +475,26 @@ pub async fn print_snapshot(session_manager: Arc<RwLock<SessionManager>>, verbos
                 elapsed
             );
 
+            if let Some(ref exit) = session.exit {
+                let icon = if exit.success() { "✅" } else { "❌" };
+                println!("     {icon} {}", exit.summary());
+            }
+
             if let Some(ref message) = session.last_message {
                 let preview = truncate_str(message, 57);
                 println!("     💬 {preview}");
             }
 
-            if verbose && !session.evidence.is_empty() {
-                let evidence = session.evidence.join(", ");
-                println!("     🔍 {evidence}");
+            if verbose {
+                if let Some(metrics) = format_metrics(&session.metrics) {
+                    println!("     📈 {metrics}");
+                }
+                if let Some((rule, lines)) = session.evidence.split_first() {
+                    println!("     🔍 {rule}");
+                    for line in lines {
+                        println!("        {}", truncate_str(line, 57));
+                    }
+                }
             }
         }
     }
@@ -337,6 +524,51 @@ mod tests {
         assert!(result.contains("2") && (result.contains("h ago") || result.contains("時間前")));
     }
 
+    #[test]
+    fn test_sparkline_and_metrics_formatting() {
+        assert_eq!(sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+        assert_eq!(sparkline(&[0.0, 0.0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+
+        assert_eq!(format_bytes(230 * 1024 * 1024), "230MB");
+        assert_eq!(format_bytes(3 * 512 * 1024 * 1024), "1.5GB");
+
+        let sample = |cpu_percent, mb: u64, child_count| ProcessMetrics {
+            cpu_percent,
+            rss_bytes: mb * 1024 * 1024,
+            child_count,
+        };
+        assert_eq!(format_metrics(&[]), None);
+        assert_eq!(
+            format_metrics(&[sample(0.0, 100, 1), sample(200.0, 200, 4)]).unwrap(),
+            "CPU 200% ▁█ | RSS 200MB ▅█ | 子プロセス 4"
+        );
+    }
+
+    #[test]
+    fn test_activity_formatting() {
+        assert_eq!(format_rate(512), "512B/s");
+        assert_eq!(format_rate(1229), "1.2KB/s");
+        assert_eq!(format_rate(3 * 1024 * 1024), "3.0MB/s");
+
+        let now = Utc::now();
+        let activity = |output_bytes_per_sec, stalled| SessionActivity {
+            output_bytes_per_sec,
+            last_output_at: Some(now - chrono::Duration::seconds(390)),
+            last_input_at: None,
+            stalled,
+        };
+        assert_eq!(format_activity(&activity(0, false), now), None);
+        assert_eq!(
+            format_activity(&activity(2048, false), now).unwrap(),
+            " ⚡ 2.0KB/s"
+        );
+        assert_eq!(
+            format_activity(&activity(0, true), now).unwrap(),
+            " ⚠️ 出力停止 6m"
+        );
+    }
+
     #[test]
     fn test_locale_suffixes() {
         let (s, m, h, d) = get_locale_suffixes();
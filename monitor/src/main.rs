@@ -1,16 +1,19 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
+use climonitor_monitor::config_reload::{ConfigLoader, ConfigWatcher, RuntimeSettings};
+use climonitor_monitor::federation::FederationClient;
 use climonitor_monitor::live_ui::LiveUI;
 use climonitor_monitor::transport_server::TransportMonitorServer;
-use climonitor_shared::{Config, ConnectionConfig};
+use climonitor_monitor::web_server::WebDashboardServer;
+use climonitor_shared::{Config, LoadedConfig};
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 #[command(name = "climonitor")]
 #[command(about = "Monitor CLI tool session status in real-time")]
 #[command(version)]
 struct Cli {
     /// Verbose output
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     verbose: bool,
 
     /// Live mode - start monitor server for real-time updates (default behavior)
@@ -18,86 +21,223 @@ struct Cli {
     live: bool,
 
     /// Log file path to save Claude's standard output
-    #[arg(long)]
+    #[arg(long, global = true)]
     log_file: Option<std::path::PathBuf>,
 
-    /// Use gRPC protocol instead of raw TCP/Unix socket
-    #[arg(long)]
+    /// Use gRPC protocol instead of Unix socket
+    #[arg(long, global = true, conflicts_with = "tcp")]
     grpc: bool,
 
-    /// gRPC bind address (only with --grpc)
-    #[arg(long, default_value = "127.0.0.1:50051")]
-    bind: String,
+    /// Use plain TCP (newline-delimited JSON) instead of Unix socket
+    #[arg(long, global = true)]
+    tcp: bool,
+
+    /// Bind address for --grpc (default: 127.0.0.1:50051) or --tcp (default: 127.0.0.1:50052)
+    #[arg(long, global = true)]
+    bind: Option<String>,
+
+    /// Serve the browser dashboard (default: 127.0.0.1:8080, use --web=ADDR to change)
+    #[arg(long, global = true, value_name = "ADDR", num_args = 0..=1, require_equals = true)]
+    web: Option<Option<String>>,
+
+    /// Aggregate sessions from an upstream monitor's gRPC server (repeatable)
+    #[arg(long, global = true, value_name = "NAME=ADDR")]
+    upstream: Vec<climonitor_shared::UpstreamSettings>,
 
     /// Unix socket path (default: /tmp/climonitor.sock)
-    #[arg(long)]
+    #[arg(long, global = true)]
     socket: Option<std::path::PathBuf>,
 
     /// Configuration file path
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     config: Option<std::path::PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand, Clone)]
+enum Commands {
+    /// Manage the configuration file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum ConfigAction {
+    /// Write a sample configuration file
+    Init {
+        /// Output path (default: ~/.climonitor/config.toml)
+        #[arg(long)]
+        path: Option<std::path::PathBuf>,
+
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the effective configuration and where each value comes from
+    Show,
+    /// Validate the effective configuration
+    Validate,
+    /// Print the configuration file search paths
+    Path,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    // 設定を読み込み（優先順位: CLI > 環境変数 > 設定ファイル > デフォルト）
-    let mut config = if let Some(config_path) = &cli.config {
-        // --config で指定された設定ファイルを読み込み
-        Config::from_file(config_path)?
-    } else if let Some((config, _path)) = Config::load_auto()? {
-        // 自動検出で設定ファイルを読み込み
-        config
+    if let Some(Commands::Config { action }) = &cli.command {
+        return run_config_command(action, &cli);
+    }
+
+    let loaded = load_config(&cli)?;
+    loaded.config.validate()?;
+
+    // 読み込んだ設定ファイルを監視し、変更時は同じ優先順位で再読み込み
+    let reload_source = loaded.path.clone().map(|path| {
+        let reload_cli = cli.clone();
+        let reload_path = path.clone();
+        let loader: ConfigLoader = Box::new(move || {
+            let mut loaded = LoadedConfig::load(Some(&reload_path))?;
+            apply_cli_overrides(&mut loaded, &reload_cli);
+            loaded.config.validate()?;
+            Ok(loaded.config)
+        });
+        (path, loader)
+    });
+    let config = loaded.config;
+
+    // ログシステムの初期化
+    config.logging.init_logging()?;
+
+    if cli.live || cli.grpc || cli.tcp {
+        // ライブモード：Monitor サーバーとして動作 (gRPC/TCPも含む)
+        run_live_mode(config, reload_source).await?;
     } else {
-        // デフォルト設定を使用
-        Config::default()
-    };
+        // デフォルト：ライブモード
+        run_live_mode(config, reload_source).await?;
+    }
 
-    // 環境変数で上書き
-    config.apply_env_overrides();
+    Ok(())
+}
 
-    // CLI引数で上書き
-    if let Some(socket_path) = cli.socket {
-        config.connection.unix_socket_path = Some(socket_path);
+/// 設定を読み込み（優先順位: CLI > 環境変数 > 設定ファイル > デフォルト）
+fn load_config(cli: &Cli) -> anyhow::Result<LoadedConfig> {
+    let mut loaded = LoadedConfig::load(cli.config.as_deref())?;
+    apply_cli_overrides(&mut loaded, cli);
+    Ok(loaded)
+}
+
+/// CLI引数で上書き
+fn apply_cli_overrides(loaded: &mut LoadedConfig, cli: &Cli) {
+    if let Some(socket_path) = cli.socket.clone() {
+        loaded.config.connection.unix_socket_path = Some(socket_path);
+        loaded.record_cli("connection.unix_socket_path", "--socket");
     }
     if cli.verbose {
-        config.logging.verbose = true;
+        loaded.config.logging.verbose = true;
+        loaded.record_cli("logging.verbose", "--verbose");
     }
     if let Some(log_file) = cli.log_file.clone() {
-        config.logging.log_file = Some(log_file);
+        loaded.config.logging.log_file = Some(log_file);
+        loaded.record_cli("logging.log_file", "--log-file");
     }
 
-    // gRPCフラグの処理
+    // gRPC/TCPフラグの処理
     if cli.grpc {
-        config.connection.grpc = Some(climonitor_shared::GrpcSettings {
-            bind_addr: cli.bind,
+        let defaults = climonitor_shared::GrpcSettings::default();
+        loaded.config.connection.tcp = None;
+        loaded.config.connection.grpc = Some(climonitor_shared::GrpcSettings {
+            bind_addr: cli.bind.clone().unwrap_or(defaults.bind_addr),
             allowed_ips: vec!["127.0.0.1".to_string()],
         });
+        loaded.record_cli("connection.grpc", "--grpc");
     }
+    if cli.tcp {
+        let defaults = climonitor_shared::TcpSettings::default();
+        loaded.config.connection.grpc = None;
+        loaded.config.connection.tcp = Some(climonitor_shared::TcpSettings {
+            bind_addr: cli.bind.clone().unwrap_or(defaults.bind_addr),
+            allowed_ips: vec!["127.0.0.1".to_string()],
+        });
+        loaded.record_cli("connection.tcp", "--tcp");
+    }
+    if !cli.upstream.is_empty() {
+        loaded
+            .config
+            .federation
+            .upstreams
+            .extend(cli.upstream.iter().cloned());
+        loaded.record_cli("federation.upstreams", "--upstream");
+    }
+    if let Some(web_addr) = &cli.web {
+        let web = loaded
+            .config
+            .web
+            .get_or_insert_with(climonitor_shared::WebSettings::default);
+        match web_addr {
+            Some(addr) => {
+                web.bind_addr = addr.clone();
+                loaded.record_cli("web.bind_addr", "--web");
+            }
+            None => loaded.record_cli("web", "--web"),
+        }
+    }
+}
 
-    // ログシステムの初期化
-    config.logging.init_logging();
-
-    // 接続設定を生成
-    let connection_config = config.to_connection_config();
-
-    if cli.live || cli.grpc {
-        // ライブモード：Monitor サーバーとして動作 (gRPCも含む)
-        run_live_mode(
-            connection_config,
-            config.logging.verbose,
-            config.logging.log_file,
-        )
-        .await?;
-    } else {
-        // デフォルト：ライブモード
-        run_live_mode(
-            connection_config,
-            config.logging.verbose,
-            config.logging.log_file,
-        )
-        .await?;
+/// `climonitor config` サブコマンド実行
+fn run_config_command(action: &ConfigAction, cli: &Cli) -> anyhow::Result<()> {
+    match action {
+        ConfigAction::Init { path, force } => {
+            let path = match path {
+                Some(path) => path.clone(),
+                None => Config::default_config_path()?,
+            };
+            if path.exists() && !force {
+                anyhow::bail!(
+                    "Config file already exists: {} (use --force to overwrite)",
+                    path.display()
+                );
+            }
+            Config::sample().save_to_file(&path)?;
+            println!("✅ Created config file: {}", path.display());
+        }
+        ConfigAction::Show => {
+            print!("{}", load_config(cli)?.render()?);
+        }
+        ConfigAction::Validate => {
+            let loaded = load_config(cli)?;
+            loaded.config.validate()?;
+            match &loaded.path {
+                Some(path) => println!("✅ Configuration is valid: {}", path.display()),
+                None => println!("✅ Configuration is valid (no config file, using defaults)"),
+            }
+        }
+        ConfigAction::Path => {
+            let active = match &cli.config {
+                Some(path) => Some(path.clone()),
+                None => Config::config_path_candidates()
+                    .into_iter()
+                    .find(|path| path.exists()),
+            };
+            if let Some(path) = &cli.config {
+                println!("{} (--config)", path.display());
+            }
+            println!("Search paths (in priority order):");
+            for path in Config::config_path_candidates() {
+                let marker = if active.as_ref() == Some(&path) {
+                    "✅"
+                } else if path.exists() {
+                    "  "
+                } else {
+                    "❌"
+                };
+                println!("  {marker} {}", path.display());
+            }
+        }
     }
 
     Ok(())
@@ -105,10 +245,13 @@ async fn main() -> anyhow::Result<()> {
 
 /// ライブモード実行
 async fn run_live_mode(
-    config: ConnectionConfig,
-    verbose: bool,
-    log_file: Option<std::path::PathBuf>,
+    monitor_config: Config,
+    reload_source: Option<(std::path::PathBuf, ConfigLoader)>,
 ) -> anyhow::Result<()> {
+    let config = monitor_config.to_connection_config();
+    let verbose = monitor_config.logging.verbose;
+    let log_file = monitor_config.logging.log_file.clone();
+
     if verbose {
         println!("🔧 Starting monitor server in verbose mode...");
         println!("🔧 Connection config: {config:?}");
@@ -118,15 +261,67 @@ async fn run_live_mode(
         }
     }
 
+    // 設定の再読み込みで更新される実行時設定
+    let runtime_settings = std::sync::Arc::new(tokio::sync::RwLock::new(
+        RuntimeSettings::from_config(&monitor_config),
+    ));
+
     // Monitor サーバー開始
-    let mut server = TransportMonitorServer::new(config, verbose, log_file)?;
+    let mut server = TransportMonitorServer::new(config, verbose, log_file)?
+        .with_runtime_settings(runtime_settings.clone());
 
     // UI更新チャネル取得
     let update_receiver = server.subscribe_ui_updates();
     let session_manager = server.get_session_manager();
 
+    // 上流monitorの購読開始
+    for upstream in &monitor_config.federation.upstreams {
+        let client = FederationClient::new(
+            upstream.clone(),
+            session_manager.clone(),
+            server.ui_update_sender(),
+        );
+        tokio::spawn(client.run());
+    }
+
+    // ブラウザ用ダッシュボード開始
+    if let Some(ref web_settings) = monitor_config.web {
+        let web_server = WebDashboardServer::new(
+            web_settings,
+            session_manager.clone(),
+            server.ui_update_sender(),
+            runtime_settings.clone(),
+        )
+        .with_launcher_messages(server.launcher_message_sender());
+        tokio::spawn(async move {
+            if let Err(e) = web_server.run().await {
+                climonitor_shared::log_error!(
+                    climonitor_shared::LogCategory::Web,
+                    "Web dashboard error: {e}"
+                );
+            }
+        });
+    }
+
+    // 設定ファイル監視開始
+    if let Some((path, loader)) = reload_source {
+        if verbose {
+            println!("👀 Watching config file: {}", path.display());
+        }
+        let watcher = ConfigWatcher::new(
+            path,
+            monitor_config,
+            loader,
+            runtime_settings.clone(),
+            server.ui_update_sender(),
+            verbose,
+        );
+        tokio::spawn(watcher.run());
+    }
+
     // LiveUI開始
-    let mut live_ui = LiveUI::new(session_manager, update_receiver, verbose);
+    let mut live_ui = LiveUI::new(session_manager, update_receiver, verbose)
+        .with_runtime_settings(runtime_settings);
 
     // サーバーとUIを並行実行
     tokio::select! {
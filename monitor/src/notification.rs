@@ -1,5 +1,6 @@
 // notification.rs - Simple notification system via user script
 
+use climonitor_shared::NotificationSettings;
 use std::path::{Path, PathBuf};
 use tokio::process::Command;
 
@@ -13,6 +14,18 @@ impl NotificationManager {
         Self { script_path }
     }
 
+    /// 通知設定から作成（無効の場合はスクリプトを実行しない）
+    pub fn from_settings(settings: &NotificationSettings) -> Self {
+        let script_path = if !settings.enabled {
+            None
+        } else if let Some(path) = &settings.script_path {
+            Some(climonitor_shared::expand_home(path)).filter(|path| path.is_file())
+        } else {
+            Self::find_notification_script()
+        };
+        Self { script_path }
+    }
+
     /// 通知スクリプトを探す (プラットフォーム固有)
     fn find_notification_script() -> Option<PathBuf> {
         if let Some(home) = home::home_dir() {
@@ -106,6 +119,21 @@ impl NotificationManager {
     pub async fn notify_waiting(&self, tool: &str, message: &str, duration: &str) {
         self.notify("waiting", tool, message, duration).await;
     }
+
+    /// CLIツール自身が出したデスクトップ通知の転送
+    pub async fn notify_terminal(&self, tool: &str, title: Option<&str>, body: &str) {
+        let message = terminal_notification_message(title, body);
+        self.notify("notification", tool, &message, "").await;
+    }
+}
+
+/// デスクトップ通知の表示用テキスト（タイトルがあれば `タイトル: 本文`）
+pub fn terminal_notification_message(title: Option<&str>, body: &str) -> String {
+    match title {
+        Some(title) if body.is_empty() => title.to_string(),
+        Some(title) => format!("{title}: {body}"),
+        None => body.to_string(),
+    }
 }
 
 impl Default for NotificationManager {
@@ -1,13 +1,120 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use climonitor_shared::{
-    LauncherInfo, LauncherStatus, LauncherToMonitor, SessionInfo, SessionStatus,
+    capability, generate_launcher_id, negotiate_protocol_version, supported_capabilities,
+    AutoApproveAction, LauncherInfo, LauncherStatus, LauncherToMonitor, MonitorToLauncher,
+    ProcessExit, SessionInfo, SessionSnapshot, SessionStatus, PROTOCOL_VERSION,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use crate::notification::terminal_notification_message;
+
+/// 保持する自動応答監査イベントの上限
+const MAX_AUTO_APPROVE_EVENTS: usize = 100;
+
+/// セッションごとに保持するリソース使用量の件数（スパークライン表示用）
+pub const MAX_METRICS_SAMPLES: usize = 30;
+
+/// 上流monitorから受信したIDの区切り文字（`<origin>/<id>`）
+pub const ORIGIN_SEPARATOR: char = '/';
+
+/// 表示用のグループ名（ブランチごと・上流monitorの接続元ごとに分ける）
+fn group_name(project: &str, launcher: &LauncherInfo) -> String {
+    let mut name = project.to_string();
+    if let Some(branch) = &launcher.metadata.git_branch {
+        name.push_str(&format!(" [{branch}]"));
+    }
+    if let Some(origin) = &launcher.origin {
+        name.push_str(&format!(" @{origin}"));
+    }
+    name
+}
+
+/// launcherへの接続応答（monitorのバージョンと対応機能を添える）
+fn connect_response(requested_id: String, launcher_id: String) -> MonitorToLauncher {
+    MonitorToLauncher::ConnectResponse {
+        requested_id,
+        launcher_id,
+        protocol_version: PROTOCOL_VERSION,
+        capabilities: supported_capabilities(),
+    }
+}
+
+/// 上流monitorから受信したIDを接続元で名前空間化
+pub fn scoped_id(origin: &str, id: &str) -> String {
+    format!("{origin}{ORIGIN_SEPARATOR}{id}")
+}
+
+/// 上流monitorとの接続状態
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpstreamState {
+    Connecting,
+    Connected,
+    /// 切断中（再接続を待機）
+    Disconnected {
+        error: String,
+    },
+}
+
+/// 上流monitorの状態
+#[derive(Debug, Clone)]
+pub struct UpstreamStatus {
+    pub name: String,
+    pub addr: String,
+    pub state: UpstreamState,
+    pub since: DateTime<Utc>,
+}
+
+impl UpstreamStatus {
+    /// 一覧表示用の要約
+    pub fn summary(&self) -> String {
+        match &self.state {
+            UpstreamState::Connecting => format!("⏳ {}", self.name),
+            UpstreamState::Connected => format!("✅ {}", self.name),
+            UpstreamState::Disconnected { .. } => format!("❌ {}", self.name),
+        }
+    }
+}
+
+/// 自動応答の監査イベント
+#[derive(Debug, Clone)]
+pub struct AutoApproveEvent {
+    pub launcher_id: String,
+    pub session_id: String,
+    pub rule: String,
+    pub action: AutoApproveAction,
+    pub prompt: String,
+    pub dry_run: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl AutoApproveEvent {
+    /// 一覧表示用の要約
+    pub fn summary(&self) -> String {
+        let decision = match self.action {
+            AutoApproveAction::Approve => "auto-approved",
+            AutoApproveAction::Deny => "auto-approve denied",
+        };
+        let dry_run = if self.dry_run { " (dry-run)" } else { "" };
+        format!("🤖 {decision}{dry_run}: {}", self.rule)
+    }
+
+    /// 承認プロンプトの内容を1行にまとめたもの
+    pub fn prompt_preview(&self) -> String {
+        self.prompt
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
 
 /// セッション管理システム
 pub struct SessionManager {
     launchers: HashMap<String, LauncherInfo>,
     sessions: HashMap<String, SessionInfo>,
+    auto_approve_events: VecDeque<AutoApproveEvent>,
+    upstreams: BTreeMap<String, UpstreamStatus>,
 }
 
 impl Default for SessionManager {
@@ -21,6 +128,8 @@ impl SessionManager {
         Self {
             launchers: HashMap::new(),
             sessions: HashMap::new(),
+            auto_approve_events: VecDeque::new(),
+            upstreams: BTreeMap::new(),
         }
     }
 
@@ -35,13 +144,48 @@ impl SessionManager {
         Ok(())
     }
 
+    /// 終了情報を報告済みのlauncherを表示用に残して切断状態にする（報告がなければ削除）
+    fn disconnect_launcher(&mut self, launcher_id: &str) {
+        let exited = self
+            .sessions
+            .values()
+            .any(|s| s.launcher_id == launcher_id && s.exit.is_some());
+        match self.launchers.get_mut(launcher_id) {
+            Some(launcher) if exited => launcher.status = LauncherStatus::Disconnected,
+            _ => {
+                self.remove_launcher(launcher_id);
+            }
+        }
+    }
+
+    /// 終了から `retention` 以上経過したlauncherとセッションを削除（削除があればtrue）
+    pub fn prune_exited(&mut self, retention: chrono::Duration) -> bool {
+        let cutoff = Utc::now() - retention;
+        let expired: Vec<String> = self
+            .launchers
+            .values()
+            // 上流monitorから受信したものは上流側の削除に従う
+            .filter(|l| l.status == LauncherStatus::Disconnected && l.origin.is_none())
+            .filter(|l| {
+                self.sessions
+                    .values()
+                    .filter(|s| s.launcher_id == l.id)
+                    .all(|s| s.exit.as_ref().is_none_or(|e| e.exited_at <= cutoff))
+            })
+            .map(|l| l.id.clone())
+            .collect();
+
+        for launcher_id in &expired {
+            self.remove_launcher(launcher_id);
+        }
+        !expired.is_empty()
+    }
+
     /// launcher切断処理
     pub fn remove_launcher(&mut self, launcher_id: &str) -> Option<LauncherInfo> {
         // launcher削除
         let launcher = self.launchers.remove(launcher_id);
 
-        // process_metrics フィールドは削除済み
-
         // 関連セッションを完全削除
         self.sessions
             .retain(|_, session| session.launcher_id != launcher_id);
@@ -62,32 +206,165 @@ impl SessionManager {
         self.sessions.insert(session.id.clone(), session);
     }
 
-    // update_process_metrics は削除済み
+    /// 上流monitorのスナップショットで接続元のlauncher/セッションを置き換え
+    ///
+    /// 上流がさらに統合したlauncherは取り込まない（互いに統合し合うmonitorの間で
+    /// 接続元が際限なく連結されるのを防ぐ。上流の新しいmonitorはそもそも配信しない）。
+    pub fn replace_origin(&mut self, origin: &str, snapshot: SessionSnapshot) {
+        self.remove_origin(origin);
+
+        let mut local_ids = std::collections::HashSet::new();
+        for mut launcher in snapshot.launchers {
+            if launcher.origin.is_some() {
+                continue;
+            }
+            local_ids.insert(launcher.id.clone());
+            launcher.id = scoped_id(origin, &launcher.id);
+            launcher.origin = Some(origin.to_string());
+            self.launchers.insert(launcher.id.clone(), launcher);
+        }
+        for mut session in snapshot.sessions {
+            if !local_ids.contains(&session.launcher_id) {
+                continue;
+            }
+            session.id = scoped_id(origin, &session.id);
+            session.launcher_id = scoped_id(origin, &session.launcher_id);
+            self.sessions.insert(session.id.clone(), session);
+        }
+    }
+
+    /// 上流monitorから受信したlauncher/セッションを削除
+    pub fn remove_origin(&mut self, origin: &str) {
+        let prefix = scoped_id(origin, "");
+        self.launchers.retain(|id, _| !id.starts_with(&prefix));
+        self.sessions
+            .retain(|_, session| !session.launcher_id.starts_with(&prefix));
+    }
+
+    /// 下流monitorへ配信するスナップショット（表示中のローカルのlauncherとそのセッション）
+    ///
+    /// 上流から統合したlauncherは配信しない（下流が上流へ直接接続する）。
+    pub fn snapshot(&self) -> SessionSnapshot {
+        let mut launchers: Vec<LauncherInfo> = self
+            .get_visible_launchers()
+            .into_iter()
+            .filter(|l| l.origin.is_none())
+            .cloned()
+            .collect();
+        launchers.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut sessions: Vec<SessionInfo> = self
+            .sessions
+            .values()
+            .filter(|s| launchers.iter().any(|l| l.id == s.launcher_id))
+            .cloned()
+            .collect();
+        sessions.sort_by(|a, b| a.id.cmp(&b.id));
+
+        SessionSnapshot {
+            launchers,
+            sessions,
+        }
+    }
+
+    /// 上流monitorとの接続状態を更新
+    pub fn set_upstream_state(&mut self, name: &str, addr: &str, state: UpstreamState) {
+        if self
+            .upstreams
+            .get(name)
+            .is_some_and(|status| status.state == state)
+        {
+            return;
+        }
+        self.upstreams.insert(
+            name.to_string(),
+            UpstreamStatus {
+                name: name.to_string(),
+                addr: addr.to_string(),
+                state,
+                since: Utc::now(),
+            },
+        );
+    }
+
+    /// 上流monitorの状態一覧（名前順）
+    pub fn get_upstreams(&self) -> impl Iterator<Item = &UpstreamStatus> {
+        self.upstreams.values()
+    }
+
+    /// メッセージ処理（送信元のlauncherへの応答があれば返す）
+    pub fn handle_message(
+        &mut self,
+        message: LauncherToMonitor,
+    ) -> Result<Option<MonitorToLauncher>, String> {
+        // 上流monitorから受信したIDと衝突しないよう、区切り文字を含むIDは受け付けない
+        let has_separator = message.launcher_id().contains(ORIGIN_SEPARATOR)
+            || message
+                .session_id()
+                .is_some_and(|id| id.contains(ORIGIN_SEPARATOR));
+        if has_separator {
+            return Err(format!(
+                "Launcher/session id must not contain '{ORIGIN_SEPARATOR}': {}",
+                message.launcher_id()
+            ));
+        }
 
-    /// メッセージ処理
-    pub fn handle_message(&mut self, message: LauncherToMonitor) -> Result<(), String> {
         match message {
             LauncherToMonitor::Connect {
                 launcher_id,
                 project,
                 tool_type,
-                claude_args,
+                tool_args,
                 working_dir,
                 timestamp,
+                protocol_version,
+                capabilities,
+                metadata,
             } => {
+                let requested_id = launcher_id;
+                // 互換性のないlauncherは理由を添えて拒否
+                if let Err(reason) = negotiate_protocol_version(protocol_version) {
+                    return Ok(Some(MonitorToLauncher::ConnectRejected {
+                        requested_id,
+                        reason,
+                    }));
+                }
+                // 接続応答を理解しないlauncher（v1）には応答せず、IDの衝突は従来どおりエラー
+                let responds = capabilities
+                    .iter()
+                    .any(|name| name == capability::CONNECT_RESPONSE);
+
+                let launcher_id = match self.launchers.get_mut(&requested_id) {
+                    // 再接続時に再送されたConnect（同じ接続時刻・作業ディレクトリ）は同じlauncher
+                    Some(existing)
+                        if existing.connected_at == timestamp
+                            && existing.working_dir == working_dir =>
+                    {
+                        existing.last_activity = Utc::now();
+                        return Ok(
+                            responds.then(|| connect_response(requested_id.clone(), requested_id))
+                        );
+                    }
+                    // 別のlauncherとIDが衝突した場合は新しいIDを割り当てる
+                    Some(_) if responds => generate_launcher_id(),
+                    _ => requested_id.clone(),
+                };
+
                 let launcher = LauncherInfo {
                     id: launcher_id.clone(),
                     project: project.clone(),
                     tool_type: tool_type.clone(),
-                    claude_args,
+                    tool_args,
                     working_dir,
                     connected_at: timestamp,
                     last_activity: timestamp,
                     status: LauncherStatus::Connected,
+                    origin: None,
+                    metadata,
                 };
 
                 // launcher を登録
-                self.add_launcher(launcher)
+                self.add_launcher(launcher)?;
+                Ok(responds.then(|| connect_response(requested_id, launcher_id)))
             }
 
             LauncherToMonitor::StateUpdate {
@@ -96,6 +373,7 @@ impl SessionManager {
                 status,
                 ui_above_text,
                 timestamp,
+                evidence,
             } => {
                 // launcher情報からプロジェクトとツールタイプを取得
                 let (project, tool_type) = self
@@ -106,6 +384,18 @@ impl SessionManager {
 
                 // 既存セッションから前回の状態変更時刻を取得
                 let existing_session = self.sessions.get(&session_id);
+                if let Some(existing) = existing_session {
+                    if existing.launcher_id != launcher_id {
+                        return Err(format!(
+                            "Session {session_id} belongs to launcher {}",
+                            existing.launcher_id
+                        ));
+                    }
+                }
+                let is_waiting_for_execution = existing_session
+                    .map(|s| s.is_waiting_for_execution)
+                    .unwrap_or(false);
+                let last_message = existing_session.and_then(|s| s.last_message.clone());
                 let (created_at, last_status_change) = existing_session
                     .map(|s| {
                         let last_change = if s.status != status {
@@ -126,20 +416,25 @@ impl SessionManager {
                     tool_type,
                     status,
                     previous_status: existing_session.as_ref().map(|s| s.status.clone()),
-                    // confidence フィールドは削除済み
-                    evidence: Vec::new(),            // 簡易実装では空
-                    last_message: None,              // 簡易実装では空
-                    launcher_context: None,          // 簡易実装では空
-                    usage_reset_time: None,          // 簡易実装では空
-                    is_waiting_for_execution: false, // 簡易実装では固定値
+                    // 判定根拠を送らないlauncherの場合は空
+                    evidence: evidence.map(|e| e.describe()).unwrap_or_default(),
+                    last_message,             // 自動応答の結果を保持
+                    launcher_context: None,   // 簡易実装では空
+                    usage_reset_time: None,   // 簡易実装では空
+                    is_waiting_for_execution, // PromptQueueUpdateで更新される予約状態を保持
                     ui_above_text,
                     created_at,
                     last_activity: timestamp,
                     last_status_change,
+                    exit: existing_session.and_then(|s| s.exit.clone()),
+                    metrics: existing_session
+                        .map(|s| s.metrics.clone())
+                        .unwrap_or_default(),
+                    activity: existing_session.and_then(|s| s.activity.clone()),
                 };
 
                 self.update_session(session);
-                Ok(())
+                Ok(None)
             }
 
             LauncherToMonitor::ContextUpdate {
@@ -158,19 +453,128 @@ impl SessionManager {
                         // コンパイラの未使用変数警告を回避
                     }
                 }
-                Ok(())
+                Ok(None)
+            }
+
+            LauncherToMonitor::PromptQueueUpdate {
+                session_id,
+                queued_prompt,
+                timestamp,
+                ..
+            } => {
+                // 既存セッションの予約状態のみ更新
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.is_waiting_for_execution = queued_prompt.is_some();
+                    session.last_activity = timestamp;
+                }
+                Ok(None)
             }
 
-            // ProcessMetrics は削除済み
+            LauncherToMonitor::AutoApproveDecision {
+                launcher_id,
+                session_id,
+                rule,
+                action,
+                prompt,
+                dry_run,
+                timestamp,
+            } => {
+                let event = AutoApproveEvent {
+                    launcher_id,
+                    session_id,
+                    rule,
+                    action,
+                    prompt,
+                    dry_run,
+                    timestamp,
+                };
+
+                if let Some(session) = self.sessions.get_mut(&event.session_id) {
+                    session.last_message = Some(event.summary());
+                    session.last_activity = timestamp;
+                }
+
+                self.auto_approve_events.push_back(event);
+                while self.auto_approve_events.len() > MAX_AUTO_APPROVE_EVENTS {
+                    self.auto_approve_events.pop_front();
+                }
+                Ok(None)
+            }
+
+            LauncherToMonitor::TerminalNotification {
+                session_id,
+                title,
+                body,
+                timestamp,
+                ..
+            } => {
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    let message = terminal_notification_message(title.as_deref(), &body);
+                    session.last_message = Some(format!("🔔 {message}"));
+                    session.last_activity = timestamp;
+                }
+                Ok(None)
+            }
+
+            LauncherToMonitor::Exited {
+                session_id,
+                exit_code,
+                signal,
+                runtime_secs,
+                timestamp,
+                ..
+            } => {
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.exit = Some(ProcessExit {
+                        exit_code,
+                        signal,
+                        runtime_secs,
+                        exited_at: timestamp,
+                    });
+                    session.last_activity = timestamp;
+                }
+                Ok(None)
+            }
+
+            LauncherToMonitor::ProcessMetrics {
+                session_id,
+                metrics,
+                ..
+            } => {
+                // 活動ではないので last_activity は更新しない
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.metrics.push(metrics);
+                    let excess = session.metrics.len().saturating_sub(MAX_METRICS_SAMPLES);
+                    session.metrics.drain(..excess);
+                }
+                Ok(None)
+            }
+
+            LauncherToMonitor::Activity {
+                session_id,
+                activity,
+                ..
+            } => {
+                // 入出力の時刻は activity 側に含まれるので last_activity は更新しない
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.activity = Some(activity);
+                }
+                Ok(None)
+            }
 
             // OutputCapture は削除済み
             LauncherToMonitor::Disconnect { launcher_id, .. } => {
-                self.remove_launcher(&launcher_id);
-                Ok(())
+                self.disconnect_launcher(&launcher_id);
+                Ok(None)
             }
         }
     }
 
+    /// 自動応答の監査イベント一覧（古い順）
+    pub fn get_auto_approve_events(&self) -> impl Iterator<Item = &AutoApproveEvent> {
+        self.auto_approve_events.iter()
+    }
+
     /// アクティブなlauncher一覧
     pub fn get_active_launchers(&self) -> Vec<&LauncherInfo> {
         self.launchers
@@ -179,6 +583,20 @@ impl SessionManager {
             .collect()
     }
 
+    /// 表示するlauncher一覧（アクティブなものと終了後の保持期間中のもの）
+    pub fn get_visible_launchers(&self) -> Vec<&LauncherInfo> {
+        self.launchers
+            .values()
+            .filter(|l| {
+                l.status != LauncherStatus::Disconnected
+                    || self
+                        .sessions
+                        .values()
+                        .any(|s| s.launcher_id == l.id && s.exit.is_some())
+            })
+            .collect()
+    }
+
     /// 全launcher ID一覧を取得
     pub fn get_launcher_ids(&self) -> Vec<String> {
         self.launchers.keys().cloned().collect()
@@ -212,23 +630,23 @@ impl SessionManager {
         let mut projects: HashMap<String, Vec<&SessionInfo>> = HashMap::new();
 
         for session in self.get_active_sessions() {
-            let project_name = session
+            let launcher = self.launchers.get(&session.launcher_id);
+            let project = session
                 .project
                 .as_deref()
-                .or_else(|| {
-                    self.launchers
-                        .get(&session.launcher_id)
-                        .and_then(|l| l.project.as_deref())
-                })
-                .unwrap_or_default()
-                .to_string();
+                .or_else(|| launcher.and_then(|l| l.project.as_deref()))
+                .unwrap_or_default();
+            let project_name = match launcher {
+                Some(launcher) => group_name(project, launcher),
+                None => project.to_string(),
+            };
 
             projects.entry(project_name).or_default().push(session);
         }
 
         // 各プロジェクト内で最新順にソート
         for sessions in projects.values_mut() {
-            sessions.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+            sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
         }
 
         projects
@@ -241,8 +659,9 @@ impl SessionManager {
         let mut projects: HashMap<String, Vec<(&LauncherInfo, Option<&SessionInfo>)>> =
             HashMap::new();
 
-        for launcher in self.get_active_launchers() {
-            let project_name = launcher.project.as_deref().unwrap_or_default().to_string();
+        for launcher in self.get_visible_launchers() {
+            let project_name =
+                group_name(launcher.project.as_deref().unwrap_or_default(), launcher);
 
             // このlauncherに対応するセッションを検索
             let session = self
@@ -281,21 +700,26 @@ pub struct SessionStats {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use climonitor_shared::{generate_connection_id, CliToolType};
+    use climonitor_shared::{
+        generate_launcher_id, CliToolType, LauncherMetadata, SessionActivity,
+        LEGACY_PROTOCOL_VERSION,
+    };
 
     #[test]
     fn test_launcher_lifecycle() {
         let mut manager = SessionManager::new();
 
         let launcher = LauncherInfo {
-            id: generate_connection_id(),
+            id: generate_launcher_id(),
             project: Some("test".to_string()),
             tool_type: CliToolType::Claude,
-            claude_args: vec!["--help".to_string()],
+            tool_args: vec!["--help".to_string()],
             working_dir: "/tmp".into(),
             connected_at: Utc::now(),
             last_activity: Utc::now(),
             status: LauncherStatus::Connected,
+            origin: None,
+            metadata: Default::default(),
         };
 
         let launcher_id = launcher.id.clone();
@@ -317,4 +741,392 @@ mod tests {
         assert_eq!(stats.total_sessions, 0);
         assert_eq!(stats.active_sessions, 0);
     }
+
+    fn connect_local(manager: &mut SessionManager, launcher_id: &str) {
+        manager
+            .handle_message(LauncherToMonitor::Connect {
+                launcher_id: launcher_id.to_string(),
+                project: None,
+                tool_type: CliToolType::Claude,
+                tool_args: Vec::new(),
+                working_dir: "/tmp".into(),
+                timestamp: Utc::now(),
+                protocol_version: PROTOCOL_VERSION,
+                capabilities: supported_capabilities(),
+                metadata: Default::default(),
+            })
+            .unwrap();
+        manager
+            .handle_message(LauncherToMonitor::StateUpdate {
+                launcher_id: launcher_id.to_string(),
+                session_id: format!("{launcher_id}-session"),
+                status: SessionStatus::Idle,
+                ui_above_text: None,
+                timestamp: Utc::now(),
+                evidence: None,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_mutual_federation_does_not_nest_origins() {
+        // 互いを上流に指定した2台のmonitor
+        let mut monitor_a = SessionManager::new();
+        let mut monitor_b = SessionManager::new();
+        connect_local(&mut monitor_a, "launcher-a");
+        connect_local(&mut monitor_b, "launcher-b");
+
+        for _ in 0..3 {
+            monitor_a.replace_origin("b", monitor_b.snapshot());
+            monitor_b.replace_origin("a", monitor_a.snapshot());
+        }
+
+        // 自分のlauncherが接続元付きで戻ってきたり、接続元が連結されたりしない
+        let mut ids = monitor_a.get_launcher_ids();
+        ids.sort();
+        assert_eq!(ids, vec!["b/launcher-b", "launcher-a"]);
+        let mut ids = monitor_b.get_launcher_ids();
+        ids.sort();
+        assert_eq!(ids, vec!["a/launcher-a", "launcher-b"]);
+        assert!(monitor_a.get_session("b/launcher-b-session").is_some());
+
+        // 統合したlauncherも配信する古い上流からは、その分を取り込まない
+        let mut legacy_snapshot = monitor_a.snapshot();
+        let mut forwarded = monitor_a.get_launcher("b/launcher-b").unwrap().clone();
+        forwarded.id = "b/launcher-b".to_string();
+        legacy_snapshot.launchers.push(forwarded);
+        legacy_snapshot.sessions.push(
+            monitor_a
+                .get_session("b/launcher-b-session")
+                .unwrap()
+                .clone(),
+        );
+        monitor_b.replace_origin("a", legacy_snapshot);
+        let mut ids = monitor_b.get_launcher_ids();
+        ids.sort();
+        assert_eq!(ids, vec!["a/launcher-a", "launcher-b"]);
+        assert!(monitor_b.get_session("a/b/launcher-b-session").is_none());
+    }
+
+    #[test]
+    fn test_origins_do_not_collide() {
+        let mut upstream = SessionManager::new();
+        upstream
+            .handle_message(LauncherToMonitor::Connect {
+                launcher_id: "launcher-1".to_string(),
+                project: Some("app".to_string()),
+                tool_type: CliToolType::Claude,
+                tool_args: Vec::new(),
+                working_dir: "/tmp".into(),
+                timestamp: Utc::now(),
+                protocol_version: PROTOCOL_VERSION,
+                capabilities: supported_capabilities(),
+                metadata: Default::default(),
+            })
+            .unwrap();
+        upstream
+            .handle_message(LauncherToMonitor::StateUpdate {
+                launcher_id: "launcher-1".to_string(),
+                session_id: "session-1".to_string(),
+                status: SessionStatus::Busy,
+                ui_above_text: None,
+                timestamp: Utc::now(),
+                evidence: None,
+            })
+            .unwrap();
+        let snapshot = upstream.snapshot();
+
+        // 同じlauncher IDでも接続元ごとに別のlauncherとして保持
+        let mut manager = SessionManager::new();
+        manager.replace_origin("devbox-a", snapshot.clone());
+        manager.replace_origin("devbox-b", snapshot);
+        assert!(manager.get_launcher("devbox-a/launcher-1").is_some());
+        assert_eq!(
+            manager
+                .get_session("devbox-b/session-1")
+                .unwrap()
+                .launcher_id,
+            "devbox-b/launcher-1"
+        );
+        let projects = manager.get_launchers_by_project();
+        assert!(projects.contains_key("app @devbox-a"));
+        assert!(projects.contains_key("app @devbox-b"));
+
+        // 統合したlauncherは下流へ配信しない
+        let mut downstream = SessionManager::new();
+        downstream.replace_origin("hub", manager.snapshot());
+        assert!(downstream.get_launcher_ids().is_empty());
+
+        // 切断された接続元のみ削除
+        manager.remove_origin("devbox-a");
+        assert!(manager.get_launcher("devbox-a/launcher-1").is_none());
+        assert!(manager.get_session("devbox-a/session-1").is_none());
+        assert!(manager.get_launcher("devbox-b/launcher-1").is_some());
+
+        // ローカルのlauncherは区切り文字を含むIDを使えない
+        let result = manager.handle_message(LauncherToMonitor::Connect {
+            launcher_id: "devbox-b/launcher-1".to_string(),
+            project: None,
+            tool_type: CliToolType::Claude,
+            tool_args: Vec::new(),
+            working_dir: "/tmp".into(),
+            timestamp: Utc::now(),
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: supported_capabilities(),
+            metadata: Default::default(),
+        });
+        assert!(result.is_err());
+    }
+
+    fn connect_message(
+        launcher_id: &str,
+        working_dir: &str,
+        timestamp: DateTime<Utc>,
+    ) -> LauncherToMonitor {
+        LauncherToMonitor::Connect {
+            launcher_id: launcher_id.to_string(),
+            project: None,
+            tool_type: CliToolType::Claude,
+            tool_args: Vec::new(),
+            working_dir: working_dir.into(),
+            timestamp,
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: supported_capabilities(),
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_colliding_launcher_id_is_reassigned() {
+        let mut manager = SessionManager::new();
+        let first_connected_at = Utc::now();
+        let response = manager
+            .handle_message(connect_message("launcher-1", "/tmp/a", first_connected_at))
+            .unwrap();
+        assert_eq!(
+            response,
+            Some(connect_response(
+                "launcher-1".to_string(),
+                "launcher-1".to_string()
+            ))
+        );
+
+        // 別のlauncherが同じIDで接続すると新しいIDが割り当てられる
+        let Some(MonitorToLauncher::ConnectResponse { launcher_id, .. }) = manager
+            .handle_message(connect_message("launcher-1", "/tmp/b", Utc::now()))
+            .unwrap()
+        else {
+            panic!("expected connect response");
+        };
+        assert_ne!(launcher_id, "launcher-1");
+        assert_eq!(manager.get_active_launchers().len(), 2);
+        assert_eq!(
+            manager.get_launcher(&launcher_id).unwrap().working_dir,
+            std::path::PathBuf::from("/tmp/b")
+        );
+
+        // 再接続時に再送されたConnectは同じlauncherとして扱う
+        let response = manager
+            .handle_message(connect_message("launcher-1", "/tmp/a", first_connected_at))
+            .unwrap();
+        assert!(matches!(
+            response,
+            Some(MonitorToLauncher::ConnectResponse { launcher_id, .. }) if launcher_id == "launcher-1"
+        ));
+        assert_eq!(manager.get_active_launchers().len(), 2);
+    }
+
+    #[test]
+    fn test_launchers_are_grouped_by_branch() {
+        let mut manager = SessionManager::new();
+        for (launcher_id, branch) in [("launcher-1", "main"), ("launcher-2", "feature-x")] {
+            manager
+                .handle_message(LauncherToMonitor::Connect {
+                    launcher_id: launcher_id.to_string(),
+                    project: Some("app".to_string()),
+                    tool_type: CliToolType::Claude,
+                    tool_args: Vec::new(),
+                    working_dir: "/tmp".into(),
+                    timestamp: Utc::now(),
+                    protocol_version: PROTOCOL_VERSION,
+                    capabilities: supported_capabilities(),
+                    metadata: LauncherMetadata {
+                        git_branch: Some(branch.to_string()),
+                        ..Default::default()
+                    },
+                })
+                .unwrap();
+        }
+
+        let projects = manager.get_launchers_by_project();
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects["app [main]"][0].0.id, "launcher-1");
+        assert_eq!(projects["app [feature-x]"][0].0.id, "launcher-2");
+    }
+
+    #[test]
+    fn test_legacy_launcher_gets_no_connect_response() {
+        let mut manager = SessionManager::new();
+        let legacy_connect = |working_dir: &str| LauncherToMonitor::Connect {
+            launcher_id: "launcher-1".to_string(),
+            project: None,
+            tool_type: CliToolType::Claude,
+            tool_args: Vec::new(),
+            working_dir: working_dir.into(),
+            timestamp: Utc::now(),
+            protocol_version: LEGACY_PROTOCOL_VERSION,
+            capabilities: Vec::new(),
+            metadata: Default::default(),
+        };
+
+        // 応答を解釈できないlauncherには応答もIDの再割り当ても行わない
+        assert_eq!(manager.handle_message(legacy_connect("/tmp/a")), Ok(None));
+        assert!(manager.handle_message(legacy_connect("/tmp/b")).is_err());
+        assert_eq!(manager.get_active_launchers().len(), 1);
+    }
+
+    #[test]
+    fn test_unsupported_protocol_version_is_rejected() {
+        let mut manager = SessionManager::new();
+        let mut message = connect_message("launcher-1", "/tmp", Utc::now());
+        if let LauncherToMonitor::Connect {
+            protocol_version, ..
+        } = &mut message
+        {
+            *protocol_version = 0;
+        }
+
+        let response = manager.handle_message(message).unwrap();
+        assert!(matches!(
+            response,
+            Some(MonitorToLauncher::ConnectRejected { requested_id, .. }) if requested_id == "launcher-1"
+        ));
+        assert!(manager.get_active_launchers().is_empty());
+    }
+
+    #[test]
+    fn test_session_id_cannot_be_taken_over_by_other_launcher() {
+        let mut manager = SessionManager::new();
+        let state_update = |launcher_id: &str| LauncherToMonitor::StateUpdate {
+            launcher_id: launcher_id.to_string(),
+            session_id: "session-1".to_string(),
+            status: SessionStatus::Busy,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: None,
+        };
+
+        manager.handle_message(state_update("launcher-1")).unwrap();
+        assert!(manager.handle_message(state_update("launcher-2")).is_err());
+        assert_eq!(
+            manager.get_session("session-1").unwrap().launcher_id,
+            "launcher-1"
+        );
+    }
+
+    #[test]
+    fn test_exited_launcher_is_kept_until_retention_expires() {
+        let mut manager = SessionManager::new();
+        for launcher_id in ["launcher-1", "launcher-2"] {
+            manager
+                .handle_message(connect_message(launcher_id, "/tmp/a", Utc::now()))
+                .unwrap();
+            manager
+                .handle_message(LauncherToMonitor::StateUpdate {
+                    launcher_id: launcher_id.to_string(),
+                    session_id: format!("{launcher_id}-session"),
+                    status: SessionStatus::Busy,
+                    ui_above_text: None,
+                    timestamp: Utc::now(),
+                    evidence: None,
+                })
+                .unwrap();
+        }
+
+        // launcher-1 は終了コードを報告してから切断、launcher-2 は報告なしで切断
+        manager
+            .handle_message(LauncherToMonitor::Exited {
+                launcher_id: "launcher-1".to_string(),
+                session_id: "launcher-1-session".to_string(),
+                exit_code: 1,
+                signal: None,
+                runtime_secs: 12,
+                timestamp: Utc::now(),
+            })
+            .unwrap();
+        for launcher_id in ["launcher-1", "launcher-2"] {
+            manager
+                .handle_message(LauncherToMonitor::Disconnect {
+                    launcher_id: launcher_id.to_string(),
+                    timestamp: Utc::now(),
+                })
+                .unwrap();
+        }
+
+        assert!(manager.get_active_launchers().is_empty());
+        assert!(manager.get_launcher("launcher-2").is_none());
+        let visible = manager.get_visible_launchers();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].status, LauncherStatus::Disconnected);
+        let exit = manager
+            .get_session("launcher-1-session")
+            .and_then(|s| s.exit.clone())
+            .unwrap();
+        assert!(!exit.success());
+        assert_eq!(manager.snapshot().sessions.len(), 1);
+
+        // 保持期間内は残り、過ぎると削除される
+        assert!(!manager.prune_exited(chrono::Duration::seconds(300)));
+        assert!(manager.get_launcher("launcher-1").is_some());
+        assert!(manager.prune_exited(chrono::Duration::zero()));
+        assert!(manager.get_launcher("launcher-1").is_none());
+        assert!(manager.get_session("launcher-1-session").is_none());
+    }
+
+    #[test]
+    fn test_activity_is_kept_across_state_updates() {
+        let mut manager = SessionManager::new();
+        manager
+            .handle_message(connect_message("launcher-1", "/tmp/a", Utc::now()))
+            .unwrap();
+        let state_update = |status| LauncherToMonitor::StateUpdate {
+            launcher_id: "launcher-1".to_string(),
+            session_id: "session-1".to_string(),
+            status,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: None,
+        };
+        manager
+            .handle_message(state_update(SessionStatus::Busy))
+            .unwrap();
+        let last_activity = manager.get_session("session-1").unwrap().last_activity;
+
+        let activity = SessionActivity {
+            output_bytes_per_sec: 0,
+            last_output_at: Some(Utc::now() - chrono::Duration::minutes(6)),
+            last_input_at: None,
+            stalled: true,
+        };
+        manager
+            .handle_message(LauncherToMonitor::Activity {
+                launcher_id: "launcher-1".to_string(),
+                session_id: "session-1".to_string(),
+                activity: activity.clone(),
+                timestamp: Utc::now(),
+            })
+            .unwrap();
+        let session = manager.get_session("session-1").unwrap();
+        assert_eq!(session.activity.as_ref(), Some(&activity));
+        assert_eq!(session.last_activity, last_activity);
+
+        // 状態更新で消えない
+        manager
+            .handle_message(state_update(SessionStatus::Idle))
+            .unwrap();
+        assert_eq!(
+            manager.get_session("session-1").unwrap().activity,
+            Some(activity)
+        );
+    }
 }
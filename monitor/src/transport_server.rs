@@ -2,43 +2,69 @@ use anyhow::Result;
 use async_trait::async_trait;
 use climonitor_shared::{
     transport::{MessageHandler, MessageReceiver},
-    ConnectionConfig, LauncherToMonitor,
+    ConnectionConfig, LauncherToMonitor, MonitorToLauncher, ProcessExit,
 };
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 use tokio::task::JoinHandle;
 
+use crate::config_reload::{RuntimeSettings, SharedRuntimeSettings};
 use crate::notification::NotificationManager;
-use crate::session_manager::SessionManager;
+use crate::session_manager::{AutoApproveEvent, SessionManager};
+
+/// 終了したlauncherの保持期間を確認する間隔
+const PRUNE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
 
 /// 抽象化されたTransport Monitor サーバー
 pub struct TransportMonitorServer {
     config: ConnectionConfig,
     session_manager: Arc<RwLock<SessionManager>>,
     ui_update_sender: broadcast::Sender<()>,
+    /// launcherへ能動的に送るメッセージ（ダッシュボードからのプロンプト予約など）
+    launcher_message_sender: broadcast::Sender<MonitorToLauncher>,
     task_handles: Vec<JoinHandle<()>>,
     verbose: bool,
     _log_file: Option<PathBuf>,
     _message_receiver: Option<Box<dyn MessageReceiver>>,
+    runtime_settings: SharedRuntimeSettings,
 }
 
 impl TransportMonitorServer {
     pub fn new(config: ConnectionConfig, verbose: bool, log_file: Option<PathBuf>) -> Result<Self> {
         let session_manager = Arc::new(RwLock::new(SessionManager::new()));
         let (ui_update_sender, _) = broadcast::channel(100);
+        let (launcher_message_sender, _) = broadcast::channel(16);
+        let allowed_ips = match &config {
+            ConnectionConfig::Grpc { allowed_ips, .. }
+            | ConnectionConfig::Tcp { allowed_ips, .. } => allowed_ips.clone(),
+            #[cfg(unix)]
+            ConnectionConfig::Unix { .. } => Vec::new(),
+        };
+        let runtime_settings = Arc::new(RwLock::new(RuntimeSettings {
+            allowed_ips,
+            ..RuntimeSettings::default()
+        }));
 
         Ok(Self {
             config,
             session_manager,
             ui_update_sender,
+            launcher_message_sender,
             task_handles: Vec::new(),
             verbose,
             _log_file: log_file,
             _message_receiver: None,
+            runtime_settings,
         })
     }
 
+    /// 設定の再読み込みで更新される実行時設定を共有
+    pub fn with_runtime_settings(mut self, runtime_settings: SharedRuntimeSettings) -> Self {
+        self.runtime_settings = runtime_settings;
+        self
+    }
+
     /// サーバー開始とメインループ実行
     pub async fn run(&mut self) -> Result<()> {
         if self.verbose {
@@ -49,12 +75,20 @@ impl TransportMonitorServer {
         let handler = MonitorMessageHandler {
             session_manager: Arc::clone(&self.session_manager),
             ui_update_sender: self.ui_update_sender.clone(),
+            launcher_message_sender: self.launcher_message_sender.clone(),
             verbose: self.verbose,
+            runtime_settings: Arc::clone(&self.runtime_settings),
         };
 
         // Create message receiver
-        let mut message_receiver =
-            crate::transports::create_message_receiver(&self.config, Box::new(handler)).await?;
+        let mut message_receiver = crate::transports::create_message_receiver(
+            &self.config,
+            Box::new(handler),
+            Arc::clone(&self.runtime_settings),
+        )
+        .await?;
+
+        self.task_handles.push(self.spawn_exited_pruner());
 
         if self.verbose {
             println!("⚡ Server running, waiting for launcher connections...");
@@ -80,7 +114,23 @@ impl TransportMonitorServer {
         Ok(())
     }
 
-    // This method is no longer needed with the new trait-based approach
+    /// 保持期間（ui.exited_retention_secs）を過ぎた終了済みlauncherを定期的に削除
+    fn spawn_exited_pruner(&self) -> JoinHandle<()> {
+        let session_manager = Arc::clone(&self.session_manager);
+        let runtime_settings = Arc::clone(&self.runtime_settings);
+        let ui_update_sender = self.ui_update_sender.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+            loop {
+                interval.tick().await;
+                let retention_secs = runtime_settings.read().await.ui.exited_retention_secs;
+                let retention = chrono::Duration::seconds(retention_secs as i64);
+                if session_manager.write().await.prune_exited(retention) {
+                    let _ = ui_update_sender.send(());
+                }
+            }
+        })
+    }
 
     // This method is no longer needed with the new trait-based approach
 
@@ -90,6 +140,7 @@ impl TransportMonitorServer {
 
     /// 必要に応じて通知を送信（既存のロジックを再利用）
     async fn send_notification_if_needed(
+        notification_settings: &climonitor_shared::NotificationSettings,
         tool_name: String,
         duration_seconds: u64,
         status: climonitor_shared::SessionStatus,
@@ -98,7 +149,7 @@ impl TransportMonitorServer {
     ) {
         use climonitor_shared::SessionStatus;
 
-        let notification_manager = NotificationManager::new();
+        let notification_manager = NotificationManager::from_settings(notification_settings);
         let message = ui_above_text.unwrap_or_else(|| "状態変化".to_string());
         let duration_str = format!("{duration_seconds}s");
 
@@ -137,6 +188,16 @@ impl TransportMonitorServer {
         Arc::clone(&self.session_manager)
     }
 
+    /// UI更新通知の送信側取得（設定の再読み込み通知用）
+    pub fn ui_update_sender(&self) -> broadcast::Sender<()> {
+        self.ui_update_sender.clone()
+    }
+
+    /// launcherへ送るメッセージの送信側取得（ダッシュボードからのプロンプト予約用）
+    pub fn launcher_message_sender(&self) -> broadcast::Sender<MonitorToLauncher> {
+        self.launcher_message_sender.clone()
+    }
+
     /// サーバー終了
     async fn shutdown(&mut self, message_receiver: &mut Box<dyn MessageReceiver>) -> Result<()> {
         // 全タスクを終了
@@ -159,12 +220,27 @@ impl TransportMonitorServer {
 struct MonitorMessageHandler {
     session_manager: Arc<RwLock<SessionManager>>,
     ui_update_sender: broadcast::Sender<()>,
+    launcher_message_sender: broadcast::Sender<MonitorToLauncher>,
     verbose: bool,
+    runtime_settings: SharedRuntimeSettings,
+}
+
+impl MonitorMessageHandler {
+    /// 通知用のツール名
+    fn tool_name(manager: &SessionManager, launcher_id: &str) -> String {
+        manager
+            .get_launcher(launcher_id)
+            .map(|l| l.tool_type.to_command().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
 }
 
 #[async_trait]
 impl MessageHandler for MonitorMessageHandler {
-    async fn handle_message(&self, message: LauncherToMonitor) -> Result<()> {
+    async fn handle_message(
+        &self,
+        message: LauncherToMonitor,
+    ) -> Result<Option<MonitorToLauncher>> {
         if self.verbose {
             println!("📨 Handling message: {message:?}");
         }
@@ -180,10 +256,7 @@ impl MessageHandler for MonitorMessageHandler {
             } => {
                 let (tool_name, duration_seconds, previous_status) = {
                     let manager = self.session_manager.read().await;
-                    let tool_name = manager
-                        .get_launcher(launcher_id)
-                        .map(|l| l.tool_type.to_command().to_string())
-                        .unwrap_or_else(|| "unknown".to_string());
+                    let tool_name = Self::tool_name(&manager, launcher_id);
 
                     let (duration_seconds, previous_status) =
                         if let Some(session) = manager.get_session(session_id) {
@@ -207,37 +280,169 @@ impl MessageHandler for MonitorMessageHandler {
             }
             _ => None,
         };
+        let terminal_notification = match &message {
+            LauncherToMonitor::TerminalNotification {
+                launcher_id,
+                title,
+                body,
+                ..
+            } => {
+                let tool_name = Self::tool_name(&*self.session_manager.read().await, launcher_id);
+                Some((tool_name, title.clone(), body.clone()))
+            }
+            _ => None,
+        };
+        // 異常終了はエラー通知
+        let abnormal_exit = match &message {
+            LauncherToMonitor::Exited {
+                launcher_id,
+                exit_code,
+                signal,
+                runtime_secs,
+                timestamp,
+                ..
+            } => {
+                let exit = ProcessExit {
+                    exit_code: *exit_code,
+                    signal: signal.clone(),
+                    runtime_secs: *runtime_secs,
+                    exited_at: *timestamp,
+                };
+                if exit.success() {
+                    None
+                } else {
+                    let tool_name =
+                        Self::tool_name(&*self.session_manager.read().await, launcher_id);
+                    Some((tool_name, exit.summary()))
+                }
+            }
+            _ => None,
+        };
+
+        // 自動応答の判定は監査のため常にログへ残す
+        let auto_approve_event = match &message {
+            LauncherToMonitor::AutoApproveDecision {
+                launcher_id,
+                session_id,
+                rule,
+                action,
+                prompt,
+                dry_run,
+                timestamp,
+            } => Some(AutoApproveEvent {
+                launcher_id: launcher_id.clone(),
+                session_id: session_id.clone(),
+                rule: rule.clone(),
+                action: action.clone(),
+                prompt: prompt.clone(),
+                dry_run: *dry_run,
+                timestamp: *timestamp,
+            }),
+            _ => None,
+        };
 
         // セッションマネージャーで処理
-        if let Err(e) = self.session_manager.write().await.handle_message(message) {
-            climonitor_shared::log_warn!(
-                climonitor_shared::LogCategory::Session,
-                "Message handling error: {e}"
-            );
-        } else {
-            if self.verbose {
-                println!("✅ Message processed successfully");
+        let result = self.session_manager.write().await.handle_message(message);
+        let reply = match result {
+            Err(e) => {
+                climonitor_shared::log_warn!(
+                    climonitor_shared::LogCategory::Session,
+                    "Message handling error: {e}"
+                );
+                None
             }
+            Ok(reply) => {
+                if self.verbose {
+                    println!("✅ Message processed successfully");
+                }
 
-            // 通知送信（StateUpdateの場合のみ）
-            if let Some((tool_name, duration_seconds, status, ui_above_text, previous_status)) =
-                notification_info
-            {
-                TransportMonitorServer::send_notification_if_needed(
-                    tool_name,
-                    duration_seconds,
-                    status,
-                    ui_above_text,
-                    previous_status,
-                )
-                .await;
+                match &reply {
+                    Some(MonitorToLauncher::ConnectResponse {
+                        requested_id,
+                        launcher_id,
+                        ..
+                    }) if requested_id != launcher_id => {
+                        climonitor_shared::log_info!(
+                            climonitor_shared::LogCategory::Session,
+                            "🆔 Launcher id {requested_id} is already in use, assigned {launcher_id}"
+                        );
+                    }
+                    Some(MonitorToLauncher::ConnectRejected {
+                        requested_id,
+                        reason,
+                    }) => {
+                        climonitor_shared::log_warn!(
+                            climonitor_shared::LogCategory::Session,
+                            "🚫 Rejected launcher {requested_id}: {reason}"
+                        );
+                    }
+                    _ => {}
+                }
+
+                // 通知送信（StateUpdateの場合のみ）
+                if let Some((tool_name, duration_seconds, status, ui_above_text, previous_status)) =
+                    notification_info
+                {
+                    let notification_settings =
+                        self.runtime_settings.read().await.notification.clone();
+                    TransportMonitorServer::send_notification_if_needed(
+                        &notification_settings,
+                        tool_name,
+                        duration_seconds,
+                        status,
+                        ui_above_text,
+                        previous_status,
+                    )
+                    .await;
+                }
+
+                // CLIツールのデスクトップ通知を通知スクリプトへ転送
+                if let Some((tool_name, title, body)) = terminal_notification {
+                    let notification_settings =
+                        self.runtime_settings.read().await.notification.clone();
+                    NotificationManager::from_settings(&notification_settings)
+                        .notify_terminal(&tool_name, title.as_deref(), &body)
+                        .await;
+                }
+
+                if let Some(event) = auto_approve_event {
+                    let tool_name =
+                        Self::tool_name(&*self.session_manager.read().await, &event.launcher_id);
+                    climonitor_shared::log_info!(
+                        climonitor_shared::LogCategory::Session,
+                        "{} [{tool_name}] {}",
+                        event.summary(),
+                        event.prompt_preview()
+                    );
+                }
+
+                if let Some((tool_name, summary)) = abnormal_exit {
+                    let notification_settings =
+                        self.runtime_settings.read().await.notification.clone();
+                    NotificationManager::from_settings(&notification_settings)
+                        .notify_error(&tool_name, &format!("Exited with {summary}"))
+                        .await;
+                }
+                reply
             }
-        }
+        };
 
         // UI更新通知
         let _ = self.ui_update_sender.send(());
 
-        Ok(())
+        Ok(reply)
+    }
+
+    async fn session_snapshot(&self) -> Option<climonitor_shared::SessionSnapshot> {
+        Some(self.session_manager.read().await.snapshot())
+    }
+
+    fn subscribe_updates(&self) -> Option<broadcast::Receiver<()>> {
+        Some(self.ui_update_sender.subscribe())
+    }
+
+    fn subscribe_launcher_messages(&self) -> Option<broadcast::Receiver<MonitorToLauncher>> {
+        Some(self.launcher_message_sender.subscribe())
     }
 }
 
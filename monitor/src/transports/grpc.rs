@@ -1,16 +1,21 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use futures_util::StreamExt;
 use std::net::SocketAddr;
 use std::str::FromStr;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::transport::Server;
 use tonic::{Request, Response, Status, Streaming};
 
+use crate::config_reload::SharedRuntimeSettings;
+use climonitor_shared::logging::tracing::Instrument;
 use climonitor_shared::{
     grpc::monitor_service_server::{MonitorService, MonitorServiceServer},
-    grpc::{LauncherMessage as GrpcLauncherMessage, MonitorMessage},
+    grpc::{
+        LauncherMessage as GrpcLauncherMessage, MonitorMessage,
+        SessionSnapshot as GrpcSessionSnapshot, WatchRequest,
+    },
     message_conversion as grpc_conversion,
     transport::{MessageHandler, MessageReceiver},
     ConnectionConfig,
@@ -19,20 +24,22 @@ use climonitor_shared::{
 /// gRPC メッセージレシーバー実装
 pub struct GrpcMessageReceiver {
     bind_addr: String,
-    allowed_ips: Vec<String>,
     handler: std::sync::Arc<dyn MessageHandler>,
+    /// IP許可リストは設定の再読み込みで更新される
+    runtime_settings: SharedRuntimeSettings,
 }
 
 impl GrpcMessageReceiver {
-    pub async fn new(config: &ConnectionConfig, handler: Box<dyn MessageHandler>) -> Result<Self> {
+    pub async fn new(
+        config: &ConnectionConfig,
+        handler: Box<dyn MessageHandler>,
+        runtime_settings: SharedRuntimeSettings,
+    ) -> Result<Self> {
         match config {
-            ConnectionConfig::Grpc {
-                bind_addr,
-                allowed_ips,
-            } => Ok(Self {
+            ConnectionConfig::Grpc { bind_addr, .. } => Ok(Self {
                 bind_addr: bind_addr.clone(),
-                allowed_ips: allowed_ips.clone(),
                 handler: std::sync::Arc::from(handler),
+                runtime_settings,
             }),
             _ => anyhow::bail!("gRPC transport requires gRPC configuration"),
         }
@@ -42,11 +49,12 @@ impl GrpcMessageReceiver {
 #[async_trait]
 impl MessageReceiver for GrpcMessageReceiver {
     async fn start_server(&mut self) -> Result<()> {
-        let addr = SocketAddr::from_str(&self.bind_addr)?;
+        let addr = SocketAddr::from_str(&self.bind_addr)
+            .with_context(|| format!("Invalid gRPC bind address: {}", self.bind_addr))?;
 
         let service = GrpcMonitorService {
             handler: std::sync::Arc::clone(&self.handler),
-            allowed_ips: self.allowed_ips.clone(),
+            runtime_settings: std::sync::Arc::clone(&self.runtime_settings),
         };
 
         println!("🚀 gRPC server listening on: {}", addr);
@@ -68,7 +76,24 @@ impl MessageReceiver for GrpcMessageReceiver {
 /// gRPC サービス実装
 struct GrpcMonitorService {
     handler: std::sync::Arc<dyn MessageHandler>,
-    allowed_ips: Vec<String>,
+    runtime_settings: SharedRuntimeSettings,
+}
+
+impl GrpcMonitorService {
+    /// IP許可チェック
+    async fn check_allowed(&self, remote_addr: Option<SocketAddr>) -> Result<(), Status> {
+        if let Some(remote_addr) = remote_addr {
+            let allowed_ips = self.runtime_settings.read().await.allowed_ips.clone();
+            if !climonitor_shared::ip_utils::is_ip_allowed_by_list(&remote_addr.ip(), &allowed_ips)
+            {
+                return Err(Status::permission_denied(format!(
+                    "IP address {} is not allowed",
+                    remote_addr.ip()
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -79,64 +104,150 @@ impl MonitorService for GrpcMonitorService {
         &self,
         request: Request<Streaming<GrpcLauncherMessage>>,
     ) -> Result<Response<Self::StreamSessionStream>, Status> {
-        // IP許可チェック
-        if let Some(remote_addr) = request.remote_addr() {
-            if !climonitor_shared::ip_utils::is_ip_allowed_by_list(
-                &remote_addr.ip(),
-                &self.allowed_ips,
-            ) {
-                return Err(Status::permission_denied(format!(
-                    "IP address {} is not allowed",
-                    remote_addr.ip()
-                )));
-            }
-        }
+        self.check_allowed(request.remote_addr()).await?;
 
+        let peer = request
+            .remote_addr()
+            .map(|addr| addr.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
         let mut stream = request.into_inner();
         let handler = std::sync::Arc::clone(&self.handler);
-
-        // 入力ストリームを処理
-        tokio::spawn(async move {
-            while let Some(result) = stream.next().await {
-                match result {
-                    Ok(grpc_message) => {
-                        // gRPCメッセージを内部プロトコルに変換
-                        match grpc_conversion::grpc_conversion::from_grpc_launcher_message(
-                            grpc_message,
-                        ) {
-                            Ok(message) => {
-                                // ハンドラーに渡す
-                                if let Err(e) = handler.handle_message(message).await {
+        let span = climonitor_shared::logging::grpc_stream_span(&peer);
+        // 応答ストリームは入力ストリームの終了まで開いておく
+        let (tx, rx) = mpsc::channel(16);
+
+        // 入力ストリームを処理し、接続中のlauncher宛てのメッセージも同じ応答ストリームで送る
+        let stream_span = span.clone();
+        tokio::spawn(
+            async move {
+                let mut pushes = handler.subscribe_launcher_messages();
+                let mut route = super::PushRoute::default();
+                loop {
+                    let result = tokio::select! {
+                        result = stream.next() => match result {
+                            Some(result) => result,
+                            None => break,
+                        },
+                        pushed = super::next_launcher_message(&mut pushes) => {
+                            if route.accepts(&pushed) {
+                                send_to_launcher(&tx, pushed).await;
+                            }
+                            continue;
+                        }
+                    };
+                    match result {
+                        Ok(grpc_message) => {
+                            // gRPCメッセージを内部プロトコルに変換
+                            match grpc_conversion::grpc_conversion::from_grpc_launcher_message(
+                                grpc_message,
+                            ) {
+                                Ok(message) => {
+                                    climonitor_shared::logging::record_message_ids(
+                                        &stream_span,
+                                        &message,
+                                    );
+                                    route.observe(&message);
+                                    // ハンドラーに渡し、応答があればlauncherに返す
+                                    match handler.handle_message(message).await {
+                                        Ok(Some(reply)) => {
+                                            route.observe_reply(&reply);
+                                            send_to_launcher(&tx, reply).await;
+                                        }
+                                        Ok(None) => {}
+                                        Err(e) => {
+                                            climonitor_shared::log_warn!(
+                                                climonitor_shared::LogCategory::Grpc,
+                                                "⚠️  Failed to handle gRPC message: {e}"
+                                            );
+                                        }
+                                    }
+                                }
+                                Err(e) => {
                                     climonitor_shared::log_warn!(
                                         climonitor_shared::LogCategory::Grpc,
-                                        "⚠️  Failed to handle gRPC message: {e}"
+                                        "⚠️  Failed to convert gRPC message: {e}"
                                     );
                                 }
                             }
-                            Err(e) => {
-                                climonitor_shared::log_warn!(
-                                    climonitor_shared::LogCategory::Grpc,
-                                    "⚠️  Failed to convert gRPC message: {e}"
-                                );
-                            }
+                        }
+                        Err(e) => {
+                            climonitor_shared::log_warn!(
+                                climonitor_shared::LogCategory::Grpc,
+                                "⚠️  gRPC stream error: {e}"
+                            );
+                            break;
                         }
                     }
-                    Err(e) => {
-                        climonitor_shared::log_warn!(
-                            climonitor_shared::LogCategory::Grpc,
-                            "⚠️  gRPC stream error: {e}"
-                        );
-                        break;
+                }
+            }
+            .instrument(span),
+        );
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type WatchSessionsStream = ReceiverStream<Result<GrpcSessionSnapshot, Status>>;
+
+    async fn watch_sessions(
+        &self,
+        request: Request<WatchRequest>,
+    ) -> Result<Response<Self::WatchSessionsStream>, Status> {
+        self.check_allowed(request.remote_addr()).await?;
+
+        // 購読してから最初のスナップショットを取得し、その間の更新を取りこぼさない
+        let Some(mut updates) = self.handler.subscribe_updates() else {
+            return Err(Status::unimplemented("Session watching is not supported"));
+        };
+        let handler = std::sync::Arc::clone(&self.handler);
+        let subscriber = request.into_inner().subscriber;
+        climonitor_shared::log_info!(
+            climonitor_shared::LogCategory::Grpc,
+            "📡 Downstream monitor subscribed: {subscriber}"
+        );
+
+        let (tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            let mut last_sent = None;
+            loop {
+                let Some(snapshot) = handler.session_snapshot().await else {
+                    break;
+                };
+                // 変化がなければ送信しない
+                if last_sent.as_ref() != Some(&snapshot) {
+                    let message = grpc_conversion::grpc_conversion::to_grpc_session_snapshot(
+                        snapshot.clone(),
+                    );
+                    if tx.send(Ok(message)).await.is_err() {
+                        break; // 下流monitorが切断
                     }
+                    last_sent = Some(snapshot);
+                }
+
+                match updates.recv().await {
+                    Ok(()) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
                 }
             }
+            climonitor_shared::log_info!(
+                climonitor_shared::LogCategory::Grpc,
+                "📡 Downstream monitor unsubscribed: {subscriber}"
+            );
         });
 
-        // 空のレスポンスストリームを返す（現在Monitor→Launcherメッセージは未実装）
-        let (tx, rx) = mpsc::channel(1);
-        drop(tx); // すぐに閉じる
-        let output_stream = ReceiverStream::new(rx);
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
 
-        Ok(Response::new(output_stream))
+/// 応答ストリームでlauncherへ送信（launcherが既に切断していても受信処理は続ける）
+async fn send_to_launcher(
+    tx: &mpsc::Sender<Result<MonitorMessage, Status>>,
+    message: climonitor_shared::MonitorToLauncher,
+) {
+    let message = grpc_conversion::grpc_conversion::to_grpc_monitor_message(message);
+    if tx.send(Ok(message)).await.is_err() {
+        climonitor_shared::log_debug!(
+            climonitor_shared::LogCategory::Grpc,
+            "Response stream closed by launcher"
+        );
     }
 }
@@ -1,10 +1,15 @@
+use crate::config_reload::SharedRuntimeSettings;
 use anyhow::Result;
+use climonitor_shared::logging::tracing;
 use climonitor_shared::{
     transport::{MessageHandler, MessageReceiver},
-    ConnectionConfig,
+    ConnectionConfig, LauncherToMonitor, LogCategory, MonitorToLauncher,
 };
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast;
 
 pub mod grpc;
+pub mod tcp;
 #[cfg(unix)]
 pub mod unix;
 
@@ -12,6 +17,7 @@ pub mod unix;
 pub async fn create_message_receiver(
     config: &ConnectionConfig,
     handler: Box<dyn MessageHandler>,
+    runtime_settings: SharedRuntimeSettings,
 ) -> Result<Box<dyn MessageReceiver>> {
     match config {
         #[cfg(unix)]
@@ -20,8 +26,165 @@ pub async fn create_message_receiver(
             Ok(Box::new(receiver))
         }
         ConnectionConfig::Grpc { .. } => {
-            let receiver = grpc::GrpcMessageReceiver::new(config, handler).await?;
+            let receiver =
+                grpc::GrpcMessageReceiver::new(config, handler, runtime_settings).await?;
             Ok(Box::new(receiver))
         }
+        ConnectionConfig::Tcp { .. } => {
+            let receiver = tcp::TcpMessageReceiver::new(config, handler, runtime_settings).await?;
+            Ok(Box::new(receiver))
+        }
+    }
+}
+
+/// 接続中のlauncher宛てにmonitorから能動的に送るメッセージの選別
+///
+/// 接続要求のcapabilitiesと割り当てたlauncher IDを記録し、プロンプト予約に
+/// 対応したlauncher宛てのメッセージだけを転送する。
+#[derive(Debug, Default)]
+pub(crate) struct PushRoute {
+    launcher_id: Option<String>,
+    prompt_queue: bool,
+}
+
+impl PushRoute {
+    /// launcherからのメッセージを記録
+    pub(crate) fn observe(&mut self, message: &LauncherToMonitor) {
+        if let LauncherToMonitor::Connect { capabilities, .. } = message {
+            self.prompt_queue = capabilities
+                .iter()
+                .any(|capability| capability == climonitor_shared::capability::PROMPT_QUEUE);
+        }
+        if self.launcher_id.is_none() {
+            self.launcher_id = Some(message.launcher_id().to_string());
+        }
+    }
+
+    /// 接続応答で割り当てたlauncher IDを記録（IDが衝突した場合は要求と異なる）
+    pub(crate) fn observe_reply(&mut self, reply: &MonitorToLauncher) {
+        if let MonitorToLauncher::ConnectResponse { launcher_id, .. } = reply {
+            self.launcher_id = Some(launcher_id.clone());
+        }
+    }
+
+    /// この接続に転送するか
+    pub(crate) fn accepts(&self, message: &MonitorToLauncher) -> bool {
+        match message {
+            MonitorToLauncher::QueuePrompt { launcher_id, .. }
+            | MonitorToLauncher::ClearQueuedPrompt { launcher_id } => {
+                self.prompt_queue && self.launcher_id.as_deref() == Some(launcher_id.as_str())
+            }
+            // 接続応答は要求した接続にだけ返す
+            MonitorToLauncher::ConnectResponse { .. }
+            | MonitorToLauncher::ConnectRejected { .. } => false,
+        }
+    }
+}
+
+/// 次にlauncherへ送るメッセージを待機（購読できない場合・終了後は待ち続ける）
+pub(crate) async fn next_launcher_message(
+    receiver: &mut Option<broadcast::Receiver<MonitorToLauncher>>,
+) -> MonitorToLauncher {
+    loop {
+        let Some(active) = receiver.as_mut() else {
+            return std::future::pending().await;
+        };
+        match active.recv().await {
+            Ok(message) => return message,
+            Err(broadcast::error::RecvError::Lagged(_)) => {}
+            Err(broadcast::error::RecvError::Closed) => *receiver = None,
+        }
+    }
+}
+
+/// 改行区切りJSONのメッセージを読み取ってハンドラーに渡す（Unix socket/TCP共通）
+///
+/// ハンドラーの応答と、接続中のlauncher宛てのメッセージは同じ接続に改行区切りJSONで返す。
+/// ログはtransport共通カテゴリで出力する（接続種別はconnection spanで区別）
+pub(crate) async fn handle_json_lines<S>(handler: &dyn MessageHandler, stream: S) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    let mut pushes = handler.subscribe_launcher_messages();
+    let mut route = PushRoute::default();
+
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => line,
+            pushed = next_launcher_message(&mut pushes) => {
+                if route.accepts(&pushed) {
+                    write_json_line(&mut writer, &pushed).await?;
+                }
+                continue;
+            }
+        };
+        match line {
+            Ok(None) => break, // 接続終了
+            Ok(Some(line)) => {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+
+                // JSONメッセージをデシリアライズ
+                match serde_json::from_str::<LauncherToMonitor>(trimmed) {
+                    Ok(message) => {
+                        climonitor_shared::logging::record_message_ids(
+                            &tracing::Span::current(),
+                            &message,
+                        );
+                        route.observe(&message);
+                        match handler.handle_message(message).await {
+                            Ok(Some(reply)) => {
+                                route.observe_reply(&reply);
+                                write_json_line(&mut writer, &reply).await?;
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                climonitor_shared::log_warn!(
+                                    LogCategory::Transport,
+                                    "⚠️  Failed to handle message: {e}"
+                                );
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        climonitor_shared::log_warn!(
+                            LogCategory::Transport,
+                            "⚠️  Failed to parse message '{}': {e} (launcher may use a newer protocol than {})",
+                            trimmed,
+                            climonitor_shared::PROTOCOL_VERSION
+                        );
+                    }
+                }
+            }
+            Err(e) => {
+                climonitor_shared::log_warn!(
+                    LogCategory::Transport,
+                    "⚠️  Failed to read from socket: {e}"
+                );
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// launcherへ改行区切りJSONで送信（launcherが既に切断していても受信処理は続ける）
+async fn write_json_line<W>(writer: &mut W, message: &MonitorToLauncher) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    if let Err(e) = writer.write_all(line.as_bytes()).await {
+        climonitor_shared::log_debug!(
+            LogCategory::Transport,
+            "Failed to send message to launcher: {e}"
+        );
     }
+    Ok(())
 }
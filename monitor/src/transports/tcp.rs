@@ -0,0 +1,338 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+use crate::config_reload::SharedRuntimeSettings;
+use climonitor_shared::logging::tracing::Instrument;
+use climonitor_shared::{
+    transport::{MessageHandler, MessageReceiver},
+    ConnectionConfig, LogCategory,
+};
+
+/// TCP サーバー実装（改行区切りJSON）
+pub struct TcpMessageReceiver {
+    bind_addr: String,
+    handler: Arc<dyn MessageHandler>,
+    /// IP許可リストは設定の再読み込みで更新される
+    runtime_settings: SharedRuntimeSettings,
+}
+
+impl TcpMessageReceiver {
+    pub async fn new(
+        config: &ConnectionConfig,
+        handler: Box<dyn MessageHandler>,
+        runtime_settings: SharedRuntimeSettings,
+    ) -> Result<Self> {
+        match config {
+            ConnectionConfig::Tcp { bind_addr, .. } => Ok(Self {
+                bind_addr: bind_addr.clone(),
+                handler: Arc::from(handler),
+                runtime_settings,
+            }),
+            _ => anyhow::bail!("TCP transport requires TCP configuration"),
+        }
+    }
+
+    /// 接続を受け付けて処理（許可されていないIPからの接続は即座に閉じる）
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
+        loop {
+            let (stream, peer_addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    climonitor_shared::log_warn!(
+                        LogCategory::Tcp,
+                        "⚠️  Failed to accept TCP connection: {e}"
+                    );
+                    continue;
+                }
+            };
+
+            let allowed_ips = self.runtime_settings.read().await.allowed_ips.clone();
+            if !climonitor_shared::ip_utils::is_ip_allowed_by_list(&peer_addr.ip(), &allowed_ips) {
+                climonitor_shared::log_warn!(
+                    LogCategory::Tcp,
+                    "🚫 Rejected TCP connection from {}: IP address is not allowed",
+                    peer_addr.ip()
+                );
+                continue;
+            }
+
+            let handler = Arc::clone(&self.handler);
+            // 各接続を並行処理
+            let span = climonitor_shared::logging::connection_span("tcp", &peer_addr.to_string());
+            tokio::spawn(
+                async move {
+                    if let Err(e) = super::handle_json_lines(&*handler, stream).await {
+                        climonitor_shared::log_warn!(
+                            LogCategory::Tcp,
+                            "⚠️  Connection handling failed: {e}"
+                        );
+                    }
+                }
+                .instrument(span),
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl MessageReceiver for TcpMessageReceiver {
+    async fn start_server(&mut self) -> Result<()> {
+        let listener = TcpListener::bind(&self.bind_addr)
+            .await
+            .with_context(|| format!("Failed to bind TCP address: {}", self.bind_addr))?;
+        println!("🚀 TCP server listening on: {}", self.bind_addr);
+
+        self.serve(listener).await
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_reload::RuntimeSettings;
+    use climonitor_shared::{
+        supported_capabilities, CliToolType, LauncherToMonitor, MonitorToLauncher, PROTOCOL_VERSION,
+    };
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpStream;
+    use tokio::sync::{broadcast, RwLock};
+
+    #[derive(Clone)]
+    struct RecordingHandler {
+        messages: Arc<Mutex<Vec<LauncherToMonitor>>>,
+        launcher_messages: broadcast::Sender<MonitorToLauncher>,
+    }
+
+    impl Default for RecordingHandler {
+        fn default() -> Self {
+            Self {
+                messages: Arc::default(),
+                launcher_messages: broadcast::channel(16).0,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MessageHandler for RecordingHandler {
+        async fn handle_message(
+            &self,
+            message: LauncherToMonitor,
+        ) -> Result<Option<MonitorToLauncher>> {
+            // Connectには割り当てたIDを応答する
+            let reply = match &message {
+                LauncherToMonitor::Connect { launcher_id, .. } => {
+                    Some(MonitorToLauncher::ConnectResponse {
+                        requested_id: launcher_id.clone(),
+                        launcher_id: "assigned".to_string(),
+                        protocol_version: PROTOCOL_VERSION,
+                        capabilities: Vec::new(),
+                    })
+                }
+                _ => None,
+            };
+            self.messages.lock().unwrap().push(message);
+            Ok(reply)
+        }
+
+        fn subscribe_launcher_messages(&self) -> Option<broadcast::Receiver<MonitorToLauncher>> {
+            Some(self.launcher_messages.subscribe())
+        }
+    }
+
+    async fn start_receiver(
+        allowed_ips: &[&str],
+    ) -> (
+        RecordingHandler,
+        SharedRuntimeSettings,
+        std::net::SocketAddr,
+    ) {
+        let handler = RecordingHandler::default();
+        let runtime_settings = Arc::new(RwLock::new(RuntimeSettings {
+            allowed_ips: allowed_ips.iter().map(|ip| ip.to_string()).collect(),
+            ..RuntimeSettings::default()
+        }));
+        let config = ConnectionConfig::Tcp {
+            bind_addr: "127.0.0.1:0".to_string(),
+            allowed_ips: Vec::new(),
+        };
+        let receiver = TcpMessageReceiver::new(
+            &config,
+            Box::new(handler.clone()),
+            Arc::clone(&runtime_settings),
+        )
+        .await
+        .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = receiver.serve(listener).await;
+        });
+        (handler, runtime_settings, addr)
+    }
+
+    fn disconnect_line() -> String {
+        let message = LauncherToMonitor::Disconnect {
+            launcher_id: "launcher".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        format!("{}\n", serde_json::to_string(&message).unwrap())
+    }
+
+    fn connect_line(capabilities: Vec<String>) -> String {
+        let message = LauncherToMonitor::Connect {
+            launcher_id: "launcher".to_string(),
+            project: None,
+            tool_type: CliToolType::Claude,
+            tool_args: Vec::new(),
+            working_dir: "/tmp".into(),
+            timestamp: chrono::Utc::now(),
+            protocol_version: PROTOCOL_VERSION,
+            capabilities,
+            metadata: Default::default(),
+        };
+        format!("{}\n", serde_json::to_string(&message).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_receives_json_lines_from_allowed_ip() {
+        let (handler, _, addr) = start_receiver(&["127.0.0.1"]).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("{}\nnot json\n", disconnect_line().trim()).as_bytes())
+            .await
+            .unwrap();
+        stream.shutdown().await.unwrap();
+
+        for _ in 0..50 {
+            if !handler.messages.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let messages = handler.messages.lock().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], LauncherToMonitor::Disconnect { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_connection_from_disallowed_ip() {
+        let (handler, runtime_settings, addr) = start_receiver(&["10.0.0.0/8"]).await;
+
+        // 許可されていない接続はメッセージを読まずに閉じられる
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let _ = stream.write_all(disconnect_line().as_bytes()).await;
+        let mut buf = [0u8; 1];
+        let read = tokio::time::timeout(Duration::from_secs(1), stream.read(&mut buf)).await;
+        assert!(matches!(read, Ok(Ok(0)) | Ok(Err(_))));
+        assert!(handler.messages.lock().unwrap().is_empty());
+
+        // 再読み込みで許可リストが更新されると以降の接続は受け付ける
+        runtime_settings.write().await.allowed_ips = vec!["127.0.0.1".to_string()];
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(disconnect_line().as_bytes())
+            .await
+            .unwrap();
+        stream.shutdown().await.unwrap();
+        for _ in 0..50 {
+            if !handler.messages.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(handler.messages.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_replies_on_same_connection() {
+        let (_, _, addr) = start_receiver(&["127.0.0.1"]).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(connect_line(supported_capabilities()).as_bytes())
+            .await
+            .unwrap();
+
+        let mut lines = BufReader::new(stream).lines();
+        let line = tokio::time::timeout(Duration::from_secs(1), lines.next_line())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<MonitorToLauncher>(&line).unwrap(),
+            MonitorToLauncher::ConnectResponse {
+                requested_id: "launcher".to_string(),
+                launcher_id: "assigned".to_string(),
+                protocol_version: PROTOCOL_VERSION,
+                capabilities: Vec::new(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forwards_queued_prompt_to_connected_launcher() {
+        let (handler, _, addr) = start_receiver(&["127.0.0.1"]).await;
+        let queue_prompt = |launcher_id: &str| MonitorToLauncher::QueuePrompt {
+            launcher_id: launcher_id.to_string(),
+            text: "run the tests".to_string(),
+        };
+
+        // プロンプト予約に対応したlauncherと、capabilitiesを送らない古いlauncher
+        let mut current = TcpStream::connect(addr).await.unwrap();
+        current
+            .write_all(connect_line(supported_capabilities()).as_bytes())
+            .await
+            .unwrap();
+        let mut current = BufReader::new(current).lines();
+        let mut legacy = TcpStream::connect(addr).await.unwrap();
+        legacy
+            .write_all(connect_line(Vec::new()).as_bytes())
+            .await
+            .unwrap();
+        let mut legacy = BufReader::new(legacy).lines();
+        for lines in [&mut current, &mut legacy] {
+            let reply = tokio::time::timeout(Duration::from_secs(1), lines.next_line())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert!(matches!(
+                serde_json::from_str::<MonitorToLauncher>(&reply).unwrap(),
+                MonitorToLauncher::ConnectResponse { .. }
+            ));
+        }
+
+        // 割り当てたlauncher ID宛てのメッセージだけを、対応したlauncherに転送する
+        for launcher_id in ["launcher", "other", "assigned"] {
+            handler
+                .launcher_messages
+                .send(queue_prompt(launcher_id))
+                .unwrap();
+        }
+        let line = tokio::time::timeout(Duration::from_secs(1), current.next_line())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<MonitorToLauncher>(&line).unwrap(),
+            queue_prompt("assigned")
+        );
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), legacy.next_line())
+                .await
+                .is_err()
+        );
+    }
+}
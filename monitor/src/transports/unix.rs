@@ -1,12 +1,12 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use std::path::PathBuf;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::net::{UnixListener, UnixStream};
+use tokio::net::UnixListener;
 
+use climonitor_shared::logging::tracing::Instrument;
 use climonitor_shared::{
     transport::{MessageHandler, MessageReceiver},
-    ConnectionConfig, LauncherToMonitor,
+    ConnectionConfig,
 };
 
 /// Unix Socket サーバー実装
@@ -25,55 +25,6 @@ impl UnixMessageReceiver {
             _ => anyhow::bail!("Unix transport requires Unix socket configuration"),
         }
     }
-
-    async fn handle_connection_static(
-        handler: &dyn MessageHandler,
-        stream: UnixStream,
-    ) -> Result<()> {
-        let mut reader = BufReader::new(stream);
-        let mut line = String::new();
-
-        loop {
-            line.clear();
-            match reader.read_line(&mut line).await {
-                Ok(0) => break, // 接続終了
-                Ok(_) => {
-                    let trimmed = line.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-
-                    // JSONメッセージをデシリアライズ
-                    match serde_json::from_str::<LauncherToMonitor>(trimmed) {
-                        Ok(message) => {
-                            if let Err(e) = handler.handle_message(message).await {
-                                climonitor_shared::log_warn!(
-                                    climonitor_shared::LogCategory::UnixSocket,
-                                    "⚠️  Failed to handle message: {e}"
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            climonitor_shared::log_warn!(
-                                climonitor_shared::LogCategory::UnixSocket,
-                                "⚠️  Failed to parse message '{}': {e}",
-                                trimmed
-                            );
-                        }
-                    }
-                }
-                Err(e) => {
-                    climonitor_shared::log_warn!(
-                        climonitor_shared::LogCategory::UnixSocket,
-                        "⚠️  Failed to read from Unix socket: {e}"
-                    );
-                    break;
-                }
-            }
-        }
-
-        Ok(())
-    }
 }
 
 #[async_trait]
@@ -95,14 +46,21 @@ impl MessageReceiver for UnixMessageReceiver {
                 Ok((stream, _addr)) => {
                     let handler = std::sync::Arc::clone(&self.handler);
                     // 各接続を並行処理
-                    tokio::spawn(async move {
-                        if let Err(e) = Self::handle_connection_static(&*handler, stream).await {
-                            climonitor_shared::log_warn!(
-                                climonitor_shared::LogCategory::UnixSocket,
-                                "⚠️  Connection handling failed: {e}"
-                            );
+                    let span = climonitor_shared::logging::connection_span(
+                        "unix",
+                        &self.socket_path.display().to_string(),
+                    );
+                    tokio::spawn(
+                        async move {
+                            if let Err(e) = super::handle_json_lines(&*handler, stream).await {
+                                climonitor_shared::log_warn!(
+                                    climonitor_shared::LogCategory::UnixSocket,
+                                    "⚠️  Connection handling failed: {e}"
+                                );
+                            }
                         }
-                    });
+                        .instrument(span),
+                    );
                 }
                 Err(e) => {
                     climonitor_shared::log_warn!(
@@ -0,0 +1,531 @@
+// web_server.rs - ブラウザ用ダッシュボード（HTTP + WebSocket）
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use climonitor_shared::{LauncherInfo, LogCategory, MonitorToLauncher, SessionInfo, WebSettings};
+use futures_util::{SinkExt, StreamExt};
+use hyper::header::{
+    HeaderValue, CONNECTION, CONTENT_TYPE, HOST, ORIGIN, SEC_WEBSOCKET_ACCEPT, UPGRADE,
+};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, RwLock};
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use crate::config_reload::SharedRuntimeSettings;
+use crate::session_manager::SessionManager;
+
+/// 埋め込みダッシュボード
+const DASHBOARD_HTML: &str = include_str!("web/dashboard.html");
+
+/// WebSocketで送信するメッセージ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DashboardMessage {
+    /// 接続直後の全体状態
+    Snapshot {
+        launchers: Vec<LauncherInfo>,
+        sessions: Vec<SessionInfo>,
+        timestamp: DateTime<Utc>,
+    },
+    /// 前回送信からの差分（追加・変更されたものと削除されたID）
+    Delta {
+        launchers: Vec<LauncherInfo>,
+        sessions: Vec<SessionInfo>,
+        removed_launchers: Vec<String>,
+        removed_sessions: Vec<String>,
+        timestamp: DateTime<Utc>,
+    },
+    /// ダッシュボードからの操作を受け付けなかった理由
+    CommandRejected { reason: String },
+}
+
+/// ダッシュボードから受け付ける操作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DashboardCommand {
+    /// 次のBusy→Idle遷移時に送信するプロンプトを予約
+    QueuePrompt { launcher_id: String, text: String },
+    /// 予約済みプロンプトを取り消し
+    ClearQueuedPrompt { launcher_id: String },
+}
+
+/// ダッシュボードに表示する状態（LiveUIと同じくアクティブなlauncherとそのセッション）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DashboardState {
+    launchers: BTreeMap<String, LauncherInfo>,
+    sessions: BTreeMap<String, SessionInfo>,
+}
+
+impl DashboardState {
+    pub fn capture(session_manager: &SessionManager) -> Self {
+        let mut state = Self::default();
+        for (_, launchers) in session_manager.get_launchers_by_project() {
+            for (launcher, session) in launchers {
+                state
+                    .launchers
+                    .insert(launcher.id.clone(), launcher.clone());
+                if let Some(session) = session {
+                    state.sessions.insert(session.id.clone(), session.clone());
+                }
+            }
+        }
+        state
+    }
+
+    pub fn snapshot(&self) -> DashboardMessage {
+        DashboardMessage::Snapshot {
+            launchers: self.launchers.values().cloned().collect(),
+            sessions: self.sessions.values().cloned().collect(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// 新しい状態との差分（変化がなければNone）
+    pub fn delta(&self, next: &DashboardState) -> Option<DashboardMessage> {
+        if self == next {
+            return None;
+        }
+
+        Some(DashboardMessage::Delta {
+            launchers: changed_values(&self.launchers, &next.launchers),
+            sessions: changed_values(&self.sessions, &next.sessions),
+            removed_launchers: removed_keys(&self.launchers, &next.launchers),
+            removed_sessions: removed_keys(&self.sessions, &next.sessions),
+            timestamp: Utc::now(),
+        })
+    }
+}
+
+fn changed_values<T: Clone + PartialEq>(
+    previous: &BTreeMap<String, T>,
+    next: &BTreeMap<String, T>,
+) -> Vec<T> {
+    next.iter()
+        .filter(|(id, value)| previous.get(*id) != Some(value))
+        .map(|(_, value)| value.clone())
+        .collect()
+}
+
+fn removed_keys<T>(previous: &BTreeMap<String, T>, next: &BTreeMap<String, T>) -> Vec<String> {
+    previous
+        .keys()
+        .filter(|id| !next.contains_key(*id))
+        .cloned()
+        .collect()
+}
+
+/// ダッシュボードサーバー（`/` でHTML、`/ws` でWebSocketを提供）
+#[derive(Clone)]
+pub struct WebDashboardServer {
+    bind_addr: String,
+    session_manager: Arc<RwLock<SessionManager>>,
+    ui_update_sender: broadcast::Sender<()>,
+    /// IP・Originの許可リストは設定の再読み込みで更新されるため接続ごとに参照
+    runtime_settings: SharedRuntimeSettings,
+    /// プロンプト予約の送信先（未設定の場合は予約を受け付けない）
+    launcher_messages: Option<broadcast::Sender<MonitorToLauncher>>,
+}
+
+impl WebDashboardServer {
+    pub fn new(
+        settings: &WebSettings,
+        session_manager: Arc<RwLock<SessionManager>>,
+        ui_update_sender: broadcast::Sender<()>,
+        runtime_settings: SharedRuntimeSettings,
+    ) -> Self {
+        Self {
+            bind_addr: settings.bind_addr.clone(),
+            session_manager,
+            ui_update_sender,
+            runtime_settings,
+            launcher_messages: None,
+        }
+    }
+
+    /// ダッシュボードからのプロンプト予約をlauncherへ送る
+    pub fn with_launcher_messages(
+        mut self,
+        launcher_messages: broadcast::Sender<MonitorToLauncher>,
+    ) -> Self {
+        self.launcher_messages = Some(launcher_messages);
+        self
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let listener = TcpListener::bind(&self.bind_addr)
+            .await
+            .with_context(|| format!("Failed to bind web dashboard address: {}", self.bind_addr))?;
+        println!("🌐 Web dashboard listening on: http://{}", self.bind_addr);
+
+        self.serve(listener).await
+    }
+
+    /// 接続を受け付けて処理（許可されていないIPからの接続は即座に閉じる）
+    pub async fn serve(self, listener: TcpListener) -> Result<()> {
+        loop {
+            let (stream, peer_addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    climonitor_shared::log_warn!(
+                        LogCategory::Web,
+                        "⚠️  Failed to accept web connection: {e}"
+                    );
+                    continue;
+                }
+            };
+
+            let allowed_ips = self.runtime_settings.read().await.web_allowed_ips.clone();
+            if !climonitor_shared::ip_utils::is_ip_allowed_by_list(&peer_addr.ip(), &allowed_ips) {
+                climonitor_shared::log_warn!(
+                    LogCategory::Web,
+                    "🚫 Rejected web connection from {}: IP address is not allowed",
+                    peer_addr.ip()
+                );
+                continue;
+            }
+
+            let server = self.clone();
+            tokio::spawn(async move {
+                let service = hyper::service::service_fn(move |request| {
+                    let server = server.clone();
+                    async move { Ok::<_, Infallible>(server.handle_request(request).await) }
+                });
+                if let Err(e) = hyper::server::conn::Http::new()
+                    .http1_only(true)
+                    .serve_connection(stream, service)
+                    .with_upgrades()
+                    .await
+                {
+                    climonitor_shared::log_debug!(
+                        LogCategory::Web,
+                        "Web connection from {peer_addr} closed: {e}"
+                    );
+                }
+            });
+        }
+    }
+
+    async fn handle_request(&self, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::GET {
+            return plain_response(StatusCode::METHOD_NOT_ALLOWED, "Method Not Allowed");
+        }
+
+        match request.uri().path() {
+            "/" | "/index.html" => Response::builder()
+                .header(CONTENT_TYPE, "text/html; charset=utf-8")
+                .body(Body::from(DASHBOARD_HTML))
+                .unwrap(),
+            "/ws" => self.upgrade_websocket(request).await,
+            _ => plain_response(StatusCode::NOT_FOUND, "Not Found"),
+        }
+    }
+
+    /// WebSocketへのアップグレード（ハンドシェイク後の送信は別タスク）
+    async fn upgrade_websocket(&self, mut request: Request<Body>) -> Response<Body> {
+        let is_upgrade = request
+            .headers()
+            .get(UPGRADE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+        let Some(key) = request.headers().get("sec-websocket-key") else {
+            return plain_response(StatusCode::BAD_REQUEST, "Expected WebSocket upgrade");
+        };
+        if !is_upgrade {
+            return plain_response(StatusCode::BAD_REQUEST, "Expected WebSocket upgrade");
+        }
+        // 他のサイトのページからのWebSocket接続を拒否（ブラウザはIP許可リストを通過するため）
+        let header = |name| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+        };
+        let (origin, host) = (header(ORIGIN), header(HOST));
+        let allowed_origins = self
+            .runtime_settings
+            .read()
+            .await
+            .web_allowed_origins
+            .clone();
+        if !is_origin_allowed(origin, host, &allowed_origins) {
+            climonitor_shared::log_warn!(
+                LogCategory::Web,
+                "🚫 Rejected WebSocket connection: origin {} is not allowed",
+                origin.unwrap_or_default()
+            );
+            return plain_response(StatusCode::FORBIDDEN, "Origin not allowed");
+        }
+        let accept_key = derive_accept_key(key.as_bytes());
+
+        let server = self.clone();
+        tokio::spawn(async move {
+            match hyper::upgrade::on(&mut request).await {
+                Ok(upgraded) => {
+                    let socket =
+                        WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+                    if let Err(e) = server.stream_updates(socket).await {
+                        climonitor_shared::log_debug!(
+                            LogCategory::Web,
+                            "WebSocket session ended: {e}"
+                        );
+                    }
+                }
+                Err(e) => {
+                    climonitor_shared::log_warn!(
+                        LogCategory::Web,
+                        "⚠️  WebSocket upgrade failed: {e}"
+                    );
+                }
+            }
+        });
+
+        Response::builder()
+            .status(StatusCode::SWITCHING_PROTOCOLS)
+            .header(CONNECTION, HeaderValue::from_static("Upgrade"))
+            .header(UPGRADE, HeaderValue::from_static("websocket"))
+            .header(SEC_WEBSOCKET_ACCEPT, accept_key)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    /// スナップショット送信後、UI更新通知ごとに差分を送信
+    async fn stream_updates<S>(&self, mut socket: WebSocketStream<S>) -> Result<()>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+    {
+        // 購読してから状態を取得し、その間の更新を取りこぼさない
+        let mut updates = self.ui_update_sender.subscribe();
+        let mut state = DashboardState::capture(&*self.session_manager.read().await);
+        send_json(&mut socket, &state.snapshot()).await?;
+
+        loop {
+            tokio::select! {
+                update = updates.recv() => {
+                    if let Err(broadcast::error::RecvError::Closed) = update {
+                        break;
+                    }
+                    let next = DashboardState::capture(&*self.session_manager.read().await);
+                    if let Some(delta) = state.delta(&next) {
+                        send_json(&mut socket, &delta).await?;
+                    }
+                    state = next;
+                }
+
+                incoming = socket.next() => match incoming {
+                    // Pingへの応答はtungsteniteが行う
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(Message::Text(text))) => {
+                        if let Err(reason) = self.handle_command(&text).await {
+                            send_json(&mut socket, &DashboardMessage::CommandRejected { reason })
+                                .await?;
+                        }
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                },
+            }
+        }
+
+        let _ = socket.close(None).await;
+        Ok(())
+    }
+
+    /// ダッシュボードからの操作をlauncherへ転送（受け付けない場合は理由を返す）
+    ///
+    /// 予約の結果はlauncherからのPromptQueueUpdateで通常の差分として届く。
+    async fn handle_command(&self, text: &str) -> Result<(), String> {
+        let command = serde_json::from_str::<DashboardCommand>(text)
+            .map_err(|e| format!("Invalid command: {e}"))?;
+        if !self.runtime_settings.read().await.web_allow_prompt_queue {
+            return Err(
+                "Prompt queueing from the dashboard is disabled (web.allow_prompt_queue)"
+                    .to_string(),
+            );
+        }
+        let Some(launcher_messages) = &self.launcher_messages else {
+            return Err("Prompt queueing is not available".to_string());
+        };
+
+        let (launcher_id, message) = match command {
+            DashboardCommand::QueuePrompt { launcher_id, text } => {
+                if text.trim().is_empty() {
+                    return Err("Prompt is empty".to_string());
+                }
+                let message = MonitorToLauncher::QueuePrompt {
+                    launcher_id: launcher_id.clone(),
+                    text,
+                };
+                (launcher_id, message)
+            }
+            DashboardCommand::ClearQueuedPrompt { launcher_id } => {
+                let message = MonitorToLauncher::ClearQueuedPrompt {
+                    launcher_id: launcher_id.clone(),
+                };
+                (launcher_id, message)
+            }
+        };
+        // 上流monitorから受信したlauncherにはこのmonitorから送れない
+        match self.session_manager.read().await.get_launcher(&launcher_id) {
+            Some(launcher) if launcher.origin.is_none() => {}
+            Some(launcher) => {
+                return Err(format!(
+                    "Launcher {launcher_id} is connected to monitor {}",
+                    launcher.origin.as_deref().unwrap_or_default()
+                ))
+            }
+            None => return Err(format!("Unknown launcher: {launcher_id}")),
+        }
+
+        climonitor_shared::log_info!(
+            LogCategory::Web,
+            "⏳ Forwarding {} from dashboard to launcher {launcher_id}",
+            match &message {
+                MonitorToLauncher::QueuePrompt { .. } => "queued prompt",
+                _ => "queued prompt removal",
+            }
+        );
+        launcher_messages
+            .send(message)
+            .map_err(|_| format!("Launcher {launcher_id} is not connected"))?;
+        Ok(())
+    }
+}
+
+async fn send_json<S>(socket: &mut WebSocketStream<S>, message: &DashboardMessage) -> Result<()>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let json = serde_json::to_string(message)?;
+    socket.send(Message::Text(json)).await?;
+    Ok(())
+}
+
+/// WebSocket接続のOriginを許可するか
+///
+/// Originを送らないブラウザ以外のクライアントは許可し、ブラウザからの接続は
+/// Originが `Host` と一致するか `allowed_origins` にある場合のみ許可する。
+pub fn is_origin_allowed(
+    origin: Option<&str>,
+    host: Option<&str>,
+    allowed_origins: &[String],
+) -> bool {
+    let Some(origin) = origin else {
+        return true;
+    };
+    let origin = origin.trim_end_matches('/');
+    if allowed_origins
+        .iter()
+        .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+    {
+        return true;
+    }
+    match (origin.split_once("://"), host) {
+        (Some((_, origin_host)), Some(host)) => origin_host.eq_ignore_ascii_case(host),
+        _ => false,
+    }
+}
+
+fn plain_response(status: StatusCode, body: &'static str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use climonitor_shared::{
+        supported_capabilities, CliToolType, LauncherToMonitor, SessionStatus, PROTOCOL_VERSION,
+    };
+    use std::path::PathBuf;
+
+    fn connect(manager: &mut SessionManager, launcher_id: &str) {
+        manager
+            .handle_message(LauncherToMonitor::Connect {
+                launcher_id: launcher_id.to_string(),
+                project: Some("project".to_string()),
+                tool_type: CliToolType::Claude,
+                tool_args: Vec::new(),
+                working_dir: PathBuf::from("/tmp"),
+                timestamp: Utc::now(),
+                protocol_version: PROTOCOL_VERSION,
+                capabilities: supported_capabilities(),
+                metadata: Default::default(),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_is_origin_allowed() {
+        let host = Some("127.0.0.1:8080");
+        // ブラウザ以外のクライアント
+        assert!(is_origin_allowed(None, host, &[]));
+        // ダッシュボード自身のページ
+        assert!(is_origin_allowed(Some("http://127.0.0.1:8080"), host, &[]));
+        assert!(is_origin_allowed(
+            Some("HTTP://LOCALHOST:8080/"),
+            Some("localhost:8080"),
+            &[]
+        ));
+        // 他のサイトのページ
+        assert!(!is_origin_allowed(Some("https://evil.example"), host, &[]));
+        assert!(!is_origin_allowed(Some("null"), host, &[]));
+        assert!(!is_origin_allowed(Some("http://127.0.0.1:8080"), None, &[]));
+        // 許可リストにあるOrigin
+        let allowed = vec!["https://dashboard.example/".to_string()];
+        assert!(is_origin_allowed(
+            Some("https://dashboard.example"),
+            host,
+            &allowed
+        ));
+    }
+
+    #[test]
+    fn test_delta_reports_changed_and_removed_entries() {
+        let mut manager = SessionManager::new();
+        connect(&mut manager, "launcher-1");
+        connect(&mut manager, "launcher-2");
+        let previous = DashboardState::capture(&manager);
+        assert_eq!(previous.delta(&DashboardState::capture(&manager)), None);
+
+        manager
+            .handle_message(LauncherToMonitor::StateUpdate {
+                launcher_id: "launcher-1".to_string(),
+                session_id: "session-1".to_string(),
+                status: SessionStatus::Busy,
+                ui_above_text: None,
+                timestamp: Utc::now(),
+                evidence: None,
+            })
+            .unwrap();
+        manager.remove_launcher("launcher-2");
+        let next = DashboardState::capture(&manager);
+
+        let Some(DashboardMessage::Delta {
+            launchers,
+            sessions,
+            removed_launchers,
+            removed_sessions,
+            ..
+        }) = previous.delta(&next)
+        else {
+            panic!("expected delta");
+        };
+        // 状態更新はセッションのみ変化し、launcherは差分に含まれない
+        assert!(launchers.is_empty());
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].status, SessionStatus::Busy);
+        assert_eq!(removed_launchers, vec!["launcher-2"]);
+        assert!(removed_sessions.is_empty());
+    }
+}
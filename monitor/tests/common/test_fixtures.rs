@@ -8,7 +8,8 @@
 
 use chrono::Utc;
 use climonitor_shared::{
-    CliToolType, LauncherInfo, LauncherStatus, LauncherToMonitor, SessionInfo, SessionStatus,
+    supported_capabilities, CliToolType, LauncherInfo, LauncherStatus, LauncherToMonitor,
+    ProcessMetrics, SessionInfo, SessionStatus, PROTOCOL_VERSION,
 };
 use std::path::PathBuf;
 
@@ -22,9 +23,12 @@ pub fn create_test_launcher_message(
             launcher_id,
             project: Some("test-project".to_string()),
             tool_type: CliToolType::Claude,
-            claude_args: vec!["--help".to_string()],
+            tool_args: vec!["--help".to_string()],
             working_dir: PathBuf::from("/tmp/test"),
             timestamp: Utc::now(),
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: supported_capabilities(),
+            metadata: Default::default(),
         },
         TestMessageType::StatusUpdate => LauncherToMonitor::StateUpdate {
             launcher_id,
@@ -32,10 +36,16 @@ pub fn create_test_launcher_message(
             status: SessionStatus::Busy,
             ui_above_text: Some("test UI text".to_string()),
             timestamp: Utc::now(),
+            evidence: None,
         },
-        // ProcessMetrics は削除済み
-        TestMessageType::ProcessMetrics => LauncherToMonitor::Disconnect {
+        TestMessageType::ProcessMetrics => LauncherToMonitor::ProcessMetrics {
             launcher_id,
+            session_id: "test_session".to_string(),
+            metrics: ProcessMetrics {
+                cpu_percent: 25.0,
+                rss_bytes: 128 * 1024 * 1024,
+                child_count: 2,
+            },
             timestamp: Utc::now(),
         },
         TestMessageType::Disconnect => LauncherToMonitor::Disconnect {
@@ -49,7 +59,7 @@ pub fn create_test_launcher_message(
 pub enum TestMessageType {
     Register,
     StatusUpdate,
-    ProcessMetrics, // 削除済み機能だが、後方互換性のため維持
+    ProcessMetrics,
     Disconnect,
 }
 
@@ -64,11 +74,13 @@ pub fn create_test_launcher_info(launcher_id: String, tool_type: CliToolType) ->
         id: launcher_id,
         project: Some("test-project".to_string()),
         tool_type,
-        claude_args: vec!["--help".to_string()],
+        tool_args: vec!["--help".to_string()],
         working_dir: PathBuf::from("/tmp/test"),
         connected_at: Utc::now(),
         last_activity: Utc::now(),
         status: LauncherStatus::Connected,
+        origin: None,
+        metadata: Default::default(),
     }
 }
 
@@ -91,5 +103,8 @@ pub fn create_test_session_info(launcher_id: String, status: SessionStatus) -> S
         usage_reset_time: None,
         is_waiting_for_execution: false,
         ui_above_text: Some("test UI text".to_string()),
+        exit: None,
+        metrics: Vec::new(),
+        activity: None,
     }
 }
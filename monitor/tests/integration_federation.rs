@@ -0,0 +1,158 @@
+// 上流monitorのセッションを購読するfederationの統合テスト
+
+use chrono::Utc;
+use climonitor_launcher::transports::create_message_sender;
+use climonitor_monitor::federation::FederationClient;
+use climonitor_monitor::session_manager::{SessionManager, UpstreamState};
+use climonitor_monitor::transport_server::TransportMonitorServer;
+use climonitor_shared::{
+    supported_capabilities, CliToolType, ConnectionConfig, LauncherMetadata, LauncherToMonitor,
+    SessionStatus, UpstreamSettings, PROTOCOL_VERSION,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+
+/// 空いているポートを確保
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// 条件を満たすまで待機（最大10秒）
+async fn wait_for<F>(session_manager: &RwLock<SessionManager>, description: &str, condition: F)
+where
+    F: Fn(&SessionManager) -> bool,
+{
+    for _ in 0..200 {
+        if condition(&*session_manager.read().await) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("timed out waiting for {description}");
+}
+
+fn upstream_state(session_manager: &SessionManager, name: &str) -> Option<UpstreamState> {
+    session_manager
+        .get_upstreams()
+        .find(|upstream| upstream.name == name)
+        .map(|upstream| upstream.state.clone())
+}
+
+#[tokio::test]
+async fn test_downstream_merges_upstream_sessions_and_reconnects() {
+    let bind_addr = format!("127.0.0.1:{}", free_port());
+
+    // 上流monitorが起動する前から購読を開始し、再接続を待つ
+    let downstream = Arc::new(RwLock::new(SessionManager::new()));
+    let (ui_update_sender, _) = broadcast::channel(100);
+    let client = FederationClient::new(
+        UpstreamSettings {
+            name: "devbox".to_string(),
+            addr: bind_addr.clone(),
+        },
+        Arc::clone(&downstream),
+        ui_update_sender,
+    );
+    tokio::spawn(client.run());
+    wait_for(&downstream, "upstream to be unreachable", |manager| {
+        matches!(
+            upstream_state(manager, "devbox"),
+            Some(UpstreamState::Disconnected { .. })
+        )
+    })
+    .await;
+
+    // ローカルのlauncherと同じIDを上流でも使う
+    downstream
+        .write()
+        .await
+        .handle_message(LauncherToMonitor::Connect {
+            launcher_id: "launcher-1".to_string(),
+            project: Some("local".to_string()),
+            tool_type: CliToolType::Gemini,
+            tool_args: Vec::new(),
+            working_dir: PathBuf::from("/tmp"),
+            timestamp: Utc::now(),
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: supported_capabilities(),
+            metadata: Default::default(),
+        })
+        .unwrap();
+
+    let config = ConnectionConfig::Grpc {
+        bind_addr,
+        allowed_ips: vec!["127.0.0.1".to_string()],
+    };
+    let mut upstream = TransportMonitorServer::new(config.clone(), false, None).unwrap();
+    tokio::spawn(async move {
+        let _ = upstream.run().await;
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let sender = create_message_sender(&config, "launcher-1".to_string(), false);
+    sender
+        .send_connect(
+            Some("remote".to_string()),
+            CliToolType::Claude,
+            Vec::new(),
+            std::env::temp_dir(),
+            LauncherMetadata {
+                git_branch: Some("main".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    sender
+        .send_status_update(
+            "session-1".to_string(),
+            SessionStatus::WaitingInput,
+            Utc::now(),
+            None,
+        )
+        .await
+        .unwrap();
+    sender.flush().await.unwrap();
+
+    wait_for(&downstream, "upstream session", |manager| {
+        manager
+            .get_session("devbox/session-1")
+            .is_some_and(|session| session.status == SessionStatus::WaitingInput)
+    })
+    .await;
+    {
+        let manager = downstream.read().await;
+        assert_eq!(
+            upstream_state(&manager, "devbox"),
+            Some(UpstreamState::Connected)
+        );
+        let remote = manager.get_launcher("devbox/launcher-1").unwrap();
+        assert_eq!(remote.origin.as_deref(), Some("devbox"));
+        assert_eq!(remote.tool_type, CliToolType::Claude);
+        assert_eq!(remote.metadata.git_branch.as_deref(), Some("main"));
+        assert!(manager
+            .get_launchers_by_project()
+            .contains_key("remote [main] @devbox"));
+        let local = manager.get_launcher("launcher-1").unwrap();
+        assert_eq!(local.tool_type, CliToolType::Gemini);
+        assert_eq!(
+            manager.get_session("devbox/session-1").unwrap().launcher_id,
+            "devbox/launcher-1"
+        );
+    }
+
+    // 上流でlauncherが切断されると下流からも消える
+    sender.send_disconnect().await.unwrap();
+    sender.flush().await.unwrap();
+    wait_for(&downstream, "upstream launcher removal", |manager| {
+        manager.get_launcher("devbox/launcher-1").is_none()
+    })
+    .await;
+    assert!(downstream.read().await.get_launcher("launcher-1").is_some());
+}
@@ -76,7 +76,36 @@ fn test_protocol_serialization_state_update() {
     }
 }
 
-// ProcessMetrics テストは削除済み（機能削除のため）
+#[test]
+fn test_protocol_serialization_process_metrics() {
+    // ProcessMetrics メッセージのシリアライゼーション/デシリアライゼーションテスト
+    let launcher_id = generate_test_id();
+    let original_message =
+        create_test_launcher_message(launcher_id.clone(), TestMessageType::ProcessMetrics);
+
+    let json_str = serde_json::to_string(&original_message).unwrap();
+    let deserialized_message: LauncherToMonitor = serde_json::from_str(&json_str).unwrap();
+
+    match (&original_message, &deserialized_message) {
+        (
+            LauncherToMonitor::ProcessMetrics {
+                launcher_id: orig_id,
+                metrics: orig_metrics,
+                ..
+            },
+            LauncherToMonitor::ProcessMetrics {
+                launcher_id: deser_id,
+                metrics: deser_metrics,
+                ..
+            },
+        ) => {
+            assert_eq!(orig_id, deser_id);
+            assert_eq!(orig_metrics, deser_metrics);
+            assert_eq!(deser_metrics.child_count, 2);
+        }
+        _ => panic!("メッセージタイプが一致しません"),
+    }
+}
 
 #[test]
 fn test_protocol_serialization_disconnect() {
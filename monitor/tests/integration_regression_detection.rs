@@ -3,7 +3,10 @@
 #[cfg(test)]
 mod common;
 
-use climonitor_shared::{CliToolType, LauncherToMonitor, SessionStatus};
+use climonitor_shared::{
+    supported_capabilities, CliToolType, LauncherToMonitor, SessionStatus, LEGACY_PROTOCOL_VERSION,
+    PROTOCOL_VERSION,
+};
 use common::{create_test_launcher_message, TestMessageType};
 
 #[test]
@@ -30,10 +33,17 @@ fn test_protocol_backward_compatibility() {
         LauncherToMonitor::Connect {
             launcher_id,
             tool_type,
+            tool_args,
+            protocol_version,
+            capabilities,
             ..
         } => {
             assert_eq!(launcher_id, "test_launcher_123");
             assert_eq!(tool_type, CliToolType::Claude);
+            // 旧フィールド名・バージョン情報なしのConnectはプロトコルv1として扱う
+            assert_eq!(tool_args, vec!["--help".to_string()]);
+            assert_eq!(protocol_version, LEGACY_PROTOCOL_VERSION);
+            assert!(capabilities.is_empty());
         }
         _ => panic!("予期しないメッセージタイプ"),
     }
@@ -127,14 +137,15 @@ fn test_message_type_structure_stability() {
         create_test_launcher_message(test_launcher_id.clone(), TestMessageType::Register);
     let state_msg =
         create_test_launcher_message(test_launcher_id.clone(), TestMessageType::StatusUpdate);
-    // ProcessMetrics は削除済み
+    let metrics_msg =
+        create_test_launcher_message(test_launcher_id.clone(), TestMessageType::ProcessMetrics);
     let disconnect_msg =
         create_test_launcher_message(test_launcher_id.clone(), TestMessageType::Disconnect);
 
     // 各メッセージがシリアライズ可能であることを確認
     assert!(serde_json::to_string(&connect_msg).is_ok());
     assert!(serde_json::to_string(&state_msg).is_ok());
-    // metrics_msg は削除済み
+    assert!(serde_json::to_string(&metrics_msg).is_ok());
     assert!(serde_json::to_string(&disconnect_msg).is_ok());
 
     // 必須フィールドが存在することを確認
@@ -174,7 +185,7 @@ fn test_timestamp_field_presence() {
     let messages = vec![
         create_test_launcher_message(test_launcher_id.clone(), TestMessageType::Register),
         create_test_launcher_message(test_launcher_id.clone(), TestMessageType::StatusUpdate),
-        // ProcessMetrics は削除済み
+        create_test_launcher_message(test_launcher_id.clone(), TestMessageType::ProcessMetrics),
         create_test_launcher_message(test_launcher_id.clone(), TestMessageType::Disconnect),
     ];
 
@@ -192,10 +203,27 @@ fn test_timestamp_field_presence() {
             LauncherToMonitor::ContextUpdate { .. } => {
                 assert!(json_value["ContextUpdate"]["timestamp"].is_string());
             }
-            // ProcessMetrics は削除済み
+            LauncherToMonitor::PromptQueueUpdate { .. } => {
+                assert!(json_value["PromptQueueUpdate"]["timestamp"].is_string());
+            }
+            LauncherToMonitor::AutoApproveDecision { .. } => {
+                assert!(json_value["AutoApproveDecision"]["timestamp"].is_string());
+            }
+            LauncherToMonitor::TerminalNotification { .. } => {
+                assert!(json_value["TerminalNotification"]["timestamp"].is_string());
+            }
+            LauncherToMonitor::Exited { .. } => {
+                assert!(json_value["Exited"]["timestamp"].is_string());
+            }
+            LauncherToMonitor::ProcessMetrics { .. } => {
+                assert!(json_value["ProcessMetrics"]["timestamp"].is_string());
+            }
+            LauncherToMonitor::Activity { .. } => {
+                assert!(json_value["Activity"]["timestamp"].is_string());
+            }
             LauncherToMonitor::Disconnect { .. } => {
                 assert!(json_value["Disconnect"]["timestamp"].is_string());
-            } // ProcessMetrics削除により到達不可能
+            }
         }
     }
 }
@@ -235,9 +263,12 @@ fn test_unicode_support() {
         launcher_id: "unicode_test".to_string(),
         project: Some(unicode_project.to_string()),
         tool_type: CliToolType::Claude,
-        claude_args: vec!["--project".to_string(), unicode_project.to_string()],
+        tool_args: vec!["--project".to_string(), unicode_project.to_string()],
         working_dir: "/tmp/unicode_test".into(),
         timestamp: chrono::Utc::now(),
+        protocol_version: PROTOCOL_VERSION,
+        capabilities: supported_capabilities(),
+        metadata: Default::default(),
     };
 
     // StateUpdate メッセージでのUnicode
@@ -247,6 +278,7 @@ fn test_unicode_support() {
         status: SessionStatus::Busy,
         ui_above_text: Some(unicode_ui_text.to_string()),
         timestamp: chrono::Utc::now(),
+        evidence: None,
     };
 
     // シリアライゼーション/デシリアライゼーション確認
@@ -259,12 +291,10 @@ fn test_unicode_support() {
     // Unicode文字が保持されていることを確認
     match connect_deserialized {
         LauncherToMonitor::Connect {
-            project,
-            claude_args,
-            ..
+            project, tool_args, ..
         } => {
             assert_eq!(project, Some(unicode_project.to_string()));
-            assert!(claude_args.contains(&unicode_project.to_string()));
+            assert!(tool_args.contains(&unicode_project.to_string()));
         }
         _ => panic!("Connect メッセージの構造が異なります"),
     }
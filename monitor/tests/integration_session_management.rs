@@ -6,7 +6,8 @@ mod common;
 use chrono::Utc;
 use climonitor_monitor::session_manager::SessionManager;
 use climonitor_shared::{
-    generate_connection_id, CliToolType, LauncherInfo, LauncherStatus, SessionStatus,
+    generate_launcher_id, AutoApproveAction, CliToolType, EvidenceLine, LauncherInfo,
+    LauncherStatus, LauncherToMonitor, SessionStatus, StateEvidence,
 };
 use common::{create_test_launcher_info, create_test_session_info};
 use std::path::PathBuf;
@@ -22,16 +23,18 @@ fn test_session_manager_launcher_lifecycle() {
     assert_eq!(stats.active_sessions, 0);
 
     // ランチャー追加
-    let launcher_id = generate_connection_id();
+    let launcher_id = generate_launcher_id();
     let launcher_info = LauncherInfo {
         id: launcher_id.clone(),
         project: Some("test-project".to_string()),
         tool_type: CliToolType::Claude,
-        claude_args: vec!["--help".to_string()],
+        tool_args: vec!["--help".to_string()],
         working_dir: PathBuf::from("/tmp/test"),
         connected_at: Utc::now(),
         last_activity: Utc::now(),
         status: LauncherStatus::Connected,
+        origin: None,
+        metadata: Default::default(),
     };
 
     let result = manager.add_launcher(launcher_info.clone());
@@ -60,7 +63,7 @@ fn test_session_manager_duplicate_launcher() {
     // 重複ランチャー登録のテスト
     let mut manager = SessionManager::new();
 
-    let launcher_id = generate_connection_id();
+    let launcher_id = generate_launcher_id();
     let launcher_info1 = create_test_launcher_info(launcher_id.clone(), CliToolType::Claude);
     let launcher_info2 = create_test_launcher_info(launcher_id.clone(), CliToolType::Gemini);
 
@@ -120,7 +123,7 @@ fn test_session_manager_session_operations() {
     let mut manager = SessionManager::new();
 
     // ランチャー登録
-    let launcher_id = generate_connection_id();
+    let launcher_id = generate_launcher_id();
     let launcher_info = create_test_launcher_info(launcher_id.clone(), CliToolType::Claude);
     manager.add_launcher(launcher_info).unwrap();
 
@@ -150,7 +153,7 @@ fn test_session_manager_launcher_removal_cleans_sessions() {
     let mut manager = SessionManager::new();
 
     // ランチャー登録
-    let launcher_id = generate_connection_id();
+    let launcher_id = generate_launcher_id();
     let launcher_info = create_test_launcher_info(launcher_id.clone(), CliToolType::Claude);
     manager.add_launcher(launcher_info).unwrap();
 
@@ -225,3 +228,224 @@ fn test_launcher_status_types() {
         assert!(launcher_ids.contains(&launcher_id));
     }
 }
+
+#[test]
+fn test_prompt_queue_update_sets_waiting_flag() {
+    // プロンプト予約状態がStateUpdateを跨いで保持されることを確認
+    let mut manager = SessionManager::new();
+    let launcher_id = generate_launcher_id();
+    let session_id = "queue_session".to_string();
+
+    manager
+        .handle_message(LauncherToMonitor::StateUpdate {
+            launcher_id: launcher_id.clone(),
+            session_id: session_id.clone(),
+            status: SessionStatus::Busy,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: None,
+        })
+        .unwrap();
+    assert!(
+        !manager
+            .get_session(&session_id)
+            .unwrap()
+            .is_waiting_for_execution
+    );
+
+    manager
+        .handle_message(LauncherToMonitor::PromptQueueUpdate {
+            launcher_id: launcher_id.clone(),
+            session_id: session_id.clone(),
+            queued_prompt: Some("run the tests".to_string()),
+            timestamp: Utc::now(),
+        })
+        .unwrap();
+    assert!(
+        manager
+            .get_session(&session_id)
+            .unwrap()
+            .is_waiting_for_execution
+    );
+
+    // 状態更新後も予約状態は維持される
+    manager
+        .handle_message(LauncherToMonitor::StateUpdate {
+            launcher_id: launcher_id.clone(),
+            session_id: session_id.clone(),
+            status: SessionStatus::Busy,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: None,
+        })
+        .unwrap();
+    assert!(
+        manager
+            .get_session(&session_id)
+            .unwrap()
+            .is_waiting_for_execution
+    );
+
+    manager
+        .handle_message(LauncherToMonitor::PromptQueueUpdate {
+            launcher_id,
+            session_id: session_id.clone(),
+            queued_prompt: None,
+            timestamp: Utc::now(),
+        })
+        .unwrap();
+    assert!(
+        !manager
+            .get_session(&session_id)
+            .unwrap()
+            .is_waiting_for_execution
+    );
+}
+
+#[test]
+fn test_auto_approve_decision_is_recorded() {
+    // 自動応答の判定が監査イベントとして記録されることを確認
+    let mut manager = SessionManager::new();
+    let launcher_id = generate_launcher_id();
+    let session_id = "auto_approve_session".to_string();
+
+    manager
+        .handle_message(LauncherToMonitor::StateUpdate {
+            launcher_id: launcher_id.clone(),
+            session_id: session_id.clone(),
+            status: SessionStatus::WaitingInput,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: None,
+        })
+        .unwrap();
+
+    manager
+        .handle_message(LauncherToMonitor::AutoApproveDecision {
+            launcher_id: launcher_id.clone(),
+            session_id: session_id.clone(),
+            rule: "Read(".to_string(),
+            action: AutoApproveAction::Approve,
+            prompt: "Read(src/main.rs)\nDo you want to proceed?".to_string(),
+            dry_run: true,
+            timestamp: Utc::now(),
+        })
+        .unwrap();
+
+    let events: Vec<_> = manager.get_auto_approve_events().collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].rule, "Read(");
+    assert!(events[0].dry_run);
+    assert_eq!(
+        events[0].prompt_preview(),
+        "Read(src/main.rs) / Do you want to proceed?"
+    );
+
+    // 直近の判定はセッションのメッセージとして表示され、状態更新後も保持される
+    manager
+        .handle_message(LauncherToMonitor::StateUpdate {
+            launcher_id,
+            session_id: session_id.clone(),
+            status: SessionStatus::Busy,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: None,
+        })
+        .unwrap();
+    let message = manager
+        .get_session(&session_id)
+        .unwrap()
+        .last_message
+        .clone()
+        .unwrap();
+    assert!(message.contains("dry-run"));
+    assert!(message.contains("Read("));
+}
+
+#[test]
+fn test_terminal_notification_updates_last_message() {
+    // CLIツールのデスクトップ通知はセッションのメッセージとして表示される
+    let mut manager = SessionManager::new();
+    let launcher_id = generate_launcher_id();
+    let session_id = "notification_session".to_string();
+
+    manager
+        .handle_message(LauncherToMonitor::StateUpdate {
+            launcher_id: launcher_id.clone(),
+            session_id: session_id.clone(),
+            status: SessionStatus::Busy,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: None,
+        })
+        .unwrap();
+
+    let reply = manager
+        .handle_message(LauncherToMonitor::TerminalNotification {
+            launcher_id,
+            session_id: session_id.clone(),
+            title: Some("Claude Code".to_string()),
+            body: "Claude is waiting for your input".to_string(),
+            timestamp: Utc::now(),
+        })
+        .unwrap();
+    assert!(reply.is_none());
+
+    let session = manager.get_session(&session_id).unwrap();
+    assert_eq!(
+        session.last_message.as_deref(),
+        Some("🔔 Claude Code: Claude is waiting for your input")
+    );
+    // 状態はlauncherのStateUpdateでのみ変わる
+    assert_eq!(session.status, SessionStatus::Busy);
+}
+
+#[test]
+fn test_state_update_evidence_is_shown_on_session() {
+    // 状態判定の根拠はSessionInfo.evidenceに表示用の文字列として入る
+    let mut manager = SessionManager::new();
+    let launcher_id = generate_launcher_id();
+    let session_id = "evidence_session".to_string();
+
+    manager
+        .handle_message(LauncherToMonitor::StateUpdate {
+            launcher_id: launcher_id.clone(),
+            session_id: session_id.clone(),
+            status: SessionStatus::WaitingInput,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: Some(StateEvidence {
+                rules: vec!["claude.approval_prompt(\"Do you want\")".to_string()],
+                lines: vec![EvidenceLine {
+                    row: 4,
+                    text: "│ Do you want to edit? │".to_string(),
+                }],
+                confidence: 0.85,
+            }),
+        })
+        .unwrap();
+    assert_eq!(
+        manager.get_session(&session_id).unwrap().evidence,
+        vec![
+            "claude.approval_prompt(\"Do you want\") (85%)".to_string(),
+            "L5: │ Do you want to edit? │".to_string(),
+        ]
+    );
+
+    // 根拠のない更新では前回の根拠を残さない
+    manager
+        .handle_message(LauncherToMonitor::StateUpdate {
+            launcher_id,
+            session_id: session_id.clone(),
+            status: SessionStatus::Busy,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence: None,
+        })
+        .unwrap();
+    assert!(manager
+        .get_session(&session_id)
+        .unwrap()
+        .evidence
+        .is_empty());
+}
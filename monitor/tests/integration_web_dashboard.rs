@@ -0,0 +1,362 @@
+// ブラウザ用ダッシュボード（HTTP + WebSocket）の統合テスト
+
+use chrono::Utc;
+use climonitor_monitor::config_reload::{RuntimeSettings, SharedRuntimeSettings};
+use climonitor_monitor::session_manager::SessionManager;
+use climonitor_monitor::web_server::{DashboardMessage, WebDashboardServer};
+use climonitor_shared::{
+    supported_capabilities, CliToolType, LauncherToMonitor, MonitorToLauncher, SessionStatus,
+    WebSettings, PROTOCOL_VERSION,
+};
+use futures_util::{SinkExt, StreamExt};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, RwLock};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+
+type WebSocket = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<TcpStream>>;
+
+async fn start_server(
+    allowed_ips: &[&str],
+) -> (
+    Arc<RwLock<SessionManager>>,
+    broadcast::Sender<()>,
+    SharedRuntimeSettings,
+    SocketAddr,
+) {
+    start_server_with(allowed_ips, |server| server).await
+}
+
+async fn start_server_with(
+    allowed_ips: &[&str],
+    configure: impl FnOnce(WebDashboardServer) -> WebDashboardServer,
+) -> (
+    Arc<RwLock<SessionManager>>,
+    broadcast::Sender<()>,
+    SharedRuntimeSettings,
+    SocketAddr,
+) {
+    let session_manager = Arc::new(RwLock::new(SessionManager::new()));
+    let (ui_update_sender, _) = broadcast::channel(100);
+    let settings = WebSettings {
+        bind_addr: "127.0.0.1:0".to_string(),
+        allowed_ips: allowed_ips.iter().map(|ip| ip.to_string()).collect(),
+        ..WebSettings::default()
+    };
+    let runtime_settings = Arc::new(RwLock::new(RuntimeSettings {
+        web_allowed_ips: settings.allowed_ips.clone(),
+        ..RuntimeSettings::default()
+    }));
+    let server = configure(WebDashboardServer::new(
+        &settings,
+        Arc::clone(&session_manager),
+        ui_update_sender.clone(),
+        Arc::clone(&runtime_settings),
+    ));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(server.serve(listener));
+    (session_manager, ui_update_sender, runtime_settings, addr)
+}
+
+/// SessionManagerにメッセージを反映し、UI更新を通知
+async fn apply(
+    session_manager: &RwLock<SessionManager>,
+    ui_update_sender: &broadcast::Sender<()>,
+    message: LauncherToMonitor,
+) {
+    session_manager
+        .write()
+        .await
+        .handle_message(message)
+        .unwrap();
+    let _ = ui_update_sender.send(());
+}
+
+async fn next_message(socket: &mut WebSocket) -> DashboardMessage {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(2), socket.next())
+            .await
+            .expect("timed out waiting for dashboard message")
+            .expect("websocket closed")
+            .unwrap();
+        if let Message::Text(json) = message {
+            return serde_json::from_str(&json).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_dashboard_page_is_served() {
+    let (_, _, _, addr) = start_server(&["127.0.0.1"]).await;
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("text/html"));
+    assert!(response.contains("<title>climonitor</title>"));
+}
+
+#[tokio::test]
+async fn test_websocket_pushes_snapshot_and_deltas() {
+    let (session_manager, ui_update_sender, _, addr) = start_server(&["127.0.0.1"]).await;
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .unwrap();
+
+    // 接続直後は空のスナップショット
+    match next_message(&mut socket).await {
+        DashboardMessage::Snapshot {
+            launchers,
+            sessions,
+            ..
+        } => {
+            assert!(launchers.is_empty());
+            assert!(sessions.is_empty());
+        }
+        other => panic!("expected snapshot, got {other:?}"),
+    }
+
+    apply(
+        &session_manager,
+        &ui_update_sender,
+        LauncherToMonitor::Connect {
+            launcher_id: "launcher-1".to_string(),
+            project: Some("web-project".to_string()),
+            tool_type: CliToolType::Claude,
+            tool_args: Vec::new(),
+            working_dir: PathBuf::from("/tmp"),
+            timestamp: Utc::now(),
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: supported_capabilities(),
+            metadata: Default::default(),
+        },
+    )
+    .await;
+    match next_message(&mut socket).await {
+        DashboardMessage::Delta { launchers, .. } => {
+            assert_eq!(launchers.len(), 1);
+            assert_eq!(launchers[0].id, "launcher-1");
+            assert_eq!(launchers[0].project.as_deref(), Some("web-project"));
+        }
+        other => panic!("expected delta, got {other:?}"),
+    }
+
+    apply(
+        &session_manager,
+        &ui_update_sender,
+        LauncherToMonitor::StateUpdate {
+            launcher_id: "launcher-1".to_string(),
+            session_id: "session-1".to_string(),
+            status: SessionStatus::WaitingInput,
+            ui_above_text: Some("Edit file?".to_string()),
+            timestamp: Utc::now(),
+            evidence: None,
+        },
+    )
+    .await;
+    match next_message(&mut socket).await {
+        DashboardMessage::Delta { sessions, .. } => {
+            assert_eq!(sessions.len(), 1);
+            assert_eq!(sessions[0].status, SessionStatus::WaitingInput);
+            assert_eq!(sessions[0].ui_above_text.as_deref(), Some("Edit file?"));
+        }
+        other => panic!("expected delta, got {other:?}"),
+    }
+
+    apply(
+        &session_manager,
+        &ui_update_sender,
+        LauncherToMonitor::Disconnect {
+            launcher_id: "launcher-1".to_string(),
+            timestamp: Utc::now(),
+        },
+    )
+    .await;
+    match next_message(&mut socket).await {
+        DashboardMessage::Delta {
+            removed_launchers, ..
+        } => assert_eq!(removed_launchers, vec!["launcher-1"]),
+        other => panic!("expected delta, got {other:?}"),
+    }
+
+    // 後から接続したクライアントは現在の状態をスナップショットで受け取る
+    let (mut late_socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        next_message(&mut late_socket).await,
+        DashboardMessage::Snapshot { launchers, .. } if launchers.is_empty()
+    ));
+}
+
+#[tokio::test]
+async fn test_websocket_rejects_disallowed_ip() {
+    let (_, _, runtime_settings, addr) = start_server(&["10.0.0.0/8"]).await;
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(2),
+        tokio_tungstenite::connect_async(format!("ws://{addr}/ws")),
+    )
+    .await
+    .unwrap();
+    assert!(result.is_err());
+
+    // 再読み込みで許可リストが更新されると以降の接続は受け付ける
+    runtime_settings.write().await.web_allowed_ips = vec!["127.0.0.1".to_string()];
+    let result = tokio::time::timeout(
+        Duration::from_secs(2),
+        tokio_tungstenite::connect_async(format!("ws://{addr}/ws")),
+    )
+    .await
+    .unwrap();
+    assert!(result.is_ok());
+}
+
+/// Originヘッダー付きでWebSocket接続（ブラウザからの接続を模擬）
+async fn connect_with_origin(
+    addr: SocketAddr,
+    origin: &str,
+) -> Result<WebSocket, tokio_tungstenite::tungstenite::Error> {
+    let mut request = format!("ws://{addr}/ws").into_client_request().unwrap();
+    request
+        .headers_mut()
+        .insert("Origin", origin.parse().unwrap());
+    tokio::time::timeout(
+        Duration::from_secs(2),
+        tokio_tungstenite::connect_async(request),
+    )
+    .await
+    .unwrap()
+    .map(|(socket, _)| socket)
+}
+
+#[tokio::test]
+async fn test_websocket_rejects_cross_origin_handshake() {
+    let (_, _, runtime_settings, addr) = start_server(&[]).await;
+
+    // 他のサイトのページからの接続は拒否
+    match connect_with_origin(addr, "https://evil.example").await {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), 403);
+        }
+        other => panic!("expected 403, got {other:?}"),
+    }
+
+    // ダッシュボード自身のページからの接続は受け付ける
+    let mut socket = connect_with_origin(addr, &format!("http://{addr}"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        next_message(&mut socket).await,
+        DashboardMessage::Snapshot { .. }
+    ));
+
+    // 許可リストに追加したOriginは再読み込み後に受け付ける
+    runtime_settings.write().await.web_allowed_origins = vec!["https://evil.example".to_string()];
+    assert!(connect_with_origin(addr, "https://evil.example")
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_dashboard_queues_prompt_for_local_launcher() {
+    let (launcher_messages, mut pushed) = broadcast::channel(16);
+    let (session_manager, ui_update_sender, runtime_settings, addr) =
+        start_server_with(&["127.0.0.1"], |server| {
+            server.with_launcher_messages(launcher_messages)
+        })
+        .await;
+    apply(
+        &session_manager,
+        &ui_update_sender,
+        LauncherToMonitor::Connect {
+            launcher_id: "launcher-1".to_string(),
+            project: Some("web-project".to_string()),
+            tool_type: CliToolType::Claude,
+            tool_args: Vec::new(),
+            working_dir: PathBuf::from("/tmp"),
+            timestamp: Utc::now(),
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: supported_capabilities(),
+            metadata: Default::default(),
+        },
+    )
+    .await;
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        next_message(&mut socket).await,
+        DashboardMessage::Snapshot { .. }
+    ));
+
+    let queue_prompt = Message::Text(
+        r#"{"type":"queue_prompt","launcher_id":"launcher-1","text":"run the tests"}"#.to_string(),
+    );
+
+    // 設定で有効にするまでは受け付けない
+    socket.send(queue_prompt.clone()).await.unwrap();
+    match next_message(&mut socket).await {
+        DashboardMessage::CommandRejected { reason } => {
+            assert!(reason.contains("web.allow_prompt_queue"));
+        }
+        other => panic!("expected rejection, got {other:?}"),
+    }
+    assert!(pushed.try_recv().is_err());
+
+    runtime_settings.write().await.web_allow_prompt_queue = true;
+    socket.send(queue_prompt).await.unwrap();
+    let message = tokio::time::timeout(Duration::from_secs(2), pushed.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        message,
+        MonitorToLauncher::QueuePrompt {
+            launcher_id: "launcher-1".to_string(),
+            text: "run the tests".to_string(),
+        }
+    );
+
+    socket
+        .send(Message::Text(
+            r#"{"type":"clear_queued_prompt","launcher_id":"launcher-1"}"#.to_string(),
+        ))
+        .await
+        .unwrap();
+    let message = tokio::time::timeout(Duration::from_secs(2), pushed.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        message,
+        MonitorToLauncher::ClearQueuedPrompt {
+            launcher_id: "launcher-1".to_string(),
+        }
+    );
+
+    // 未知のlauncherへの予約は理由を返す
+    socket
+        .send(Message::Text(
+            r#"{"type":"queue_prompt","launcher_id":"missing","text":"hello"}"#.to_string(),
+        ))
+        .await
+        .unwrap();
+    assert!(matches!(
+        next_message(&mut socket).await,
+        DashboardMessage::CommandRejected { reason } if reason.contains("missing")
+    ));
+}
@@ -1,11 +1,16 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
 
+use crate::logging::{LogCategory, LogFormat, LogLevel, RotatingFileWriter};
+use crate::protocol::{AutoApproveAction, SessionStatus};
 use crate::transport::ConnectionConfig;
 
 /// メインの設定構造体
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     /// 接続設定
     #[serde(default)]
@@ -22,10 +27,38 @@ pub struct Config {
     /// UI設定
     #[serde(default)]
     pub ui: UiSettings,
+
+    /// 承認プロンプトの自動応答設定
+    #[serde(default)]
+    pub auto_approve: AutoApproveSettings,
+
+    /// launcherが送信する状態遷移のフィルタ設定
+    #[serde(default)]
+    pub state_filter: StateFilterSettings,
+
+    /// launcherの状態通知の頻度設定
+    #[serde(default)]
+    pub state_report: StateReportSettings,
+
+    /// launcherが送信するリソース使用量の設定
+    #[serde(default)]
+    pub process_metrics: ProcessMetricsSettings,
+
+    /// ブラウザ用ダッシュボード（セクションがある場合のみ起動）
+    pub web: Option<WebSettings>,
+
+    /// 上流monitorの集約設定
+    #[serde(default)]
+    pub federation: FederationSettings,
+
+    /// プロジェクト設定（プロジェクト内の .climonitor/config.toml で使用）
+    #[serde(default)]
+    pub project: ProjectSettings,
 }
 
 /// gRPC関連の設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GrpcSettings {
     /// gRPCサーバーのバインドアドレス
     #[serde(default = "default_grpc_bind_addr")]
@@ -36,26 +69,163 @@ pub struct GrpcSettings {
     pub allowed_ips: Vec<String>,
 }
 
+impl Default for GrpcSettings {
+    fn default() -> Self {
+        Self {
+            bind_addr: default_grpc_bind_addr(),
+            allowed_ips: Vec::new(),
+        }
+    }
+}
+
 fn default_grpc_bind_addr() -> String {
     "127.0.0.1:50051".to_string()
 }
 
+/// TCP（改行区切りJSON）関連の設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TcpSettings {
+    /// TCPサーバーのバインドアドレス（launcherでは接続先）
+    #[serde(default = "default_tcp_bind_addr")]
+    pub bind_addr: String,
+
+    /// IP許可リスト
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+}
+
+impl Default for TcpSettings {
+    fn default() -> Self {
+        Self {
+            bind_addr: default_tcp_bind_addr(),
+            allowed_ips: Vec::new(),
+        }
+    }
+}
+
+fn default_tcp_bind_addr() -> String {
+    "127.0.0.1:50052".to_string()
+}
+
+/// ブラウザ用ダッシュボード（HTTP + WebSocket）の設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebSettings {
+    /// HTTPサーバーのバインドアドレス
+    #[serde(default = "default_web_bind_addr")]
+    pub bind_addr: String,
+
+    /// IP許可リスト
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+
+    /// WebSocket接続を許可する追加のOrigin（`Host` と一致するOriginは常に許可）
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// ダッシュボードからlauncherへのプロンプト予約を受け付けるか
+    #[serde(default)]
+    pub allow_prompt_queue: bool,
+}
+
+impl Default for WebSettings {
+    fn default() -> Self {
+        Self {
+            bind_addr: default_web_bind_addr(),
+            allowed_ips: Vec::new(),
+            allowed_origins: Vec::new(),
+            allow_prompt_queue: false,
+        }
+    }
+}
+
+fn default_web_bind_addr() -> String {
+    "127.0.0.1:8080".to_string()
+}
+
+/// プロジェクト設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectSettings {
+    /// monitorに表示するプロジェクト名（自動検出より優先）
+    pub name: Option<String>,
+}
+
+impl ProjectSettings {
+    /// `<dir>/.climonitor/config.toml` の [project] を読み込み
+    ///
+    /// ファイルがない、または [project] を読めない場合は None（他のセクションは検証しない）
+    pub fn load_from_dir(dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(dir.join(".climonitor").join("config.toml")).ok()?;
+        let raw: toml::Value = toml::from_str(&content).ok()?;
+        raw.get("project")?.clone().try_into().ok()
+    }
+}
+
+/// 上流monitorの集約（federation）設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FederationSettings {
+    /// 購読する上流monitor（gRPC）
+    #[serde(default)]
+    pub upstreams: Vec<UpstreamSettings>,
+}
+
+/// 上流monitorの接続先
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpstreamSettings {
+    /// ホスト名（セッションIDの名前空間として使用）
+    pub name: String,
+
+    /// 上流monitorのgRPCアドレス（host:port）
+    pub addr: String,
+}
+
+impl std::str::FromStr for UpstreamSettings {
+    type Err = String;
+
+    /// `NAME=ADDR` 形式から生成（`--upstream` 用）
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (name, addr) = s
+            .split_once('=')
+            .ok_or_else(|| format!("'{s}' must be in NAME=ADDR form"))?;
+        Ok(Self {
+            name: name.trim().to_string(),
+            addr: addr.trim().to_string(),
+        })
+    }
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
 
+fn default_log_max_file_size_mb() -> u64 {
+    10
+}
+
+fn default_log_max_files() -> usize {
+    3
+}
+
 /// 接続関連の設定
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct ConnectionSettings {
     /// Unix socket接続時のソケットパス
     pub unix_socket_path: Option<PathBuf>,
 
     /// gRPC接続設定
     pub grpc: Option<GrpcSettings>,
+
+    /// TCP接続設定
+    pub tcp: Option<TcpSettings>,
 }
 
 /// ログ関連の設定
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct LoggingSettings {
     /// 詳細ログを有効にするか
     #[serde(default)]
@@ -71,37 +241,313 @@ pub struct LoggingSettings {
 
     /// ログファイルのパス（実装済み：CLIツールの出力保存用）
     pub log_file: Option<PathBuf>,
+
+    /// 診断ログの出力形式 (text, json)
+    #[serde(default)]
+    pub format: LogFormat,
+
+    /// 診断ログの出力先ファイル（未指定時はstderr）
+    pub diagnostic_file: Option<PathBuf>,
+
+    /// 診断ログファイルのローテーションサイズ（MB）
+    #[serde(default = "default_log_max_file_size_mb")]
+    pub max_file_size_mb: u64,
+
+    /// 保持するローテーション済みファイル数
+    #[serde(default = "default_log_max_files")]
+    pub max_files: usize,
+
+    /// tracingのsubscriber経由で出力するか（span情報付き、`RUST_LOG` でフィルタ可能）
+    #[serde(default)]
+    pub tracing: bool,
+}
+
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            verbose: false,
+            level: default_log_level(),
+            categories: Vec::new(),
+            log_file: None,
+            format: LogFormat::default(),
+            diagnostic_file: None,
+            max_file_size_mb: default_log_max_file_size_mb(),
+            max_files: default_log_max_files(),
+            tracing: false,
+        }
+    }
 }
 
 impl LoggingSettings {
+    /// ログレベルとカテゴリフィルタを解析
+    fn parse_filters(&self) -> Result<(LogLevel, Vec<LogCategory>)> {
+        // 未知のカテゴリはエラー
+        let categories = self
+            .categories
+            .iter()
+            .map(|name| name.parse::<LogCategory>().map_err(anyhow::Error::msg))
+            .collect::<Result<Vec<_>>>()?;
+        let level = self.level.parse::<LogLevel>().map_err(anyhow::Error::msg)?;
+        Ok((level, categories))
+    }
+
+    /// ログレベルとカテゴリフィルタのみを反映（設定の再読み込み用）
+    pub fn apply_filters(&self) -> Result<()> {
+        let (level, categories) = self.parse_filters()?;
+        crate::logging::set_log_level(level);
+        crate::logging::set_log_categories(categories);
+        Ok(())
+    }
+
     /// ログシステムを初期化
-    pub fn init_logging(&self) {
+    pub fn init_logging(&self) -> Result<()> {
+        let (level, categories) = self.parse_filters()?;
+
         // ログレベルを設定
-        let level = crate::logging::LogLevel::from(self.level.as_str());
         crate::logging::set_log_level(level);
+        crate::logging::set_log_format(self.format);
+        crate::logging::set_log_categories(categories.clone());
+
+        // 診断ログのファイル出力（PTY出力と混ざらないようstderrの代わりに使用）
+        let writer = match &self.diagnostic_file {
+            Some(path) => {
+                let path = expand_home(path);
+                let writer = RotatingFileWriter::open(
+                    &path,
+                    self.max_file_size_mb.saturating_mul(1024 * 1024),
+                    self.max_files,
+                )
+                .with_context(|| {
+                    format!("Failed to open diagnostic log file: {}", path.display())
+                })?;
+                Some(writer)
+            }
+            None => None,
+        };
 
-        // 必要に応じてログ出力先を設定
-        if let Some(_log_file) = &self.log_file {
-            // ファイル出力は将来実装
-            // TODO: ファイル出力の実装
+        if self.tracing {
+            // ログマクロはtracingイベントとして出力される
+            crate::logging::init_tracing_subscriber(self.format, level, &categories, writer)?;
+        } else {
+            crate::logging::set_log_file(writer);
         }
+
+        Ok(())
     }
 }
 
-/// 通知関連の設定（現在は実装されていない - ~/.climonitor/notify.sh が存在する場合のみ動作）
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// 先頭の `~` をホームディレクトリに展開
+pub fn expand_home(path: &std::path::Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~") {
+        if let Some(home_dir) = home::home_dir() {
+            return home_dir.join(rest);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// 通知関連の設定（monitor実行中の変更も反映される）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct NotificationSettings {
-    /// 将来の実装用プレースホルダー
-    #[serde(default)]
-    _placeholder: bool,
+    /// 通知スクリプトを実行するか
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// 通知スクリプトのパス（省略時は ~/.climonitor/notify.sh）
+    pub script_path: Option<PathBuf>,
 }
 
-/// UI関連の設定（現在は実装されていない - ハードコードされた値を使用）
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            script_path: None,
+        }
+    }
+}
+
+/// UI関連の設定（monitor実行中の変更も反映される）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct UiSettings {
-    /// 将来の実装用プレースホルダー
+    /// 定期再描画の間隔（秒）
+    #[serde(default = "default_ui_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+    /// 終了したCLIツールの行を表示し続ける時間（秒）
+    #[serde(default = "default_exited_retention_secs")]
+    pub exited_retention_secs: u64,
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self {
+            refresh_interval_secs: default_ui_refresh_interval_secs(),
+            exited_retention_secs: default_exited_retention_secs(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_ui_refresh_interval_secs() -> u64 {
+    5
+}
+
+fn default_exited_retention_secs() -> u64 {
+    300
+}
+
+/// 承認プロンプトの自動応答設定
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AutoApproveSettings {
+    /// 自動応答を有効にするか
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// trueの場合はキー入力を送信せず、判定結果の記録のみ行う
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// 判定ルール（denyルールが優先、approveルールは上から順に評価）
+    #[serde(default)]
+    pub rules: Vec<AutoApproveRule>,
+}
+
+/// 自動応答ルール
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AutoApproveRule {
+    /// 対象プロジェクト名（省略時は全プロジェクト）
+    pub project: Option<String>,
+
+    /// UI boxの内容に対するパターン（部分一致、`*` は任意の文字列）
+    pub pattern: String,
+
+    /// 判定 (approve, deny)
+    #[serde(default = "default_auto_approve_action")]
+    pub action: AutoApproveAction,
+
+    /// 承認時に送信するキー入力（省略時はEnter）
+    #[serde(default = "default_auto_approve_keys")]
+    pub keys: String,
+}
+
+fn default_auto_approve_action() -> AutoApproveAction {
+    AutoApproveAction::Approve
+}
+
+fn default_auto_approve_keys() -> String {
+    "\r".to_string()
+}
+
+/// 状態遷移フィルタの設定（検出結果のちらつきをmonitorへ送る前に抑える）
+///
+/// 状態名は `connected`, `busy`, `waiting_input`, `idle`, `error`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateFilterSettings {
+    /// 状態ごとの最短継続時間（ミリ秒）。この間は別の状態へ遷移しない
     #[serde(default)]
-    _placeholder: bool,
+    pub min_dwell_ms: BTreeMap<String, u64>,
+
+    /// 遷移先の状態ごとの確定待ち時間（ミリ秒）。検出結果がこの間続いた場合のみ遷移
+    #[serde(default = "default_state_filter_confirm_ms")]
+    pub confirm_ms: BTreeMap<String, u64>,
+
+    /// 遷移元の状態ごとに許可する遷移先（記載のない状態からはすべて許可）
+    #[serde(default = "default_state_filter_allowed_transitions")]
+    pub allowed_transitions: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for StateFilterSettings {
+    fn default() -> Self {
+        Self {
+            min_dwell_ms: BTreeMap::new(),
+            confirm_ms: default_state_filter_confirm_ms(),
+            allowed_transitions: default_state_filter_allowed_transitions(),
+        }
+    }
+}
+
+/// 連続処理中に"esc to interrupt"が一瞬消えてもIdleと判定しない
+fn default_state_filter_confirm_ms() -> BTreeMap<String, u64> {
+    BTreeMap::from([("idle".to_string(), 1500)])
+}
+
+/// 起動直後のプロンプト表示ではIdle（完了）にしない
+fn default_state_filter_allowed_transitions() -> BTreeMap<String, Vec<String>> {
+    BTreeMap::from([(
+        "connected".to_string(),
+        vec![
+            "busy".to_string(),
+            "waiting_input".to_string(),
+            "error".to_string(),
+        ],
+    )])
+}
+
+/// 状態通知の設定（launcherは状態・コンテキストが変化した時点で確認・送信する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateReportSettings {
+    /// 状態確認の最短間隔（ミリ秒）。この間の変化はまとめて1回で処理
+    #[serde(default = "default_state_report_min_interval_ms")]
+    pub min_interval_ms: u64,
+
+    /// 変化の通知がなくても状態を確認し直す間隔（秒）
+    #[serde(default = "default_state_report_keepalive_secs")]
+    pub keepalive_secs: u64,
+
+    /// Busyのまま出力がこの時間（秒）止まっていれば停止中として報告。0で無効
+    #[serde(default = "default_state_report_stall_after_secs")]
+    pub stall_after_secs: u64,
+}
+
+impl Default for StateReportSettings {
+    fn default() -> Self {
+        Self {
+            min_interval_ms: default_state_report_min_interval_ms(),
+            keepalive_secs: default_state_report_keepalive_secs(),
+            stall_after_secs: default_state_report_stall_after_secs(),
+        }
+    }
+}
+
+fn default_state_report_min_interval_ms() -> u64 {
+    200
+}
+
+fn default_state_report_keepalive_secs() -> u64 {
+    10
+}
+
+fn default_state_report_stall_after_secs() -> u64 {
+    300
+}
+
+/// CLIツールのプロセスツリーのリソース使用量（CPU・RSS・子プロセス数）の計測設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessMetricsSettings {
+    /// 計測・送信の間隔（秒）。0で無効
+    #[serde(default = "default_process_metrics_interval_secs")]
+    pub interval_secs: u64,
+}
+
+impl Default for ProcessMetricsSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_process_metrics_interval_secs(),
+        }
+    }
+}
+
+fn default_process_metrics_interval_secs() -> u64 {
+    5
 }
 
 impl Config {
@@ -113,6 +559,10 @@ impl Config {
         let config: Config = toml::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {}", path.as_ref().display()))?;
 
+        config
+            .validate()
+            .with_context(|| format!("Invalid config file: {}", path.as_ref().display()))?;
+
         Ok(config)
     }
 
@@ -184,31 +634,262 @@ impl Config {
         Ok(None)
     }
 
-    /// 環境変数で設定を上書き
-    pub fn apply_env_overrides(&mut self) {
+    /// 環境変数で設定を上書き（適用した設定キーと環境変数名の組を返す）
+    pub fn apply_env_overrides(&mut self) -> Vec<(&'static str, &'static str)> {
+        let mut applied = Vec::new();
+
         // 接続設定
         if let Ok(socket_path) = std::env::var("CLIMONITOR_SOCKET_PATH") {
             self.connection.unix_socket_path = Some(PathBuf::from(socket_path));
+            applied.push(("connection.unix_socket_path", "CLIMONITOR_SOCKET_PATH"));
+        }
+
+        if let Ok(grpc_addr) = std::env::var("CLIMONITOR_GRPC_ADDR") {
+            self.connection
+                .grpc
+                .get_or_insert_with(GrpcSettings::default)
+                .bind_addr = grpc_addr;
+            applied.push(("connection.grpc.bind_addr", "CLIMONITOR_GRPC_ADDR"));
+        }
+
+        if let Ok(tcp_addr) = std::env::var("CLIMONITOR_TCP_ADDR") {
+            self.connection
+                .tcp
+                .get_or_insert_with(TcpSettings::default)
+                .bind_addr = tcp_addr;
+            applied.push(("connection.tcp.bind_addr", "CLIMONITOR_TCP_ADDR"));
         }
 
         // ログ設定
         if let Ok(verbose) = std::env::var("CLIMONITOR_VERBOSE") {
             self.logging.verbose = verbose == "1" || verbose.to_lowercase() == "true";
+            applied.push(("logging.verbose", "CLIMONITOR_VERBOSE"));
         }
 
         if let Ok(log_file) = std::env::var("CLIMONITOR_LOG_FILE") {
             self.logging.log_file = Some(PathBuf::from(log_file));
+            applied.push(("logging.log_file", "CLIMONITOR_LOG_FILE"));
         }
+
+        if let Ok(diagnostic_file) = std::env::var("CLIMONITOR_DIAGNOSTIC_FILE") {
+            self.logging.diagnostic_file = Some(PathBuf::from(diagnostic_file));
+            applied.push(("logging.diagnostic_file", "CLIMONITOR_DIAGNOSTIC_FILE"));
+        }
+
+        if let Ok(tracing) = std::env::var("CLIMONITOR_TRACING") {
+            self.logging.tracing = tracing == "1" || tracing.to_lowercase() == "true";
+            applied.push(("logging.tracing", "CLIMONITOR_TRACING"));
+        }
+
+        if let Ok(format) = std::env::var("CLIMONITOR_LOG_FORMAT") {
+            let format = match format.to_lowercase().as_str() {
+                "json" => Some(LogFormat::Json),
+                "text" => Some(LogFormat::Text),
+                _ => None,
+            };
+            if let Some(format) = format {
+                self.logging.format = format;
+                applied.push(("logging.format", "CLIMONITOR_LOG_FORMAT"));
+            }
+        }
+
+        // 自動応答設定
+        if let Ok(dry_run) = std::env::var("CLIMONITOR_AUTO_APPROVE_DRY_RUN") {
+            self.auto_approve.dry_run = dry_run == "1" || dry_run.to_lowercase() == "true";
+            applied.push(("auto_approve.dry_run", "CLIMONITOR_AUTO_APPROVE_DRY_RUN"));
+        }
+
+        applied
+    }
+
+    /// 設定値を検証（問題をまとめて1つのエラーとして返す）
+    pub fn validate(&self) -> Result<()> {
+        let mut issues = Vec::new();
+
+        // 接続設定
+        if let Some(socket_path) = &self.connection.unix_socket_path {
+            let socket_dir = socket_path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty());
+            if let Some(dir) = socket_dir {
+                match std::fs::metadata(dir) {
+                    Ok(metadata) if !metadata.is_dir() => issues.push(format!(
+                        "connection.unix_socket_path: '{}' is not a directory",
+                        dir.display()
+                    )),
+                    Ok(metadata) if metadata.permissions().readonly() => issues.push(format!(
+                        "connection.unix_socket_path: directory '{}' is read-only",
+                        dir.display()
+                    )),
+                    Ok(_) => {}
+                    Err(e) => issues.push(format!(
+                        "connection.unix_socket_path: directory '{}' is not accessible ({e})",
+                        dir.display()
+                    )),
+                }
+            }
+        }
+
+        let listeners = [
+            (
+                "connection.grpc",
+                self.connection
+                    .grpc
+                    .as_ref()
+                    .map(|grpc| (&grpc.bind_addr, &grpc.allowed_ips)),
+            ),
+            (
+                "connection.tcp",
+                self.connection
+                    .tcp
+                    .as_ref()
+                    .map(|tcp| (&tcp.bind_addr, &tcp.allowed_ips)),
+            ),
+            (
+                "web",
+                self.web
+                    .as_ref()
+                    .map(|web| (&web.bind_addr, &web.allowed_ips)),
+            ),
+        ];
+        for (key, listener) in listeners {
+            let Some((bind_addr, allowed_ips)) = listener else {
+                continue;
+            };
+            if let Err(e) = validate_address(bind_addr) {
+                issues.push(format!("{key}.bind_addr: {e}"));
+            }
+            for (index, pattern) in allowed_ips.iter().enumerate() {
+                if let Err(e) = crate::ip_utils::validate_ip_pattern(pattern) {
+                    issues.push(format!("{key}.allowed_ips[{index}]: {e}"));
+                }
+            }
+        }
+        if self.connection.grpc.is_some() && self.connection.tcp.is_some() {
+            issues.push(
+                "connection: [connection.grpc] and [connection.tcp] cannot be used together"
+                    .to_string(),
+            );
+        }
+
+        let mut upstream_names = std::collections::HashSet::new();
+        for (index, upstream) in self.federation.upstreams.iter().enumerate() {
+            let key = format!("federation.upstreams[{index}]");
+            if upstream.name.is_empty() || upstream.name.contains('/') {
+                issues.push(format!(
+                    "{key}.name: '{}' must be non-empty and must not contain '/'",
+                    upstream.name
+                ));
+            } else if !upstream_names.insert(upstream.name.as_str()) {
+                issues.push(format!(
+                    "{key}.name: '{}' is already used by another upstream",
+                    upstream.name
+                ));
+            }
+            if let Err(e) = validate_address(upstream.addr.trim_start_matches("http://")) {
+                issues.push(format!("{key}.addr: {e}"));
+            }
+        }
+
+        // ログ設定
+        if let Err(e) = self.logging.level.parse::<LogLevel>() {
+            issues.push(format!("logging.level: {e}"));
+        }
+        for (index, name) in self.logging.categories.iter().enumerate() {
+            if let Err(e) = name.parse::<LogCategory>() {
+                issues.push(format!("logging.categories[{index}]: {e}"));
+            }
+        }
+        if self.logging.max_file_size_mb == 0 {
+            issues.push("logging.max_file_size_mb: must be greater than 0".to_string());
+        }
+        if self.logging.max_files == 0 {
+            issues.push("logging.max_files: must be greater than 0".to_string());
+        }
+
+        // 通知・UI設定
+        if let Some(script_path) = &self.notification.script_path {
+            if !expand_home(script_path).is_file() {
+                issues.push(format!(
+                    "notification.script_path: '{}' is not a file",
+                    script_path.display()
+                ));
+            }
+        }
+        if self.ui.refresh_interval_secs == 0 {
+            issues.push("ui.refresh_interval_secs: must be greater than 0".to_string());
+        }
+
+        // 自動応答設定
+        for (index, rule) in self.auto_approve.rules.iter().enumerate() {
+            if rule.pattern.trim_matches('*').trim().is_empty() {
+                issues.push(format!(
+                    "auto_approve.rules[{index}].pattern: pattern '{}' would never match",
+                    rule.pattern
+                ));
+            }
+        }
+
+        // 状態遷移フィルタ設定
+        let filter = &self.state_filter;
+        let state_names = filter
+            .min_dwell_ms
+            .keys()
+            .map(|name| ("state_filter.min_dwell_ms".to_string(), name))
+            .chain(
+                filter
+                    .confirm_ms
+                    .keys()
+                    .map(|name| ("state_filter.confirm_ms".to_string(), name)),
+            )
+            .chain(
+                filter
+                    .allowed_transitions
+                    .iter()
+                    .flat_map(|(from, targets)| {
+                        std::iter::once(("state_filter.allowed_transitions".to_string(), from))
+                            .chain(targets.iter().enumerate().map(move |(index, to)| {
+                                (
+                                    format!("state_filter.allowed_transitions.{from}[{index}]"),
+                                    to,
+                                )
+                            }))
+                    }),
+            );
+        for (key, name) in state_names {
+            if let Err(e) = name.parse::<SessionStatus>() {
+                issues.push(format!("{key}: {e}"));
+            }
+        }
+        if self.state_report.keepalive_secs == 0 {
+            issues.push("state_report.keepalive_secs: must be greater than 0".to_string());
+        }
+
+        if issues.is_empty() {
+            return Ok(());
+        }
+
+        let details = issues
+            .iter()
+            .map(|issue| format!("  - {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        anyhow::bail!("Invalid configuration:\n{details}")
     }
 
     /// 設定からConnectionConfigを生成
     pub fn to_connection_config(&self) -> ConnectionConfig {
-        // gRPC設定がある場合はgRPCを優先
+        // gRPC → TCP → Unix socket の順に優先
         if let Some(ref grpc_config) = self.connection.grpc {
             ConnectionConfig::Grpc {
                 bind_addr: grpc_config.bind_addr.clone(),
                 allowed_ips: grpc_config.allowed_ips.clone(),
             }
+        } else if let Some(ref tcp_config) = self.connection.tcp {
+            ConnectionConfig::Tcp {
+                bind_addr: tcp_config.bind_addr.clone(),
+                allowed_ips: tcp_config.allowed_ips.clone(),
+            }
         } else {
             #[cfg(unix)]
             {
@@ -238,12 +919,164 @@ impl Config {
         config.logging.verbose = false;
         config.logging.log_file = Some(PathBuf::from("~/.climonitor/climonitor.log"));
 
-        // 通知設定とUI設定は現在未実装
-
         config
     }
 }
 
+/// `host:port` 形式のアドレスを検証
+fn validate_address(addr: &str) -> std::result::Result<(), String> {
+    if addr.parse::<std::net::SocketAddr>().is_ok() {
+        return Ok(());
+    }
+
+    let Some((host, port)) = addr.rsplit_once(':') else {
+        return Err(format!("'{addr}' must be in host:port form"));
+    };
+    if port.parse::<u16>().is_err() {
+        return Err(format!("'{addr}' has an invalid port '{port}'"));
+    }
+    let valid_host = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    if !valid_host {
+        return Err(format!("'{addr}' has an invalid host '{host}'"));
+    }
+    Ok(())
+}
+
+/// 設定値の由来
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueOrigin {
+    Default,
+    File(PathBuf),
+    Env(&'static str),
+    Cli(&'static str),
+}
+
+impl fmt::Display for ValueOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueOrigin::Default => write!(f, "default"),
+            ValueOrigin::File(path) => write!(f, "file {}", path.display()),
+            ValueOrigin::Env(var) => write!(f, "env {var}"),
+            ValueOrigin::Cli(flag) => write!(f, "cli {flag}"),
+        }
+    }
+}
+
+/// 設定ファイル・環境変数・CLI引数をマージした設定と、各値の由来
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub config: Config,
+    /// 読み込んだ設定ファイル（なければデフォルト設定）
+    pub path: Option<PathBuf>,
+    origins: HashMap<String, ValueOrigin>,
+}
+
+impl LoadedConfig {
+    /// 設定ファイル（明示指定または自動検出）を読み込み、環境変数で上書き
+    pub fn load(explicit_path: Option<&Path>) -> Result<Self> {
+        let path = match explicit_path {
+            Some(path) => Some(path.to_path_buf()),
+            None => Config::config_path_candidates()
+                .into_iter()
+                .find(|path| path.exists()),
+        };
+
+        let mut origins = HashMap::new();
+        let mut config = match &path {
+            Some(path) => {
+                let config = Config::from_file(path)?;
+                let content = std::fs::read_to_string(path)?;
+                let raw: toml::Value = toml::from_str(&content)?;
+                for (key, _) in flatten_toml(&raw) {
+                    origins.insert(key, ValueOrigin::File(path.clone()));
+                }
+                config
+            }
+            None => Config::default(),
+        };
+
+        for (key, var) in config.apply_env_overrides() {
+            origins.insert(key.to_string(), ValueOrigin::Env(var));
+        }
+
+        Ok(Self {
+            config,
+            path,
+            origins,
+        })
+    }
+
+    /// CLI引数による上書きを記録（テーブルを指定した場合は配下の値すべてが対象）
+    pub fn record_cli(&mut self, key: &str, flag: &'static str) {
+        self.origins
+            .retain(|existing, _| !existing.starts_with(&format!("{key}.")));
+        self.origins.insert(key.to_string(), ValueOrigin::Cli(flag));
+    }
+
+    /// 設定キーの由来を取得（親テーブルの由来を継承）
+    pub fn origin(&self, key: &str) -> ValueOrigin {
+        let mut current = key;
+        loop {
+            if let Some(origin) = self.origins.get(current) {
+                return origin.clone();
+            }
+            match current.rsplit_once('.') {
+                Some((parent, _)) => current = parent,
+                None => return ValueOrigin::Default,
+            }
+        }
+    }
+
+    /// 有効な設定を `key = value  # 由来` 形式で出力
+    pub fn render(&self) -> Result<String> {
+        let value = toml::Value::try_from(&self.config).context("Failed to serialize config")?;
+        let entries: Vec<(String, String)> = flatten_toml(&value)
+            .into_iter()
+            .map(|(key, value)| (format!("{key} = {value}"), self.origin(&key).to_string()))
+            .collect();
+        let width = entries
+            .iter()
+            .map(|(line, _)| line.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut output = match &self.path {
+            Some(path) => format!("# config file: {}\n", path.display()),
+            None => "# config file: (none, using defaults)\n".to_string(),
+        };
+        for (line, origin) in entries {
+            output.push_str(&format!("{line:<width$}  # {origin}\n"));
+        }
+        Ok(output)
+    }
+}
+
+/// TOMLのテーブルを `a.b.c` 形式のキーに展開（配列は1つの値として扱う）
+fn flatten_toml(value: &toml::Value) -> Vec<(String, toml::Value)> {
+    fn walk(prefix: &str, value: &toml::Value, out: &mut Vec<(String, toml::Value)>) {
+        match value {
+            toml::Value::Table(table) => {
+                for (key, child) in table {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    walk(&path, child, out);
+                }
+            }
+            _ => out.push((prefix.to_string(), value.clone())),
+        }
+    }
+
+    let mut entries = Vec::new();
+    walk("", value, &mut entries);
+    entries
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,8 +1086,9 @@ mod tests {
         let config = Config::default();
         assert!(config.connection.unix_socket_path.is_none());
         assert!(!config.logging.verbose);
-        assert!(!config.notification._placeholder);
-        assert!(!config.ui._placeholder);
+        assert!(config.notification.enabled);
+        assert!(config.notification.script_path.is_none());
+        assert_eq!(config.ui.refresh_interval_secs, 5);
     }
 
     #[test]
@@ -278,7 +1112,8 @@ unix_socket_path = "/tmp/test.sock"
 [logging]
 verbose = true
 
-# [notification] と [ui] セクションは未実装
+[ui]
+refresh_interval_secs = 2
 "#;
 
         let config: Config = toml::from_str(toml_content).unwrap();
@@ -287,7 +1122,268 @@ verbose = true
             Some(PathBuf::from("/tmp/test.sock"))
         );
         assert!(config.logging.verbose);
-        // 通知とUI設定は未実装のためテストなし
+        assert_eq!(config.ui.refresh_interval_secs, 2);
+        assert!(config.notification.enabled);
+    }
+
+    #[test]
+    fn test_auto_approve_deserialization() {
+        let toml_content = r#"
+[auto_approve]
+enabled = true
+dry_run = true
+
+[[auto_approve.rules]]
+project = "climonitor"
+pattern = "Read("
+
+[[auto_approve.rules]]
+pattern = "Bash*rm "
+action = "deny"
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert!(config.auto_approve.enabled);
+        assert!(config.auto_approve.dry_run);
+        assert_eq!(config.auto_approve.rules.len(), 2);
+
+        let approve_rule = &config.auto_approve.rules[0];
+        assert_eq!(approve_rule.project.as_deref(), Some("climonitor"));
+        assert_eq!(approve_rule.action, AutoApproveAction::Approve);
+        assert_eq!(approve_rule.keys, "\r");
+
+        let deny_rule = &config.auto_approve.rules[1];
+        assert!(deny_rule.project.is_none());
+        assert_eq!(deny_rule.action, AutoApproveAction::Deny);
+    }
+
+    #[test]
+    fn test_state_filter_settings() {
+        // 既定ではIdleの確定待ちとConnected→Idleの禁止のみ
+        let config = Config::default();
+        assert_eq!(config.state_filter.confirm_ms.get("idle"), Some(&1500));
+        assert!(config.state_filter.min_dwell_ms.is_empty());
+        assert!(!config.state_filter.allowed_transitions["connected"].contains(&"idle".to_string()));
+
+        let toml_content = r#"
+[state_filter]
+min_dwell_ms = { busy = 3000 }
+confirm_ms = { idle = 2000, waiting_input = 500 }
+
+[state_filter.allowed_transitions]
+connected = ["busy"]
+error = ["busy", "idel"]
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.state_filter.min_dwell_ms.get("busy"), Some(&3000));
+        assert_eq!(config.state_filter.confirm_ms.len(), 2);
+
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("state_filter.allowed_transitions.error[1]: Unknown state: idel"));
+        assert!(!message.contains("error[0]"));
+
+        let config: Config =
+            toml::from_str("[state_report]\nmin_interval_ms = 50\nkeepalive_secs = 0\n").unwrap();
+        assert_eq!(config.state_report.min_interval_ms, 50);
+        assert_eq!(config.state_report.stall_after_secs, 300);
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("state_report.keepalive_secs"));
+
+        // 空のテーブルを指定すると既定値を打ち消せる
+        let config: Config = toml::from_str("[state_filter]\nconfirm_ms = {}\n").unwrap();
+        assert!(config.state_filter.confirm_ms.is_empty());
+        assert_eq!(config.state_filter.allowed_transitions.len(), 1);
+    }
+
+    #[test]
+    fn test_process_metrics_settings() {
+        let config = Config::default();
+        assert_eq!(config.process_metrics.interval_secs, 5);
+
+        let config: Config = toml::from_str("[process_metrics]\ninterval_secs = 0\n").unwrap();
+        assert_eq!(config.process_metrics.interval_secs, 0);
+        assert!(config.validate().is_ok());
+        assert!(toml::from_str::<Config>("[process_metrics]\ninterval = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_logging_settings_deserialization() {
+        let toml_content = r#"
+[logging]
+level = "debug"
+categories = ["grpc", "session"]
+format = "json"
+diagnostic_file = "/tmp/climonitor-diag.log"
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(
+            config.logging.diagnostic_file,
+            Some(PathBuf::from("/tmp/climonitor-diag.log"))
+        );
+        assert_eq!(config.logging.max_file_size_mb, 10);
+        assert_eq!(config.logging.max_files, 3);
+
+        // 未知のカテゴリは初期化時にエラー
+        let invalid = LoggingSettings {
+            categories: vec!["nonexistent".to_string()],
+            ..LoggingSettings::default()
+        };
+        assert!(invalid.init_logging().is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_rejected() {
+        let toml_content = r#"
+[connection]
+type = "grpc"
+"#;
+        let err = toml::from_str::<Config>(toml_content).unwrap_err();
+        assert!(err.to_string().contains("unknown field `type`"));
+
+        let toml_content = r#"
+[logging]
+verbos = true
+"#;
+        assert!(toml::from_str::<Config>(toml_content).is_err());
+    }
+
+    #[test]
+    fn test_validate_reports_all_issues() {
+        let toml_content = r#"
+[connection]
+unix_socket_path = "/nonexistent-climonitor-dir/climonitor.sock"
+
+[connection.grpc]
+bind_addr = "127.0.0.1"
+allowed_ips = ["127.0.0.1", "10.0.0.0/40"]
+
+[logging]
+level = "verbos"
+categories = ["grpc", "nope"]
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        let message = config.validate().unwrap_err().to_string();
+
+        assert!(message.contains("connection.unix_socket_path"));
+        assert!(message.contains("connection.grpc.bind_addr: '127.0.0.1'"));
+        assert!(message.contains("connection.grpc.allowed_ips[1]"));
+        assert!(message.contains("logging.level: Unknown log level: verbos"));
+        assert!(message.contains("logging.categories[1]"));
+        assert!(!message.contains("allowed_ips[0]"));
+
+        // gRPCとTCPは同時に指定できない
+        let mut config = Config::default();
+        config.connection.grpc = Some(GrpcSettings::default());
+        config.connection.tcp = Some(TcpSettings {
+            bind_addr: "localhost".to_string(),
+            allowed_ips: Vec::new(),
+        });
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("connection.tcp.bind_addr: 'localhost'"));
+        assert!(message.contains("cannot be used together"));
+
+        let config = Config {
+            web: Some(WebSettings {
+                bind_addr: "0.0.0.0:8080".to_string(),
+                allowed_ips: vec!["192.168.1.0/33".to_string()],
+                ..WebSettings::default()
+            }),
+            ..Config::default()
+        };
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("web.allowed_ips[0]"));
+        assert!(!message.contains("web.bind_addr"));
+
+        // 上流monitorの名前は名前空間になるため重複や'/'は不可
+        let upstreams = [
+            "devbox=10.0.0.5:50051",
+            "devbox=10.0.0.6:50051",
+            "a/b=http://host",
+        ];
+        let config = Config {
+            federation: FederationSettings {
+                upstreams: upstreams.iter().map(|s| s.parse().unwrap()).collect(),
+            },
+            ..Config::default()
+        };
+        let message = config.validate().unwrap_err().to_string();
+        assert!(!message.contains("federation.upstreams[0]"));
+        assert!(message.contains("federation.upstreams[1].name: 'devbox' is already used"));
+        assert!(message.contains("federation.upstreams[2].name"));
+        assert!(message.contains("federation.upstreams[2].addr"));
+        assert!("devbox".parse::<UpstreamSettings>().is_err());
+
+        assert!(Config::default().validate().is_ok());
+        assert!(Config::sample().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_address() {
+        assert!(validate_address("127.0.0.1:50051").is_ok());
+        assert!(validate_address("[::1]:50051").is_ok());
+        assert!(validate_address("monitor-host.local:50051").is_ok());
+
+        assert!(validate_address("127.0.0.1").is_err());
+        assert!(validate_address("127.0.0.1:99999").is_err());
+        assert!(validate_address(":50051").is_err());
+        assert!(validate_address("bad host:50051").is_err());
+    }
+
+    #[test]
+    fn test_example_configs_are_valid() {
+        let examples = [
+            include_str!("../../examples/config-grpc.toml"),
+            include_str!("../../examples/config-remote.toml"),
+            include_str!("../../examples/config-secure.toml"),
+            include_str!("../../examples/config-unix.toml"),
+        ];
+
+        for content in examples {
+            let config: Config = toml::from_str(content).unwrap();
+            config.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_loaded_config_origins() {
+        let config_path = std::env::temp_dir().join(format!(
+            "test_climonitor_origins_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &config_path,
+            "[logging]\nlevel = \"debug\"\n\n[connection.grpc]\nbind_addr = \"127.0.0.1:6000\"\n",
+        )
+        .unwrap();
+
+        let mut loaded = LoadedConfig::load(Some(&config_path)).unwrap();
+        assert_eq!(loaded.config.logging.level, "debug");
+        assert_eq!(
+            loaded.origin("logging.level"),
+            ValueOrigin::File(config_path.clone())
+        );
+        assert_eq!(loaded.origin("logging.max_files"), ValueOrigin::Default);
+        assert_eq!(
+            loaded.origin("connection.grpc.allowed_ips"),
+            ValueOrigin::Default
+        );
+
+        loaded.record_cli("connection.grpc", "--grpc");
+        assert_eq!(
+            loaded.origin("connection.grpc.bind_addr"),
+            ValueOrigin::Cli("--grpc")
+        );
+
+        let rendered = loaded.render().unwrap();
+        assert!(rendered.contains(&format!("# config file: {}", config_path.display())));
+        assert!(rendered
+            .lines()
+            .any(|line| line.starts_with("logging.level = \"debug\"") && line.contains("# file")));
+        assert!(!rendered.contains("_placeholder"));
+
+        std::fs::remove_file(&config_path).ok();
     }
 
     #[test]
@@ -319,18 +1415,35 @@ verbose = true
         // 環境変数を設定
         std::env::set_var("CLIMONITOR_SOCKET_PATH", "/tmp/test.sock");
         std::env::set_var("CLIMONITOR_VERBOSE", "true");
+        std::env::set_var("CLIMONITOR_GRPC_ADDR", "192.168.1.100:50051");
+        std::env::set_var("CLIMONITOR_TCP_ADDR", "0.0.0.0:50052");
 
-        config.apply_env_overrides();
+        let applied = config.apply_env_overrides();
 
         assert_eq!(
             config.connection.unix_socket_path,
             Some(PathBuf::from("/tmp/test.sock"))
         );
         assert!(config.logging.verbose);
+        assert_eq!(
+            config
+                .connection
+                .grpc
+                .as_ref()
+                .map(|g| g.bind_addr.as_str()),
+            Some("192.168.1.100:50051")
+        );
+        assert!(applied.contains(&("connection.grpc.bind_addr", "CLIMONITOR_GRPC_ADDR")));
+        assert_eq!(
+            config.connection.tcp.as_ref().map(|t| t.bind_addr.as_str()),
+            Some("0.0.0.0:50052")
+        );
 
         // 環境変数をクリア
         std::env::remove_var("CLIMONITOR_SOCKET_PATH");
         std::env::remove_var("CLIMONITOR_VERBOSE");
+        std::env::remove_var("CLIMONITOR_GRPC_ADDR");
+        std::env::remove_var("CLIMONITOR_TCP_ADDR");
     }
 
     #[cfg(unix)]
@@ -345,9 +1458,7 @@ verbose = true
             ConnectionConfig::Unix { socket_path } => {
                 assert_eq!(socket_path, PathBuf::from("/tmp/test.sock"));
             }
-            ConnectionConfig::Grpc { .. } => {
-                panic!("Expected Unix config, got Grpc");
-            }
+            other => panic!("Expected Unix config, got {other:?}"),
         }
 
         // デフォルトパスをテスト
@@ -356,9 +1467,23 @@ verbose = true
             ConnectionConfig::Unix { socket_path } => {
                 assert_eq!(socket_path, std::env::temp_dir().join("climonitor.sock"));
             }
-            ConnectionConfig::Grpc { .. } => {
-                panic!("Expected Unix config, got Grpc");
+            other => panic!("Expected Unix config, got {other:?}"),
+        }
+
+        // TCP設定はUnix socketより優先
+        config.connection.tcp = Some(TcpSettings {
+            bind_addr: "0.0.0.0:6000".to_string(),
+            allowed_ips: vec!["10.0.0.0/8".to_string()],
+        });
+        match config.to_connection_config() {
+            ConnectionConfig::Tcp {
+                bind_addr,
+                allowed_ips,
+            } => {
+                assert_eq!(bind_addr, "0.0.0.0:6000");
+                assert_eq!(allowed_ips, vec!["10.0.0.0/8"]);
             }
+            other => panic!("Expected Tcp config, got {other:?}"),
         }
     }
 }
@@ -17,6 +17,29 @@ pub fn is_ip_allowed_by_list(peer_ip: &IpAddr, allowed_ips: &[String]) -> bool {
     false
 }
 
+/// IP許可リストのエントリを検証（`any`, `localhost`, 単一IP, CIDR記法）
+pub fn validate_ip_pattern(pattern: &str) -> Result<(), String> {
+    if matches!(pattern, "any" | "localhost") || IpAddr::from_str(pattern).is_ok() {
+        return Ok(());
+    }
+
+    let Some((network_str, prefix_len_str)) = pattern.split_once('/') else {
+        return Err(format!(
+            "'{pattern}' is not an IP address, CIDR block, 'localhost' or 'any'"
+        ));
+    };
+
+    let network_ip = IpAddr::from_str(network_str)
+        .map_err(|_| format!("'{pattern}' has an invalid network address '{network_str}'"))?;
+    let max_prefix_len = if network_ip.is_ipv4() { 32 } else { 128 };
+    match prefix_len_str.parse::<u8>() {
+        Ok(prefix_len) if prefix_len <= max_prefix_len => Ok(()),
+        _ => Err(format!(
+            "'{pattern}' has an invalid prefix length '{prefix_len_str}' (expected 0-{max_prefix_len})"
+        )),
+    }
+}
+
 /// IP許可チェック関数
 fn is_ip_match(peer_ip: &IpAddr, pattern: &str) -> bool {
     // 特別なパターンを処理
@@ -120,6 +143,19 @@ mod tests {
         assert!(is_ip_match(&ip, "any"));
     }
 
+    #[test]
+    fn test_validate_ip_pattern() {
+        assert!(validate_ip_pattern("any").is_ok());
+        assert!(validate_ip_pattern("localhost").is_ok());
+        assert!(validate_ip_pattern("10.0.0.1").is_ok());
+        assert!(validate_ip_pattern("192.168.1.0/24").is_ok());
+        assert!(validate_ip_pattern("fe80::/10").is_ok());
+
+        assert!(validate_ip_pattern("192.168.1.0/33").is_err());
+        assert!(validate_ip_pattern("192.168.1/24").is_err());
+        assert!(validate_ip_pattern("example.com").is_err());
+    }
+
     #[test]
     fn test_allowed_by_list() {
         let ip = IpAddr::from_str("192.168.1.100").unwrap();
@@ -1,6 +1,12 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::sync::atomic::{AtomicU8, Ordering};
-use std::sync::OnceLock;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock, RwLock};
+
+pub use tracing;
 
 /// ログレベル
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -24,6 +30,18 @@ impl fmt::Display for LogLevel {
     }
 }
 
+impl From<LogLevel> for tracing::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => tracing::Level::ERROR,
+            LogLevel::Warn => tracing::Level::WARN,
+            LogLevel::Info => tracing::Level::INFO,
+            LogLevel::Debug => tracing::Level::DEBUG,
+            LogLevel::Trace => tracing::Level::TRACE,
+        }
+    }
+}
+
 impl From<&str> for LogLevel {
     fn from(s: &str) -> Self {
         match s.to_lowercase().as_str() {
@@ -37,6 +55,35 @@ impl From<&str> for LogLevel {
     }
 }
 
+impl std::str::FromStr for LogLevel {
+    type Err = String;
+
+    /// 厳密な変換（未知のレベルはエラー）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "error" => Ok(LogLevel::Error),
+            "warn" => Ok(LogLevel::Warn),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            "trace" => Ok(LogLevel::Trace),
+            _ => Err(format!(
+                "Unknown log level: {s} (expected error, warn, info, debug or trace)"
+            )),
+        }
+    }
+}
+
+/// ログ出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 人が読むためのテキスト形式
+    #[default]
+    Text,
+    /// 1行1レコードのJSON形式（JSON Lines）
+    Json,
+}
+
 /// ログカテゴリ
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogCategory {
@@ -47,6 +94,7 @@ pub enum LogCategory {
 
     // Transport specific
     UnixSocket,
+    Tcp,
     Grpc,
 
     // Screen detection
@@ -61,6 +109,7 @@ pub enum LogCategory {
     // UI
     Display,
     Notification,
+    Web,
 }
 
 impl fmt::Display for LogCategory {
@@ -70,6 +119,7 @@ impl fmt::Display for LogCategory {
             LogCategory::Transport => write!(f, "TRANSPORT"),
             LogCategory::Session => write!(f, "SESSION"),
             LogCategory::UnixSocket => write!(f, "UNIX"),
+            LogCategory::Tcp => write!(f, "TCP"),
             LogCategory::Grpc => write!(f, "GRPC"),
             LogCategory::Screen => write!(f, "SCREEN"),
             LogCategory::Claude => write!(f, "CLAUDE"),
@@ -78,8 +128,61 @@ impl fmt::Display for LogCategory {
             LogCategory::Connection => write!(f, "CONNECTION"),
             LogCategory::Display => write!(f, "DISPLAY"),
             LogCategory::Notification => write!(f, "NOTIFICATION"),
+            LogCategory::Web => write!(f, "WEB"),
+        }
+    }
+}
+
+impl LogCategory {
+    /// tracingのターゲット名（`climonitor::<category>`）
+    pub const fn target(&self) -> &'static str {
+        match self {
+            LogCategory::System => "climonitor::system",
+            LogCategory::Transport => "climonitor::transport",
+            LogCategory::Session => "climonitor::session",
+            LogCategory::UnixSocket => "climonitor::unix",
+            LogCategory::Tcp => "climonitor::tcp",
+            LogCategory::Grpc => "climonitor::grpc",
+            LogCategory::Screen => "climonitor::screen",
+            LogCategory::Claude => "climonitor::claude",
+            LogCategory::Gemini => "climonitor::gemini",
+            LogCategory::Protocol => "climonitor::protocol",
+            LogCategory::Connection => "climonitor::connection",
+            LogCategory::Display => "climonitor::display",
+            LogCategory::Notification => "climonitor::notification",
+            LogCategory::Web => "climonitor::web",
         }
     }
+
+    /// 全カテゴリ
+    pub const ALL: [LogCategory; 14] = [
+        LogCategory::System,
+        LogCategory::Transport,
+        LogCategory::Session,
+        LogCategory::UnixSocket,
+        LogCategory::Tcp,
+        LogCategory::Grpc,
+        LogCategory::Screen,
+        LogCategory::Claude,
+        LogCategory::Gemini,
+        LogCategory::Protocol,
+        LogCategory::Connection,
+        LogCategory::Display,
+        LogCategory::Notification,
+        LogCategory::Web,
+    ];
+}
+
+impl std::str::FromStr for LogCategory {
+    type Err = String;
+
+    /// 表示名（大文字小文字は区別しない）からカテゴリを取得
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LogCategory::ALL
+            .into_iter()
+            .find(|category| category.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("Unknown log category: {s}"))
+    }
 }
 
 /// グローバルログレベル
@@ -89,6 +192,115 @@ static GLOBAL_LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
 type LogOutputFn = Box<dyn Fn(&str) + Send + Sync>;
 static LOG_OUTPUT: OnceLock<LogOutputFn> = OnceLock::new();
 
+/// ログ出力形式
+static LOG_FORMAT: AtomicU8 = AtomicU8::new(LogFormat::Text as u8);
+
+/// 有効なカテゴリ（None = 全て有効）
+static LOG_CATEGORIES: RwLock<Option<Vec<LogCategory>>> = RwLock::new(None);
+
+/// ログに付与するlauncher ID
+static LOG_LAUNCHER_ID: RwLock<Option<String>> = RwLock::new(None);
+
+/// ログファイル出力先（設定時はstderrに出力しない）
+static LOG_FILE: Mutex<Option<RotatingFileWriter>> = Mutex::new(None);
+
+/// tracingのsubscriberがファイルに出力しているか
+static TRACING_TO_FILE: AtomicBool = AtomicBool::new(false);
+
+/// サイズ上限でローテーションするログファイル
+///
+/// `path` が上限を超えると `path.1`, `path.2`, ... に順に退避し、`max_files` を超えた分は削除する。
+pub struct RotatingFileWriter {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFileWriter {
+    pub fn open(
+        path: impl Into<PathBuf>,
+        max_bytes: u64,
+        max_files: usize,
+    ) -> std::io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            file,
+            size,
+        })
+    }
+
+    /// 1行書き込み（必要に応じてローテーション）
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        self.write_all(format!("{line}\n").as_bytes())
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+
+        if self.max_files == 0 {
+            // 世代を残さない場合は切り詰めのみ
+            self.file = File::create(&self.path)?;
+        } else {
+            let _ = std::fs::remove_file(self.rotated_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    std::fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+            self.file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+        }
+
+        self.size = 0;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Write for RotatingFileWriter {
+    /// 書き込み単位でローテーションを判定（1回の書き込みは分割しない）
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = buf.len() as u64;
+        if self.max_bytes > 0 && self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+
+        self.file.write_all(buf)?;
+        self.size += len;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
 /// ログレベルを設定
 pub fn set_log_level(level: LogLevel) {
     GLOBAL_LOG_LEVEL.store(level as u8, Ordering::Relaxed);
@@ -114,81 +326,290 @@ where
     let _ = LOG_OUTPUT.set(Box::new(output));
 }
 
+/// ログ出力形式を設定
+pub fn set_log_format(format: LogFormat) {
+    LOG_FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+/// 現在のログ出力形式を取得
+pub fn get_log_format() -> LogFormat {
+    match LOG_FORMAT.load(Ordering::Relaxed) {
+        1 => LogFormat::Json,
+        _ => LogFormat::Text,
+    }
+}
+
+/// 有効にするカテゴリを設定（空の場合は全て有効）
+pub fn set_log_categories(categories: Vec<LogCategory>) {
+    if let Ok(mut current) = LOG_CATEGORIES.write() {
+        *current = if categories.is_empty() {
+            None
+        } else {
+            Some(categories)
+        };
+    }
+}
+
+/// カテゴリが有効かどうか
+pub fn is_category_enabled(category: LogCategory) -> bool {
+    LOG_CATEGORIES
+        .read()
+        .map(|current| match current.as_ref() {
+            Some(categories) => categories.contains(&category),
+            None => true,
+        })
+        .unwrap_or(true)
+}
+
+/// ログに付与するlauncher IDを設定
+pub fn set_log_launcher_id(launcher_id: impl Into<String>) {
+    if let Ok(mut current) = LOG_LAUNCHER_ID.write() {
+        *current = Some(launcher_id.into());
+    }
+}
+
+/// ログファイル出力を設定（None でstderr出力に戻す）
+pub fn set_log_file(writer: Option<RotatingFileWriter>) {
+    if let Ok(mut current) = LOG_FILE.lock() {
+        *current = writer;
+    }
+}
+
+/// ログがstderr以外（ファイル・出力関数）に出力されるか
+///
+/// launcherではstderrがラップしたツールの画面と共有されるため、`--verbose` 以外で
+/// 出力するログはこれが true の場合に限る。
+pub fn has_diagnostic_sink() -> bool {
+    if LOG_OUTPUT.get().is_some() {
+        return true;
+    }
+    if is_tracing_enabled() {
+        return TRACING_TO_FILE.load(Ordering::Relaxed);
+    }
+    LOG_FILE.lock().is_ok_and(|file| file.is_some())
+}
+
+/// 1件のログを指定形式の1行に整形
+pub fn format_log_record(
+    format: LogFormat,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    level: LogLevel,
+    category: LogCategory,
+    launcher_id: Option<&str>,
+    message: &str,
+) -> String {
+    match format {
+        LogFormat::Text => {
+            let timestamp = timestamp.format("%H:%M:%S%.3f");
+            match launcher_id {
+                Some(id) => format!("[{timestamp}] [{level}] [{category}] [{id}] {message}"),
+                None => format!("[{timestamp}] [{level}] [{category}] {message}"),
+            }
+        }
+        LogFormat::Json => serde_json::json!({
+            "timestamp": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            "level": level.to_string(),
+            "category": category.to_string(),
+            "launcher_id": launcher_id,
+            "message": message,
+        })
+        .to_string(),
+    }
+}
+
+/// tracingのsubscriberが有効かどうか（有効な場合はログマクロをtracingイベントとして出力）
+pub fn is_tracing_enabled() -> bool {
+    tracing::dispatcher::get_default(|dispatch| !dispatch.is::<tracing::subscriber::NoSubscriber>())
+}
+
+/// tracingのfmt subscriberをグローバルに設定
+///
+/// `RUST_LOG` が設定されている場合はそちらを優先する。
+pub fn init_tracing_subscriber(
+    format: LogFormat,
+    level: LogLevel,
+    categories: &[LogCategory],
+    file: Option<RotatingFileWriter>,
+) -> anyhow::Result<()> {
+    use tracing_subscriber::fmt::writer::BoxMakeWriter;
+    use tracing_subscriber::EnvFilter;
+
+    let level_name = level.to_string().to_lowercase();
+    let filter = EnvFilter::try_from_default_env().or_else(|_| {
+        let directives = if categories.is_empty() {
+            format!("climonitor={level_name}")
+        } else {
+            categories
+                .iter()
+                .map(|category| format!("{}={level_name}", category.target()))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        EnvFilter::try_new(directives)
+    })?;
+
+    let ansi = file.is_none();
+    let to_file = file.is_some();
+    let writer = match file {
+        Some(file) => BoxMakeWriter::new(Mutex::new(file)),
+        None => BoxMakeWriter::new(std::io::stderr),
+    };
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(ansi);
+
+    let result = match format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Json => builder.json().with_current_span(true).try_init(),
+    };
+    result.map_err(|e| anyhow::anyhow!("Failed to initialize tracing subscriber: {e}"))?;
+    TRACING_TO_FILE.store(to_file, Ordering::Relaxed);
+    Ok(())
+}
+
+/// launcher接続単位のspan（launcher側）
+pub fn launcher_span(launcher_id: &str, session_id: &str) -> tracing::Span {
+    tracing::info_span!(
+        target: "climonitor::connection",
+        "launcher",
+        launcher_id = %launcher_id,
+        session_id = %session_id
+    )
+}
+
+/// 受信した接続単位のspan（monitor側、IDは最初のメッセージ受信時に記録）
+pub fn connection_span(transport: &'static str, peer: &str) -> tracing::Span {
+    tracing::info_span!(
+        target: "climonitor::connection",
+        "connection",
+        transport,
+        peer = %peer,
+        launcher_id = tracing::field::Empty,
+        session_id = tracing::field::Empty
+    )
+}
+
+/// gRPCストリーム単位のspan（monitor側、IDは最初のメッセージ受信時に記録）
+pub fn grpc_stream_span(peer: &str) -> tracing::Span {
+    tracing::info_span!(
+        target: "climonitor::grpc",
+        "grpc_stream",
+        peer = %peer,
+        launcher_id = tracing::field::Empty,
+        session_id = tracing::field::Empty
+    )
+}
+
+/// メッセージに含まれるlauncher_id/session_idをspanに記録
+pub fn record_message_ids(span: &tracing::Span, message: &crate::LauncherToMonitor) {
+    span.record("launcher_id", message.launcher_id());
+    if let Some(session_id) = message.session_id() {
+        span.record("session_id", session_id);
+    }
+}
+
 /// ログメッセージの出力
 pub fn log_message(level: LogLevel, category: LogCategory, message: &str) {
     let current_level = get_log_level();
 
     // レベルチェック
-    if level > current_level {
+    if level > current_level || !is_category_enabled(category) {
         return;
     }
 
-    // タイムスタンプとフォーマット
-    let timestamp = chrono::Utc::now().format("%H:%M:%S%.3f");
-    let formatted = format!("[{timestamp}] [{level}] [{category}] {message}");
+    let launcher_id = LOG_LAUNCHER_ID
+        .read()
+        .ok()
+        .and_then(|current| current.clone());
+    let formatted = format_log_record(
+        get_log_format(),
+        chrono::Utc::now(),
+        level,
+        category,
+        launcher_id.as_deref(),
+        message,
+    );
 
     // 出力
     if let Some(output) = LOG_OUTPUT.get() {
         output(&formatted);
-    } else {
-        // デフォルトはeprintln!
-        eprintln!("{formatted}");
+        return;
+    }
+
+    // ファイル出力が設定されている場合はPTY出力と混ざらないようstderrには出さない
+    if let Ok(mut file) = LOG_FILE.lock() {
+        if let Some(writer) = file.as_mut() {
+            if writer.write_line(&formatted).is_ok() {
+                return;
+            }
+        }
     }
+
+    // デフォルトはeprintln!
+    eprintln!("{formatted}");
+}
+
+/// ログマクロの共通実装
+///
+/// tracingのsubscriberが有効な場合は `LogCategory::target()` をターゲットとしたイベントを出力し、
+/// それ以外は組み込みの出力先（stderr/ファイル）に出力する。
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_event {
+    ($level:ident, $tracing_level:ident, $category:expr, $($arg:tt)*) => {
+        if $crate::logging::is_tracing_enabled() {
+            $crate::logging::tracing::event!(
+                target: $category.target(),
+                $crate::logging::tracing::Level::$tracing_level,
+                category = %$category,
+                "{}",
+                format_args!($($arg)*)
+            );
+        } else {
+            $crate::logging::log_message(
+                $crate::logging::LogLevel::$level,
+                $category,
+                &format!($($arg)*)
+            );
+        }
+    };
 }
 
 /// ログマクロ
 #[macro_export]
 macro_rules! log_error {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_message(
-            $crate::logging::LogLevel::Error,
-            $category,
-            &format!($($arg)*)
-        );
+        $crate::__log_event!(Error, ERROR, $category, $($arg)*)
     };
 }
 
 #[macro_export]
 macro_rules! log_warn {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_message(
-            $crate::logging::LogLevel::Warn,
-            $category,
-            &format!($($arg)*)
-        );
+        $crate::__log_event!(Warn, WARN, $category, $($arg)*)
     };
 }
 
 #[macro_export]
 macro_rules! log_info {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_message(
-            $crate::logging::LogLevel::Info,
-            $category,
-            &format!($($arg)*)
-        );
+        $crate::__log_event!(Info, INFO, $category, $($arg)*)
     };
 }
 
 #[macro_export]
 macro_rules! log_debug {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_message(
-            $crate::logging::LogLevel::Debug,
-            $category,
-            &format!($($arg)*)
-        );
+        $crate::__log_event!(Debug, DEBUG, $category, $($arg)*)
     };
 }
 
 #[macro_export]
 macro_rules! log_trace {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_message(
-            $crate::logging::LogLevel::Trace,
-            $category,
-            &format!($($arg)*)
-        );
+        $crate::__log_event!(Trace, TRACE, $category, $($arg)*)
     };
 }
 
@@ -249,6 +670,8 @@ mod tests {
         assert_eq!(LogLevel::from("warn"), LogLevel::Warn);
         assert_eq!(LogLevel::from("debug"), LogLevel::Debug);
         assert_eq!(LogLevel::from("invalid"), LogLevel::Info);
+        assert_eq!("Debug".parse::<LogLevel>(), Ok(LogLevel::Debug));
+        assert!("verbos".parse::<LogLevel>().is_err());
     }
 
     #[test]
@@ -277,4 +700,108 @@ mod tests {
         assert!(messages[1].contains("WARN"));
         assert!(messages[2].contains("INFO"));
     }
+
+    #[test]
+    fn test_log_category_from_str() {
+        assert_eq!("grpc".parse::<LogCategory>(), Ok(LogCategory::Grpc));
+        assert_eq!("Unix".parse::<LogCategory>(), Ok(LogCategory::UnixSocket));
+        assert_eq!("tcp".parse::<LogCategory>(), Ok(LogCategory::Tcp));
+        assert_eq!("web".parse::<LogCategory>(), Ok(LogCategory::Web));
+        assert!("unknown".parse::<LogCategory>().is_err());
+    }
+
+    #[test]
+    fn test_format_log_record_json() {
+        let timestamp = chrono::Utc::now();
+        let line = format_log_record(
+            LogFormat::Json,
+            timestamp,
+            LogLevel::Warn,
+            LogCategory::Screen,
+            Some("launcher-1"),
+            "box \"found\"",
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["category"], "SCREEN");
+        assert_eq!(value["launcher_id"], "launcher-1");
+        assert_eq!(value["message"], "box \"found\"");
+        assert!(value["timestamp"].is_string());
+
+        let text = format_log_record(
+            LogFormat::Text,
+            timestamp,
+            LogLevel::Info,
+            LogCategory::System,
+            None,
+            "hello",
+        );
+        assert!(text.ends_with("[INFO] [SYSTEM] hello"));
+    }
+
+    #[test]
+    fn test_log_macros_emit_tracing_events() {
+        #[derive(Clone)]
+        struct Capture(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for Capture {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let capture = Capture(buffer.clone());
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_current_span(true)
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer(move || capture.clone())
+            .finish();
+
+        // スコープ内のみ有効なsubscriber（グローバル状態は変更しない）
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(is_tracing_enabled());
+            let span = launcher_span("launcher-1", "session-1");
+            let _guard = span.enter();
+            log_warn!(LogCategory::Grpc, "stream closed: {}", 42);
+        });
+
+        let output = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(value["target"], LogCategory::Grpc.target());
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["fields"]["message"], "stream closed: 42");
+        assert_eq!(value["span"]["launcher_id"], "launcher-1");
+        assert_eq!(value["span"]["session_id"], "session-1");
+    }
+
+    #[test]
+    fn test_rotating_file_writer() {
+        let dir = std::env::temp_dir().join(format!("climonitor_log_test_{}", std::process::id()));
+        let path = dir.join("diag.log");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut writer = RotatingFileWriter::open(&path, 32, 2).unwrap();
+        for i in 0..10 {
+            writer.write_line(&format!("line number {i:02}")).unwrap();
+        }
+
+        assert!(path.exists());
+        assert!(dir.join("diag.log.1").exists());
+        assert!(dir.join("diag.log.2").exists());
+        assert!(!dir.join("diag.log.3").exists());
+
+        let current = std::fs::read_to_string(&path).unwrap();
+        assert!(current.contains("line number 09"));
+        assert!(current.len() as u64 <= 32);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
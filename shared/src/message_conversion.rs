@@ -1,8 +1,20 @@
 use crate::grpc::{
-    launcher_message, ConnectRequest, ContextUpdate as GrpcContextUpdate, DisconnectRequest,
-    LauncherMessage, StateUpdate as GrpcStateUpdate,
+    launcher_message, monitor_message, ActivityUpdate as GrpcActivityUpdate,
+    AutoApproveDecision as GrpcAutoApproveDecision, ClearQueuedPrompt, ConnectRequest,
+    ConnectResponse, ContextUpdate as GrpcContextUpdate, DisconnectRequest,
+    EvidenceLine as GrpcEvidenceLine, Exited as GrpcExited, LauncherInfo as GrpcLauncherInfo,
+    LauncherMessage, LauncherMetadata as GrpcLauncherMetadata, MonitorMessage,
+    ProcessExit as GrpcProcessExit, ProcessMetrics as GrpcProcessMetrics,
+    ProcessMetricsUpdate as GrpcProcessMetricsUpdate, PromptQueueUpdate as GrpcPromptQueueUpdate,
+    QueuePrompt, SessionActivity as GrpcSessionActivity, SessionInfo as GrpcSessionInfo,
+    SessionSnapshot as GrpcSessionSnapshot, StateEvidence as GrpcStateEvidence,
+    StateUpdate as GrpcStateUpdate, TerminalNotification as GrpcTerminalNotification,
+};
+use crate::{
+    AutoApproveAction, CliToolType, EvidenceLine, LauncherInfo, LauncherMetadata, LauncherStatus,
+    LauncherToMonitor, MonitorToLauncher, ProcessExit, ProcessMetrics, SessionActivity,
+    SessionInfo, SessionSnapshot, SessionStatus, StateEvidence, LEGACY_PROTOCOL_VERSION,
 };
-use crate::{CliToolType, LauncherToMonitor, SessionStatus};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 
@@ -10,6 +22,15 @@ use chrono::{DateTime, Utc};
 pub mod grpc_conversion {
     use super::*;
 
+    /// protobufのプロトコルバージョンを変換（未設定の0はバージョン導入前のpeer）
+    pub fn protocol_version_from_grpc(version: u32) -> u32 {
+        if version == 0 {
+            LEGACY_PROTOCOL_VERSION
+        } else {
+            version
+        }
+    }
+
     /// CliToolType を gRPC の i32 値に変換
     pub fn cli_tool_type_to_grpc(tool_type: CliToolType) -> i32 {
         match tool_type {
@@ -48,6 +69,42 @@ pub mod grpc_conversion {
         }
     }
 
+    /// AutoApproveAction を gRPC の i32 値に変換
+    pub fn auto_approve_action_to_grpc(action: AutoApproveAction) -> i32 {
+        match action {
+            AutoApproveAction::Approve => 0,
+            AutoApproveAction::Deny => 1,
+        }
+    }
+
+    /// gRPC の i32 値を AutoApproveAction に変換
+    pub fn auto_approve_action_from_grpc(value: i32) -> AutoApproveAction {
+        match value {
+            0 => AutoApproveAction::Approve,
+            _ => AutoApproveAction::Deny,
+        }
+    }
+
+    /// LauncherStatus を gRPC の i32 値に変換
+    pub fn launcher_status_to_grpc(status: LauncherStatus) -> i32 {
+        match status {
+            LauncherStatus::Connected => 0,
+            LauncherStatus::Active => 1,
+            LauncherStatus::Idle => 2,
+            LauncherStatus::Disconnected => 3,
+        }
+    }
+
+    /// gRPC の i32 値を LauncherStatus に変換
+    pub fn launcher_status_from_grpc(value: i32) -> LauncherStatus {
+        match value {
+            1 => LauncherStatus::Active,
+            2 => LauncherStatus::Idle,
+            3 => LauncherStatus::Disconnected,
+            _ => LauncherStatus::Connected,
+        }
+    }
+
     /// DateTime<Utc> を gRPC Timestamp に変換
     pub fn to_grpc_timestamp(dt: DateTime<Utc>) -> prost_types::Timestamp {
         prost_types::Timestamp {
@@ -69,17 +126,23 @@ pub mod grpc_conversion {
                 launcher_id,
                 project,
                 tool_type,
-                claude_args,
+                tool_args,
                 working_dir,
                 timestamp,
+                protocol_version,
+                capabilities,
+                metadata,
             } => LauncherMessage {
                 message: Some(launcher_message::Message::Connect(ConnectRequest {
                     launcher_id,
                     project,
                     tool_type: cli_tool_type_to_grpc(tool_type),
-                    claude_args,
+                    tool_args,
                     working_dir: working_dir.to_string_lossy().to_string(),
                     timestamp: Some(to_grpc_timestamp(timestamp)),
+                    protocol_version,
+                    capabilities,
+                    metadata: Some(launcher_metadata_to_grpc(metadata)),
                 })),
             },
 
@@ -89,6 +152,7 @@ pub mod grpc_conversion {
                 status,
                 ui_above_text,
                 timestamp,
+                evidence,
             } => LauncherMessage {
                 message: Some(launcher_message::Message::StateUpdate(GrpcStateUpdate {
                     launcher_id,
@@ -96,6 +160,7 @@ pub mod grpc_conversion {
                     status: session_status_to_grpc(status),
                     ui_above_text,
                     timestamp: Some(to_grpc_timestamp(timestamp)),
+                    evidence: evidence.map(state_evidence_to_grpc),
                 })),
             },
 
@@ -115,6 +180,110 @@ pub mod grpc_conversion {
                 )),
             },
 
+            LauncherToMonitor::PromptQueueUpdate {
+                launcher_id,
+                session_id,
+                queued_prompt,
+                timestamp,
+            } => LauncherMessage {
+                message: Some(launcher_message::Message::PromptQueueUpdate(
+                    GrpcPromptQueueUpdate {
+                        launcher_id,
+                        session_id,
+                        queued_prompt,
+                        timestamp: Some(to_grpc_timestamp(timestamp)),
+                    },
+                )),
+            },
+
+            LauncherToMonitor::AutoApproveDecision {
+                launcher_id,
+                session_id,
+                rule,
+                action,
+                prompt,
+                dry_run,
+                timestamp,
+            } => LauncherMessage {
+                message: Some(launcher_message::Message::AutoApproveDecision(
+                    GrpcAutoApproveDecision {
+                        launcher_id,
+                        session_id,
+                        rule,
+                        action: auto_approve_action_to_grpc(action),
+                        prompt,
+                        dry_run,
+                        timestamp: Some(to_grpc_timestamp(timestamp)),
+                    },
+                )),
+            },
+
+            LauncherToMonitor::TerminalNotification {
+                launcher_id,
+                session_id,
+                title,
+                body,
+                timestamp,
+            } => LauncherMessage {
+                message: Some(launcher_message::Message::TerminalNotification(
+                    GrpcTerminalNotification {
+                        launcher_id,
+                        session_id,
+                        title,
+                        body,
+                        timestamp: Some(to_grpc_timestamp(timestamp)),
+                    },
+                )),
+            },
+
+            LauncherToMonitor::ProcessMetrics {
+                launcher_id,
+                session_id,
+                metrics,
+                timestamp,
+            } => LauncherMessage {
+                message: Some(launcher_message::Message::ProcessMetrics(
+                    GrpcProcessMetricsUpdate {
+                        launcher_id,
+                        session_id,
+                        metrics: Some(process_metrics_to_grpc(metrics)),
+                        timestamp: Some(to_grpc_timestamp(timestamp)),
+                    },
+                )),
+            },
+
+            LauncherToMonitor::Activity {
+                launcher_id,
+                session_id,
+                activity,
+                timestamp,
+            } => LauncherMessage {
+                message: Some(launcher_message::Message::Activity(GrpcActivityUpdate {
+                    launcher_id,
+                    session_id,
+                    activity: Some(session_activity_to_grpc(activity)),
+                    timestamp: Some(to_grpc_timestamp(timestamp)),
+                })),
+            },
+
+            LauncherToMonitor::Exited {
+                launcher_id,
+                session_id,
+                exit_code,
+                signal,
+                runtime_secs,
+                timestamp,
+            } => LauncherMessage {
+                message: Some(launcher_message::Message::Exited(GrpcExited {
+                    launcher_id,
+                    session_id,
+                    exit_code,
+                    signal,
+                    runtime_secs,
+                    timestamp: Some(to_grpc_timestamp(timestamp)),
+                })),
+            },
+
             LauncherToMonitor::Disconnect {
                 launcher_id,
                 timestamp,
@@ -140,9 +309,12 @@ pub mod grpc_conversion {
                 launcher_id: connect_req.launcher_id,
                 project: connect_req.project,
                 tool_type: cli_tool_type_from_grpc(connect_req.tool_type),
-                claude_args: connect_req.claude_args,
+                tool_args: connect_req.tool_args,
                 working_dir: std::path::PathBuf::from(connect_req.working_dir),
                 timestamp: from_grpc_timestamp(connect_req.timestamp),
+                protocol_version: protocol_version_from_grpc(connect_req.protocol_version),
+                capabilities: connect_req.capabilities,
+                metadata: launcher_metadata_from_grpc(connect_req.metadata),
             },
 
             launcher_message::Message::StateUpdate(state_update) => {
@@ -152,6 +324,7 @@ pub mod grpc_conversion {
                     status: session_status_from_grpc(state_update.status),
                     ui_above_text: state_update.ui_above_text,
                     timestamp: from_grpc_timestamp(state_update.timestamp),
+                    evidence: state_update.evidence.map(state_evidence_from_grpc),
                 }
             }
 
@@ -164,6 +337,62 @@ pub mod grpc_conversion {
                 }
             }
 
+            launcher_message::Message::PromptQueueUpdate(queue_update) => {
+                LauncherToMonitor::PromptQueueUpdate {
+                    launcher_id: queue_update.launcher_id,
+                    session_id: queue_update.session_id,
+                    queued_prompt: queue_update.queued_prompt,
+                    timestamp: from_grpc_timestamp(queue_update.timestamp),
+                }
+            }
+
+            launcher_message::Message::AutoApproveDecision(decision) => {
+                LauncherToMonitor::AutoApproveDecision {
+                    launcher_id: decision.launcher_id,
+                    session_id: decision.session_id,
+                    rule: decision.rule,
+                    action: auto_approve_action_from_grpc(decision.action),
+                    prompt: decision.prompt,
+                    dry_run: decision.dry_run,
+                    timestamp: from_grpc_timestamp(decision.timestamp),
+                }
+            }
+
+            launcher_message::Message::TerminalNotification(notification) => {
+                LauncherToMonitor::TerminalNotification {
+                    launcher_id: notification.launcher_id,
+                    session_id: notification.session_id,
+                    title: notification.title,
+                    body: notification.body,
+                    timestamp: from_grpc_timestamp(notification.timestamp),
+                }
+            }
+
+            launcher_message::Message::ProcessMetrics(update) => {
+                LauncherToMonitor::ProcessMetrics {
+                    launcher_id: update.launcher_id,
+                    session_id: update.session_id,
+                    metrics: process_metrics_from_grpc(update.metrics.unwrap_or_default()),
+                    timestamp: from_grpc_timestamp(update.timestamp),
+                }
+            }
+
+            launcher_message::Message::Activity(update) => LauncherToMonitor::Activity {
+                launcher_id: update.launcher_id,
+                session_id: update.session_id,
+                activity: session_activity_from_grpc(update.activity.unwrap_or_default()),
+                timestamp: from_grpc_timestamp(update.timestamp),
+            },
+
+            launcher_message::Message::Exited(exited) => LauncherToMonitor::Exited {
+                launcher_id: exited.launcher_id,
+                session_id: exited.session_id,
+                exit_code: exited.exit_code,
+                signal: exited.signal,
+                runtime_secs: exited.runtime_secs,
+                timestamp: from_grpc_timestamp(exited.timestamp),
+            },
+
             launcher_message::Message::Disconnect(disconnect_req) => {
                 LauncherToMonitor::Disconnect {
                     launcher_id: disconnect_req.launcher_id,
@@ -174,4 +403,589 @@ pub mod grpc_conversion {
 
         Ok(protocol_msg)
     }
+
+    /// MonitorToLauncher を gRPC MonitorMessage に変換
+    pub fn to_grpc_monitor_message(message: MonitorToLauncher) -> MonitorMessage {
+        let grpc_msg = match message {
+            MonitorToLauncher::ConnectResponse {
+                requested_id,
+                launcher_id,
+                protocol_version,
+                capabilities,
+            } => monitor_message::Message::ConnectResponse(ConnectResponse {
+                launcher_id,
+                success: true,
+                message: None,
+                requested_launcher_id: requested_id,
+                protocol_version,
+                capabilities,
+            }),
+            MonitorToLauncher::ConnectRejected {
+                requested_id,
+                reason,
+            } => monitor_message::Message::ConnectResponse(ConnectResponse {
+                launcher_id: requested_id.clone(),
+                success: false,
+                message: Some(reason),
+                requested_launcher_id: requested_id,
+                protocol_version: crate::PROTOCOL_VERSION,
+                capabilities: Vec::new(),
+            }),
+            MonitorToLauncher::QueuePrompt { launcher_id, text } => {
+                monitor_message::Message::QueuePrompt(QueuePrompt { launcher_id, text })
+            }
+            MonitorToLauncher::ClearQueuedPrompt { launcher_id } => {
+                monitor_message::Message::ClearQueuedPrompt(ClearQueuedPrompt { launcher_id })
+            }
+        };
+
+        MonitorMessage {
+            message: Some(grpc_msg),
+        }
+    }
+
+    /// gRPC MonitorMessage を MonitorToLauncher に変換（対象外のメッセージは None）
+    pub fn from_grpc_monitor_message(msg: MonitorMessage) -> Option<MonitorToLauncher> {
+        match msg.message? {
+            monitor_message::Message::ConnectResponse(response) if response.success => {
+                Some(MonitorToLauncher::ConnectResponse {
+                    requested_id: response.requested_launcher_id,
+                    launcher_id: response.launcher_id,
+                    protocol_version: protocol_version_from_grpc(response.protocol_version),
+                    capabilities: response.capabilities,
+                })
+            }
+            monitor_message::Message::ConnectResponse(response) => {
+                Some(MonitorToLauncher::ConnectRejected {
+                    requested_id: response.requested_launcher_id,
+                    reason: response.message.unwrap_or_default(),
+                })
+            }
+            monitor_message::Message::QueuePrompt(queue_prompt) => {
+                Some(MonitorToLauncher::QueuePrompt {
+                    launcher_id: queue_prompt.launcher_id,
+                    text: queue_prompt.text,
+                })
+            }
+            monitor_message::Message::ClearQueuedPrompt(clear) => {
+                Some(MonitorToLauncher::ClearQueuedPrompt {
+                    launcher_id: clear.launcher_id,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn launcher_metadata_to_grpc(metadata: LauncherMetadata) -> GrpcLauncherMetadata {
+        GrpcLauncherMetadata {
+            git_branch: metadata.git_branch,
+            git_worktree: metadata.git_worktree,
+            hostname: metadata.hostname,
+            pid: metadata.pid,
+            tty: metadata.tty,
+            user: metadata.user,
+            label: metadata.label,
+        }
+    }
+
+    fn state_evidence_to_grpc(evidence: StateEvidence) -> GrpcStateEvidence {
+        GrpcStateEvidence {
+            rules: evidence.rules,
+            lines: evidence
+                .lines
+                .into_iter()
+                .map(|line| GrpcEvidenceLine {
+                    row: line.row,
+                    text: line.text,
+                })
+                .collect(),
+            confidence: evidence.confidence,
+        }
+    }
+
+    fn state_evidence_from_grpc(evidence: GrpcStateEvidence) -> StateEvidence {
+        StateEvidence {
+            rules: evidence.rules,
+            lines: evidence
+                .lines
+                .into_iter()
+                .map(|line| EvidenceLine {
+                    row: line.row,
+                    text: line.text,
+                })
+                .collect(),
+            confidence: evidence.confidence,
+        }
+    }
+
+    fn launcher_metadata_from_grpc(metadata: Option<GrpcLauncherMetadata>) -> LauncherMetadata {
+        let metadata = metadata.unwrap_or_default();
+        LauncherMetadata {
+            git_branch: metadata.git_branch,
+            git_worktree: metadata.git_worktree,
+            hostname: metadata.hostname,
+            pid: metadata.pid,
+            tty: metadata.tty,
+            user: metadata.user,
+            label: metadata.label,
+        }
+    }
+
+    /// SessionSnapshot を gRPC SessionSnapshot に変換
+    pub fn to_grpc_session_snapshot(snapshot: SessionSnapshot) -> GrpcSessionSnapshot {
+        let launchers = snapshot
+            .launchers
+            .into_iter()
+            .map(|launcher| GrpcLauncherInfo {
+                id: launcher.id,
+                project: launcher.project,
+                tool_type: cli_tool_type_to_grpc(launcher.tool_type),
+                tool_args: launcher.tool_args,
+                working_dir: launcher.working_dir.to_string_lossy().to_string(),
+                connected_at: Some(to_grpc_timestamp(launcher.connected_at)),
+                last_activity: Some(to_grpc_timestamp(launcher.last_activity)),
+                status: launcher_status_to_grpc(launcher.status),
+                origin: launcher.origin,
+                metadata: Some(launcher_metadata_to_grpc(launcher.metadata)),
+            })
+            .collect();
+        let sessions = snapshot
+            .sessions
+            .into_iter()
+            .map(|session| GrpcSessionInfo {
+                id: session.id,
+                launcher_id: session.launcher_id,
+                project: session.project,
+                tool_type: session.tool_type.map(cli_tool_type_to_grpc),
+                status: session_status_to_grpc(session.status),
+                previous_status: session.previous_status.map(session_status_to_grpc),
+                evidence: session.evidence,
+                last_message: session.last_message,
+                created_at: Some(to_grpc_timestamp(session.created_at)),
+                last_activity: Some(to_grpc_timestamp(session.last_activity)),
+                last_status_change: Some(to_grpc_timestamp(session.last_status_change)),
+                launcher_context: session.launcher_context,
+                usage_reset_time: session.usage_reset_time,
+                is_waiting_for_execution: session.is_waiting_for_execution,
+                ui_above_text: session.ui_above_text,
+                exit: session.exit.map(process_exit_to_grpc),
+                metrics: session
+                    .metrics
+                    .into_iter()
+                    .map(process_metrics_to_grpc)
+                    .collect(),
+                activity: session.activity.map(session_activity_to_grpc),
+            })
+            .collect();
+
+        GrpcSessionSnapshot {
+            launchers,
+            sessions,
+            timestamp: Some(to_grpc_timestamp(Utc::now())),
+        }
+    }
+
+    /// ProcessExit を gRPC ProcessExit に変換
+    pub fn process_exit_to_grpc(exit: ProcessExit) -> GrpcProcessExit {
+        GrpcProcessExit {
+            exit_code: exit.exit_code,
+            signal: exit.signal,
+            runtime_secs: exit.runtime_secs,
+            exited_at: Some(to_grpc_timestamp(exit.exited_at)),
+        }
+    }
+
+    /// gRPC ProcessExit を ProcessExit に変換
+    pub fn process_exit_from_grpc(exit: GrpcProcessExit) -> ProcessExit {
+        ProcessExit {
+            exit_code: exit.exit_code,
+            signal: exit.signal,
+            runtime_secs: exit.runtime_secs,
+            exited_at: from_grpc_timestamp(exit.exited_at),
+        }
+    }
+
+    /// ProcessMetrics を gRPC ProcessMetrics に変換
+    pub fn process_metrics_to_grpc(metrics: ProcessMetrics) -> GrpcProcessMetrics {
+        GrpcProcessMetrics {
+            cpu_percent: metrics.cpu_percent,
+            rss_bytes: metrics.rss_bytes,
+            child_count: metrics.child_count,
+        }
+    }
+
+    /// gRPC ProcessMetrics を ProcessMetrics に変換
+    pub fn process_metrics_from_grpc(metrics: GrpcProcessMetrics) -> ProcessMetrics {
+        ProcessMetrics {
+            cpu_percent: metrics.cpu_percent,
+            rss_bytes: metrics.rss_bytes,
+            child_count: metrics.child_count,
+        }
+    }
+
+    /// SessionActivity を gRPC SessionActivity に変換
+    pub fn session_activity_to_grpc(activity: SessionActivity) -> GrpcSessionActivity {
+        GrpcSessionActivity {
+            output_bytes_per_sec: activity.output_bytes_per_sec,
+            last_output_at: activity.last_output_at.map(to_grpc_timestamp),
+            last_input_at: activity.last_input_at.map(to_grpc_timestamp),
+            stalled: activity.stalled,
+        }
+    }
+
+    /// gRPC SessionActivity を SessionActivity に変換（未設定の時刻は None）
+    pub fn session_activity_from_grpc(activity: GrpcSessionActivity) -> SessionActivity {
+        SessionActivity {
+            output_bytes_per_sec: activity.output_bytes_per_sec,
+            last_output_at: activity
+                .last_output_at
+                .map(|ts| from_grpc_timestamp(Some(ts))),
+            last_input_at: activity
+                .last_input_at
+                .map(|ts| from_grpc_timestamp(Some(ts))),
+            stalled: activity.stalled,
+        }
+    }
+
+    /// gRPC SessionSnapshot を SessionSnapshot に変換
+    pub fn from_grpc_session_snapshot(snapshot: GrpcSessionSnapshot) -> SessionSnapshot {
+        let launchers = snapshot
+            .launchers
+            .into_iter()
+            .map(|launcher| LauncherInfo {
+                id: launcher.id,
+                project: launcher.project,
+                tool_type: cli_tool_type_from_grpc(launcher.tool_type),
+                tool_args: launcher.tool_args,
+                working_dir: std::path::PathBuf::from(launcher.working_dir),
+                connected_at: from_grpc_timestamp(launcher.connected_at),
+                last_activity: from_grpc_timestamp(launcher.last_activity),
+                status: launcher_status_from_grpc(launcher.status),
+                origin: launcher.origin,
+                metadata: launcher_metadata_from_grpc(launcher.metadata),
+            })
+            .collect();
+        let sessions = snapshot
+            .sessions
+            .into_iter()
+            .map(|session| SessionInfo {
+                id: session.id,
+                launcher_id: session.launcher_id,
+                project: session.project,
+                tool_type: session.tool_type.map(cli_tool_type_from_grpc),
+                status: session_status_from_grpc(session.status),
+                previous_status: session.previous_status.map(session_status_from_grpc),
+                evidence: session.evidence,
+                last_message: session.last_message,
+                created_at: from_grpc_timestamp(session.created_at),
+                last_activity: from_grpc_timestamp(session.last_activity),
+                last_status_change: from_grpc_timestamp(session.last_status_change),
+                launcher_context: session.launcher_context,
+                usage_reset_time: session.usage_reset_time,
+                is_waiting_for_execution: session.is_waiting_for_execution,
+                ui_above_text: session.ui_above_text,
+                exit: session.exit.map(process_exit_from_grpc),
+                metrics: session
+                    .metrics
+                    .into_iter()
+                    .map(process_metrics_from_grpc)
+                    .collect(),
+                activity: session.activity.map(session_activity_from_grpc),
+            })
+            .collect();
+
+        SessionSnapshot {
+            launchers,
+            sessions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::grpc_conversion::*;
+    use super::*;
+
+    #[test]
+    fn test_session_snapshot_round_trip() {
+        // gRPC Timestampはナノ秒精度なのでそのまま比較できる
+        let now = Utc::now();
+        let snapshot = SessionSnapshot {
+            launchers: vec![LauncherInfo {
+                id: "launcher-1".to_string(),
+                project: Some("project".to_string()),
+                tool_type: CliToolType::Gemini,
+                tool_args: vec!["--verbose".to_string()],
+                working_dir: std::path::PathBuf::from("/tmp/project"),
+                connected_at: now,
+                last_activity: now,
+                status: LauncherStatus::Active,
+                origin: Some("devbox".to_string()),
+                metadata: LauncherMetadata {
+                    git_branch: Some("main".to_string()),
+                    hostname: Some("devbox".to_string()),
+                    pid: Some(1234),
+                    label: Some("reviewer".to_string()),
+                    ..Default::default()
+                },
+            }],
+            sessions: vec![SessionInfo {
+                id: "session-1".to_string(),
+                launcher_id: "launcher-1".to_string(),
+                project: None,
+                tool_type: Some(CliToolType::Gemini),
+                status: SessionStatus::WaitingInput,
+                previous_status: Some(SessionStatus::Busy),
+                evidence: vec!["prompt".to_string()],
+                last_message: Some("message".to_string()),
+                created_at: now,
+                last_activity: now,
+                last_status_change: now,
+                launcher_context: None,
+                usage_reset_time: Some("3pm".to_string()),
+                is_waiting_for_execution: true,
+                ui_above_text: Some("Edit file?".to_string()),
+                exit: Some(ProcessExit {
+                    exit_code: 1,
+                    signal: None,
+                    runtime_secs: 42,
+                    exited_at: now,
+                }),
+                metrics: vec![ProcessMetrics {
+                    cpu_percent: 12.5,
+                    rss_bytes: 256 * 1024 * 1024,
+                    child_count: 3,
+                }],
+                activity: Some(SessionActivity {
+                    output_bytes_per_sec: 2048,
+                    last_output_at: Some(now),
+                    last_input_at: None,
+                    stalled: false,
+                }),
+            }],
+        };
+
+        let converted = from_grpc_session_snapshot(to_grpc_session_snapshot(snapshot.clone()));
+        assert_eq!(converted, snapshot);
+    }
+
+    #[test]
+    fn test_connect_response_round_trip() {
+        let response = MonitorToLauncher::ConnectResponse {
+            requested_id: "launcher-1".to_string(),
+            launcher_id: "launcher-2".to_string(),
+            protocol_version: crate::PROTOCOL_VERSION,
+            capabilities: crate::supported_capabilities(),
+        };
+        let converted = from_grpc_monitor_message(to_grpc_monitor_message(response.clone()));
+        assert_eq!(converted, Some(response));
+
+        let rejected = MonitorToLauncher::ConnectRejected {
+            requested_id: "launcher-1".to_string(),
+            reason: "protocol version 0 is not supported".to_string(),
+        };
+        let converted = from_grpc_monitor_message(to_grpc_monitor_message(rejected.clone()));
+        assert_eq!(converted, Some(rejected));
+    }
+
+    #[test]
+    fn test_connect_without_protocol_version_is_legacy() {
+        // バージョン導入前のlauncherはprotocol_versionを送らない（0として受信される）
+        let message = LauncherMessage {
+            message: Some(launcher_message::Message::Connect(ConnectRequest {
+                launcher_id: "launcher-1".to_string(),
+                project: None,
+                tool_type: cli_tool_type_to_grpc(CliToolType::Claude),
+                tool_args: vec!["--resume".to_string()],
+                working_dir: "/tmp".to_string(),
+                timestamp: None,
+                protocol_version: 0,
+                capabilities: Vec::new(),
+                metadata: None,
+            })),
+        };
+
+        match from_grpc_launcher_message(message).unwrap() {
+            LauncherToMonitor::Connect {
+                tool_args,
+                protocol_version,
+                capabilities,
+                metadata,
+                ..
+            } => {
+                assert_eq!(tool_args, vec!["--resume"]);
+                assert_eq!(protocol_version, LEGACY_PROTOCOL_VERSION);
+                assert!(capabilities.is_empty());
+                assert_eq!(metadata, LauncherMetadata::default());
+            }
+            other => panic!("expected connect, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_terminal_notification_round_trip() {
+        let message = LauncherToMonitor::TerminalNotification {
+            launcher_id: "launcher-1".to_string(),
+            session_id: "session-1".to_string(),
+            title: Some("Claude Code".to_string()),
+            body: "Claude needs your permission to use Bash".to_string(),
+            timestamp: Utc::now(),
+        };
+        assert_eq!(
+            message.required_capability(),
+            Some(crate::capability::TERMINAL_NOTIFICATION)
+        );
+
+        let converted =
+            from_grpc_launcher_message(to_grpc_launcher_message(message).unwrap()).unwrap();
+        match converted {
+            LauncherToMonitor::TerminalNotification {
+                session_id,
+                title,
+                body,
+                ..
+            } => {
+                assert_eq!(session_id, "session-1");
+                assert_eq!(title.as_deref(), Some("Claude Code"));
+                assert_eq!(body, "Claude needs your permission to use Bash");
+            }
+            other => panic!("expected terminal notification, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_exited_round_trip() {
+        let message = LauncherToMonitor::Exited {
+            launcher_id: "launcher-1".to_string(),
+            session_id: "session-1".to_string(),
+            exit_code: 1,
+            signal: Some("Killed".to_string()),
+            runtime_secs: 723,
+            timestamp: Utc::now(),
+        };
+        assert_eq!(
+            message.required_capability(),
+            Some(crate::capability::PROCESS_EXIT)
+        );
+
+        let converted =
+            from_grpc_launcher_message(to_grpc_launcher_message(message).unwrap()).unwrap();
+        match converted {
+            LauncherToMonitor::Exited {
+                session_id,
+                exit_code,
+                signal,
+                runtime_secs,
+                ..
+            } => {
+                assert_eq!(session_id, "session-1");
+                assert_eq!(exit_code, 1);
+                assert_eq!(signal.as_deref(), Some("Killed"));
+                assert_eq!(runtime_secs, 723);
+            }
+            other => panic!("expected exited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_process_metrics_round_trip() {
+        let metrics = ProcessMetrics {
+            cpu_percent: 150.0,
+            rss_bytes: 1024 * 1024 * 1024,
+            child_count: 12,
+        };
+        let message = LauncherToMonitor::ProcessMetrics {
+            launcher_id: "launcher-1".to_string(),
+            session_id: "session-1".to_string(),
+            metrics: metrics.clone(),
+            timestamp: Utc::now(),
+        };
+        assert_eq!(
+            message.required_capability(),
+            Some(crate::capability::PROCESS_METRICS)
+        );
+
+        let converted =
+            from_grpc_launcher_message(to_grpc_launcher_message(message).unwrap()).unwrap();
+        match converted {
+            LauncherToMonitor::ProcessMetrics {
+                session_id,
+                metrics: converted,
+                ..
+            } => {
+                assert_eq!(session_id, "session-1");
+                assert_eq!(converted, metrics);
+            }
+            other => panic!("expected process metrics, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_activity_round_trip() {
+        let activity = SessionActivity {
+            output_bytes_per_sec: 0,
+            last_output_at: Some(Utc::now()),
+            last_input_at: None,
+            stalled: true,
+        };
+        let message = LauncherToMonitor::Activity {
+            launcher_id: "launcher-1".to_string(),
+            session_id: "session-1".to_string(),
+            activity: activity.clone(),
+            timestamp: Utc::now(),
+        };
+        assert_eq!(
+            message.required_capability(),
+            Some(crate::capability::ACTIVITY)
+        );
+
+        let converted =
+            from_grpc_launcher_message(to_grpc_launcher_message(message).unwrap()).unwrap();
+        match converted {
+            LauncherToMonitor::Activity {
+                activity: converted,
+                ..
+            } => assert_eq!(converted, activity),
+            other => panic!("expected activity, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_state_update_evidence_round_trip() {
+        let evidence = StateEvidence {
+            rules: vec!["claude.approval_prompt(\"Do you want\")".to_string()],
+            lines: vec![EvidenceLine {
+                row: 12,
+                text: "│ Do you want to create hello.txt? │".to_string(),
+            }],
+            confidence: 0.85,
+        };
+        let state_update = |evidence| LauncherToMonitor::StateUpdate {
+            launcher_id: "launcher-1".to_string(),
+            session_id: "session-1".to_string(),
+            status: SessionStatus::WaitingInput,
+            ui_above_text: None,
+            timestamp: Utc::now(),
+            evidence,
+        };
+
+        for expected in [Some(evidence.clone()), None] {
+            let converted = from_grpc_launcher_message(
+                to_grpc_launcher_message(state_update(expected.clone())).unwrap(),
+            )
+            .unwrap();
+            match converted {
+                LauncherToMonitor::StateUpdate { evidence, .. } => assert_eq!(evidence, expected),
+                other => panic!("expected state update, got {other:?}"),
+            }
+        }
+
+        assert_eq!(
+            evidence.describe(),
+            vec![
+                "claude.approval_prompt(\"Do you want\") (85%)",
+                "L13: │ Do you want to create hello.txt? │",
+            ]
+        );
+    }
 }
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// セッション状態
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SessionStatus {
     Connected,    // 🔗 接続済み
     Busy,         // 🟢 実行中
@@ -41,17 +41,114 @@ impl SessionStatus {
     }
 }
 
+impl std::str::FromStr for SessionStatus {
+    type Err = String;
+
+    /// 設定ファイル用の名前（`busy`, `waiting_input` など）から変換
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "connected" => Ok(Self::Connected),
+            "busy" => Ok(Self::Busy),
+            "waiting_input" => Ok(Self::WaitingInput),
+            "idle" => Ok(Self::Idle),
+            "error" => Ok(Self::Error),
+            _ => Err(format!(
+                "Unknown state: {s} (expected connected, busy, waiting_input, idle or error)"
+            )),
+        }
+    }
+}
+
+/// プロトコルバージョン（互換性のない変更を加えたら上げる）
+///
+/// - 1: バージョン情報のない初期プロトコル
+/// - 2: Connect/ConnectResponse でのバージョン・機能交換、`claude_args` → `tool_args`
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// 接続を受け付ける最も古いプロトコルバージョン
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// バージョン情報を送らない（バージョン導入前の）peerのプロトコルバージョン
+pub const LEGACY_PROTOCOL_VERSION: u32 = 1;
+
+fn legacy_protocol_version() -> u32 {
+    LEGACY_PROTOCOL_VERSION
+}
+
+/// Connect/ConnectResponse で交換する機能名
+pub mod capability {
+    /// 接続応答によるlauncher IDの割り当て
+    pub const CONNECT_RESPONSE: &str = "connect_response";
+    /// 予約プロンプト（PromptQueueUpdate / QueuePrompt / ClearQueuedPrompt）
+    pub const PROMPT_QUEUE: &str = "prompt_queue";
+    /// 自動応答の監査（AutoApproveDecision）
+    pub const AUTO_APPROVE: &str = "auto_approve";
+    /// CLIツールのデスクトップ通知の転送（TerminalNotification）
+    pub const TERMINAL_NOTIFICATION: &str = "terminal_notification";
+    /// CLIツールの終了コード・実行時間の報告（Exited）
+    pub const PROCESS_EXIT: &str = "process_exit";
+    /// CLIツールのプロセスツリーのリソース使用量（ProcessMetrics）
+    pub const PROCESS_METRICS: &str = "process_metrics";
+    /// PTY入出力の活動状況（Activity）
+    pub const ACTIVITY: &str = "activity";
+}
+
+/// このビルドが対応する機能一覧
+pub fn supported_capabilities() -> Vec<String> {
+    [
+        capability::CONNECT_RESPONSE,
+        capability::PROMPT_QUEUE,
+        capability::AUTO_APPROVE,
+        capability::TERMINAL_NOTIFICATION,
+        capability::PROCESS_EXIT,
+        capability::PROCESS_METRICS,
+        capability::ACTIVITY,
+    ]
+    .iter()
+    .map(|name| name.to_string())
+    .collect()
+}
+
+/// 相手のプロトコルバージョンと互換性があるか確認し、使用するバージョンを返す
+pub fn negotiate_protocol_version(peer_version: u32) -> Result<u32, String> {
+    let version = peer_version.min(PROTOCOL_VERSION);
+    if version < MIN_PROTOCOL_VERSION {
+        return Err(format!(
+            "protocol version {peer_version} is not supported (supported: {MIN_PROTOCOL_VERSION}-{PROTOCOL_VERSION}); please upgrade climonitor on both sides"
+        ));
+    }
+    Ok(version)
+}
+
+/// 自動承認ルールの判定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoApproveAction {
+    Approve, // 設定されたキー入力で承認
+    Deny,    // 自動応答しない（ユーザーの判断に委ねる）
+}
+
 /// launcher → monitor へのメッセージ
+///
+/// `launcher_id` はlauncherプロセス（接続）単位、`session_id` はその中で動作するCLIツールの
+/// セッション単位のID。launcherは `ConnectResponse` で通知されたIDを以降の送信に使用する。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LauncherToMonitor {
-    /// launcher接続
+    /// launcher接続（monitorは `ConnectResponse` で使用するlauncher IDを返す）
     Connect {
         launcher_id: String,
         project: Option<String>,
         tool_type: CliToolType,
-        claude_args: Vec<String>, // 互換性のため保持（将来はtool_argsに変更予定）
+        #[serde(alias = "claude_args")] // プロトコルv1の名前
+        tool_args: Vec<String>,
         working_dir: PathBuf,
         timestamp: DateTime<Utc>,
+        #[serde(default = "legacy_protocol_version")]
+        protocol_version: u32,
+        #[serde(default)]
+        capabilities: Vec<String>,
+        #[serde(default)]
+        metadata: LauncherMetadata,
     },
     /// セッション状態更新
     StateUpdate {
@@ -60,6 +157,9 @@ pub enum LauncherToMonitor {
         status: SessionStatus,
         ui_above_text: Option<String>, // UI box上の⏺文字以降の具体的なテキスト
         timestamp: DateTime<Utc>,
+        /// 状態判定の根拠（判定根拠を送らないlauncherでは None）
+        #[serde(default)]
+        evidence: Option<StateEvidence>,
     },
     /// コンテキスト情報のみ更新（状態変化なし）
     ContextUpdate {
@@ -68,6 +168,54 @@ pub enum LauncherToMonitor {
         ui_above_text: Option<String>, // UI box上の⏺文字以降の具体的なテキスト
         timestamp: DateTime<Utc>,
     },
+    /// 予約プロンプトの状態変化（None = 予約なし）
+    PromptQueueUpdate {
+        launcher_id: String,
+        session_id: String,
+        queued_prompt: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+    /// 承認プロンプトへの自動判定（監査用）
+    AutoApproveDecision {
+        launcher_id: String,
+        session_id: String,
+        rule: String, // マッチしたルールのパターン
+        action: AutoApproveAction,
+        prompt: String, // 判定対象となったUI boxの内容
+        dry_run: bool,  // trueの場合はキー入力を送信していない
+        timestamp: DateTime<Utc>,
+    },
+    /// CLIツールが出したデスクトップ通知（OSC 9 / OSC 777）
+    TerminalNotification {
+        launcher_id: String,
+        session_id: String,
+        title: Option<String>,
+        body: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// CLIツールのプロセスツリーのリソース使用量（定期送信）
+    ProcessMetrics {
+        launcher_id: String,
+        session_id: String,
+        metrics: ProcessMetrics,
+        timestamp: DateTime<Utc>,
+    },
+    /// PTY入出力の活動状況（変化時と一定間隔で送信）
+    Activity {
+        launcher_id: String,
+        session_id: String,
+        activity: SessionActivity,
+        timestamp: DateTime<Utc>,
+    },
+    /// CLIツールのプロセス終了（`Disconnect` の直前に送信）
+    Exited {
+        launcher_id: String,
+        session_id: String,
+        exit_code: u32,
+        signal: Option<String>, // シグナルで終了した場合のシグナル名
+        runtime_secs: u64,      // 起動から終了までの時間
+        timestamp: DateTime<Utc>,
+    },
     /// launcher切断
     Disconnect {
         launcher_id: String,
@@ -75,19 +223,220 @@ pub enum LauncherToMonitor {
     },
 }
 
-// monitor → launcher へのメッセージは現在未使用（将来拡張時に追加予定）
+impl LauncherToMonitor {
+    /// 送信元のlauncher ID
+    pub fn launcher_id(&self) -> &str {
+        match self {
+            Self::Connect { launcher_id, .. }
+            | Self::StateUpdate { launcher_id, .. }
+            | Self::ContextUpdate { launcher_id, .. }
+            | Self::PromptQueueUpdate { launcher_id, .. }
+            | Self::AutoApproveDecision { launcher_id, .. }
+            | Self::TerminalNotification { launcher_id, .. }
+            | Self::ProcessMetrics { launcher_id, .. }
+            | Self::Activity { launcher_id, .. }
+            | Self::Exited { launcher_id, .. }
+            | Self::Disconnect { launcher_id, .. } => launcher_id,
+        }
+    }
+
+    /// 送信元のlauncher IDを置き換え（monitorが割り当てたIDの適用用）
+    pub fn set_launcher_id(&mut self, id: &str) {
+        match self {
+            Self::Connect { launcher_id, .. }
+            | Self::StateUpdate { launcher_id, .. }
+            | Self::ContextUpdate { launcher_id, .. }
+            | Self::PromptQueueUpdate { launcher_id, .. }
+            | Self::AutoApproveDecision { launcher_id, .. }
+            | Self::TerminalNotification { launcher_id, .. }
+            | Self::ProcessMetrics { launcher_id, .. }
+            | Self::Activity { launcher_id, .. }
+            | Self::Exited { launcher_id, .. }
+            | Self::Disconnect { launcher_id, .. } => *launcher_id = id.to_string(),
+        }
+    }
+
+    /// 対象のセッションID（セッションに紐づかないメッセージは None）
+    pub fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::StateUpdate { session_id, .. }
+            | Self::ContextUpdate { session_id, .. }
+            | Self::PromptQueueUpdate { session_id, .. }
+            | Self::AutoApproveDecision { session_id, .. }
+            | Self::TerminalNotification { session_id, .. }
+            | Self::ProcessMetrics { session_id, .. }
+            | Self::Activity { session_id, .. }
+            | Self::Exited { session_id, .. } => Some(session_id),
+            Self::Connect { .. } | Self::Disconnect { .. } => None,
+        }
+    }
+
+    /// monitorが対応している必要のある機能（基本メッセージは None）
+    pub fn required_capability(&self) -> Option<&'static str> {
+        match self {
+            Self::PromptQueueUpdate { .. } => Some(capability::PROMPT_QUEUE),
+            Self::AutoApproveDecision { .. } => Some(capability::AUTO_APPROVE),
+            Self::TerminalNotification { .. } => Some(capability::TERMINAL_NOTIFICATION),
+            Self::ProcessMetrics { .. } => Some(capability::PROCESS_METRICS),
+            Self::Activity { .. } => Some(capability::ACTIVITY),
+            Self::Exited { .. } => Some(capability::PROCESS_EXIT),
+            Self::Connect { .. }
+            | Self::StateUpdate { .. }
+            | Self::ContextUpdate { .. }
+            | Self::Disconnect { .. } => None,
+        }
+    }
+
+    /// 接続先のプロトコルバージョンに合わせてJSONにシリアライズ
+    ///
+    /// v1のmonitorは `tool_args` を解釈できないため、Connectの引数を `claude_args` として送る。
+    pub fn to_json(&self, peer_version: u32) -> serde_json::Result<String> {
+        let mut value = serde_json::to_value(self)?;
+        if peer_version < PROTOCOL_VERSION {
+            if let Some(connect) = value.get_mut("Connect").and_then(|v| v.as_object_mut()) {
+                if let Some(args) = connect.remove("tool_args") {
+                    connect.insert("claude_args".to_string(), args);
+                }
+            }
+        }
+        serde_json::to_string(&value)
+    }
+}
+
+/// monitor → launcher へのメッセージ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MonitorToLauncher {
+    /// 接続応答（IDが衝突した場合、launcher_id は requested_id と異なる新しいIDになる）
+    ConnectResponse {
+        requested_id: String,
+        launcher_id: String,
+        #[serde(default = "legacy_protocol_version")]
+        protocol_version: u32,
+        #[serde(default)]
+        capabilities: Vec<String>,
+    },
+    /// 接続拒否（プロトコルの非互換など）
+    ConnectRejected {
+        requested_id: String,
+        reason: String,
+    },
+    /// 次のBusy→Idle遷移時に送信するプロンプトを予約
+    QueuePrompt { launcher_id: String, text: String },
+    /// 予約済みプロンプトを取り消し
+    ClearQueuedPrompt { launcher_id: String },
+}
 
 /// launcher情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LauncherInfo {
     pub id: String,
     pub project: Option<String>,
     pub tool_type: CliToolType,
-    pub claude_args: Vec<String>, // 互換性のため保持
+    #[serde(alias = "claude_args")] // プロトコルv1の名前
+    pub tool_args: Vec<String>,
     pub working_dir: PathBuf,
     pub connected_at: DateTime<Utc>,
     pub last_activity: DateTime<Utc>,
     pub status: LauncherStatus,
+    /// 上流monitorから受信したlauncherの接続元（ローカルのlauncherはNone）
+    #[serde(default)]
+    pub origin: Option<String>,
+    #[serde(default)]
+    pub metadata: LauncherMetadata,
+}
+
+/// launcherの実行環境（同じプロジェクトの複数launcherを区別するための情報）
+///
+/// 取得できなかった項目は None。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LauncherMetadata {
+    /// gitブランチ名（detached HEADの場合は短縮コミットハッシュ）
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    /// `git worktree add` で作成した作業ツリーの名前（メインの作業ツリーは None）
+    #[serde(default)]
+    pub git_worktree: Option<String>,
+    #[serde(default)]
+    pub hostname: Option<String>,
+    /// CLIツールの子プロセスのPID
+    #[serde(default)]
+    pub pid: Option<u32>,
+    /// 制御端末（例: `/dev/pts/3`）
+    #[serde(default)]
+    pub tty: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
+    /// `--label` で指定した表示名
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl LauncherMetadata {
+    /// 同じグループ内でlauncherを区別する表示名（ラベル > worktree > tty > PID）
+    pub fn instance_label(&self) -> Option<String> {
+        self.label
+            .clone()
+            .or_else(|| self.git_worktree.clone())
+            .or_else(|| {
+                self.tty
+                    .as_deref()
+                    .map(|tty| tty.trim_start_matches("/dev/").to_string())
+            })
+            .or_else(|| self.pid.map(|pid| format!("pid {pid}")))
+    }
+
+    /// 実行環境の要約（例: `alice@devbox pid 1234 pts/3`）
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        match (&self.user, &self.hostname) {
+            (Some(user), Some(host)) => parts.push(format!("{user}@{host}")),
+            (Some(name), None) | (None, Some(name)) => parts.push(name.clone()),
+            (None, None) => {}
+        }
+        if let Some(pid) = self.pid {
+            parts.push(format!("pid {pid}"));
+        }
+        if let Some(tty) = &self.tty {
+            parts.push(tty.trim_start_matches("/dev/").to_string());
+        }
+        parts.join(" ")
+    }
+}
+
+/// 状態判定の根拠（誤検出をmonitor側から調査するための情報）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StateEvidence {
+    /// マッチした検出ルール（例: `claude.approval_prompt("Do you want")`）
+    pub rules: Vec<String>,
+    /// ルールにマッチした画面の行
+    pub lines: Vec<EvidenceLine>,
+    /// 判定の確からしさ（0.0〜1.0）
+    pub confidence: f32,
+}
+
+/// 判定根拠となった画面の行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvidenceLine {
+    /// 画面上の行番号（0始まり）
+    pub row: u32,
+    pub text: String,
+}
+
+impl StateEvidence {
+    /// 表示用の文字列（`SessionInfo::evidence`）
+    pub fn describe(&self) -> Vec<String> {
+        let mut items = vec![format!(
+            "{} ({:.0}%)",
+            self.rules.join(" + "),
+            self.confidence * 100.0
+        )];
+        items.extend(
+            self.lines
+                .iter()
+                .map(|line| format!("L{}: {}", line.row + 1, line.text.trim())),
+        );
+        items
+    }
 }
 
 /// launcher状態
@@ -100,7 +449,7 @@ pub enum LauncherStatus {
 }
 
 /// セッション情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SessionInfo {
     pub id: String,
     pub launcher_id: String,
@@ -108,7 +457,7 @@ pub struct SessionInfo {
     pub tool_type: Option<CliToolType>,
     pub status: SessionStatus,
     pub previous_status: Option<SessionStatus>, // 前の状態（通知判定用）
-    pub evidence: Vec<String>,
+    pub evidence: Vec<String>,                  // 状態判定の根拠（`StateEvidence::describe`）
     pub last_message: Option<String>,
     pub created_at: DateTime<Utc>,
     pub last_activity: DateTime<Utc>,
@@ -117,16 +466,244 @@ pub struct SessionInfo {
     pub usage_reset_time: Option<String>,
     pub is_waiting_for_execution: bool,
     pub ui_above_text: Option<String>, // UI box上の⏺文字以降の具体的なテキスト
+    /// CLIツールの終了情報（終了後も一定時間表示する）
+    #[serde(default)]
+    pub exit: Option<ProcessExit>,
+    /// 直近のリソース使用量（古い順）
+    #[serde(default)]
+    pub metrics: Vec<ProcessMetrics>,
+    /// PTY入出力の活動状況
+    #[serde(default)]
+    pub activity: Option<SessionActivity>,
 }
 
-// ProcessMetrics は現在未使用（将来拡張時に追加予定）
+/// PTY入出力の活動状況
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionActivity {
+    /// 直近の出力レート（バイト/秒）
+    pub output_bytes_per_sec: u64,
+    pub last_output_at: Option<DateTime<Utc>>,
+    /// 最後のユーザー入力の時刻
+    pub last_input_at: Option<DateTime<Utc>>,
+    /// Busyのまま出力が止まっている
+    pub stalled: bool,
+}
+
+/// CLIツールのプロセスツリー（CLIツール本体と子孫プロセス）のリソース使用量
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessMetrics {
+    /// 前回の計測からのCPU使用率（1コア=100%）
+    pub cpu_percent: f32,
+    /// 常駐メモリの合計（バイト）
+    pub rss_bytes: u64,
+    /// CLIツール本体を除いた子孫プロセス数
+    pub child_count: u32,
+}
+
+/// CLIツールの終了情報
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessExit {
+    pub exit_code: u32,
+    /// シグナルで終了した場合のシグナル名
+    pub signal: Option<String>,
+    pub runtime_secs: u64,
+    pub exited_at: DateTime<Utc>,
+}
+
+impl ProcessExit {
+    /// 正常終了か（終了コード0かつシグナルなし）
+    pub fn success(&self) -> bool {
+        self.exit_code == 0 && self.signal.is_none()
+    }
 
-/// 接続ID生成
-pub fn generate_connection_id() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_millis();
-    format!("launcher-{timestamp:x}")
+    /// 表示用の要約（例: `exit 1 after 12m 3s`）
+    pub fn summary(&self) -> String {
+        let reason = match &self.signal {
+            Some(signal) => format!("signal {signal}"),
+            None => format!("exit {}", self.exit_code),
+        };
+        let secs = self.runtime_secs;
+        let runtime = match (secs / 3600, secs % 3600 / 60, secs % 60) {
+            (0, 0, s) => format!("{s}s"),
+            (0, m, s) => format!("{m}m {s}s"),
+            (h, m, _) => format!("{h}h {m}m"),
+        };
+        format!("{reason} after {runtime}")
+    }
+}
+
+/// launcher/セッション一覧（下流monitorへの配信用）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub launchers: Vec<LauncherInfo>,
+    pub sessions: Vec<SessionInfo>,
+}
+
+/// launcher ID生成
+pub fn generate_launcher_id() -> String {
+    format!("launcher-{}", uuid::Uuid::new_v4().simple())
+}
+
+/// セッションID生成
+pub fn generate_session_id() -> String {
+    format!("session-{}", uuid::Uuid::new_v4().simple())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_generated_ids_are_unique_and_distinguishable() {
+        // 同一ミリ秒内に連続生成しても衝突しない
+        let launcher_ids: HashSet<_> = (0..1000).map(|_| generate_launcher_id()).collect();
+        assert_eq!(launcher_ids.len(), 1000);
+
+        let launcher_id = generate_launcher_id();
+        let session_id = generate_session_id();
+        assert!(launcher_id.starts_with("launcher-"));
+        assert!(session_id.starts_with("session-"));
+        assert_ne!(launcher_id[9..], session_id[8..]);
+    }
+
+    #[test]
+    fn test_set_launcher_id() {
+        let mut message = LauncherToMonitor::Disconnect {
+            launcher_id: "launcher-1".to_string(),
+            timestamp: Utc::now(),
+        };
+        message.set_launcher_id("launcher-2");
+        assert_eq!(message.launcher_id(), "launcher-2");
+    }
+
+    #[test]
+    fn test_legacy_connect_json_is_migrated() {
+        // v1のlauncherは claude_args を送り、バージョン情報を含まない
+        let json = r#"{"Connect":{"launcher_id":"launcher-1","project":null,"tool_type":"Claude","claude_args":["--resume"],"working_dir":"/tmp","timestamp":"2024-01-01T00:00:00Z"}}"#;
+        let message: LauncherToMonitor = serde_json::from_str(json).unwrap();
+        match message {
+            LauncherToMonitor::Connect {
+                tool_args,
+                protocol_version,
+                capabilities,
+                metadata,
+                ..
+            } => {
+                assert_eq!(tool_args, vec!["--resume"]);
+                assert_eq!(protocol_version, LEGACY_PROTOCOL_VERSION);
+                assert!(capabilities.is_empty());
+                assert_eq!(metadata, LauncherMetadata::default());
+            }
+            other => panic!("expected connect, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_connect_json_for_legacy_peer() {
+        let message = LauncherToMonitor::Connect {
+            launcher_id: "launcher-1".to_string(),
+            project: None,
+            tool_type: CliToolType::Claude,
+            tool_args: vec!["--resume".to_string()],
+            working_dir: PathBuf::from("/tmp"),
+            timestamp: Utc::now(),
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: supported_capabilities(),
+            metadata: LauncherMetadata::default(),
+        };
+
+        let current = message.to_json(PROTOCOL_VERSION).unwrap();
+        assert!(current.contains(r#""tool_args":["--resume"]"#));
+
+        let legacy = message.to_json(LEGACY_PROTOCOL_VERSION).unwrap();
+        assert!(legacy.contains(r#""claude_args":["--resume"]"#));
+        assert!(!legacy.contains("tool_args"));
+        // v2以降はどちらの名前も受け付ける
+        let parsed: LauncherToMonitor = serde_json::from_str(&legacy).unwrap();
+        assert!(matches!(
+            parsed,
+            LauncherToMonitor::Connect { tool_args, .. } if tool_args == vec!["--resume"]
+        ));
+
+        // Connect以外は変わらない
+        let disconnect = LauncherToMonitor::Disconnect {
+            launcher_id: "launcher-1".to_string(),
+            timestamp: Utc::now(),
+        };
+        assert_eq!(
+            disconnect.to_json(LEGACY_PROTOCOL_VERSION).unwrap(),
+            serde_json::to_string(&disconnect).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_state_update_without_evidence() {
+        // 根拠を送らない古いlauncherのStateUpdate
+        let json = r#"{"StateUpdate":{"launcher_id":"launcher-1","session_id":"session-1","status":"Busy","ui_above_text":null,"timestamp":"2024-01-01T00:00:00Z"}}"#;
+        let message: LauncherToMonitor = serde_json::from_str(json).unwrap();
+        match message {
+            LauncherToMonitor::StateUpdate { evidence, .. } => assert_eq!(evidence, None),
+            other => panic!("expected state update, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_process_exit_summary() {
+        let exit = |exit_code, signal: Option<&str>, runtime_secs| ProcessExit {
+            exit_code,
+            signal: signal.map(|s| s.to_string()),
+            runtime_secs,
+            exited_at: Utc::now(),
+        };
+        assert!(exit(0, None, 5).success());
+        assert_eq!(exit(0, None, 5).summary(), "exit 0 after 5s");
+        assert!(!exit(2, None, 723).success());
+        assert_eq!(exit(2, None, 723).summary(), "exit 2 after 12m 3s");
+        assert!(!exit(1, Some("Killed"), 7500).success());
+        assert_eq!(
+            exit(1, Some("Killed"), 7500).summary(),
+            "signal Killed after 2h 5m"
+        );
+    }
+
+    #[test]
+    fn test_launcher_metadata_labels() {
+        let mut metadata = LauncherMetadata {
+            hostname: Some("devbox".to_string()),
+            user: Some("alice".to_string()),
+            pid: Some(1234),
+            tty: Some("/dev/pts/3".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(metadata.summary(), "alice@devbox pid 1234 pts/3");
+        assert_eq!(metadata.instance_label().as_deref(), Some("pts/3"));
+
+        metadata.git_worktree = Some("feature-x".to_string());
+        assert_eq!(metadata.instance_label().as_deref(), Some("feature-x"));
+        metadata.label = Some("reviewer".to_string());
+        assert_eq!(metadata.instance_label().as_deref(), Some("reviewer"));
+
+        assert_eq!(LauncherMetadata::default().summary(), "");
+        assert_eq!(LauncherMetadata::default().instance_label(), None);
+    }
+
+    #[test]
+    fn test_negotiate_protocol_version() {
+        assert_eq!(
+            negotiate_protocol_version(PROTOCOL_VERSION),
+            Ok(PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            negotiate_protocol_version(LEGACY_PROTOCOL_VERSION),
+            Ok(LEGACY_PROTOCOL_VERSION)
+        );
+        // 新しいpeerには自分のバージョンで応答する
+        assert_eq!(
+            negotiate_protocol_version(PROTOCOL_VERSION + 1),
+            Ok(PROTOCOL_VERSION)
+        );
+        let error = negotiate_protocol_version(0).unwrap_err();
+        assert!(error.contains("protocol version 0 is not supported"));
+    }
 }
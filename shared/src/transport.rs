@@ -1,8 +1,12 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use std::net::SocketAddr;
+use tokio::sync::broadcast;
 
-use crate::{CliToolType, LauncherToMonitor, SessionStatus};
+use crate::{
+    AutoApproveAction, CliToolType, LauncherToMonitor, MonitorToLauncher, ProcessMetrics,
+    SessionActivity, SessionSnapshot, SessionStatus, StateEvidence,
+};
 
 /// 接続設定
 #[derive(Debug, Clone)]
@@ -13,6 +17,11 @@ pub enum ConnectionConfig {
         bind_addr: String,        // "0.0.0.0:50051" or "localhost:50051"
         allowed_ips: Vec<String>, // IP許可リスト
     },
+    /// 改行区切りJSON over TCP（Unix socketと同じメッセージ形式）
+    Tcp {
+        bind_addr: String,        // "0.0.0.0:50052" or "localhost:50052"
+        allowed_ips: Vec<String>, // IP許可リスト
+    },
 }
 
 impl ConnectionConfig {
@@ -32,6 +41,14 @@ impl ConnectionConfig {
         }
     }
 
+    /// デフォルトのTCP設定
+    pub fn default_tcp() -> Self {
+        Self::Tcp {
+            bind_addr: "127.0.0.1:50052".to_string(),
+            allowed_ips: Vec::new(),
+        }
+    }
+
     /// 環境変数から設定を読み込み
     pub fn from_env() -> Self {
         if let Ok(grpc_addr) = std::env::var("CLIMONITOR_GRPC_ADDR") {
@@ -40,6 +57,12 @@ impl ConnectionConfig {
                 allowed_ips: Vec::new(),
             };
         }
+        if let Ok(tcp_addr) = std::env::var("CLIMONITOR_TCP_ADDR") {
+            return Self::Tcp {
+                bind_addr: tcp_addr,
+                allowed_ips: Vec::new(),
+            };
+        }
         #[cfg(unix)]
         {
             if let Ok(socket_path) = std::env::var("CLIMONITOR_SOCKET_PATH") {
@@ -60,7 +83,8 @@ impl ConnectionConfig {
         match self {
             #[cfg(unix)]
             ConnectionConfig::Unix { .. } => true, // Unix socketは常に許可
-            ConnectionConfig::Grpc { allowed_ips, .. } => {
+            ConnectionConfig::Grpc { allowed_ips, .. }
+            | ConnectionConfig::Tcp { allowed_ips, .. } => {
                 crate::ip_utils::is_ip_allowed_by_list(&peer_addr.ip(), allowed_ips)
             }
         }
@@ -76,6 +100,7 @@ pub trait MessageSender: Send + Sync {
         tool_type: CliToolType,
         args: Vec<String>,
         working_dir: std::path::PathBuf,
+        metadata: crate::LauncherMetadata,
     ) -> Result<()>;
 
     async fn send_status_update(
@@ -83,7 +108,7 @@ pub trait MessageSender: Send + Sync {
         session_id: String,
         status: SessionStatus,
         timestamp: chrono::DateTime<chrono::Utc>,
-        project_name: Option<String>,
+        evidence: Option<StateEvidence>,
     ) -> Result<()>;
 
     async fn send_context_update(
@@ -93,7 +118,56 @@ pub trait MessageSender: Send + Sync {
         timestamp: chrono::DateTime<chrono::Utc>,
     ) -> Result<()>;
 
-    async fn send_disconnect(&self, session_id: String) -> Result<()>;
+    async fn send_prompt_queue_update(
+        &self,
+        session_id: String,
+        queued_prompt: Option<String>,
+    ) -> Result<()>;
+
+    async fn send_auto_approve_decision(
+        &self,
+        session_id: String,
+        rule: String,
+        action: AutoApproveAction,
+        prompt: String,
+        dry_run: bool,
+    ) -> Result<()>;
+
+    async fn send_terminal_notification(
+        &self,
+        session_id: String,
+        title: Option<String>,
+        body: String,
+    ) -> Result<()>;
+
+    /// CLIツールのプロセスツリーのリソース使用量を送信
+    async fn send_process_metrics(&self, session_id: String, metrics: ProcessMetrics)
+        -> Result<()>;
+
+    /// PTY入出力の活動状況を送信
+    async fn send_activity(&self, session_id: String, activity: SessionActivity) -> Result<()>;
+
+    /// CLIツールの終了を報告（`send_disconnect` の前に送る）
+    async fn send_process_exit(
+        &self,
+        session_id: String,
+        exit_code: u32,
+        signal: Option<String>,
+        runtime_secs: u64,
+    ) -> Result<()>;
+
+    /// launcherの切断（launcher配下のセッションもmonitor側で削除される）
+    async fn send_disconnect(&self) -> Result<()>;
+
+    /// 送信待ちのメッセージをすべて送り終えるまで待機
+    async fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Monitor → Launcher メッセージの購読（受信できないtransportでは None）
+    fn subscribe_monitor_messages(&self) -> Option<broadcast::Receiver<MonitorToLauncher>> {
+        None
+    }
 }
 
 /// 抽象的なサーバーインターフェース
@@ -106,5 +180,24 @@ pub trait MessageReceiver: Send + Sync {
 /// メッセージハンドラートレイト
 #[async_trait]
 pub trait MessageHandler: Send + Sync {
-    async fn handle_message(&self, message: LauncherToMonitor) -> Result<()>;
+    /// メッセージを処理し、送信元のlauncherへの応答があれば返す
+    async fn handle_message(&self, message: LauncherToMonitor)
+        -> Result<Option<MonitorToLauncher>>;
+
+    /// 現在のlauncher/セッション一覧（下流monitorへの配信に対応する場合のみ）
+    async fn session_snapshot(&self) -> Option<SessionSnapshot> {
+        None
+    }
+
+    /// 状態変化の通知を購読（下流monitorへの配信に対応する場合のみ）
+    fn subscribe_updates(&self) -> Option<broadcast::Receiver<()>> {
+        None
+    }
+
+    /// launcherへ能動的に送るメッセージを購読（プロンプト予約に対応する場合のみ）
+    ///
+    /// 各接続は接続中のlauncher宛てのメッセージだけを転送する。
+    fn subscribe_launcher_messages(&self) -> Option<broadcast::Receiver<MonitorToLauncher>> {
+        None
+    }
 }